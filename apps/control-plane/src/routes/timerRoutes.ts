@@ -1,7 +1,14 @@
 import express, { Application, Request, Response } from 'express';
 import { ZodError } from 'zod';
 import { TimerService } from '../services/timerService';
-import { timerCancelSchema, timerCreateSchema, TimerRecord } from '../types/timer';
+import {
+  sessionRevokeSchema,
+  timerCancelSchema,
+  timerCreateSchema,
+  TimerRecord,
+  timerRescheduleSchema,
+  timerSnoozeSchema,
+} from '../types/timer';
 import { logger } from '../telemetry/logger';
 
 const toResponse = (timer: TimerRecord) => ({
@@ -21,6 +28,22 @@ const toResponse = (timer: TimerRecord) => ({
   cancelledAt: timer.cancelledAt,
   cancelReason: timer.cancelReason,
   cancelledBy: timer.cancelledBy,
+  accuracyBudgetMs: timer.accuracyBudgetMs,
+  fireLatencyMs: timer.fireLatencyMs,
+  durationUs: timer.durationUs,
+  fireLatencyUs: timer.fireLatencyUs,
+  budgetOutcome: timer.budgetOutcome,
+  preFireNoticeMs: timer.preFireNoticeMs,
+  idempotencyKey: timer.idempotencyKey,
+  sessionId: timer.sessionId,
+  snoozeCount: timer.snoozeCount,
+  watchdogIntervalMs: timer.watchdogIntervalMs,
+  lastKickedAt: timer.lastKickedAt,
+  priority: timer.priority,
+  deadlineGraceMs: timer.deadlineGraceMs,
+  acknowledgedAt: timer.acknowledgedAt,
+  failedAt: timer.failedAt,
+  failureReason: timer.failureReason,
 });
 
 const tenantFromQuery = (req: Request): string => {
@@ -110,5 +133,86 @@ export const registerTimerRoutes = (app: Application, timerService: TimerService
     }
   });
 
+  router.post('/:id/reschedule', async (req, res) => {
+    try {
+      const tenantId = tenantFromHeader(req);
+      const payload = timerRescheduleSchema.parse(req.body);
+      if (payload.tenantId !== tenantId) {
+        res.status(400).json({ message: 'tenantId mismatch between header and payload' });
+        return;
+      }
+      const timer = await timerService.rescheduleTimer(tenantId, req.params.id, payload);
+      if (!timer) {
+        res.status(404).json({ message: 'Timer not found' });
+        return;
+      }
+      res.json(toResponse(timer));
+    } catch (err) {
+      handleError(err, res);
+    }
+  });
+
+  router.post('/:id/snooze', async (req, res) => {
+    try {
+      const tenantId = tenantFromHeader(req);
+      const payload = timerSnoozeSchema.parse(req.body);
+      if (payload.tenantId !== tenantId) {
+        res.status(400).json({ message: 'tenantId mismatch between header and payload' });
+        return;
+      }
+      const timer = await timerService.snoozeTimer(tenantId, req.params.id, payload);
+      if (!timer) {
+        res.status(404).json({ message: 'Timer not found' });
+        return;
+      }
+      res.json(toResponse(timer));
+    } catch (err) {
+      handleError(err, res);
+    }
+  });
+
+  router.post('/:id/kick', async (req, res) => {
+    try {
+      const tenantId = tenantFromHeader(req);
+      const timer = await timerService.kickWatchdog(tenantId, req.params.id);
+      if (!timer) {
+        res.status(404).json({ message: 'Timer not found' });
+        return;
+      }
+      res.json(toResponse(timer));
+    } catch (err) {
+      handleError(err, res);
+    }
+  });
+
+  router.post('/:id/acknowledge', async (req, res) => {
+    try {
+      const tenantId = tenantFromHeader(req);
+      const timer = await timerService.acknowledgeDeadline(tenantId, req.params.id);
+      if (!timer) {
+        res.status(404).json({ message: 'Timer not found' });
+        return;
+      }
+      res.json(toResponse(timer));
+    } catch (err) {
+      handleError(err, res);
+    }
+  });
+
+  router.post('/sessions/:sessionId/revoke', async (req, res) => {
+    try {
+      const tenantId = tenantFromHeader(req);
+      const payload = sessionRevokeSchema.parse(req.body);
+      if (payload.tenantId !== tenantId) {
+        res.status(400).json({ message: 'tenantId mismatch between header and payload' });
+        return;
+      }
+      const timers = await timerService.revokeSession(tenantId, req.params.sessionId, payload);
+      res.json({ cancelled: timers.map(toResponse) });
+    } catch (err) {
+      handleError(err, res);
+    }
+  });
+
   app.use('/timers', router);
 };
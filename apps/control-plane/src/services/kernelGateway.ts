@@ -14,14 +14,24 @@ const DEFAULT_GRPC_URL = 'localhost:50051';
 type GrpcKernelClient = grpc.Client & {
   scheduleTimer: grpc.handleUnaryCall<any, any>;
   cancelTimer: grpc.handleUnaryCall<any, any>;
+  revokeSession: grpc.handleUnaryCall<any, any>;
+  snoozeTimer: grpc.handleUnaryCall<any, any>;
+  kickWatchdog: grpc.handleUnaryCall<any, any>;
+  acknowledgeTimer: grpc.handleUnaryCall<any, any>;
+  updateTimer: grpc.handleUnaryCall<any, any>;
   getTimer: grpc.handleUnaryCall<any, any>;
   listTimers: grpc.handleUnaryCall<any, any>;
 };
 
-type ScheduleTimerMethod = (request: any) => Promise<any>;
-type CancelTimerMethod = (request: any) => Promise<any>;
-type GetTimerMethod = (request: any) => Promise<any>;
-type ListTimersMethod = (request: any) => Promise<any>;
+type ScheduleTimerMethod = (request: any, metadata: grpc.Metadata) => Promise<any>;
+type CancelTimerMethod = (request: any, metadata: grpc.Metadata) => Promise<any>;
+type RevokeSessionMethod = (request: any, metadata: grpc.Metadata) => Promise<any>;
+type SnoozeTimerMethod = (request: any, metadata: grpc.Metadata) => Promise<any>;
+type KickWatchdogMethod = (request: any, metadata: grpc.Metadata) => Promise<any>;
+type AcknowledgeTimerMethod = (request: any, metadata: grpc.Metadata) => Promise<any>;
+type UpdateTimerMethod = (request: any, metadata: grpc.Metadata) => Promise<any>;
+type GetTimerMethod = (request: any, metadata: grpc.Metadata) => Promise<any>;
+type ListTimersMethod = (request: any, metadata: grpc.Metadata) => Promise<any>;
 type KernelClientConstructor = new (address: string, credentials: grpc.ChannelCredentials) => GrpcKernelClient;
 
 const loaderOptions: protoLoader.Options = {
@@ -59,6 +69,16 @@ export interface TimerScheduleCommand {
   labels?: Record<string, string>;
   actionBundle?: TimerActionBundle;
   agentBinding?: AgentBinding;
+  accuracyBudgetMs?: number;
+  preFireNoticeMs?: number;
+  idempotencyKey?: string;
+  sessionId?: string;
+  /** Makes the timer a watchdog that only fires when kicks stop for this long. */
+  watchdogIntervalMs?: number;
+  /** Orders timers that come due together; higher fires first. */
+  priority?: number;
+  /** Makes the timer a deadline that fails unless acknowledged within this long of its fire time. */
+  deadlineGraceMs?: number;
 }
 
 export interface TimerCancelCommand {
@@ -68,9 +88,48 @@ export interface TimerCancelCommand {
   reason?: string;
 }
 
+/** Cancels every pending timer the tenant scheduled under `sessionId`. */
+export interface SessionRevokeCommand {
+  tenantId: string;
+  sessionId: string;
+  requestedBy: string;
+}
+
+/** Moves a pending timer to `fireAt`, or shifts it by `extendByMs` (negative pulls it in). */
+export interface TimerRescheduleCommand {
+  tenantId: string;
+  timerId: string;
+  fireAt?: string;
+  extendByMs?: number;
+}
+
+/** Re-arms a fired timer that no subscriber has acknowledged, `delayMs` from now. */
+export interface TimerSnoozeCommand {
+  tenantId: string;
+  timerId: string;
+  delayMs: number;
+}
+
+/** Checks in with a pending watchdog timer, pushing its fire time out by its interval. */
+export interface TimerKickCommand {
+  tenantId: string;
+  timerId: string;
+}
+
+/** Settles a pending deadline timer so it does not fail. */
+export interface TimerAcknowledgeCommand {
+  tenantId: string;
+  timerId: string;
+}
+
 export interface KernelGateway {
   schedule(command: TimerScheduleCommand): Promise<TimerRecord>;
   cancel(command: TimerCancelCommand): Promise<TimerRecord | null>;
+  reschedule(command: TimerRescheduleCommand): Promise<TimerRecord | null>;
+  revokeSession(command: SessionRevokeCommand): Promise<TimerRecord[]>;
+  snooze(command: TimerSnoozeCommand): Promise<TimerRecord | null>;
+  kick(command: TimerKickCommand): Promise<TimerRecord | null>;
+  acknowledge(command: TimerAcknowledgeCommand): Promise<TimerRecord | null>;
   list(tenantId: string): Promise<TimerRecord[]>;
   get(tenantId: string, timerId: string): Promise<TimerRecord | null>;
 }
@@ -79,6 +138,13 @@ export class InMemoryKernelGateway implements KernelGateway {
   constructor(private readonly repository = new InMemoryTimerRepository()) {}
 
   async schedule(command: TimerScheduleCommand): Promise<TimerRecord> {
+    if (command.idempotencyKey) {
+      const timers = await this.repository.list(command.tenantId);
+      const existing = timers.find((timer) => timer.idempotencyKey === command.idempotencyKey);
+      if (existing) {
+        return existing;
+      }
+    }
     const timer: TimerRecord = {
       id: uuid(),
       tenantId: command.tenantId,
@@ -92,6 +158,13 @@ export class InMemoryKernelGateway implements KernelGateway {
       labels: command.labels ?? {},
       actionBundle: cloneNullable(command.actionBundle),
       agentBinding: cloneNullable(command.agentBinding),
+      accuracyBudgetMs: command.accuracyBudgetMs,
+      preFireNoticeMs: command.preFireNoticeMs,
+      idempotencyKey: command.idempotencyKey,
+      sessionId: command.sessionId,
+      watchdogIntervalMs: command.watchdogIntervalMs,
+      priority: command.priority,
+      deadlineGraceMs: command.deadlineGraceMs,
     };
     return this.repository.save(timer);
   }
@@ -116,6 +189,113 @@ export class InMemoryKernelGateway implements KernelGateway {
     return this.repository.update(cancelled);
   }
 
+  async reschedule(command: TimerRescheduleCommand): Promise<TimerRecord | null> {
+    const existing = await this.repository.findById(command.tenantId, command.timerId);
+    if (!existing) {
+      return null;
+    }
+    if (existing.status === 'cancelled' || existing.status === 'fired') {
+      throw new Error('Timer has already fired or been cancelled');
+    }
+
+    const fireAt = command.fireAt
+      ? new Date(command.fireAt)
+      : new Date(new Date(existing.fireAt).getTime() + (command.extendByMs ?? 0));
+    if (fireAt.getTime() <= Date.now()) {
+      throw new Error('fireAt must be in the future');
+    }
+    const rescheduled: TimerRecord = {
+      ...existing,
+      fireAt: fireAt.toISOString(),
+      durationMs: fireAt.getTime() - new Date(existing.createdAt).getTime(),
+    };
+    return this.repository.update(rescheduled);
+  }
+
+  async snooze(command: TimerSnoozeCommand): Promise<TimerRecord | null> {
+    const existing = await this.repository.findById(command.tenantId, command.timerId);
+    if (!existing) {
+      return null;
+    }
+    if (existing.status !== 'fired') {
+      throw new Error('Only fired timers can be snoozed');
+    }
+    const fireAt = new Date(Date.now() + command.delayMs);
+    const snoozed: TimerRecord = {
+      ...existing,
+      status: 'scheduled',
+      fireAt: fireAt.toISOString(),
+      durationMs: fireAt.getTime() - new Date(existing.createdAt).getTime(),
+      firedAt: undefined,
+      fireLatencyMs: undefined,
+      fireLatencyUs: undefined,
+      snoozeCount: (existing.snoozeCount ?? 0) + 1,
+    };
+    return this.repository.update(snoozed);
+  }
+
+  async kick(command: TimerKickCommand): Promise<TimerRecord | null> {
+    const existing = await this.repository.findById(command.tenantId, command.timerId);
+    if (!existing) {
+      return null;
+    }
+    if (!existing.watchdogIntervalMs) {
+      throw new Error('Only watchdog timers can be kicked');
+    }
+    if (existing.status !== 'scheduled' && existing.status !== 'armed') {
+      throw new Error('Timer has already fired or been cancelled');
+    }
+    const now = new Date();
+    const fireAt = new Date(now.getTime() + existing.watchdogIntervalMs);
+    const kicked: TimerRecord = {
+      ...existing,
+      fireAt: fireAt.toISOString(),
+      durationMs: fireAt.getTime() - new Date(existing.createdAt).getTime(),
+      lastKickedAt: now.toISOString(),
+    };
+    return this.repository.update(kicked);
+  }
+
+  async acknowledge(command: TimerAcknowledgeCommand): Promise<TimerRecord | null> {
+    const existing = await this.repository.findById(command.tenantId, command.timerId);
+    if (!existing) {
+      return null;
+    }
+    if (existing.deadlineGraceMs === undefined) {
+      throw new Error('Only deadline timers can be acknowledged');
+    }
+    if (existing.status !== 'scheduled' && existing.status !== 'armed') {
+      throw new Error('Timer has already fired or been cancelled');
+    }
+    const acknowledged: TimerRecord = {
+      ...existing,
+      status: 'acknowledged',
+      acknowledgedAt: new Date().toISOString(),
+    };
+    return this.repository.update(acknowledged);
+  }
+
+  async revokeSession(command: SessionRevokeCommand): Promise<TimerRecord[]> {
+    const timers = await this.repository.list(command.tenantId);
+    const revoked: TimerRecord[] = [];
+    for (const timer of timers) {
+      const pending = timer.status === 'scheduled' || timer.status === 'armed';
+      if (timer.sessionId !== command.sessionId || !pending) {
+        continue;
+      }
+      const cancelled = await this.cancel({
+        tenantId: command.tenantId,
+        timerId: timer.id,
+        requestedBy: command.requestedBy,
+        reason: `session ${command.sessionId} revoked`,
+      });
+      if (cancelled) {
+        revoked.push(cancelled);
+      }
+    }
+    return revoked;
+  }
+
   async list(tenantId: string): Promise<TimerRecord[]> {
     return this.repository.list(tenantId);
   }
@@ -129,22 +309,36 @@ export class GrpcKernelGateway implements KernelGateway {
   private readonly client: GrpcKernelClient;
   private readonly scheduleTimer: ScheduleTimerMethod;
   private readonly cancelTimer: CancelTimerMethod;
+  private readonly revokeSessionTimers: RevokeSessionMethod;
+  private readonly snoozeTimer: SnoozeTimerMethod;
+  private readonly kickWatchdog: KickWatchdogMethod;
+  private readonly acknowledgeTimer: AcknowledgeTimerMethod;
+  private readonly updateTimer: UpdateTimerMethod;
   private readonly getTimer: GetTimerMethod;
   private readonly listTimers: ListTimersMethod;
+  private readonly metadata = new grpc.Metadata();
 
-  constructor(address: string) {
+  constructor(address: string, apiToken?: string) {
     const ClientCtor = loadKernelClientCtor();
     this.client = new ClientCtor(address, grpc.credentials.createInsecure());
     this.scheduleTimer = promisify(this.client.scheduleTimer.bind(this.client));
     this.cancelTimer = promisify(this.client.cancelTimer.bind(this.client));
+    this.revokeSessionTimers = promisify(this.client.revokeSession.bind(this.client));
+    this.snoozeTimer = promisify(this.client.snoozeTimer.bind(this.client));
+    this.kickWatchdog = promisify(this.client.kickWatchdog.bind(this.client));
+    this.acknowledgeTimer = promisify(this.client.acknowledgeTimer.bind(this.client));
+    this.updateTimer = promisify(this.client.updateTimer.bind(this.client));
     this.getTimer = promisify(this.client.getTimer.bind(this.client));
     this.listTimers = promisify(this.client.listTimers.bind(this.client));
+    if (apiToken) {
+      this.metadata.set('authorization', `Bearer ${apiToken}`);
+    }
   }
 
   async schedule(command: TimerScheduleCommand): Promise<TimerRecord> {
     try {
       const request = buildScheduleRequest(command);
-      const response = await this.scheduleTimer(request);
+      const response = await this.scheduleTimer(request, this.metadata);
       return mapTimer(response?.timer);
     } catch (error) {
       throw normalizeGrpcError('scheduleTimer', error);
@@ -153,12 +347,15 @@ export class GrpcKernelGateway implements KernelGateway {
 
   async cancel(command: TimerCancelCommand): Promise<TimerRecord | null> {
     try {
-      const response = await this.cancelTimer({
-        tenantId: command.tenantId,
-        timerId: command.timerId,
-        requestedBy: command.requestedBy,
-        reason: command.reason ?? '',
-      });
+      const response = await this.cancelTimer(
+        {
+          tenantId: command.tenantId,
+          timerId: command.timerId,
+          requestedBy: command.requestedBy,
+          reason: command.reason ?? '',
+        },
+        this.metadata,
+      );
       return mapTimer(response);
     } catch (error) {
       if (isGrpcNotFound(error)) {
@@ -168,9 +365,89 @@ export class GrpcKernelGateway implements KernelGateway {
     }
   }
 
+  async reschedule(command: TimerRescheduleCommand): Promise<TimerRecord | null> {
+    try {
+      const change = command.fireAt
+        ? { fireTimeIso: command.fireAt }
+        : { delayDeltaMs: command.extendByMs ?? 0 };
+      const response = await this.updateTimer(
+        { tenantId: command.tenantId, timerId: command.timerId, ...change },
+        this.metadata,
+      );
+      return mapTimer(response);
+    } catch (error) {
+      if (isGrpcNotFound(error)) {
+        return null;
+      }
+      throw normalizeGrpcError('updateTimer', error);
+    }
+  }
+
+  async snooze(command: TimerSnoozeCommand): Promise<TimerRecord | null> {
+    try {
+      const response = await this.snoozeTimer(
+        { tenantId: command.tenantId, timerId: command.timerId, delayMs: command.delayMs },
+        this.metadata,
+      );
+      return mapTimer(response);
+    } catch (error) {
+      if (isGrpcNotFound(error)) {
+        return null;
+      }
+      throw normalizeGrpcError('snoozeTimer', error);
+    }
+  }
+
+  async kick(command: TimerKickCommand): Promise<TimerRecord | null> {
+    try {
+      const response = await this.kickWatchdog(
+        { tenantId: command.tenantId, timerId: command.timerId },
+        this.metadata,
+      );
+      return mapTimer(response);
+    } catch (error) {
+      if (isGrpcNotFound(error)) {
+        return null;
+      }
+      throw normalizeGrpcError('kickWatchdog', error);
+    }
+  }
+
+  async acknowledge(command: TimerAcknowledgeCommand): Promise<TimerRecord | null> {
+    try {
+      const response = await this.acknowledgeTimer(
+        { tenantId: command.tenantId, timerId: command.timerId },
+        this.metadata,
+      );
+      return mapTimer(response);
+    } catch (error) {
+      if (isGrpcNotFound(error)) {
+        return null;
+      }
+      throw normalizeGrpcError('acknowledgeTimer', error);
+    }
+  }
+
+  async revokeSession(command: SessionRevokeCommand): Promise<TimerRecord[]> {
+    try {
+      const response = await this.revokeSessionTimers(
+        {
+          tenantId: command.tenantId,
+          sessionId: command.sessionId,
+          requestedBy: command.requestedBy,
+        },
+        this.metadata,
+      );
+      const timers: unknown[] = response?.cancelled ?? [];
+      return timers.map((timer) => mapTimer(timer));
+    } catch (error) {
+      throw normalizeGrpcError('revokeSession', error);
+    }
+  }
+
   async list(tenantId: string): Promise<TimerRecord[]> {
     try {
-      const response = await this.listTimers({ tenantId });
+      const response = await this.listTimers({ tenantId }, this.metadata);
       const timers: unknown[] = response?.timers ?? [];
       return timers.map((timer) => mapTimer(timer));
     } catch (error) {
@@ -180,7 +457,7 @@ export class GrpcKernelGateway implements KernelGateway {
 
   async get(tenantId: string, timerId: string): Promise<TimerRecord | null> {
     try {
-      const response = await this.getTimer({ tenantId, timerId });
+      const response = await this.getTimer({ tenantId, timerId }, this.metadata);
       return mapTimer(response);
     } catch (error) {
       if (isGrpcNotFound(error)) {
@@ -201,7 +478,7 @@ export const createKernelGateway = (): KernelGateway => {
   }
 
   try {
-    const gateway = new GrpcKernelGateway(grpcUrl);
+    const gateway = new GrpcKernelGateway(grpcUrl, process.env.KERNEL_API_TOKEN);
     logger.info({ grpcUrl }, 'Connected to horology kernel via gRPC');
     return gateway;
   } catch (error) {
@@ -224,6 +501,14 @@ const buildScheduleRequest = (command: TimerScheduleCommand) => {
     metadataJson: toJsonString(command.metadata),
     actionBundleJson: toJsonString(command.actionBundle),
     agentBindingJson: toJsonString(command.agentBinding),
+    accuracyBudgetMs: command.accuracyBudgetMs ?? 0,
+    preFireNoticeMs: command.preFireNoticeMs ?? 0,
+    idempotencyKey: command.idempotencyKey ?? '',
+    sessionId: command.sessionId ?? '',
+    watchdogIntervalMs: command.watchdogIntervalMs ?? 0,
+    priority: command.priority ?? 0,
+    deadline: command.deadlineGraceMs !== undefined,
+    deadlineGraceMs: command.deadlineGraceMs ?? 0,
   };
 };
 
@@ -250,6 +535,25 @@ const mapTimer = (payload: any): TimerRecord => {
     labels: convertStringMap(payload.labels) ?? {},
     actionBundle: parseJson(payload.actionBundleJson) as TimerActionBundle | undefined,
     agentBinding: parseJson(payload.agentBindingJson) as AgentBinding | undefined,
+    accuracyBudgetMs: optionalNumber(payload.accuracyBudgetMs),
+    fireLatencyMs: optionalNumber(payload.fireLatencyMs),
+    durationUs: optionalNumber(payload.durationUs),
+    fireLatencyUs: optionalNumber(payload.fireLatencyUs),
+    budgetOutcome: mapBudgetOutcome(payload.budgetOutcome),
+    preFireNoticeMs: optionalNumber(payload.preFireNoticeMs),
+    idempotencyKey: optionalString(payload.idempotencyKey),
+    sessionId: optionalString(payload.sessionId),
+    snoozeCount: optionalNumber(payload.snoozeCount),
+    watchdogIntervalMs: optionalNumber(payload.watchdogIntervalMs),
+    lastKickedAt: optionalString(payload.lastKickedAtIso),
+    priority: optionalNumber(payload.priority),
+    deadlineGraceMs: payload.deadline ? Number(payload.deadlineGraceMs ?? 0) : undefined,
+    acknowledgedAt: optionalString(payload.acknowledgedAtIso),
+    failedAt: optionalString(payload.failedAtIso),
+    failureReason: payload.failureReason
+      ? { code: String(payload.failureReason.code ?? ''), message: String(payload.failureReason.message ?? '') }
+      : undefined,
+    settledAt: optionalString(payload.settledAtIso),
   };
 
   return record;
@@ -278,6 +582,18 @@ const mapStatus = (status: unknown): TimerStatus => {
     case 'failed':
     case '5':
       return 'failed';
+    case 'TIMER_STATUS_MISSED':
+    case 'missed':
+    case '6':
+      return 'missed';
+    case 'TIMER_STATUS_ACKNOWLEDGED':
+    case 'acknowledged':
+    case '7':
+      return 'acknowledged';
+    case 'TIMER_STATUS_SETTLED':
+    case 'settled':
+    case '8':
+      return 'settled';
     case 'TIMER_STATUS_UNSPECIFIED':
     case '0':
     default:
@@ -285,6 +601,24 @@ const mapStatus = (status: unknown): TimerStatus => {
   }
 };
 
+const mapBudgetOutcome = (outcome: unknown): TimerRecord['budgetOutcome'] => {
+  switch (String(outcome ?? '')) {
+    case 'BUDGET_OUTCOME_WITHIN_BUDGET':
+    case '1':
+      return 'within_budget';
+    case 'BUDGET_OUTCOME_OVER_BUDGET':
+    case '2':
+      return 'over_budget';
+    default:
+      return undefined;
+  }
+};
+
+const optionalNumber = (value: unknown): number | undefined => {
+  const parsed = Number(value ?? 0);
+  return Number.isFinite(parsed) && parsed > 0 ? parsed : undefined;
+};
+
 const toJsonString = (value: unknown): string => {
   if (value === undefined || value === null) {
     return '';
@@ -1,6 +1,22 @@
-import { TimerCancelInput, TimerCreateInput, TimerRecord } from '../types/timer';
+import {
+  SessionRevokeInput,
+  TimerCancelInput,
+  TimerCreateInput,
+  TimerRecord,
+  TimerRescheduleInput,
+  TimerSnoozeInput,
+} from '../types/timer';
 import { computeFireTimestamp, parseDurationMs } from '../utils/duration';
-import { KernelGateway, TimerCancelCommand, TimerScheduleCommand } from './kernelGateway';
+import {
+  KernelGateway,
+  SessionRevokeCommand,
+  TimerAcknowledgeCommand,
+  TimerCancelCommand,
+  TimerKickCommand,
+  TimerRescheduleCommand,
+  TimerSnoozeCommand,
+  TimerScheduleCommand,
+} from './kernelGateway';
 
 export class TimerService {
   constructor(private readonly kernelGateway: KernelGateway) {}
@@ -22,6 +38,13 @@ export class TimerService {
       labels: input.labels ? { ...input.labels } : {},
       actionBundle: cloneNullable(input.actionBundle),
       agentBinding: cloneNullable(input.agentBinding),
+      accuracyBudgetMs: input.accuracyBudgetMs,
+      preFireNoticeMs: input.preFireNoticeMs,
+      idempotencyKey: input.idempotencyKey,
+      sessionId: input.sessionId,
+      watchdogIntervalMs: input.watchdogIntervalMs,
+      priority: input.priority,
+      deadlineGraceMs: input.deadlineGraceMs,
     };
 
     return this.kernelGateway.schedule(scheduleCommand);
@@ -46,6 +69,63 @@ export class TimerService {
     return this.kernelGateway.cancel(command);
   }
 
+  async rescheduleTimer(
+    tenantId: string,
+    id: string,
+    payload: TimerRescheduleInput,
+  ): Promise<TimerRecord | null> {
+    const command: TimerRescheduleCommand = {
+      tenantId,
+      timerId: id,
+      fireAt: payload.fireAt,
+      extendByMs: payload.extendByMs,
+    };
+
+    return this.kernelGateway.reschedule(command);
+  }
+
+  async snoozeTimer(tenantId: string, id: string, payload: TimerSnoozeInput): Promise<TimerRecord | null> {
+    const command: TimerSnoozeCommand = {
+      tenantId,
+      timerId: id,
+      delayMs: payload.delayMs,
+    };
+
+    return this.kernelGateway.snooze(command);
+  }
+
+  async kickWatchdog(tenantId: string, id: string): Promise<TimerRecord | null> {
+    const command: TimerKickCommand = {
+      tenantId,
+      timerId: id,
+    };
+
+    return this.kernelGateway.kick(command);
+  }
+
+  async acknowledgeDeadline(tenantId: string, id: string): Promise<TimerRecord | null> {
+    const command: TimerAcknowledgeCommand = {
+      tenantId,
+      timerId: id,
+    };
+
+    return this.kernelGateway.acknowledge(command);
+  }
+
+  async revokeSession(
+    tenantId: string,
+    sessionId: string,
+    payload: SessionRevokeInput,
+  ): Promise<TimerRecord[]> {
+    const command: SessionRevokeCommand = {
+      tenantId,
+      sessionId,
+      requestedBy: payload.requestedBy,
+    };
+
+    return this.kernelGateway.revokeSession(command);
+  }
+
   private durationFromFireAt(fireAt: string, now = new Date()): number {
     const fireAtDate = new Date(fireAt);
     if (Number.isNaN(fireAtDate.getTime())) {
@@ -2,12 +2,25 @@ import { z } from 'zod';
 
 type TimerActionShape = {
   id: string;
-  kind: 'webhook' | 'command' | 'agent_prompt' | 'workflow_event';
+  kind:
+    | 'webhook'
+    | 'command'
+    | 'agent_prompt'
+    | 'workflow_event'
+    | 'slack'
+    | 'email'
+    | 'sms'
+    | 'pagerduty'
+    | 'nats.publish';
   parameters: Record<string, unknown>;
   escalation?: {
     afterAttempts: number;
     escalatesTo?: TimerActionShape;
   };
+  idempotency?: {
+    cacheKey: string;
+    ttlSeconds?: number;
+  };
 };
 
 type TimerActionInput = Omit<TimerActionShape, 'parameters' | 'escalation'> & {
@@ -21,7 +34,18 @@ type TimerActionInput = Omit<TimerActionShape, 'parameters' | 'escalation'> & {
 const timerActionLazy: z.ZodType<TimerActionShape, z.ZodTypeDef, TimerActionInput> = z.lazy(() =>
   z.object({
     id: z.string().min(1),
-    kind: z.enum(['webhook', 'command', 'agent_prompt', 'workflow_event']),
+    kind: z.enum([
+      'webhook',
+      'command',
+      'agent_prompt',
+      'workflow_event',
+      'slack',
+      'email',
+      'sms',
+      'pagerduty',
+      // No `grpc`: only the kernel's embedded orchestrator runs those, not the standalone one.
+      'nats.publish',
+    ]),
     parameters: z.record(z.unknown()).default({}),
     escalation: z
       .object({
@@ -29,6 +53,12 @@ const timerActionLazy: z.ZodType<TimerActionShape, z.ZodTypeDef, TimerActionInpu
         escalatesTo: timerActionLazy.optional(),
       })
       .optional(),
+    idempotency: z
+      .object({
+        cacheKey: z.string().min(1),
+        ttlSeconds: z.number().int().positive().optional(),
+      })
+      .optional(),
   }),
 );
 
@@ -42,10 +72,34 @@ export const retryPolicySchema = z
   })
   .optional();
 
+// Mirrors the orchestrator's deny-list so bad headers are rejected at creation time.
+const DENIED_ACTION_HEADERS = [
+  'host',
+  'content-length',
+  'transfer-encoding',
+  'connection',
+  'upgrade',
+  'te',
+  'trailer',
+  'proxy-authorization',
+];
+
+export const actionHeadersSchema = z
+  .record(z.string())
+  .refine(
+    (headers) =>
+      Object.keys(headers).every((name) => {
+        const normalized = name.trim().toLowerCase();
+        return !DENIED_ACTION_HEADERS.includes(normalized) && !normalized.startsWith('x-minoots-');
+      }),
+    'Action headers may not override transport or x-minoots-* headers',
+  );
+
 export const timerActionBundleSchema = z.object({
   actions: z.array(timerActionSchema).min(1),
   concurrency: z.number().int().positive().default(1),
   retryPolicy: retryPolicySchema,
+  headers: actionHeadersSchema.optional(),
 });
 
 export const agentBindingSchema = z
@@ -68,10 +122,21 @@ export const timerCreateSchema = z
     labels: z.record(z.string()).optional(),
     actionBundle: timerActionBundleSchema.optional(),
     agentBinding: agentBindingSchema,
+    accuracyBudgetMs: z.number().int().positive().optional(),
+    preFireNoticeMs: z.number().int().positive().optional(),
+    idempotencyKey: z.string().min(1).max(256).optional(),
+    sessionId: z.string().min(1).max(256).optional(),
+    watchdogIntervalMs: z.number().int().positive().optional(),
+    priority: z.number().int().nonnegative().optional(),
+    deadlineGraceMs: z.number().int().nonnegative().optional(),
   })
   .refine(
     (value) => Boolean(value.duration ?? value.fireAt),
     'Either duration or fireAt must be provided',
+  )
+  .refine(
+    (value) => value.watchdogIntervalMs === undefined || value.deadlineGraceMs === undefined,
+    'A timer cannot be both a watchdog and a deadline',
   );
 
 export const timerCancelSchema = z.object({
@@ -80,13 +145,45 @@ export const timerCancelSchema = z.object({
   reason: z.string().min(1).optional(),
 });
 
+export const sessionRevokeSchema = z.object({
+  tenantId: z.string().min(1),
+  requestedBy: z.string().min(1),
+});
+
+export const timerSnoozeSchema = z.object({
+  tenantId: z.string().min(1),
+  delayMs: z.number().int().positive(),
+});
+
+export const timerRescheduleSchema = z
+  .object({
+    tenantId: z.string().min(1),
+    fireAt: z.string().datetime().optional(),
+    extendByMs: z.number().int().optional(),
+  })
+  .refine(
+    (value) => (value.fireAt === undefined) !== (value.extendByMs === undefined),
+    'Exactly one of fireAt or extendByMs must be provided',
+  );
+
 export type TimerCreateInput = z.infer<typeof timerCreateSchema>;
 export type TimerCancelInput = z.infer<typeof timerCancelSchema>;
+export type TimerRescheduleInput = z.infer<typeof timerRescheduleSchema>;
+export type SessionRevokeInput = z.infer<typeof sessionRevokeSchema>;
+export type TimerSnoozeInput = z.infer<typeof timerSnoozeSchema>;
 export type TimerAction = z.infer<typeof timerActionSchema>;
 export type TimerActionBundle = z.infer<typeof timerActionBundleSchema>;
 export type AgentBinding = z.infer<typeof agentBindingSchema>;
 
-export type TimerStatus = 'scheduled' | 'armed' | 'fired' | 'cancelled' | 'failed';
+export type TimerStatus =
+  | 'scheduled'
+  | 'armed'
+  | 'fired'
+  | 'cancelled'
+  | 'failed'
+  | 'missed'
+  | 'acknowledged'
+  | 'settled';
 
 export interface TimerRecord {
   id: string;
@@ -105,4 +202,21 @@ export interface TimerRecord {
   cancelledAt?: string;
   cancelReason?: string;
   cancelledBy?: string;
+  accuracyBudgetMs?: number;
+  fireLatencyMs?: number;
+  durationUs?: number;
+  fireLatencyUs?: number;
+  budgetOutcome?: 'within_budget' | 'over_budget';
+  preFireNoticeMs?: number;
+  idempotencyKey?: string;
+  sessionId?: string;
+  snoozeCount?: number;
+  watchdogIntervalMs?: number;
+  lastKickedAt?: string;
+  priority?: number;
+  deadlineGraceMs?: number;
+  acknowledgedAt?: string;
+  failedAt?: string;
+  failureReason?: { code: string; message: string };
+  settledAt?: string;
 }
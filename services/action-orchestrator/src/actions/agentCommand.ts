@@ -1,26 +1,51 @@
 import { z } from 'zod';
 import { ActionExecutor, ExecutionResult, TimerAction, TimerInstance } from '../types';
 import { logger } from '../logger';
+import { checkDestination, EgressPolicy, loadEgressPolicy } from '../infra/egressPolicy';
+import { callMcpTool, McpTimeoutError } from './mcpClient';
+import { renderTemplates, templateContext } from './templates';
 
 const agentCommandSchema = z.object({
   adapter: z.enum(['mcp', 'langchain', 'autogen', 'custom']).default('mcp'),
   target: z.string().min(1),
   payload: z.record(z.any()).default({}),
+  /** MCP tool the payload is passed to as arguments. */
+  tool: z.string().min(1).default('timer_fired'),
+  acknowledgementTimeoutMs: z.number().int().positive().default(60000),
 });
 
 export class AgentCommandExecutor implements ActionExecutor {
+  constructor(private readonly egress: EgressPolicy = loadEgressPolicy()) {}
+
   canHandle(action: TimerAction): boolean {
     return action.kind === 'agent_prompt';
   }
 
   async execute(action: TimerAction, timer: TimerInstance): Promise<ExecutionResult> {
-    const payload = agentCommandSchema.parse(action.parameters ?? {});
+    // The timer's agent binding supplies defaults, including `payloadTemplate` for the payload.
+    const binding = timer.agentBinding;
+    const payload = agentCommandSchema.parse(
+      renderTemplates(
+        {
+          adapter: binding?.adapter,
+          target: binding?.target,
+          payload: binding?.payloadTemplate,
+          acknowledgementTimeoutMs: binding?.acknowledgementTimeoutMs,
+          ...action.parameters,
+        },
+        templateContext(timer),
+      ),
+    );
     logger.info(
       { actionId: action.id, adapter: payload.adapter, target: payload.target, timerId: timer.id },
       'Dispatching agent command',
     );
 
-    // Integration with MCP/LangChain/autogen will be implemented in future milestones.
+    if (payload.adapter === 'mcp') {
+      return this.dispatchMcp(action, timer, payload);
+    }
+
+    // Integration with LangChain/autogen will be implemented in future milestones.
     return {
       actionId: action.id,
       success: true,
@@ -28,7 +53,49 @@ export class AgentCommandExecutor implements ActionExecutor {
       metadata: {
         adapter: payload.adapter,
         target: payload.target,
+        payload: payload.payload,
       },
     };
   }
+
+  // The target is the agent's MCP endpoint (Streamable HTTP); the tool's result is its acknowledgement.
+  private async dispatchMcp(
+    action: TimerAction,
+    timer: TimerInstance,
+    payload: z.infer<typeof agentCommandSchema>,
+  ): Promise<ExecutionResult> {
+    const metadata = { adapter: payload.adapter, target: payload.target, tool: payload.tool };
+    const egress = checkDestination(this.egress, payload.target);
+    if (!egress.allowed) {
+      logger.warn({ actionId: action.id, timerId: timer.id, target: payload.target }, 'Egress policy blocked action');
+      return { actionId: action.id, success: false, output: egress.reason, metadata: { policy: 'egress_denied' } };
+    }
+    try {
+      const result = await callMcpTool(
+        payload.target,
+        payload.tool,
+        payload.payload,
+        payload.acknowledgementTimeoutMs,
+        this.egress,
+      );
+      return {
+        actionId: action.id,
+        success: !result.isError,
+        output: result.text || (result.isError ? 'Agent rejected the timer' : 'Agent acknowledged'),
+        metadata,
+      };
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown error dispatching to MCP agent';
+      logger.error(
+        { actionId: action.id, timerId: timer.id, target: payload.target, error: message },
+        'MCP dispatch failed',
+      );
+      return {
+        actionId: action.id,
+        success: false,
+        output: message,
+        metadata: { ...metadata, timedOut: error instanceof McpTimeoutError },
+      };
+    }
+  }
 }
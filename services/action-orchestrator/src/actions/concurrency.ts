@@ -0,0 +1,74 @@
+import { ActionExecutor, TimerAction, TimerInstance } from '../types';
+
+/** Counting semaphore; waiters are served in arrival order. */
+export class ConcurrencyLimit {
+  private active = 0;
+  private readonly waiting: Array<() => void> = [];
+
+  constructor(readonly limit: number) {}
+
+  async run<T>(task: () => Promise<T>): Promise<T> {
+    if (this.active >= this.limit) {
+      await new Promise<void>((resolve) => this.waiting.push(resolve));
+    } else {
+      this.active += 1;
+    }
+    try {
+      return await task();
+    } finally {
+      const next = this.waiting.shift();
+      if (next) {
+        // The slot passes straight to the next waiter.
+        next();
+      } else {
+        this.active -= 1;
+      }
+    }
+  }
+}
+
+/**
+ * Global cap on action attempts running at once across all timers, from
+ * `ORCHESTRATOR_MAX_CONCURRENT_ACTIONS` (default 32).
+ */
+export const loadGlobalLimit = (env: NodeJS.ProcessEnv = process.env): ConcurrencyLimit => {
+  const raw = env.ORCHESTRATOR_MAX_CONCURRENT_ACTIONS;
+  const limit = raw === undefined || raw === '' ? 32 : Number(raw);
+  if (!Number.isInteger(limit) || limit <= 0) {
+    throw new Error('ORCHESTRATOR_MAX_CONCURRENT_ACTIONS must be a positive integer');
+  }
+  return new ConcurrencyLimit(limit);
+};
+
+/**
+ * Runs attempts of `executor` under `limit`. Slots are held per attempt, not across retry
+ * backoff, so waiting retries do not starve other timers.
+ */
+export const limitExecutor = (executor: ActionExecutor, limit: ConcurrencyLimit): ActionExecutor => ({
+  canHandle: (action: TimerAction) => executor.canHandle(action),
+  execute: (action: TimerAction, timer: TimerInstance) => limit.run(() => executor.execute(action, timer)),
+});
+
+/** A bundle's `concurrency`: actions of one timer that may run at once, sequential by default. */
+export const bundleConcurrency = (timer: TimerInstance): number => {
+  const concurrency = timer.actionBundle?.concurrency;
+  return typeof concurrency === 'number' && Number.isFinite(concurrency) ? Math.max(1, Math.floor(concurrency)) : 1;
+};
+
+/** Maps `items` with at most `concurrency` calls in flight, keeping results in input order. */
+export const mapConcurrently = async <T, R>(
+  items: T[],
+  concurrency: number,
+  task: (item: T) => Promise<R>,
+): Promise<R[]> => {
+  const results = new Array<R>(items.length);
+  let next = 0;
+  const worker = async () => {
+    while (next < items.length) {
+      const index = next++;
+      results[index] = await task(items[index]);
+    }
+  };
+  await Promise.all(Array.from({ length: Math.min(concurrency, items.length) }, worker));
+  return results;
+};
@@ -0,0 +1,171 @@
+export type BreakerState = 'closed' | 'open' | 'half_open';
+
+export interface DestinationGuardConfig {
+  /** Consecutive failures that open a host's breaker. */
+  failureThreshold: number;
+  /** How long an open breaker rejects calls before letting one probe through. */
+  cooldownMs: number;
+  /** Sustained requests per second per host; unset means unlimited. */
+  ratePerSecond?: number;
+  /** Requests a host may receive at once after being idle. */
+  burst: number;
+}
+
+const positiveNumber = (raw: string | undefined, name: string): number | undefined => {
+  if (raw === undefined || raw === '') {
+    return undefined;
+  }
+  const value = Number(raw);
+  if (!Number.isFinite(value) || value <= 0) {
+    throw new Error(`${name} must be a positive number`);
+  }
+  return value;
+};
+
+export const loadDestinationGuardConfig = (env: NodeJS.ProcessEnv = process.env): DestinationGuardConfig => {
+  const ratePerSecond = positiveNumber(env.ORCHESTRATOR_HOST_RATE_PER_SECOND, 'ORCHESTRATOR_HOST_RATE_PER_SECOND');
+  return {
+    failureThreshold: Math.floor(
+      positiveNumber(env.ORCHESTRATOR_BREAKER_FAILURE_THRESHOLD, 'ORCHESTRATOR_BREAKER_FAILURE_THRESHOLD') ?? 5,
+    ),
+    cooldownMs: positiveNumber(env.ORCHESTRATOR_BREAKER_COOLDOWN_MS, 'ORCHESTRATOR_BREAKER_COOLDOWN_MS') ?? 30_000,
+    ratePerSecond,
+    burst: positiveNumber(env.ORCHESTRATOR_HOST_BURST, 'ORCHESTRATOR_HOST_BURST') ?? Math.max(1, ratePerSecond ?? 1),
+  };
+};
+
+interface HostState {
+  breaker: BreakerState;
+  consecutiveFailures: number;
+  openedAt: number;
+  /** Set while the single half-open probe is in flight. */
+  probing: boolean;
+  tokens: number;
+  refilledAt: number;
+  rejected: number;
+  throttled: number;
+  opened: number;
+}
+
+export type Admission =
+  | { admitted: true }
+  | { admitted: false; reason: 'circuit_open' | 'rate_limited'; message: string };
+
+/**
+ * Per-host circuit breakers and token buckets for outgoing webhooks. A breaker opens after
+ * `failureThreshold` consecutive failures and rejects calls until `cooldownMs` has passed; then
+ * one probe is let through (half-open), closing the breaker if it succeeds and reopening it if not.
+ * Rejections fail fast instead of occupying the orchestrator while a host is down or saturated;
+ * they are ordinary failures, so the action's retry policy tries again after its backoff.
+ */
+export class DestinationGuard {
+  private readonly hosts = new Map<string, HostState>();
+
+  constructor(
+    private readonly config: DestinationGuardConfig = loadDestinationGuardConfig(),
+    private readonly now: () => number = Date.now,
+  ) {}
+
+  /** Call before sending to `host`; an admitted call must be followed by `record`. */
+  admit(host: string): Admission {
+    const state = this.state(host);
+    const now = this.now();
+    if (state.breaker === 'open' && now - state.openedAt >= this.config.cooldownMs) {
+      state.breaker = 'half_open';
+    }
+    if (state.breaker === 'open' || (state.breaker === 'half_open' && state.probing)) {
+      state.rejected += 1;
+      return { admitted: false, reason: 'circuit_open', message: `circuit open for ${host}` };
+    }
+    if (this.config.ratePerSecond !== undefined) {
+      const refill = ((now - state.refilledAt) / 1000) * this.config.ratePerSecond;
+      state.tokens = Math.min(this.config.burst, state.tokens + refill);
+      state.refilledAt = now;
+      if (state.tokens < 1) {
+        state.throttled += 1;
+        return { admitted: false, reason: 'rate_limited', message: `rate limit reached for ${host}` };
+      }
+      state.tokens -= 1;
+    }
+    if (state.breaker === 'half_open') {
+      state.probing = true;
+    }
+    return { admitted: true };
+  }
+
+  /** Records the outcome of an admitted call. Only failures that point at the host should count. */
+  record(host: string, success: boolean): void {
+    const state = this.state(host);
+    state.probing = false;
+    if (success) {
+      state.breaker = 'closed';
+      state.consecutiveFailures = 0;
+      return;
+    }
+    state.consecutiveFailures += 1;
+    if (state.breaker === 'half_open' || state.consecutiveFailures >= this.config.failureThreshold) {
+      if (state.breaker !== 'open') {
+        state.opened += 1;
+      }
+      state.breaker = 'open';
+      state.openedAt = this.now();
+    }
+  }
+
+  /** Prometheus text exposition of every host seen so far. */
+  metrics(): string {
+    const hosts = [...this.hosts.entries()];
+    const label = (host: string) => `{host="${host.replace(/["\\\n]/g, '_')}"}`;
+    const family = (name: string, type: string, help: string, value: (state: HostState) => number) => [
+      `# HELP ${name} ${help}`,
+      `# TYPE ${name} ${type}`,
+      ...hosts.map(([host, state]) => `${name}${label(host)} ${value(state)}`),
+    ];
+    return [
+      ...family(
+        'minoots_orchestrator_breaker_state',
+        'gauge',
+        'Circuit breaker state per webhook host (0 closed, 1 half-open, 2 open).',
+        (state) => ({ closed: 0, half_open: 1, open: 2 })[state.breaker],
+      ),
+      ...family(
+        'minoots_orchestrator_breaker_opened_total',
+        'counter',
+        'Times the circuit breaker opened per webhook host.',
+        (state) => state.opened,
+      ),
+      ...family(
+        'minoots_orchestrator_breaker_rejected_total',
+        'counter',
+        'Webhook calls rejected by an open circuit breaker.',
+        (state) => state.rejected,
+      ),
+      ...family(
+        'minoots_orchestrator_rate_limited_total',
+        'counter',
+        'Webhook calls rejected by the per-host rate limit.',
+        (state) => state.throttled,
+      ),
+      '',
+    ].join('\n');
+  }
+
+  private state(host: string): HostState {
+    let state = this.hosts.get(host);
+    if (!state) {
+      state = {
+        breaker: 'closed',
+        consecutiveFailures: 0,
+        openedAt: 0,
+        probing: false,
+        tokens: this.config.burst,
+        refilledAt: this.now(),
+        rejected: 0,
+        throttled: 0,
+        opened: 0,
+      };
+      this.hosts.set(host, state);
+    }
+    return state;
+  }
+}
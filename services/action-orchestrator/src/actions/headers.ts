@@ -0,0 +1,46 @@
+import { TimerInstance } from '../types';
+import { renderTimerTemplate } from './templates';
+
+// Headers the transport or the orchestrator itself owns; integrators may not override them.
+const DENIED_HEADERS = new Set([
+  'host',
+  'content-length',
+  'transfer-encoding',
+  'connection',
+  'upgrade',
+  'te',
+  'trailer',
+  'proxy-authorization',
+]);
+const RESERVED_PREFIX = 'x-minoots-';
+
+export class HeaderPolicyError extends Error {
+  constructor(readonly header: string) {
+    super(`Header "${header}" cannot be set by action bundles`);
+  }
+}
+
+export const isDeniedHeader = (name: string): boolean => {
+  const normalized = name.trim().toLowerCase();
+  return DENIED_HEADERS.has(normalized) || normalized.startsWith(RESERVED_PREFIX);
+};
+
+/**
+ * Merges bundle-level and action-level headers (action wins), rejects denied names, and renders
+ * templated values against the fired timer.
+ */
+export const resolveHeaders = (
+  timer: TimerInstance,
+  ...sources: Array<Record<string, string> | undefined>
+): Record<string, string> => {
+  const resolved: Record<string, string> = {};
+  for (const source of sources) {
+    for (const [name, template] of Object.entries(source ?? {})) {
+      if (isDeniedHeader(name)) {
+        throw new HeaderPolicyError(name);
+      }
+      resolved[name] = renderTimerTemplate(template, timer);
+    }
+  }
+  return resolved;
+};
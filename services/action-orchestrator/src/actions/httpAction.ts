@@ -1,7 +1,12 @@
-import axios from 'axios';
+import axios, { isAxiosError } from 'axios';
 import { z } from 'zod';
 import { ActionExecutor, ExecutionResult, TimerAction, TimerInstance } from '../types';
 import { logger } from '../logger';
+import { checkDestination, EgressPolicy, loadEgressPolicy } from '../infra/egressPolicy';
+import { DestinationGuard } from './destinationGuard';
+import { HeaderPolicyError, resolveHeaders } from './headers';
+import { loadSigningSecrets, secretFor, signPayload } from './signing';
+import { renderTemplates, templateContext } from './templates';
 
 const httpActionSchema = z.object({
   url: z.string().url(),
@@ -12,28 +17,82 @@ const httpActionSchema = z.object({
 });
 
 export class HttpActionExecutor implements ActionExecutor {
+  constructor(
+    private readonly egress: EgressPolicy = loadEgressPolicy(),
+    private readonly signingSecrets: Record<string, string> = loadSigningSecrets(),
+    private readonly guard: DestinationGuard = new DestinationGuard(),
+  ) {}
+
   canHandle(action: TimerAction): boolean {
     return action.kind === 'webhook';
   }
 
   async execute(action: TimerAction, timer: TimerInstance): Promise<ExecutionResult> {
-    const payload = httpActionSchema.parse(action.parameters ?? {});
+    // Headers are rendered by resolveHeaders together with the bundle's.
+    const { headers: headerTemplates, ...parameters } = action.parameters ?? {};
+    const payload = httpActionSchema.parse({
+      ...renderTemplates(parameters, templateContext(timer)),
+      headers: headerTemplates,
+    });
+    const egress = checkDestination(this.egress, payload.url);
+    if (!egress.allowed) {
+      logger.warn({ actionId: action.id, timerId: timer.id, url: payload.url }, 'Egress policy blocked action');
+      return {
+        actionId: action.id,
+        success: false,
+        output: egress.reason,
+        metadata: { policy: 'egress_denied' },
+      };
+    }
+
+    let headers: Record<string, string>;
+    try {
+      headers = resolveHeaders(timer, timer.actionBundle?.headers, payload.headers);
+    } catch (error) {
+      if (error instanceof HeaderPolicyError) {
+        logger.warn({ actionId: action.id, timerId: timer.id, header: error.header }, 'Rejected action header');
+        return {
+          actionId: action.id,
+          success: false,
+          output: error.message,
+          metadata: { policy: 'header_denied', header: error.header },
+        };
+      }
+      throw error;
+    }
+
+    // Serialized here so the signature covers exactly the bytes sent.
+    const body = JSON.stringify(payload.body ?? { timer, event: action.kind });
+    const secret = secretFor(this.signingSecrets, timer.tenantId);
+    const timestamp = Math.floor(Date.now() / 1000);
+    const signature = secret
+      ? { 'x-minoots-timestamp': String(timestamp), 'x-minoots-signature': signPayload(secret, timestamp, body) }
+      : {};
+
+    const host = new URL(payload.url).host;
+    const admission = this.guard.admit(host);
+    if (!admission.admitted) {
+      logger.warn({ actionId: action.id, timerId: timer.id, host, reason: admission.reason }, 'Webhook held back');
+      return { actionId: action.id, success: false, output: admission.message, metadata: { [admission.reason]: host } };
+    }
+
     try {
       const response = await axios({
         url: payload.url,
         method: payload.method,
         headers: {
-          ...payload.headers,
+          'content-type': 'application/json',
+          ...headers,
           'x-minoots-timer-id': timer.id,
           'x-minoots-tenant-id': timer.tenantId,
+          ...signature,
         },
-        data: payload.body ?? {
-          timer,
-          event: action.kind,
-        },
+        data: body,
         timeout: payload.timeoutMs,
+        proxy: this.egress.proxy,
       });
 
+      this.guard.record(host, true);
       return {
         actionId: action.id,
         success: true,
@@ -44,6 +103,9 @@ export class HttpActionExecutor implements ActionExecutor {
         },
       };
     } catch (error) {
+      // Client errors other than 429 mean the host is up and answering; they do not trip the breaker.
+      const status = isAxiosError(error) ? error.response?.status : undefined;
+      this.guard.record(host, status !== undefined && status < 500 && status !== 429);
       const message =
         error instanceof Error ? error.message : 'Unknown HTTP error executing timer action';
       logger.error({ actionId: action.id, timerId: timer.id, error: message }, 'HTTP action failed');
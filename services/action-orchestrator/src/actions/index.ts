@@ -1,24 +1,114 @@
+import { DeadLetter, DeadLetterStore } from '../infra/deadLetters';
+import { loadEgressPolicy } from '../infra/egressPolicy';
 import { logger } from '../logger';
-import { ActionExecutor, TimerInstance } from '../types';
+import { ActionExecutor, ActionJournal, ExecutionResult, TimerAction, TimerInstance } from '../types';
 import { AgentCommandExecutor } from './agentCommand';
+import { bundleConcurrency, limitExecutor, loadGlobalLimit, mapConcurrently } from './concurrency';
+import { DestinationGuard } from './destinationGuard';
 import { HttpActionExecutor } from './httpAction';
+import { NatsPublishExecutor } from './natsPublish';
+import { PagerDutyExecutor, SlackExecutor, SmtpEmailExecutor, TwilioSmsExecutor } from './notifications';
+import { ActionResultCache } from './resultCache';
+import { executeWithRetry } from './retry';
+import { loadSigningSecrets } from './signing';
+import { WasmCommandExecutor } from './wasmCommand';
 
-const executors: ActionExecutor[] = [new HttpActionExecutor(), new AgentCommandExecutor()];
+export const destinationGuard = new DestinationGuard();
+const executors: ActionExecutor[] = [
+  new HttpActionExecutor(loadEgressPolicy(), loadSigningSecrets(), destinationGuard),
+  new AgentCommandExecutor(),
+  new WasmCommandExecutor(),
+  new SlackExecutor(),
+  new SmtpEmailExecutor(),
+  new TwilioSmsExecutor(),
+  new PagerDutyExecutor(),
+  new NatsPublishExecutor(),
+];
+const resultCache = new ActionResultCache();
+const globalLimit = loadGlobalLimit();
+export const deadLetters = new DeadLetterStore();
 
-export const executeActions = async (timer: TimerInstance): Promise<void> => {
+const findExecutor = (action: TimerAction): ActionExecutor | undefined => {
+  const executor = executors.find((handler) => handler.canHandle(action));
+  return executor && limitExecutor(executor, globalLimit);
+};
+
+const missingExecutor = (action: TimerAction): ExecutionResult => ({
+  actionId: action.id,
+  success: false,
+  output: `No executor for ${action.kind} actions`,
+  attempts: 0,
+});
+
+const executeAction = async (
+  timer: TimerInstance,
+  action: TimerAction,
+  journal?: ActionJournal,
+): Promise<ExecutionResult> => {
+  const executor = findExecutor(action);
+  if (!executor) {
+    // Reported as a failure so the kernel does not settle the timer as if the action had run.
+    const result = missingExecutor(action);
+    const letter = deadLetters.add(timer, action, result);
+    logger.error({ actionId: action.id, timerId: timer.id, kind: action.kind, deadLetterId: letter.id }, result.output);
+    return result;
+  }
+  const recorded = await journal?.completed(timer, action);
+  if (recorded) {
+    logger.info({ actionId: action.id, timerId: timer.id }, 'Skipping action finished before a restart');
+    return { ...recorded, metadata: { ...recorded.metadata, resumed: true } };
+  }
+  const cacheKey = resultCache.keyFor(action, timer);
+  const cached = cacheKey ? resultCache.get(cacheKey) : undefined;
+  if (cached) {
+    logger.info({ actionId: action.id, timerId: timer.id, cacheKey }, 'Skipping duplicate idempotent action');
+    return { ...cached, metadata: { ...cached.metadata, servedFromCache: true } };
+  }
+  const startedAt = Date.now();
+  const result = { ...(await executeWithRetry(executor, action, timer)), durationMs: Date.now() - startedAt };
+  if (cacheKey) {
+    resultCache.store(cacheKey, action, result);
+  }
+  if (!result.success) {
+    const letter = deadLetters.add(timer, action, result);
+    logger.error(
+      { actionId: action.id, timerId: timer.id, attempts: result.attempts, deadLetterId: letter.id },
+      'Action failed permanently; dead-lettered',
+    );
+  }
+  await journal?.record(timer, action, result);
+  return result;
+};
+
+/**
+ * Executes a fired timer's actions, up to the bundle's `concurrency` at a time (one by one by
+ * default) and within the global limit. The report has one result per action, in bundle order
+ * either way; actions of a kind no executor handles fail. Actions `journal` has a result for are
+ * not run again.
+ */
+export const executeActions = async (timer: TimerInstance, journal?: ActionJournal): Promise<ExecutionResult[]> => {
   const actions = timer.actionBundle?.actions ?? [];
-  for (const action of actions) {
-    const executor = executors.find((handler) => handler.canHandle(action));
-    if (!executor) {
-      continue;
-    }
-    try {
-      await executor.execute(action, timer);
-    } catch (error) {
-      // Individual executors already log errors; ensure the orchestrator keeps running.
-      logger.warn({ actionId: action.id, timerId: timer.id, error }, 'Action execution threw unexpectedly');
-    }
+  return mapConcurrently(actions, bundleConcurrency(timer), (action) => executeAction(timer, action, journal));
+};
+
+/**
+ * Executes dead-lettered actions again, with their retry policies, dropping those that succeed.
+ * Replays every dead letter when `ids` is omitted.
+ */
+export const replayDeadLetters = async (ids?: string[]): Promise<Array<DeadLetter & { replayed: ExecutionResult }>> => {
+  const letters = ids
+    ? ids.map((id) => deadLetters.get(id)).filter((letter): letter is DeadLetter => letter !== undefined)
+    : deadLetters.list();
+  const outcomes: Array<DeadLetter & { replayed: ExecutionResult }> = [];
+  for (const letter of letters) {
+    const executor = findExecutor(letter.action);
+    const result: ExecutionResult = executor
+      ? await executeWithRetry(executor, letter.action, letter.timer)
+      : missingExecutor(letter.action);
+    deadLetters.settle(letter.id, result);
+    outcomes.push({ ...letter, replayed: result });
   }
+  return outcomes;
 };
 
 export const registerExecutor = (executor: ActionExecutor) => {
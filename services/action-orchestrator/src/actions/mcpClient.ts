@@ -0,0 +1,111 @@
+import axios, { AxiosResponse } from 'axios';
+
+import { EgressPolicy } from '../infra/egressPolicy';
+
+const PROTOCOL_VERSION = '2025-03-26';
+const CLIENT_INFO = { name: 'minoots-action-orchestrator', version: '0.1.0' };
+
+interface JsonRpcResponse {
+  jsonrpc: '2.0';
+  id?: number | string;
+  result?: Record<string, unknown>;
+  error?: { code: number; message: string };
+}
+
+export interface ToolCallResult {
+  isError: boolean;
+  /** Text content of the result, joined by newlines. */
+  text: string;
+}
+
+export class McpTimeoutError extends Error {}
+
+// Streamable HTTP servers answer either with a JSON body or with an SSE stream carrying the response.
+const responseFor = (response: AxiosResponse<string>, id: number): JsonRpcResponse => {
+  const contentType = String(response.headers['content-type'] ?? '');
+  const messages: unknown[] = contentType.includes('text/event-stream')
+    ? response.data
+        .split(/\r?\n\r?\n/)
+        .map((event) =>
+          event
+            .split(/\r?\n/)
+            .filter((line) => line.startsWith('data:'))
+            .map((line) => line.slice(5).trimStart())
+            .join('\n'),
+        )
+        .filter((data) => data.length > 0)
+        .map((data) => JSON.parse(data))
+    : ([] as unknown[]).concat(JSON.parse(response.data));
+  const match = messages.find((message) => (message as JsonRpcResponse).id === id) as JsonRpcResponse | undefined;
+  if (!match) {
+    throw new Error(`MCP server sent no response to request ${id}`);
+  }
+  if (match.error) {
+    throw new Error(`MCP error ${match.error.code}: ${match.error.message}`);
+  }
+  return match;
+};
+
+/**
+ * Minimal MCP client over the Streamable HTTP transport: initializes a session with the server at
+ * `url`, calls one tool, and ends the session. The whole exchange must finish within `timeoutMs`,
+ * which is how long the agent has to acknowledge the call.
+ */
+export const callMcpTool = async (
+  url: string,
+  tool: string,
+  args: Record<string, unknown>,
+  timeoutMs: number,
+  egress: EgressPolicy,
+): Promise<ToolCallResult> => {
+  const abort = new AbortController();
+  const deadline = setTimeout(() => abort.abort(), timeoutMs);
+  let sessionId: string | undefined;
+  const post = (body: Record<string, unknown>) =>
+    axios.post<string>(url, JSON.stringify(body), {
+      headers: {
+        'content-type': 'application/json',
+        accept: 'application/json, text/event-stream',
+        ...(sessionId ? { 'mcp-session-id': sessionId, 'mcp-protocol-version': PROTOCOL_VERSION } : {}),
+      },
+      responseType: 'text',
+      transformResponse: (data) => data,
+      signal: abort.signal,
+      proxy: egress.proxy,
+    });
+
+  try {
+    const initialized = await post({
+      jsonrpc: '2.0',
+      id: 1,
+      method: 'initialize',
+      params: { protocolVersion: PROTOCOL_VERSION, capabilities: {}, clientInfo: CLIENT_INFO },
+    });
+    responseFor(initialized, 1);
+    sessionId = initialized.headers['mcp-session-id'] as string | undefined;
+    await post({ jsonrpc: '2.0', method: 'notifications/initialized' });
+    const called = await post({ jsonrpc: '2.0', id: 2, method: 'tools/call', params: { name: tool, arguments: args } });
+    const result = responseFor(called, 2).result ?? {};
+    const content = Array.isArray(result.content) ? (result.content as Array<{ type?: string; text?: string }>) : [];
+    return {
+      isError: result.isError === true,
+      text: content
+        .filter((item) => item.type === 'text' && typeof item.text === 'string')
+        .map((item) => item.text)
+        .join('\n'),
+    };
+  } catch (error) {
+    if (abort.signal.aborted) {
+      throw new McpTimeoutError(`no acknowledgement from ${url} within ${timeoutMs}ms`);
+    }
+    throw error;
+  } finally {
+    clearTimeout(deadline);
+    if (sessionId) {
+      // Best effort; servers also expire idle sessions.
+      void axios
+        .delete(url, { headers: { 'mcp-session-id': sessionId }, timeout: 5_000, proxy: egress.proxy })
+        .catch(() => undefined);
+    }
+  }
+};
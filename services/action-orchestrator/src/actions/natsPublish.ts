@@ -0,0 +1,103 @@
+import { connect, headers as natsHeaders, NatsConnection, StringCodec } from 'nats';
+import { z } from 'zod';
+
+import { logger } from '../logger';
+import { ActionExecutor, ExecutionResult, TimerAction, TimerInstance } from '../types';
+import { renderTemplates, templateContext } from './templates';
+
+// Concrete subjects only: tokens of printable characters, no wildcards.
+const SUBJECT = /^[^\s.*>]+(\.[^\s.*>]+)*$/;
+
+const natsPublishSchema = z.object({
+  subject: z.string().regex(SUBJECT, 'subject must be a concrete NATS subject without wildcards'),
+  payload: z.any().optional(),
+  headers: z.record(z.string()).default({}),
+});
+
+const codec = StringCodec();
+
+/**
+ * Executes `nats.publish` actions: publishes the rendered `payload` (JSON unless it renders to a
+ * string; the fired timer by default) to `subject` on the `NATS_URL` servers, and flushes so the
+ * result only reports success once the server has the message. `ORCHESTRATOR_NATS_PUBLISH_PREFIXES`
+ * (comma separated, e.g. `tenants.,jobs.`) limits which subjects timers may publish to, and the
+ * subject the orchestrator consumes timer events from is always refused.
+ */
+export class NatsPublishExecutor implements ActionExecutor {
+  private connection?: Promise<NatsConnection>;
+  private readonly prefixes: string[];
+
+  constructor(
+    private readonly servers = process.env.NATS_URL,
+    prefixes = process.env.ORCHESTRATOR_NATS_PUBLISH_PREFIXES ?? '',
+    private readonly eventSubject = process.env.NATS_SUBJECT ?? 'minoots.timer.fired',
+  ) {
+    this.prefixes = prefixes
+      .split(',')
+      .map((prefix) => prefix.trim())
+      .filter((prefix) => prefix.length > 0);
+  }
+
+  canHandle(action: TimerAction): boolean {
+    return action.kind === 'nats.publish';
+  }
+
+  async execute(action: TimerAction, timer: TimerInstance): Promise<ExecutionResult> {
+    const parsed = natsPublishSchema.safeParse(renderTemplates(action.parameters ?? {}, templateContext(timer)));
+    if (!parsed.success) {
+      return {
+        actionId: action.id,
+        success: false,
+        output: parsed.error.message,
+        metadata: { policy: 'invalid_parameters' },
+      };
+    }
+    const { subject, payload, headers } = parsed.data;
+    const denied = this.deny(subject);
+    if (denied) {
+      logger.warn({ actionId: action.id, timerId: timer.id, subject }, 'Rejected NATS publish action');
+      return { actionId: action.id, success: false, output: denied, metadata: { policy: 'subject_denied' } };
+    }
+
+    const data = typeof payload === 'string' ? payload : JSON.stringify(payload ?? { timer, event: action.kind });
+    const messageHeaders = natsHeaders();
+    for (const [name, value] of Object.entries(headers)) {
+      if (!name.toLowerCase().startsWith('x-minoots-')) {
+        messageHeaders.set(name, value);
+      }
+    }
+    messageHeaders.set('x-minoots-timer-id', timer.id);
+    messageHeaders.set('x-minoots-tenant-id', timer.tenantId);
+
+    try {
+      const connection = await this.connect();
+      connection.publish(subject, codec.encode(data), { headers: messageHeaders });
+      await connection.flush();
+      return { actionId: action.id, success: true, output: `Published to ${subject}`, metadata: { subject } };
+    } catch (error) {
+      // A failed connection is retried from scratch on the next action.
+      this.connection = undefined;
+      const message = error instanceof Error ? error.message : 'Unknown NATS error publishing timer action';
+      logger.error({ actionId: action.id, timerId: timer.id, subject, error: message }, 'NATS publish failed');
+      return { actionId: action.id, success: false, output: message };
+    }
+  }
+
+  private deny(subject: string): string | undefined {
+    if (!this.servers) {
+      return 'nats.publish actions need NATS_URL';
+    }
+    if (subject === this.eventSubject) {
+      return `subject ${subject} carries the orchestrator's own timer events`;
+    }
+    if (this.prefixes.length > 0 && !this.prefixes.some((prefix) => subject.startsWith(prefix))) {
+      return `subject ${subject} is outside the allowed prefixes`;
+    }
+    return undefined;
+  }
+
+  private connect(): Promise<NatsConnection> {
+    this.connection ??= connect({ servers: this.servers });
+    return this.connection;
+  }
+}
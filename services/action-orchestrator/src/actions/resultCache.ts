@@ -0,0 +1,62 @@
+import { ExecutionResult, TimerAction, TimerInstance } from '../types';
+import { renderTimerTemplate } from './templates';
+
+const DEFAULT_TTL_SECONDS = 3600;
+
+interface CacheEntry {
+  result: ExecutionResult;
+  expiresAt: number;
+}
+
+/**
+ * Remembers successful results of actions marked `idempotency` so that redelivered fires do not
+ * repeat the downstream call. Keys are scoped per tenant; failures are never cached.
+ */
+export class ActionResultCache {
+  private readonly entries = new Map<string, CacheEntry>();
+
+  constructor(
+    private readonly defaultTtlSeconds = Number(
+      process.env.ORCHESTRATOR_RESULT_CACHE_TTL_SECONDS ?? DEFAULT_TTL_SECONDS,
+    ),
+    private readonly now: () => number = Date.now,
+  ) {}
+
+  /** Renders the action's cache key template, or returns undefined for non-idempotent actions. */
+  keyFor(action: TimerAction, timer: TimerInstance): string | undefined {
+    if (!action.idempotency) {
+      return undefined;
+    }
+    return `${timer.tenantId}:${action.id}:${renderTimerTemplate(action.idempotency.cacheKey, timer)}`;
+  }
+
+  get(key: string): ExecutionResult | undefined {
+    const entry = this.entries.get(key);
+    if (!entry) {
+      return undefined;
+    }
+    if (entry.expiresAt <= this.now()) {
+      this.entries.delete(key);
+      return undefined;
+    }
+    return entry.result;
+  }
+
+  store(key: string, action: TimerAction, result: ExecutionResult): void {
+    if (!result.success) {
+      return;
+    }
+    const ttlSeconds = action.idempotency?.ttlSeconds ?? this.defaultTtlSeconds;
+    this.entries.set(key, { result, expiresAt: this.now() + ttlSeconds * 1000 });
+    this.evictExpired();
+  }
+
+  private evictExpired(): void {
+    const now = this.now();
+    for (const [key, entry] of this.entries) {
+      if (entry.expiresAt <= now) {
+        this.entries.delete(key);
+      }
+    }
+  }
+}
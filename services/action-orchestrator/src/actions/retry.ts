@@ -0,0 +1,65 @@
+import { logger } from '../logger';
+import { ActionExecutor, ExecutionResult, RetryPolicy, TimerAction, TimerInstance } from '../types';
+
+const DEFAULT_POLICY: Required<RetryPolicy> = {
+  maxAttempts: 3,
+  initialBackoffMs: 1_000,
+  backoffMultiplier: 2,
+  maxBackoffMs: 60_000,
+};
+
+// Bundles arrive as free-form JSON, so only numeric fields override anything.
+const numericFields = (policy?: RetryPolicy): RetryPolicy =>
+  Object.fromEntries(Object.entries(policy ?? {}).filter(([, value]) => typeof value === 'number'));
+
+/** The action's `retryPolicy`, falling back field by field to the bundle's and then the defaults. */
+export const resolveRetryPolicy = (action: TimerAction, timer: TimerInstance): Required<RetryPolicy> => {
+  const merged = {
+    ...DEFAULT_POLICY,
+    ...numericFields(timer.actionBundle?.retryPolicy),
+    ...numericFields(action.retryPolicy),
+  };
+  return {
+    maxAttempts: Math.max(1, Math.floor(merged.maxAttempts)),
+    initialBackoffMs: Math.max(0, merged.initialBackoffMs),
+    backoffMultiplier: Math.max(1, merged.backoffMultiplier),
+    maxBackoffMs: Math.max(0, merged.maxBackoffMs),
+  };
+};
+
+// Policy rejections (egress, headers) fail the same way every time.
+const isRetryable = (result: ExecutionResult): boolean => result.metadata?.policy === undefined;
+
+const sleep = (ms: number) => new Promise<void>((resolve) => setTimeout(resolve, ms));
+
+/**
+ * Executes `action`, retrying failed or throwing attempts with exponential backoff until it
+ * succeeds, fails permanently, or runs out of attempts. The result records the attempts made.
+ */
+export const executeWithRetry = async (
+  executor: ActionExecutor,
+  action: TimerAction,
+  timer: TimerInstance,
+  wait: (ms: number) => Promise<void> = sleep,
+): Promise<ExecutionResult> => {
+  const policy = resolveRetryPolicy(action, timer);
+  let backoffMs = policy.initialBackoffMs;
+  for (let attempt = 1; ; attempt += 1) {
+    let result: ExecutionResult;
+    try {
+      result = await executor.execute(action, timer);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      result = { actionId: action.id, success: false, output: message };
+    }
+    if (result.success || !isRetryable(result) || attempt >= policy.maxAttempts) {
+      return { ...result, attempts: attempt };
+    }
+    logger.warn(
+      { actionId: action.id, timerId: timer.id, attempt, backoffMs, output: result.output },
+      'Action failed; retrying',
+    );
+    await wait(backoffMs);
+    backoffMs = Math.min(backoffMs * policy.backoffMultiplier, policy.maxBackoffMs);
+  }
+};
@@ -0,0 +1,61 @@
+import { createHmac, timingSafeEqual } from 'node:crypto';
+
+/** How far a signed request's timestamp may be from the receiver's clock before it counts as a replay. */
+export const DEFAULT_TOLERANCE_SECONDS = 300;
+
+/**
+ * Per-tenant webhook signing secrets from `ORCHESTRATOR_WEBHOOK_SECRETS`, a JSON object of
+ * `{ "<tenantId>": "<secret>" }`. A `"*"` entry signs the webhooks of every other tenant.
+ */
+export const loadSigningSecrets = (env: NodeJS.ProcessEnv = process.env): Record<string, string> => {
+  const raw = env.ORCHESTRATOR_WEBHOOK_SECRETS;
+  if (!raw) {
+    return {};
+  }
+  const parsed = JSON.parse(raw) as unknown;
+  if (
+    !parsed ||
+    typeof parsed !== 'object' ||
+    Array.isArray(parsed) ||
+    !Object.values(parsed).every((secret) => typeof secret === 'string' && secret.length > 0)
+  ) {
+    throw new Error('ORCHESTRATOR_WEBHOOK_SECRETS must be a JSON object of tenant ids to non-empty secrets');
+  }
+  return parsed as Record<string, string>;
+};
+
+export const secretFor = (secrets: Record<string, string>, tenantId: string): string | undefined =>
+  secrets[tenantId] ?? secrets['*'];
+
+/** `x-minoots-signature` value: HMAC-SHA256 of `<timestamp>.<body>`, as the kernel signs event webhooks. */
+export const signPayload = (secret: string, timestamp: number, body: string): string =>
+  `sha256=${createHmac('sha256', secret).update(`${timestamp}.${body}`).digest('hex')}`;
+
+export type SignatureCheck = { valid: true } | { valid: false; reason: 'malformed' | 'stale' | 'mismatch' };
+
+/**
+ * For receivers: checks the `x-minoots-timestamp` and `x-minoots-signature` headers against the raw
+ * request body, rejecting timestamps more than `toleranceSeconds` away from `now`.
+ */
+export const verifyWebhookSignature = (
+  secret: string,
+  timestamp: string | undefined,
+  signature: string | undefined,
+  body: string,
+  toleranceSeconds = DEFAULT_TOLERANCE_SECONDS,
+  now = Date.now(),
+): SignatureCheck => {
+  const seconds = Number(timestamp);
+  if (!timestamp || !Number.isInteger(seconds) || !signature?.startsWith('sha256=')) {
+    return { valid: false, reason: 'malformed' };
+  }
+  if (Math.abs(now / 1000 - seconds) > toleranceSeconds) {
+    return { valid: false, reason: 'stale' };
+  }
+  const expected = Buffer.from(signPayload(secret, seconds, body));
+  const actual = Buffer.from(signature);
+  if (expected.length !== actual.length || !timingSafeEqual(expected, actual)) {
+    return { valid: false, reason: 'mismatch' };
+  }
+  return { valid: true };
+};
@@ -0,0 +1,86 @@
+import Handlebars from 'handlebars';
+
+import { TimerInstance } from '../types';
+
+// An isolated environment, so helpers registered here do not leak into other Handlebars users.
+const engine = Handlebars.create();
+engine.registerHelper('json', (value: unknown) => JSON.stringify(value ?? null));
+
+const MAX_CACHED_TEMPLATES = 1_000;
+const compiled = new Map<string, HandlebarsTemplateDelegate>();
+
+// A string that is nothing but one `{{path}}` expression renders to the value itself, so numbers,
+// booleans, and objects keep their type inside JSON payloads.
+const SOLE_EXPRESSION = /^\s*\{\{\s*([\w.]+)\s*\}\}\s*$/;
+
+export interface TemplateContext {
+  timer: TimerInstance;
+  /** When the timer fired; the render time for timers that have not. */
+  fired_at: string;
+}
+
+export const templateContext = (timer: TimerInstance): TemplateContext => ({
+  timer,
+  fired_at: timer.firedAt ?? new Date().toISOString(),
+});
+
+const lookupPath = (root: unknown, path: string): unknown =>
+  path.split('.').reduce<unknown>((value, segment) => {
+    if (value && typeof value === 'object' && Object.prototype.hasOwnProperty.call(value, segment)) {
+      return (value as Record<string, unknown>)[segment];
+    }
+    return undefined;
+  }, root);
+
+const compile = (template: string): HandlebarsTemplateDelegate => {
+  let render = compiled.get(template);
+  if (!render) {
+    if (compiled.size >= MAX_CACHED_TEMPLATES) {
+      compiled.clear();
+    }
+    render = engine.compile(template, { noEscape: true });
+    compiled.set(template, render);
+  }
+  return render;
+};
+
+/**
+ * Renders one template string with Handlebars against the fired timer (`{{timer.id}}`,
+ * `{{timer.metadata.foo}}`, `{{fired_at}}`, `{{json timer.labels}}`, `{{#if}}` blocks, ...). Unknown
+ * paths render as empty strings, or `undefined` for a sole expression.
+ */
+export const renderTemplate = (template: string, context: TemplateContext): unknown => {
+  const sole = SOLE_EXPRESSION.exec(template);
+  if (sole && sole[1] !== 'else' && sole[1] !== 'this') {
+    return lookupPath(context, sole[1]);
+  }
+  if (!template.includes('{{')) {
+    return template;
+  }
+  return compile(template)(context);
+};
+
+/** Renders every string inside `value`, leaving its structure and other values as they are. */
+export const renderTemplates = <T>(value: T, context: TemplateContext): T => {
+  if (typeof value === 'string') {
+    return renderTemplate(value, context) as T;
+  }
+  if (Array.isArray(value)) {
+    return value.map((item) => renderTemplates(item, context)) as T;
+  }
+  if (value && typeof value === 'object') {
+    return Object.fromEntries(
+      Object.entries(value).map(([key, item]) => [key, renderTemplates(item, context)]),
+    ) as T;
+  }
+  return value;
+};
+
+/** Renders a template that must produce text, such as a header value or cache key. */
+export const renderTimerTemplate = (template: string, timer: TimerInstance): string => {
+  const value = renderTemplate(template, templateContext(timer));
+  if (value === undefined || value === null) {
+    return '';
+  }
+  return typeof value === 'object' ? JSON.stringify(value) : String(value);
+};
@@ -0,0 +1,265 @@
+import fs from 'node:fs';
+import os from 'node:os';
+import path from 'node:path';
+import { Worker } from 'node:worker_threads';
+
+import { z } from 'zod';
+
+import { logger } from '../logger';
+import { ActionExecutor, ExecutionResult, TimerAction, TimerInstance } from '../types';
+import { renderTemplates, templateContext } from './templates';
+
+const MODULE_NAME = /^[A-Za-z0-9_-]+$/;
+const WASM_PAGE_BYTES = 64 * 1024;
+const MAX_OUTPUT_BYTES = 64 * 1024;
+/** What a module may write to stdout and stderr together before it is stopped. */
+const MAX_WRITTEN_BYTES = 1024 * 1024;
+
+const commandActionSchema = z.object({
+  module: z.string().regex(MODULE_NAME, 'module must name a .wasm file in the modules directory'),
+  args: z.array(z.string()).default([]),
+  env: z.record(z.string()).default({}),
+  stdin: z.string().optional(),
+  timeoutMs: z.number().int().positive().optional(),
+});
+
+/**
+ * Capabilities of one module, read from `<module>.json` next to `<module>.wasm`. Nothing is granted
+ * by default: no directories, and WASI preview 1 has no sockets, so modules never reach the network.
+ */
+const moduleManifestSchema = z.object({
+  /** Guest path to host directory, e.g. `{ "/data": "/srv/minoots/reports" }`. */
+  preopens: z.record(z.string()).default({}),
+  maxMemoryMb: z.number().positive().default(64),
+  timeoutMs: z.number().int().positive().default(5_000),
+});
+
+type ModuleManifest = z.infer<typeof moduleManifestSchema>;
+
+class CommandPolicyError extends Error {}
+
+const readLeb = (bytes: Uint8Array, offset: number): [number, number] => {
+  let value = 0;
+  let shift = 0;
+  let cursor = offset;
+  for (;;) {
+    const byte = bytes[cursor++];
+    if (byte === undefined) {
+      throw new CommandPolicyError('module is truncated');
+    }
+    value += (byte & 0x7f) * 2 ** shift;
+    if ((byte & 0x80) === 0) {
+      return [value, cursor];
+    }
+    shift += 7;
+  }
+};
+
+/**
+ * Largest memory the module may grow to, in bytes. Modules must declare a maximum; WebAssembly
+ * memory lives outside the V8 heap, so the worker's resource limits do not cover it.
+ */
+export const declaredMemoryLimit = (bytes: Uint8Array): number => {
+  let offset = 8;
+  let largest = 0;
+  while (offset < bytes.length) {
+    const id = bytes[offset];
+    const [size, contentStart] = readLeb(bytes, offset + 1);
+    if (id === 5) {
+      let [count, cursor] = readLeb(bytes, contentStart);
+      while (count-- > 0) {
+        const flags = bytes[cursor];
+        if ((flags & 0x01) === 0) {
+          throw new CommandPolicyError('module memory must declare a maximum size');
+        }
+        if ((flags & 0x04) !== 0) {
+          throw new CommandPolicyError('64-bit module memories are not supported');
+        }
+        const [, afterMinimum] = readLeb(bytes, cursor + 1);
+        const [maximum, next] = readLeb(bytes, afterMinimum);
+        largest = Math.max(largest, maximum * WASM_PAGE_BYTES);
+        cursor = next;
+      }
+    }
+    offset = contentStart + size;
+  }
+  return largest;
+};
+
+// Runs inside the worker, so terminating the worker stops a module that exceeds its time budget.
+// Writes to stdout and stderr are counted before they reach the scratch files, and a module that
+// goes past maxWrittenBytes is aborted by throwing out of the write call.
+const WORKER_SOURCE = `
+const fs = require('node:fs');
+const { WASI } = require('node:wasi');
+const { parentPort, workerData } = require('node:worker_threads');
+const { module, args, env, preopens, stdinPath, stdoutPath, stderrPath, maxWrittenBytes } = workerData;
+const stdin = fs.openSync(stdinPath, 'r');
+const stdout = fs.openSync(stdoutPath, 'w');
+const stderr = fs.openSync(stderrPath, 'w');
+const wasi = new WASI({ version: 'preview1', args, env, preopens, stdin, stdout, stderr, returnOnExit: true });
+let memory;
+let written = 0;
+const counted = (write) => (fd, iovs, iovsLength, ...rest) => {
+  if (fd === 1 || fd === 2) {
+    const view = new DataView(memory.buffer);
+    for (let i = 0; i < iovsLength; i++) {
+      written += view.getUint32(iovs + i * 8 + 4, true);
+    }
+    if (written > maxWrittenBytes) {
+      throw new Error('module wrote more than ' + maxWrittenBytes + ' bytes of output');
+    }
+  }
+  return write(fd, iovs, iovsLength, ...rest);
+};
+const imports = {
+  ...wasi.wasiImport,
+  fd_write: counted(wasi.wasiImport.fd_write),
+  fd_pwrite: counted(wasi.wasiImport.fd_pwrite),
+};
+WebAssembly.instantiate(module, { wasi_snapshot_preview1: imports })
+  .then((instance) => {
+    memory = instance.exports.memory;
+    parentPort.postMessage({ exitCode: wasi.start(instance) });
+  })
+  .catch((error) => parentPort.postMessage({ error: String(error && error.message ? error.message : error) }));
+`;
+
+type WorkerOutcome = { exitCode: number } | { error: string } | { timedOut: true };
+
+/** Reads at most `MAX_OUTPUT_BYTES` of a module's output, however much it wrote. */
+const readCapped = (file: string): string => {
+  const buffer = Buffer.alloc(MAX_OUTPUT_BYTES);
+  const fd = fs.openSync(file, 'r');
+  try {
+    let length = 0;
+    while (length < buffer.length) {
+      const read = fs.readSync(fd, buffer, length, buffer.length - length, length);
+      if (read === 0) {
+        break;
+      }
+      length += read;
+    }
+    return buffer.subarray(0, length).toString('utf8');
+  } finally {
+    fs.closeSync(fd);
+  }
+};
+
+/**
+ * Executes `command` actions as WASI modules from `ORCHESTRATOR_WASM_MODULES_DIR`, each in its own
+ * worker thread with only the directories its manifest grants, a memory ceiling checked against
+ * the module's declared maximum, a cap on the output it may write, and a wall-clock limit after
+ * which the worker is terminated. Only modules placed in that directory can run; actions name
+ * them, they never ship code.
+ */
+export class WasmCommandExecutor implements ActionExecutor {
+  constructor(private readonly modulesDir = process.env.ORCHESTRATOR_WASM_MODULES_DIR) {}
+
+  canHandle(action: TimerAction): boolean {
+    return action.kind === 'command';
+  }
+
+  async execute(action: TimerAction, timer: TimerInstance): Promise<ExecutionResult> {
+    try {
+      const parameters = commandActionSchema.parse(renderTemplates(action.parameters ?? {}, templateContext(timer)));
+      const { module, manifest } = await this.load(parameters.module);
+      const timeoutMs = Math.min(parameters.timeoutMs ?? manifest.timeoutMs, manifest.timeoutMs);
+      return await this.run(action, timer, parameters, module, manifest, timeoutMs);
+    } catch (error) {
+      if (error instanceof CommandPolicyError || error instanceof z.ZodError) {
+        logger.warn({ actionId: action.id, timerId: timer.id, error: error.message }, 'Rejected command action');
+        return { actionId: action.id, success: false, output: error.message, metadata: { policy: 'command_denied' } };
+      }
+      throw error;
+    }
+  }
+
+  private async load(name: string): Promise<{ module: WebAssembly.Module; manifest: ModuleManifest }> {
+    if (!this.modulesDir) {
+      throw new CommandPolicyError('command actions need ORCHESTRATOR_WASM_MODULES_DIR');
+    }
+    const wasmPath = path.join(this.modulesDir, `${name}.wasm`);
+    if (!fs.existsSync(wasmPath)) {
+      throw new CommandPolicyError(`module ${name} is not installed`);
+    }
+    const manifestPath = path.join(this.modulesDir, `${name}.json`);
+    const manifest = moduleManifestSchema.parse(
+      fs.existsSync(manifestPath) ? JSON.parse(fs.readFileSync(manifestPath, 'utf8')) : {},
+    );
+    const bytes = fs.readFileSync(wasmPath);
+    const module = await WebAssembly.compile(bytes);
+    if (WebAssembly.Module.imports(module).some((entry) => entry.kind === 'memory')) {
+      throw new CommandPolicyError(`module ${name} imports its memory`);
+    }
+    const memoryLimit = declaredMemoryLimit(bytes);
+    if (memoryLimit > manifest.maxMemoryMb * 1024 * 1024) {
+      throw new CommandPolicyError(`module ${name} may grow past its ${manifest.maxMemoryMb}MB memory limit`);
+    }
+    return { module, manifest };
+  }
+
+  private async run(
+    action: TimerAction,
+    timer: TimerInstance,
+    parameters: z.infer<typeof commandActionSchema>,
+    module: WebAssembly.Module,
+    manifest: ModuleManifest,
+    timeoutMs: number,
+  ): Promise<ExecutionResult> {
+    const scratch = fs.mkdtempSync(path.join(os.tmpdir(), 'minoots-command-'));
+    const files = {
+      stdinPath: path.join(scratch, 'stdin'),
+      stdoutPath: path.join(scratch, 'stdout'),
+      stderrPath: path.join(scratch, 'stderr'),
+    };
+    fs.writeFileSync(files.stdinPath, parameters.stdin ?? '');
+    const startedAt = Date.now();
+    try {
+      const worker = new Worker(WORKER_SOURCE, {
+        eval: true,
+        workerData: {
+          module,
+          args: [parameters.module, ...parameters.args],
+          env: { ...parameters.env, MINOOTS_TIMER_ID: timer.id, MINOOTS_TENANT_ID: timer.tenantId },
+          preopens: manifest.preopens,
+          maxWrittenBytes: MAX_WRITTEN_BYTES,
+          ...files,
+        },
+        resourceLimits: { maxOldGenerationSizeMb: 32, maxYoungGenerationSizeMb: 8 },
+      });
+      const outcome = await new Promise<WorkerOutcome>((resolve) => {
+        const deadline = setTimeout(() => {
+          resolve({ timedOut: true });
+          void worker.terminate();
+        }, timeoutMs);
+        worker.once('message', (message: WorkerOutcome) => {
+          clearTimeout(deadline);
+          resolve(message);
+          void worker.terminate();
+        });
+        worker.once('error', (error) => {
+          clearTimeout(deadline);
+          resolve({ error: error.message });
+        });
+      });
+      const stdout = readCapped(files.stdoutPath);
+      const stderr = readCapped(files.stderrPath);
+      const metadata = { module: parameters.module, elapsedMs: Date.now() - startedAt, stderr };
+      if ('timedOut' in outcome) {
+        return { actionId: action.id, success: false, output: `timed out after ${timeoutMs}ms`, metadata };
+      }
+      if ('error' in outcome) {
+        return { actionId: action.id, success: false, output: outcome.error, metadata };
+      }
+      return {
+        actionId: action.id,
+        success: outcome.exitCode === 0,
+        output: outcome.exitCode === 0 ? stdout : `exit code ${outcome.exitCode}: ${stderr || stdout}`,
+        metadata: { ...metadata, exitCode: outcome.exitCode },
+      };
+    } finally {
+      fs.rmSync(scratch, { recursive: true, force: true });
+    }
+  }
+}
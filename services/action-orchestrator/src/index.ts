@@ -1,20 +1,78 @@
-import { createEventSource } from './infra/eventSource';
+import { createEventSource, EventSource } from './infra/eventSource';
 import { executeActions } from './actions';
+import { startAdminServer } from './infra/adminServer';
+import { JetStreamWorkQueue, loadWorkQueueConfig } from './infra/workQueue';
 import { logger } from './logger';
-import { TimerEvent } from './types';
+import { ActionJournal, TimerEvent, TimerInstance } from './types';
 
-const handleEvent = async (event: TimerEvent): Promise<void> => {
+const processFiredTimer = async (
+  timer: TimerInstance,
+  eventSource: EventSource,
+  journal?: ActionJournal,
+): Promise<void> => {
+  const report = await executeActions(timer, journal);
+  logger.info({ timerId: timer.id, report }, 'Timer actions executed');
+  if (report.length > 0) {
+    await eventSource.reportExecution?.(timer, report);
+  }
+};
+
+const handleEvent = async (
+  event: TimerEvent,
+  eventSource: EventSource,
+  workQueue?: JetStreamWorkQueue,
+): Promise<void> => {
   switch (event.type) {
     case 'scheduled':
       logger.debug({ timerId: event.data.id }, 'Timer scheduled');
       break;
+    case 'pre_fire':
+      logger.info(
+        { timerId: event.data.timer.id, firesInMs: event.data.firesInMs },
+        'Timer about to fire',
+      );
+      break;
+    case 'rescheduled':
+      logger.info(
+        {
+          timerId: event.data.timer.id,
+          previousFireAt: event.data.previousFireAt,
+          fireAt: event.data.timer.fireAt,
+        },
+        'Timer rescheduled',
+      );
+      break;
     case 'fired':
-      logger.info({ timerId: event.data.id }, 'Timer fired — executing actions');
-      await executeActions(event.data);
+      if (workQueue) {
+        // Resolving only once queued keeps the kernel delivery unacknowledged until the timer is durable.
+        await workQueue.enqueue(event.data);
+        logger.info({ timerId: event.data.id }, 'Timer fired — queued for execution');
+      } else {
+        logger.info({ timerId: event.data.id }, 'Timer fired — executing actions');
+        await processFiredTimer(event.data, eventSource);
+      }
       break;
     case 'cancelled':
       logger.info({ timerId: event.data.timer.id, reason: event.data.reason }, 'Timer cancelled');
       break;
+    case 'missed':
+      logger.warn(
+        { timerId: event.data.timer.id, lateByMs: event.data.lateByMs },
+        'Timer missed during kernel downtime — actions skipped',
+      );
+      break;
+    case 'acknowledged':
+      logger.info({ timerId: event.data.id }, 'Deadline acknowledged');
+      break;
+    case 'failed':
+      logger.warn(
+        { timerId: event.data.id, failureReason: event.data.failureReason },
+        'Timer failed',
+      );
+      break;
+    case 'settled':
+      logger.debug({ timerId: event.data.id }, 'Timer settled');
+      break;
     default:
       logger.warn({ event }, 'Unhandled timer event');
   }
@@ -22,11 +80,17 @@ const handleEvent = async (event: TimerEvent): Promise<void> => {
 
 const bootstrap = async () => {
   const eventSource = await createEventSource();
-  await eventSource.start(handleEvent);
+  const workQueueConfig = loadWorkQueueConfig();
+  const workQueue = workQueueConfig ? new JetStreamWorkQueue(workQueueConfig) : undefined;
+  await workQueue?.start((timer, journal) => processFiredTimer(timer, eventSource, journal));
+  await eventSource.start((event) => handleEvent(event, eventSource, workQueue));
+  const adminServer = startAdminServer();
 
   const shutdown = async () => {
     logger.info('Shutting down action orchestrator');
     await eventSource.stop();
+    await workQueue?.stop();
+    adminServer?.close();
     process.exit(0);
   };
 
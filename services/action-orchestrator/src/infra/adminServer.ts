@@ -0,0 +1,77 @@
+import http from 'node:http';
+
+import { deadLetters, destinationGuard, replayDeadLetters } from '../actions';
+import { logger } from '../logger';
+
+const MAX_BODY_BYTES = 64 * 1024;
+
+const send = (response: http.ServerResponse, status: number, body: unknown) => {
+  response.writeHead(status, { 'content-type': 'application/json' });
+  response.end(JSON.stringify(body));
+};
+
+const readBody = (request: http.IncomingMessage): Promise<string> =>
+  new Promise((resolve, reject) => {
+    let body = '';
+    request.setEncoding('utf8');
+    request.on('data', (chunk: string) => {
+      body += chunk;
+      if (body.length > MAX_BODY_BYTES) {
+        reject(new Error('request body too large'));
+        request.destroy();
+      }
+    });
+    request.on('end', () => resolve(body));
+    request.on('error', reject);
+  });
+
+/**
+ * Operator endpoints for dead-lettered actions, served on `ORCHESTRATOR_ADMIN_PORT` when set:
+ * `GET /dead-letters` lists them and `POST /dead-letters/replay` (optional body `{"ids": [...]}`)
+ * executes them again. `GET /metrics` serves per-host breaker and rate-limit metrics for
+ * Prometheus. Requests need `authorization: Bearer $ORCHESTRATOR_ADMIN_TOKEN` when that is set.
+ */
+export const startAdminServer = (env: NodeJS.ProcessEnv = process.env): http.Server | undefined => {
+  const port = env.ORCHESTRATOR_ADMIN_PORT;
+  if (!port) {
+    return undefined;
+  }
+  const token = env.ORCHESTRATOR_ADMIN_TOKEN;
+  if (!token) {
+    logger.warn('ORCHESTRATOR_ADMIN_TOKEN is not set; admin endpoints are unauthenticated');
+  }
+
+  const server = http.createServer(async (request, response) => {
+    if (token && request.headers.authorization !== `Bearer ${token}`) {
+      send(response, 401, { error: 'unauthorized' });
+      return;
+    }
+    try {
+      if (request.method === 'GET' && request.url === '/metrics') {
+        response.writeHead(200, { 'content-type': 'text/plain; version=0.0.4' });
+        response.end(destinationGuard.metrics());
+      } else if (request.method === 'GET' && request.url === '/dead-letters') {
+        send(response, 200, { deadLetters: deadLetters.list() });
+      } else if (request.method === 'POST' && request.url === '/dead-letters/replay') {
+        const body = await readBody(request);
+        const ids = body ? (JSON.parse(body) as { ids?: unknown }).ids : undefined;
+        if (ids !== undefined && !(Array.isArray(ids) && ids.every((id) => typeof id === 'string'))) {
+          send(response, 400, { error: '`ids` must be an array of dead letter ids' });
+          return;
+        }
+        const replayed = await replayDeadLetters(ids as string[] | undefined);
+        send(response, 200, {
+          replayed: replayed.map((letter) => ({ id: letter.id, result: letter.replayed })),
+          remaining: deadLetters.list().length,
+        });
+      } else {
+        send(response, 404, { error: 'not found' });
+      }
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      send(response, error instanceof SyntaxError ? 400 : 500, { error: message });
+    }
+  });
+  server.listen(Number(port), () => logger.info({ port: Number(port) }, 'Admin endpoints listening'));
+  return server;
+};
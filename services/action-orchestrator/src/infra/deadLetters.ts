@@ -0,0 +1,86 @@
+import { randomUUID } from 'node:crypto';
+import fs from 'node:fs';
+import path from 'node:path';
+
+import { logger } from '../logger';
+import { ExecutionResult, TimerAction, TimerInstance } from '../types';
+
+export interface DeadLetter {
+  id: string;
+  timer: TimerInstance;
+  action: TimerAction;
+  attempts: number;
+  lastResult: ExecutionResult;
+  failedAt: string;
+}
+
+/**
+ * Actions that failed permanently or ran out of retries, kept in a JSON file
+ * (`ORCHESTRATOR_DLQ_PATH`) so they survive restarts until an operator replays them.
+ */
+export class DeadLetterStore {
+  private readonly letters = new Map<string, DeadLetter>();
+
+  constructor(
+    private readonly filePath = process.env.ORCHESTRATOR_DLQ_PATH ?? path.resolve('data/action-dead-letters.json'),
+  ) {
+    try {
+      const stored = JSON.parse(fs.readFileSync(this.filePath, 'utf8')) as DeadLetter[];
+      for (const letter of stored) {
+        this.letters.set(letter.id, letter);
+      }
+      logger.info({ path: this.filePath, count: this.letters.size }, 'Loaded action dead letters');
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code !== 'ENOENT') {
+        throw error;
+      }
+    }
+  }
+
+  add(timer: TimerInstance, action: TimerAction, lastResult: ExecutionResult): DeadLetter {
+    const letter: DeadLetter = {
+      id: randomUUID(),
+      timer,
+      action,
+      attempts: lastResult.attempts ?? 1,
+      lastResult,
+      failedAt: new Date().toISOString(),
+    };
+    this.letters.set(letter.id, letter);
+    this.persist();
+    return letter;
+  }
+
+  /** Oldest first. */
+  list(): DeadLetter[] {
+    return [...this.letters.values()];
+  }
+
+  get(id: string): DeadLetter | undefined {
+    return this.letters.get(id);
+  }
+
+  /** Drops a replayed letter, or records the failed replay on it. */
+  settle(id: string, result: ExecutionResult): void {
+    const letter = this.letters.get(id);
+    if (!letter) {
+      return;
+    }
+    if (result.success) {
+      this.letters.delete(id);
+    } else {
+      letter.attempts += result.attempts ?? 1;
+      letter.lastResult = result;
+      letter.failedAt = new Date().toISOString();
+    }
+    this.persist();
+  }
+
+  // Written to a temporary file and renamed, so a crash never leaves a truncated store.
+  private persist(): void {
+    fs.mkdirSync(path.dirname(this.filePath), { recursive: true });
+    const temporary = `${this.filePath}.tmp`;
+    fs.writeFileSync(temporary, JSON.stringify(this.list(), null, 2));
+    fs.renameSync(temporary, this.filePath);
+  }
+}
@@ -0,0 +1,64 @@
+import { AxiosProxyConfig } from 'axios';
+
+export interface EgressPolicy {
+  enforced: boolean;
+  proxy?: AxiosProxyConfig;
+  allowedHosts: string[];
+}
+
+export type EgressDecision = { allowed: true } | { allowed: false; reason: string };
+
+const parseProxy = (value: string): AxiosProxyConfig => {
+  const url = new URL(value);
+  const protocol = url.protocol.replace(':', '');
+  return {
+    protocol,
+    host: url.hostname,
+    port: url.port ? Number(url.port) : protocol === 'https' ? 443 : 80,
+    auth: url.username
+      ? { username: decodeURIComponent(url.username), password: decodeURIComponent(url.password) }
+      : undefined,
+  };
+};
+
+/**
+ * Reads the egress policy from the environment. In enforced mode (`ORCHESTRATOR_EGRESS_MODE=enforced`)
+ * every outbound request goes through `ORCHESTRATOR_EGRESS_PROXY` and may only target hosts listed in
+ * `ORCHESTRATOR_EGRESS_ALLOWLIST` (comma separated, `*.example.com` matches subdomains).
+ */
+export const loadEgressPolicy = (env: NodeJS.ProcessEnv = process.env): EgressPolicy => {
+  const enforced = env.ORCHESTRATOR_EGRESS_MODE === 'enforced';
+  const proxyUrl = env.ORCHESTRATOR_EGRESS_PROXY;
+  if (enforced && !proxyUrl) {
+    throw new Error('ORCHESTRATOR_EGRESS_MODE=enforced requires ORCHESTRATOR_EGRESS_PROXY');
+  }
+  const allowedHosts = (env.ORCHESTRATOR_EGRESS_ALLOWLIST ?? '')
+    .split(',')
+    .map((host) => host.trim().toLowerCase())
+    .filter((host) => host.length > 0);
+
+  return {
+    enforced,
+    proxy: proxyUrl ? parseProxy(proxyUrl) : undefined,
+    allowedHosts,
+  };
+};
+
+const hostMatches = (host: string, pattern: string): boolean => {
+  if (pattern.startsWith('*.')) {
+    const suffix = pattern.slice(1);
+    return host.endsWith(suffix) && host.length > suffix.length;
+  }
+  return host === pattern;
+};
+
+export const checkDestination = (policy: EgressPolicy, target: string): EgressDecision => {
+  if (!policy.enforced) {
+    return { allowed: true };
+  }
+  const host = new URL(target).hostname.toLowerCase();
+  if (policy.allowedHosts.some((pattern) => hostMatches(host, pattern))) {
+    return { allowed: true };
+  }
+  return { allowed: false, reason: `Destination host "${host}" is not in the egress allow-list` };
+};
@@ -7,14 +7,21 @@ import { connect, NatsConnection, StringCodec, Subscription } from 'nats';
 import { z } from 'zod';
 
 import { logger } from '../logger';
-import { TimerEvent, TimerInstance } from '../types';
+import { ExecutionResult, TimerEvent, TimerInstance } from '../types';
+
+const retryPolicySchema = z.object({
+  maxAttempts: z.number().int().positive().optional(),
+  initialBackoffMs: z.number().nonnegative().optional(),
+  backoffMultiplier: z.number().min(1).optional(),
+  maxBackoffMs: z.number().nonnegative().optional(),
+});
 
 const timerInstanceSchema = z.object({
   id: z.string(),
   tenantId: z.string(),
   name: z.string(),
   requestedBy: z.string(),
-  status: z.enum(['scheduled', 'armed', 'fired', 'cancelled', 'failed']),
+  status: z.enum(['scheduled', 'armed', 'fired', 'cancelled', 'failed', 'missed', 'acknowledged', 'settled']),
   fireAt: z.string(),
   createdAt: z.string(),
   durationMs: z.number(),
@@ -28,16 +35,31 @@ const timerInstanceSchema = z.object({
             id: z.string(),
             kind: z.string(),
             parameters: z.record(z.any()).default({}),
+            idempotency: z
+              .object({ cacheKey: z.string().min(1), ttlSeconds: z.number().positive().optional() })
+              .optional(),
+            retryPolicy: retryPolicySchema.optional(),
           }),
         )
         .default([]),
       concurrency: z.number().optional(),
+      headers: z.record(z.string()).optional(),
+      retryPolicy: retryPolicySchema.optional(),
+    })
+    .optional(),
+  agentBinding: z
+    .object({
+      adapter: z.enum(['mcp', 'langchain', 'autogen', 'custom']).optional(),
+      target: z.string().optional(),
+      payloadTemplate: z.record(z.any()).optional(),
+      acknowledgementTimeoutMs: z.number().int().positive().optional(),
     })
     .optional(),
   firedAt: z.string().optional(),
   cancelledAt: z.string().optional(),
   cancelReason: z.string().optional(),
   cancelledBy: z.string().optional(),
+  failureReason: z.object({ code: z.string(), message: z.string() }).optional(),
 });
 
 const timerEventSchema: z.ZodType<TimerEvent> = z.union([
@@ -45,6 +67,14 @@ const timerEventSchema: z.ZodType<TimerEvent> = z.union([
     type: z.literal('scheduled'),
     data: timerInstanceSchema,
   }) as z.ZodType<TimerEvent>,
+  z.object({
+    type: z.literal('pre_fire'),
+    data: z.object({ timer: timerInstanceSchema, firesInMs: z.number() }),
+  }) as z.ZodType<TimerEvent>,
+  z.object({
+    type: z.literal('rescheduled'),
+    data: z.object({ timer: timerInstanceSchema, previousFireAt: z.string() }),
+  }) as z.ZodType<TimerEvent>,
   z.object({
     type: z.literal('fired'),
     data: timerInstanceSchema,
@@ -53,12 +83,38 @@ const timerEventSchema: z.ZodType<TimerEvent> = z.union([
     type: z.literal('cancelled'),
     data: z.object({ timer: timerInstanceSchema, reason: z.string().optional() }),
   }) as z.ZodType<TimerEvent>,
+  z.object({
+    type: z.literal('missed'),
+    data: z.object({ timer: timerInstanceSchema, lateByMs: z.number() }),
+  }) as z.ZodType<TimerEvent>,
+  z.object({
+    type: z.literal('acknowledged'),
+    data: timerInstanceSchema,
+  }) as z.ZodType<TimerEvent>,
+  z.object({
+    type: z.literal('failed'),
+    data: timerInstanceSchema,
+  }) as z.ZodType<TimerEvent>,
+  z.object({
+    type: z.literal('settled'),
+    data: timerInstanceSchema,
+  }) as z.ZodType<TimerEvent>,
 ]);
 
 type EventHandler = (event: TimerEvent) => Promise<void>;
 
 type GrpcKernelClient = grpc.Client & {
-  streamTimerEvents: (request: any) => grpc.ClientReadableStream<any>;
+  streamTimerEvents: (request: any, metadata: grpc.Metadata) => grpc.ClientReadableStream<any>;
+  acknowledgeDelivery: (
+    request: any,
+    metadata: grpc.Metadata,
+    callback: (error: grpc.ServiceError | null) => void,
+  ) => void;
+  reportTimerExecution: (
+    request: any,
+    metadata: grpc.Metadata,
+    callback: (error: grpc.ServiceError | null) => void,
+  ) => void;
 };
 
 const loaderOptions: protoLoader.Options = {
@@ -94,48 +150,174 @@ const loadKernelClientCtor = (): KernelClientConstructor => {
 export interface EventSource {
   start(handler: EventHandler): Promise<void>;
   stop(): Promise<void>;
+  /**
+   * Tells the kernel how a fired timer's actions went, so it settles or fails the timer. Only
+   * sources connected to the kernel can; the others leave fired timers as they are.
+   */
+  reportExecution?(timer: TimerInstance, results: ExecutionResult[]): Promise<void>;
 }
 
+const RECONNECT_INITIAL_DELAY_MS = 1_000;
+const RECONNECT_MAX_DELAY_MS = 30_000;
+
 export class GrpcEventSource implements EventSource {
   private client?: GrpcKernelClient;
   private stream?: grpc.ClientReadableStream<any>;
-
-  constructor(private readonly address: string, private readonly tenantId: string) {}
+  private handler?: EventHandler;
+  private reconnectTimer?: NodeJS.Timeout;
+  private stopped = false;
+  // Sequence of the last event received, kept as a string because sequences are uint64.
+  private lastSequence = '0';
+  // Doubles with every reconnect that yields no event, so a down kernel is not hammered.
+  private reconnectDelayMs = RECONNECT_INITIAL_DELAY_MS;
+
+  constructor(
+    private readonly address: string,
+    private readonly tenantId: string,
+    private readonly subscriberId: string,
+    private readonly apiToken?: string,
+    private readonly topics: string[] = [],
+  ) {}
 
   async start(handler: EventHandler): Promise<void> {
     const ClientCtor = loadKernelClientCtor();
     this.client = new ClientCtor(this.address, grpc.credentials.createInsecure());
-    const request = { tenantId: this.tenantId, topics: [] as string[] };
-    this.stream = this.client.streamTimerEvents(request);
+    this.handler = handler;
+    this.stopped = false;
+    this.subscribe();
+    logger.info({ address: this.address, tenantId: this.tenantId }, 'Subscribed to horology kernel via gRPC');
+  }
+
+  async stop(): Promise<void> {
+    this.stopped = true;
+    clearTimeout(this.reconnectTimer);
+    this.stream?.cancel();
+    this.client?.close?.();
+  }
 
-    this.stream.on('data', (message) => {
+  // Opens the event stream, resuming after the last received sequence so events emitted while
+  // disconnected are replayed rather than lost.
+  private subscribe(): void {
+    const handler = this.handler;
+    if (!this.client || !handler) {
+      return;
+    }
+    const request = {
+      tenantId: this.tenantId,
+      topics: this.topics,
+      subscriberId: this.subscriberId,
+      resumeFromSequence: this.lastSequence,
+    };
+    const stream = this.client.streamTimerEvents(request, this.metadata());
+    this.stream = stream;
+
+    stream.on('data', (message) => {
+      this.reconnectDelayMs = RECONNECT_INITIAL_DELAY_MS;
       try {
+        const sequence = String(message?.sequence ?? '0');
+        if (sequence !== '0') {
+          this.lastSequence = sequence;
+        }
         const event = convertGrpcEvent(message);
         if (!event) {
           return;
         }
-        handler(event).catch((error) => {
-          logger.error({ error }, 'Timer handler failed for gRPC event');
-        });
+        handler(event)
+          .then(() => {
+            if (event.type === 'fired') {
+              this.acknowledge(event.data);
+            }
+          })
+          .catch((error) => {
+            logger.error({ error }, 'Timer handler failed for gRPC event');
+          });
       } catch (error) {
         logger.error({ error, message }, 'Failed to process gRPC timer event');
       }
     });
 
-    this.stream.on('error', (error) => {
-      logger.error({ error }, 'gRPC timer event stream error');
+    stream.on('error', (error: grpc.ServiceError) => {
+      if (this.stopped) {
+        return;
+      }
+      if (error.code === grpc.status.OUT_OF_RANGE || error.code === grpc.status.FAILED_PRECONDITION) {
+        // The kernel cannot replay from our position (retention dropped events, or it keeps no
+        // history); fall back to live events and leave the gap to reconciliation.
+        logger.warn({ error, lastSequence: this.lastSequence }, 'Kernel cannot resume timer events; streaming live only');
+        this.lastSequence = '0';
+      } else {
+        logger.error({ error }, 'gRPC timer event stream error');
+      }
+      this.reconnect(stream);
     });
 
-    this.stream.on('end', () => {
-      logger.warn('gRPC timer event stream ended');
+    stream.on('end', () => {
+      if (this.stopped) {
+        return;
+      }
+      logger.warn({ lastSequence: this.lastSequence }, 'gRPC timer event stream ended');
+      this.reconnect(stream);
     });
+  }
 
-    logger.info({ address: this.address, tenantId: this.tenantId }, 'Subscribed to horology kernel via gRPC');
+  private reconnect(stream: grpc.ClientReadableStream<any>): void {
+    // A failed stream reports both `error` and `end`; reconnect once.
+    if (this.stream !== stream) {
+      return;
+    }
+    this.stream = undefined;
+    // Up to 20% jitter so orchestrator replicas do not reconnect in lockstep after a kernel restart.
+    const delayMs = Math.round(this.reconnectDelayMs * (1 + Math.random() * 0.2));
+    this.reconnectDelayMs = Math.min(this.reconnectDelayMs * 2, RECONNECT_MAX_DELAY_MS);
+    logger.info({ lastSequence: this.lastSequence, delayMs }, 'Reconnecting to gRPC timer event stream');
+    this.reconnectTimer = setTimeout(() => {
+      logger.info({ lastSequence: this.lastSequence }, 'Resuming gRPC timer event stream');
+      this.subscribe();
+    }, delayMs);
   }
 
-  async stop(): Promise<void> {
-    this.stream?.cancel();
-    this.client?.close?.();
+  private metadata(): grpc.Metadata {
+    const metadata = new grpc.Metadata();
+    if (this.apiToken) {
+      metadata.set('authorization', `Bearer ${this.apiToken}`);
+    }
+    return metadata;
+  }
+
+  reportExecution(timer: TimerInstance, results: ExecutionResult[]): Promise<void> {
+    const request = {
+      tenantId: timer.tenantId,
+      timerId: timer.id,
+      actions: results.map((result) => ({
+        actionId: result.actionId,
+        success: result.success,
+        output: result.output ?? '',
+        metadataJson: result.metadata ? JSON.stringify(result.metadata) : '',
+        durationMs: result.durationMs ?? 0,
+        error: result.success ? '' : result.output ?? 'action failed',
+      })),
+    };
+    return new Promise((resolve) => {
+      if (!this.client) {
+        resolve();
+        return;
+      }
+      this.client.reportTimerExecution(request, this.metadata(), (error) => {
+        if (error) {
+          logger.warn({ error, timerId: timer.id }, 'Failed to report timer execution');
+        }
+        resolve();
+      });
+    });
+  }
+
+  private acknowledge(timer: TimerInstance): void {
+    const request = { tenantId: timer.tenantId, timerId: timer.id, subscriberId: this.subscriberId };
+    this.client?.acknowledgeDelivery(request, this.metadata(), (error) => {
+      if (error) {
+        logger.warn({ error, timerId: timer.id }, 'Failed to acknowledge timer delivery');
+      }
+    });
   }
 }
 
@@ -198,7 +380,13 @@ export const createEventSource = async (): Promise<EventSource> => {
   const grpcUrl = process.env.KERNEL_GRPC_URL || process.env.KERNEL_GRPC_ADDR;
   if (grpcUrl) {
     const tenantId = process.env.KERNEL_EVENT_TENANT_ID || process.env.EVENT_TENANT_ID || '__all__';
-    return new GrpcEventSource(grpcUrl, tenantId);
+    const subscriberId = process.env.ORCHESTRATOR_SUBSCRIBER_ID || 'action-orchestrator';
+    // e.g. `fired,cancelled`; the kernel then only streams those event types.
+    const topics = (process.env.KERNEL_EVENT_TOPICS ?? '')
+      .split(',')
+      .map((topic) => topic.trim())
+      .filter((topic) => topic.length > 0);
+    return new GrpcEventSource(grpcUrl, tenantId, subscriberId, process.env.KERNEL_API_TOKEN, topics);
   }
 
   const servers = process.env.NATS_URL;
@@ -220,6 +408,21 @@ const convertGrpcEvent = (message: any): TimerEvent | null => {
       const timer = convertGrpcTimer(message.scheduled?.timer);
       return timer ? { type: 'scheduled', data: timer } : null;
     }
+    case 'preFire': {
+      const timer = convertGrpcTimer(message.preFire?.timer);
+      return timer
+        ? { type: 'pre_fire', data: { timer, firesInMs: Number(message.preFire?.firesInMs ?? 0) } }
+        : null;
+    }
+    case 'rescheduled': {
+      const timer = convertGrpcTimer(message.rescheduled?.timer);
+      return timer
+        ? {
+            type: 'rescheduled',
+            data: { timer, previousFireAt: String(message.rescheduled?.previousFireTimeIso ?? '') },
+          }
+        : null;
+    }
     case 'fired': {
       const timer = convertGrpcTimer(message.fired?.timer);
       return timer ? { type: 'fired', data: timer } : null;
@@ -232,6 +435,34 @@ const convertGrpcEvent = (message: any): TimerEvent | null => {
       const reason = optionalString(message.cancelled?.reason);
       return { type: 'cancelled', data: { timer, reason } };
     }
+    case 'missed': {
+      const timer = convertGrpcTimer(message.missed?.timer);
+      return timer
+        ? { type: 'missed', data: { timer, lateByMs: Number(message.missed?.lateByMs ?? 0) } }
+        : null;
+    }
+    case 'acknowledged': {
+      const timer = convertGrpcTimer(message.acknowledged?.timer);
+      return timer ? { type: 'acknowledged', data: timer } : null;
+    }
+    case 'failed': {
+      const timer = convertGrpcTimer(message.failed?.timer);
+      return timer ? { type: 'failed', data: timer } : null;
+    }
+    case 'settled': {
+      const timer = convertGrpcTimer(message.settled?.timer);
+      return timer ? { type: 'settled', data: timer } : null;
+    }
+    case 'throttled':
+      logger.warn(
+        {
+          reason: message.throttled?.reason,
+          droppedEvents: Number(message.throttled?.droppedEvents ?? 0),
+          droppedBytes: Number(message.throttled?.droppedBytes ?? 0),
+        },
+        'Kernel dropped timer events for exceeding this subscriber\'s stream caps',
+      );
+      return null;
     default:
       return null;
   }
@@ -254,10 +485,15 @@ const convertGrpcTimer = (payload: any): TimerInstance | null => {
     metadata: parseJson(payload.metadataJson),
     labels: convertStringMap(payload.labels),
     actionBundle: parseJson(payload.actionBundleJson) as TimerInstance['actionBundle'],
+    agentBinding: parseJson(payload.agentBindingJson) as TimerInstance['agentBinding'],
     firedAt: optionalString(payload.firedAtIso),
     cancelledAt: optionalString(payload.cancelledAtIso),
     cancelReason: optionalString(payload.cancelReason),
     cancelledBy: optionalString(payload.cancelledBy),
+    priority: Number(payload.priority ?? 0),
+    failureReason: payload.failureReason
+      ? { code: String(payload.failureReason.code ?? ''), message: String(payload.failureReason.message ?? '') }
+      : undefined,
   };
 
   return timer;
@@ -282,6 +518,18 @@ const mapStatus = (status: unknown): TimerInstance['status'] => {
     case 'failed':
     case '5':
       return 'failed';
+    case 'TIMER_STATUS_MISSED':
+    case 'missed':
+    case '6':
+      return 'missed';
+    case 'TIMER_STATUS_ACKNOWLEDGED':
+    case 'acknowledged':
+    case '7':
+      return 'acknowledged';
+    case 'TIMER_STATUS_SETTLED':
+    case 'settled':
+    case '8':
+      return 'settled';
     case 'TIMER_STATUS_SCHEDULED':
     case 'scheduled':
     case '1':
@@ -0,0 +1,151 @@
+import {
+  AckPolicy,
+  connect,
+  JetStreamClient,
+  JSONCodec,
+  JsMsg,
+  KV,
+  nanos,
+  NatsConnection,
+  RetentionPolicy,
+  StorageType,
+} from 'nats';
+
+import { logger } from '../logger';
+import { ActionJournal, ExecutionResult, TimerAction, TimerInstance } from '../types';
+
+const STREAM = 'MINOOTS_ORCHESTRATOR_WORK';
+const SUBJECT = 'minoots.orchestrator.work';
+const PROGRESS_BUCKET = 'minoots-action-progress';
+// Completed actions are remembered this long, well past any redelivery of their timer.
+const PROGRESS_TTL_MS = 24 * 60 * 60 * 1000;
+// Timers re-enqueued within this window (e.g. replayed by the kernel after a reconnect) are dropped.
+const DUPLICATE_WINDOW_MS = 10 * 60 * 1000;
+
+export interface WorkQueueConfig {
+  servers: string;
+  durable: string;
+  /** How long a delivered timer may go without progress before JetStream redelivers it. */
+  ackWaitMs: number;
+  /** Timers processed at once. */
+  maxInFlight: number;
+}
+
+export const loadWorkQueueConfig = (env: NodeJS.ProcessEnv = process.env): WorkQueueConfig | undefined => {
+  const servers = env.ORCHESTRATOR_WORK_QUEUE_URL;
+  if (!servers) {
+    return undefined;
+  }
+  const ackWaitMs = Number(env.ORCHESTRATOR_WORK_QUEUE_ACK_WAIT_MS ?? 60_000);
+  const maxInFlight = Number(env.ORCHESTRATOR_WORK_QUEUE_MAX_IN_FLIGHT ?? 16);
+  if (!Number.isInteger(ackWaitMs) || ackWaitMs <= 0 || !Number.isInteger(maxInFlight) || maxInFlight <= 0) {
+    throw new Error('ORCHESTRATOR_WORK_QUEUE_ACK_WAIT_MS and _MAX_IN_FLIGHT must be positive integers');
+  }
+  return { servers, durable: env.ORCHESTRATOR_SUBSCRIBER_ID || 'action-orchestrator', ackWaitMs, maxInFlight };
+};
+
+const codec = JSONCodec<unknown>();
+
+// KV keys only allow a restricted alphabet; action ids are free-form.
+const progressKey = (timer: TimerInstance, action: TimerAction) =>
+  `${timer.id}.${Buffer.from(action.id).toString('base64url')}`;
+
+class JetStreamJournal implements ActionJournal {
+  constructor(private readonly kv: KV) {}
+
+  async completed(timer: TimerInstance, action: TimerAction): Promise<ExecutionResult | undefined> {
+    const entry = await this.kv.get(progressKey(timer, action));
+    return entry && entry.operation === 'PUT' ? (codec.decode(entry.value) as ExecutionResult) : undefined;
+  }
+
+  async record(timer: TimerInstance, action: TimerAction, result: ExecutionResult): Promise<void> {
+    await this.kv.put(progressKey(timer, action), codec.encode(result));
+  }
+}
+
+export type WorkHandler = (timer: TimerInstance, journal: ActionJournal) => Promise<void>;
+
+/**
+ * Durable hand-off between receiving a fired timer and executing its actions, on a JetStream
+ * work-queue stream. A timer is enqueued once (deduplicated by timer id) before its delivery is
+ * acknowledged to the kernel, and acknowledged to JetStream only after its actions ran and were
+ * reported. If the orchestrator dies in between, JetStream redelivers the timer after `ackWaitMs`;
+ * actions already recorded in the progress bucket under timer id and action id are skipped, so
+ * only the actions that were in flight run again.
+ */
+export class JetStreamWorkQueue {
+  private connection?: NatsConnection;
+  private jetstream?: JetStreamClient;
+  private journal?: JetStreamJournal;
+  private stopped = false;
+
+  constructor(private readonly config: WorkQueueConfig) {}
+
+  async start(handler: WorkHandler): Promise<void> {
+    this.connection = await connect({ servers: this.config.servers });
+    const manager = await this.connection.jetstreamManager();
+    const streamConfig = {
+      name: STREAM,
+      subjects: [SUBJECT],
+      retention: RetentionPolicy.Workqueue,
+      storage: StorageType.File,
+      duplicate_window: nanos(DUPLICATE_WINDOW_MS),
+    };
+    try {
+      await manager.streams.info(STREAM);
+    } catch {
+      await manager.streams.add(streamConfig);
+    }
+    await manager.consumers.add(STREAM, {
+      durable_name: this.config.durable,
+      ack_policy: AckPolicy.Explicit,
+      ack_wait: nanos(this.config.ackWaitMs),
+    });
+    this.jetstream = this.connection.jetstream();
+    this.journal = new JetStreamJournal(await this.jetstream.views.kv(PROGRESS_BUCKET, { ttl: PROGRESS_TTL_MS }));
+    const consumer = await this.jetstream.consumers.get(STREAM, this.config.durable);
+    const messages = await consumer.consume({ max_messages: this.config.maxInFlight });
+    logger.info({ stream: STREAM, durable: this.config.durable }, 'Consuming fired timers from JetStream work queue');
+
+    void (async () => {
+      const inFlight = new Set<Promise<void>>();
+      for await (const message of messages) {
+        if (this.stopped) {
+          break;
+        }
+        const work = this.process(message, handler).finally(() => inFlight.delete(work));
+        inFlight.add(work);
+        if (inFlight.size >= this.config.maxInFlight) {
+          await Promise.race(inFlight);
+        }
+      }
+    })();
+  }
+
+  async enqueue(timer: TimerInstance): Promise<void> {
+    if (!this.jetstream) {
+      throw new Error('work queue is not started');
+    }
+    await this.jetstream.publish(SUBJECT, codec.encode(timer), { msgID: timer.id });
+  }
+
+  async stop(): Promise<void> {
+    this.stopped = true;
+    await this.connection?.drain();
+  }
+
+  private async process(message: JsMsg, handler: WorkHandler): Promise<void> {
+    const timer = codec.decode(message.data) as TimerInstance;
+    // Keeps JetStream from redelivering a timer whose actions are still running.
+    const heartbeat = setInterval(() => message.working(), Math.max(1_000, this.config.ackWaitMs / 2));
+    try {
+      await handler(timer, this.journal!);
+      message.ack();
+    } catch (error) {
+      logger.error({ error, timerId: timer.id, redeliveries: message.info.redeliveryCount }, 'Queued timer failed');
+      message.nak(this.config.ackWaitMs);
+    } finally {
+      clearInterval(heartbeat);
+    }
+  }
+}
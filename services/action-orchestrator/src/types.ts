@@ -1,9 +1,33 @@
-export type ActionKind = 'webhook' | 'command' | 'agent_prompt' | 'workflow_event';
+export type ActionKind =
+  | 'webhook'
+  | 'command'
+  | 'agent_prompt'
+  | 'workflow_event'
+  | 'slack'
+  | 'email'
+  | 'sms'
+  | 'pagerduty'
+  | 'grpc'
+  | 'nats.publish';
+
+/** How often a failed action is retried; unset fields fall back to the bundle's policy, then defaults. */
+export interface RetryPolicy {
+  maxAttempts?: number;
+  initialBackoffMs?: number;
+  backoffMultiplier?: number;
+  maxBackoffMs?: number;
+}
 
 export interface TimerAction {
   id: string;
   kind: ActionKind;
   parameters: Record<string, unknown>;
+  /** Marks the action safe to deduplicate; `cacheKey` accepts `{{timer.<path>}}` placeholders. */
+  idempotency?: {
+    cacheKey: string;
+    ttlSeconds?: number;
+  };
+  retryPolicy?: RetryPolicy;
 }
 
 export interface TimerInstance {
@@ -11,7 +35,7 @@ export interface TimerInstance {
   tenantId: string;
   name: string;
   requestedBy: string;
-  status: 'scheduled' | 'armed' | 'fired' | 'cancelled' | 'failed';
+  status: 'scheduled' | 'armed' | 'fired' | 'cancelled' | 'failed' | 'missed' | 'acknowledged' | 'settled';
   fireAt: string;
   createdAt: string;
   durationMs: number;
@@ -20,26 +44,56 @@ export interface TimerInstance {
   actionBundle?: {
     actions: TimerAction[];
     concurrency?: number;
+    headers?: Record<string, string>;
+    retryPolicy?: RetryPolicy;
+  };
+  /** Agent the timer reports to; its `payloadTemplate` is rendered for `agent_prompt` actions. */
+  agentBinding?: {
+    adapter?: 'mcp' | 'langchain' | 'autogen' | 'custom';
+    target?: string;
+    payloadTemplate?: Record<string, unknown>;
+    /** How long an `mcp` agent has to acknowledge the call before the action fails. */
+    acknowledgementTimeoutMs?: number;
   };
   firedAt?: string;
   cancelledAt?: string;
   cancelReason?: string;
   cancelledBy?: string;
+  /** Higher-priority timers that fire together reach the orchestrator first. */
+  priority?: number;
+  /** Why a failed timer failed, e.g. a deadline nobody acknowledged in time or failed actions. */
+  failureReason?: { code: string; message: string };
 }
 
 export type TimerEvent =
   | { type: 'scheduled'; data: TimerInstance }
+  | { type: 'pre_fire'; data: { timer: TimerInstance; firesInMs: number } }
+  | { type: 'rescheduled'; data: { timer: TimerInstance; previousFireAt: string } }
   | { type: 'fired'; data: TimerInstance }
-  | { type: 'cancelled'; data: { timer: TimerInstance; reason?: string } };
+  | { type: 'cancelled'; data: { timer: TimerInstance; reason?: string } }
+  | { type: 'missed'; data: { timer: TimerInstance; lateByMs: number } }
+  | { type: 'acknowledged'; data: TimerInstance }
+  | { type: 'failed'; data: TimerInstance }
+  | { type: 'settled'; data: TimerInstance };
 
 export interface ExecutionResult {
   actionId: string;
   success: boolean;
   output?: string;
   metadata?: Record<string, unknown>;
+  /** Executions made, counting retries. */
+  attempts?: number;
+  /** Time spent on the action, retries included. */
+  durationMs?: number;
 }
 
 export interface ActionExecutor {
   canHandle(action: TimerAction): boolean;
   execute(action: TimerAction, timer: TimerInstance): Promise<ExecutionResult>;
 }
+
+/** Durable record of finished actions, so a redelivered timer skips what already ran. */
+export interface ActionJournal {
+  completed(timer: TimerInstance, action: TimerAction): Promise<ExecutionResult | undefined>;
+  record(timer: TimerInstance, action: TimerAction, result: ExecutionResult): Promise<void>;
+}
@@ -0,0 +1,63 @@
+//! Schedule-and-fire throughput with the timer table behind one lock versus split into shards.
+//! Criterion is not among the crate's dependencies, so this is a plain `harness = false` bench:
+//!
+//! ```text
+//! cargo bench --bench schedule_fire
+//! ```
+
+use std::time::{Duration, Instant};
+
+use horology_kernel::{HorologyKernel, SchedulerConfig, TimerSpec};
+
+const TASKS: usize = 8;
+const TIMERS_PER_TASK: usize = 2_000;
+
+/// Schedules `TASKS * TIMERS_PER_TASK` one-millisecond timers from concurrent tasks and waits for
+/// every one of them to fire.
+async fn schedule_and_fire(timer_shards: usize) -> Duration {
+    let kernel = HorologyKernel::new(SchedulerConfig {
+        timer_shards,
+        ..Default::default()
+    });
+    let started = Instant::now();
+    let tasks: Vec<_> = (0..TASKS)
+        .map(|task| {
+            let kernel = kernel.clone();
+            tokio::spawn(async move {
+                let tenant_id = format!("tenant-{task}");
+                let mut ids = Vec::with_capacity(TIMERS_PER_TASK);
+                for _ in 0..TIMERS_PER_TASK {
+                    let timer = kernel
+                        .schedule(TimerSpec {
+                            tenant_id: tenant_id.clone(),
+                            requested_by: "bench".into(),
+                            duration_ms: 1,
+                            ..Default::default()
+                        })
+                        .await
+                        .expect("schedule");
+                    ids.push(timer.id);
+                }
+                for id in ids {
+                    kernel.wait(&tenant_id, id).await;
+                }
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.expect("bench task");
+    }
+    started.elapsed()
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() {
+    let timers = (TASKS * TIMERS_PER_TASK) as f64;
+    for timer_shards in [1, 4, 16, 64] {
+        let elapsed = schedule_and_fire(timer_shards).await;
+        println!(
+            "shards={timer_shards:>3} timers={timers} elapsed={elapsed:?} throughput={:.0}/s",
+            timers / elapsed.as_secs_f64()
+        );
+    }
+}
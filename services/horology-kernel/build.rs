@@ -1,9 +1,199 @@
+#[cfg(feature = "grpc")]
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let proto_path = std::path::PathBuf::from("../../proto/timer.proto");
     println!("cargo:rerun-if-changed={}", proto_path.display());
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
+    let descriptor_path = out_dir.join("minoots_timer_descriptor.bin");
+
+    // Operator admin service; its messages reuse the kernel's from `crate::pb`. Compiled first:
+    // it also writes an empty `minoots.timer.v1.rs`, which the kernel compile then replaces.
+    let proto_root = proto_path.parent().unwrap();
+    let admin_path = proto_root.join("admin.proto");
+    println!("cargo:rerun-if-changed={}", admin_path.display());
     tonic_build::configure()
         .build_server(true)
         .build_client(true)
-        .compile(&[proto_path.clone()], &[proto_path.parent().unwrap()])?;
+        .file_descriptor_set_path(out_dir.join("minoots_admin_descriptor.bin"))
+        .extern_path(".minoots.timer.v1", "crate::pb")
+        .compile(std::slice::from_ref(&admin_path), &[proto_root])?;
+
+    tonic_build::configure()
+        .build_server(true)
+        .build_client(true)
+        .file_descriptor_set_path(&descriptor_path)
+        // A batch result or stream ack is either a full timer or a small error.
+        .type_attribute(
+            "minoots.timer.v1.ScheduleTimersBatchResult.result",
+            "#[allow(clippy::large_enum_variant)]",
+        )
+        .type_attribute(
+            "minoots.timer.v1.ScheduleTimerStreamAck.result",
+            "#[allow(clippy::large_enum_variant)]",
+        )
+        .compile(std::slice::from_ref(&proto_path), &[proto_root])?;
+
+    // Standard gRPC health checking and server reflection, served next to the kernel's service.
+    let standard = [
+        proto_root.join("grpc/health/v1/health.proto"),
+        proto_root.join("grpc/reflection/v1/reflection.proto"),
+    ];
+    for path in &standard {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+    tonic_build::configure()
+        .build_server(true)
+        .build_client(true)
+        .file_descriptor_set_path(out_dir.join("grpc_standard_descriptor.bin"))
+        .compile(&standard, &[proto_root])?;
+
+    let descriptors: prost_types::FileDescriptorSet =
+        prost::Message::decode(std::fs::read(&descriptor_path)?.as_slice())?;
+    std::fs::write(
+        out_dir.join("minoots_timer_openapi.json"),
+        serde_json::to_string_pretty(&openapi::document(&descriptors))?,
+    )?;
     Ok(())
 }
+
+#[cfg(not(feature = "grpc"))]
+fn main() {}
+
+/// OpenAPI 3 rendering of the gRPC contract for HTTP gateways: each RPC becomes `POST
+/// /v1/<Service>/<Method>` taking and returning the proto3 JSON form of its messages.
+#[cfg(feature = "grpc")]
+mod openapi {
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{DescriptorProto, FieldDescriptorProto, FileDescriptorSet};
+    use serde_json::{json, Map, Value};
+
+    pub fn document(descriptors: &FileDescriptorSet) -> Value {
+        let mut schemas = Map::new();
+        let mut paths = Map::new();
+        for file in &descriptors.file {
+            let package = file.package();
+            for message in &file.message_type {
+                add_message(&mut schemas, package, "", message);
+            }
+            for enumeration in &file.enum_type {
+                let values: Vec<&str> =
+                    enumeration.value.iter().map(|value| value.name()).collect();
+                schemas.insert(
+                    enumeration.name().to_string(),
+                    json!({"type": "string", "enum": values}),
+                );
+            }
+            for service in &file.service {
+                for method in &service.method {
+                    let mut operation = json!({
+                        "operationId": method.name(),
+                        "tags": [service.name()],
+                        "requestBody": {
+                            "required": true,
+                            "content": {"application/json": {"schema": reference(method.input_type(), package)}},
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "OK",
+                                "content": {"application/json": {"schema": reference(method.output_type(), package)}},
+                            },
+                        },
+                    });
+                    if method.server_streaming() || method.client_streaming() {
+                        operation["x-grpc-streaming"] = json!({
+                            "client": method.client_streaming(),
+                            "server": method.server_streaming(),
+                        });
+                    }
+                    paths.insert(
+                        format!("/v1/{}/{}", service.name(), method.name()),
+                        json!({"post": operation}),
+                    );
+                }
+            }
+        }
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "MINOOTS horology kernel",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "paths": paths,
+            "components": {"schemas": schemas},
+        })
+    }
+
+    /// Nested messages and enums are named `Outer.Inner`.
+    fn add_message(
+        schemas: &mut Map<String, Value>,
+        package: &str,
+        prefix: &str,
+        message: &DescriptorProto,
+    ) {
+        let name = format!("{prefix}{}", message.name());
+        for nested in &message.nested_type {
+            if !nested
+                .options
+                .as_ref()
+                .is_some_and(|options| options.map_entry())
+            {
+                add_message(schemas, package, &format!("{name}."), nested);
+            }
+        }
+        for enumeration in &message.enum_type {
+            let values: Vec<&str> = enumeration.value.iter().map(|value| value.name()).collect();
+            schemas.insert(
+                format!("{name}.{}", enumeration.name()),
+                json!({"type": "string", "enum": values}),
+            );
+        }
+        let mut properties = Map::new();
+        for field in &message.field {
+            let map_entry = message.nested_type.iter().find(|nested| {
+                nested
+                    .options
+                    .as_ref()
+                    .is_some_and(|options| options.map_entry())
+                    && field.type_name().ends_with(&format!(".{}", nested.name()))
+            });
+            let schema = match map_entry {
+                Some(entry) => json!({
+                    "type": "object",
+                    "additionalProperties": field_schema(&entry.field[1], package),
+                }),
+                None if field.label() == Label::Repeated => {
+                    json!({"type": "array", "items": field_schema(field, package)})
+                }
+                None => field_schema(field, package),
+            };
+            properties.insert(field.json_name().to_string(), schema);
+        }
+        schemas.insert(name, json!({"type": "object", "properties": properties}));
+    }
+
+    /// Proto3 JSON mapping: 64-bit integers are strings, bytes are base64.
+    fn field_schema(field: &FieldDescriptorProto, package: &str) -> Value {
+        match field.r#type() {
+            Type::Double | Type::Float => json!({"type": "number"}),
+            Type::Int32 | Type::Sint32 | Type::Sfixed32 => {
+                json!({"type": "integer", "format": "int32"})
+            }
+            Type::Uint32 | Type::Fixed32 => json!({"type": "integer", "format": "int64"}),
+            Type::Int64 | Type::Sint64 | Type::Sfixed64 | Type::Uint64 | Type::Fixed64 => {
+                json!({"type": "string", "format": "int64"})
+            }
+            Type::Bool => json!({"type": "boolean"}),
+            Type::String => json!({"type": "string"}),
+            Type::Bytes => json!({"type": "string", "format": "byte"}),
+            Type::Message | Type::Enum | Type::Group => reference(field.type_name(), package),
+        }
+    }
+
+    /// `.minoots.timer.v1.Timer` refers to `Timer`; nested types keep their outer message's name.
+    fn reference(type_name: &str, package: &str) -> Value {
+        let name = type_name
+            .trim_start_matches('.')
+            .strip_prefix(package)
+            .map_or(type_name, |name| name.trim_start_matches('.'));
+        json!({"$ref": format!("#/components/schemas/{name}")})
+    }
+}
@@ -0,0 +1,51 @@
+//! Embedding the scheduler in-process, without gRPC: schedule a timer, follow its lifecycle events,
+//! and wait for it to fire.
+//!
+//! ```text
+//! cargo run --example embedded_kernel --no-default-features
+//! ```
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use horology_kernel::tail::EventLine;
+use horology_kernel::{
+    HorologyKernel, KernelError, SchedulerConfig, TimerEvent, TimerOutcome, TimerSpec,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), KernelError> {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    // Subscribe before scheduling; a subscription only receives events sent after this.
+    let mut events = kernel.subscribe();
+
+    let timer = kernel
+        .schedule(TimerSpec {
+            tenant_id: "acme".into(),
+            requested_by: "billing-agent".into(),
+            name: Some("invoice-reminder".into()),
+            duration_ms: 250,
+            labels: HashMap::from([("env".to_string(), "dev".to_string())]),
+            pre_fire_notice_ms: Some(100),
+            ..Default::default()
+        })
+        .await?;
+    println!("scheduled {} to fire at {}", timer.id, timer.fire_at);
+
+    match kernel.wait("acme", timer.id).await {
+        TimerOutcome::Fired(fired) => println!(
+            "fired {}us after its deadline",
+            fired.fire_latency_us.unwrap_or_default()
+        ),
+        other => println!("timer ended without firing: {other:?}"),
+    }
+
+    // Scheduled, pre-fire notice, fired.
+    while let Ok(event) = events.try_recv() {
+        println!("{}", EventLine::from(&event).render(Utc::now(), false));
+        if let TimerEvent::Fired(fired) = event {
+            assert_eq!(fired.id, timer.id);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! A fired-timer consumer: streams a tenant's events under a subscriber id and acknowledges each
+//! fire once it is handled, so `GetDeliveryStatus` shows which consumers processed which timers.
+//! This is the loop the action orchestrator runs over NATS, taken straight from the kernel's event
+//! stream.
+//!
+//! ```text
+//! KERNEL_GRPC_URL=http://127.0.0.1:50051 MINOOTS_TENANT=demo cargo run --example event_consumer
+//! ```
+//!
+//! With authentication enforced, set `MINOOTS_API_TOKEN` to a token with the `stream` scope.
+
+use horology_kernel::pb::horology_kernel_client::HorologyKernelClient;
+use horology_kernel::pb::{
+    timer_event::Event, AcknowledgeDeliveryRequest, TimerEventStreamRequest,
+};
+use tonic::Request;
+
+const SUBSCRIBER_ID: &str = "example-consumer";
+
+fn authorized<T>(message: T, token: Option<&str>) -> Request<T> {
+    let mut request = Request::new(message);
+    if let Some(secret) = token {
+        if let Ok(value) = format!("Bearer {secret}").parse() {
+            request.metadata_mut().insert("authorization", value);
+        }
+    }
+    request
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let url =
+        std::env::var("KERNEL_GRPC_URL").unwrap_or_else(|_| "http://127.0.0.1:50051".to_string());
+    let tenant_id = std::env::var("MINOOTS_TENANT").unwrap_or_else(|_| "demo".to_string());
+    let token = std::env::var("MINOOTS_API_TOKEN").ok();
+    let mut client = HorologyKernelClient::connect(url).await?;
+
+    let mut events = client
+        .stream_timer_events(authorized(
+            TimerEventStreamRequest {
+                tenant_id: tenant_id.clone(),
+                subscriber_id: SUBSCRIBER_ID.into(),
+                ..Default::default()
+            },
+            token.as_deref(),
+        ))
+        .await?
+        .into_inner();
+    println!("consuming {tenant_id} events as {SUBSCRIBER_ID}");
+
+    while let Some(event) = events.message().await? {
+        let timer = match event.event {
+            Some(Event::Fired(fired)) => fired.timer,
+            Some(Event::Missed(missed)) => {
+                if let Some(timer) = missed.timer {
+                    println!("{} was missed during downtime", timer.name);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let Some(timer) = timer else { continue };
+        println!("handling {} ({})", timer.name, timer.id);
+        client
+            .acknowledge_delivery(authorized(
+                AcknowledgeDeliveryRequest {
+                    tenant_id: tenant_id.clone(),
+                    timer_id: timer.id,
+                    subscriber_id: SUBSCRIBER_ID.into(),
+                },
+                token.as_deref(),
+            ))
+            .await?;
+    }
+    Ok(())
+}
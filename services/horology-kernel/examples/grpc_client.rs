@@ -0,0 +1,79 @@
+//! Scheduling against a running kernel over gRPC, the way agents and SDKs do, with the API token
+//! sent as `authorization: Bearer <secret>` metadata on every call.
+//!
+//! ```text
+//! KERNEL_GRPC_URL=http://127.0.0.1:50051 MINOOTS_API_TOKEN=<secret> cargo run --example grpc_client
+//! ```
+//!
+//! The token needs the `schedule` scope for the tenant; it may be omitted while the kernel runs
+//! without `MINOOTS_ADMIN_TOKEN`.
+
+use horology_kernel::pb::horology_kernel_client::HorologyKernelClient;
+use horology_kernel::pb::{
+    timer_schedule_request::ScheduleTime, TimerScheduleRequest, TimerStatus, TimerWaitRequest,
+};
+use tonic::metadata::{AsciiMetadataValue, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::transport::Channel;
+use tonic::{Request, Status};
+
+/// Attaches the API token to every outgoing call.
+struct BearerToken(Option<AsciiMetadataValue>);
+
+impl Interceptor for BearerToken {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(token) = &self.0 {
+            request
+                .metadata_mut()
+                .insert("authorization", token.clone());
+        }
+        Ok(request)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let url =
+        std::env::var("KERNEL_GRPC_URL").unwrap_or_else(|_| "http://127.0.0.1:50051".to_string());
+    let token = std::env::var("MINOOTS_API_TOKEN")
+        .ok()
+        .map(|secret| MetadataValue::try_from(format!("Bearer {secret}")))
+        .transpose()?;
+    let tenant_id = std::env::var("MINOOTS_TENANT").unwrap_or_else(|_| "demo".to_string());
+
+    let channel = Channel::from_shared(url)?.connect().await?;
+    let mut client = HorologyKernelClient::with_interceptor(channel, BearerToken(token));
+
+    let timer = client
+        .schedule_timer(TimerScheduleRequest {
+            tenant_id: tenant_id.clone(),
+            requested_by: "example-agent".into(),
+            name: "grpc-example".into(),
+            schedule_time: Some(ScheduleTime::DurationMs(500)),
+            labels: [("source".to_string(), "example".to_string())].into(),
+            // Re-running the example within the kernel's lifetime returns the same timer.
+            idempotency_key: "examples/grpc_client".into(),
+            ..Default::default()
+        })
+        .await?
+        .into_inner()
+        .timer
+        .ok_or("kernel returned no timer")?;
+    println!("scheduled {} to fire at {}", timer.id, timer.fire_at_iso);
+
+    let done = client
+        .wait_timer(TimerWaitRequest {
+            tenant_id,
+            timer_id: timer.id,
+        })
+        .await?
+        .into_inner();
+    let status = TimerStatus::try_from(done.status).unwrap_or(TimerStatus::Unspecified);
+    println!(
+        "timer {} finished as {} at {}",
+        done.id,
+        status.as_str_name(),
+        done.fired_at_iso
+    );
+    Ok(())
+}
@@ -0,0 +1,146 @@
+//! A temporal graph of dependent timers: each node waits for all of its dependencies to fire and
+//! then starts its own delay. The kernel schedules one-shot timers only, so the graph is driven from
+//! its event stream: every fired node schedules the nodes it unblocks.
+//!
+//! Node timers inherit the root's labels and agent binding unless the node overrides them, so shared
+//! configuration is written once per graph.
+//!
+//! ```text
+//! cargo run --example temporal_graph --no-default-features
+//! ```
+//!
+//! ```text
+//! extract ─┬─> transform-orders ──┬─> load
+//!          └─> transform-refunds ─┘
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use horology_kernel::{HorologyKernel, KernelError, SchedulerConfig, TimerEvent, TimerSpec};
+use serde_json::json;
+
+const TENANT: &str = "acme";
+const GRAPH: &str = "nightly-etl";
+
+struct Node {
+    name: &'static str,
+    delay_ms: u64,
+    after: &'static [&'static str],
+    /// Added to, or replacing, the root's labels.
+    labels: &'static [(&'static str, &'static str)],
+    /// Replaces the root's agent binding.
+    agent: Option<&'static str>,
+}
+
+const NODES: &[Node] = &[
+    Node {
+        name: "extract",
+        delay_ms: 100,
+        after: &[],
+        labels: &[],
+        agent: None,
+    },
+    Node {
+        name: "transform-orders",
+        delay_ms: 150,
+        after: &["extract"],
+        labels: &[],
+        agent: None,
+    },
+    Node {
+        name: "transform-refunds",
+        delay_ms: 50,
+        after: &["extract"],
+        labels: &[("team", "finance")],
+        agent: Some("refunds-agent"),
+    },
+    Node {
+        name: "load",
+        delay_ms: 100,
+        after: &["transform-orders", "transform-refunds"],
+        labels: &[("tier", "critical")],
+        agent: None,
+    },
+];
+
+/// Settings every node timer inherits from the graph root.
+fn root_defaults() -> TimerSpec {
+    TimerSpec {
+        tenant_id: TENANT.into(),
+        requested_by: "etl-planner".into(),
+        labels: HashMap::from([
+            ("graph".to_string(), GRAPH.to_string()),
+            ("team".to_string(), "data".to_string()),
+            ("tier".to_string(), "standard".to_string()),
+        ]),
+        agent_binding: Some(json!({ "agent": "etl-agent" })),
+        ..Default::default()
+    }
+}
+
+/// Builds a node's timer from the root's settings with the node's overrides applied.
+fn node_spec(root: &TimerSpec, node: &Node) -> TimerSpec {
+    let mut labels = root.labels.clone();
+    labels.extend(
+        node.labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+    );
+    labels.insert("node".to_string(), node.name.to_string());
+    TimerSpec {
+        name: Some(node.name.into()),
+        duration_ms: node.delay_ms,
+        labels,
+        agent_binding: node
+            .agent
+            .map(|agent| json!({ "agent": agent }))
+            .or_else(|| root.agent_binding.clone()),
+        // Retried plans must not start a node twice.
+        idempotency_key: Some(format!("{GRAPH}/{}", node.name)),
+        ..root.clone()
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), KernelError> {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let mut events = kernel.subscribe();
+    let root = root_defaults();
+    for node in NODES.iter().filter(|node| node.after.is_empty()) {
+        kernel.schedule(node_spec(&root, node)).await?;
+    }
+
+    let mut fired = HashSet::new();
+    while fired.len() < NODES.len() {
+        let Ok(TimerEvent::Fired(timer)) = events.recv().await else {
+            continue;
+        };
+        if timer.labels.get("graph").map(String::as_str) != Some(GRAPH) {
+            continue;
+        }
+        let agent = timer
+            .agent_binding
+            .as_ref()
+            .and_then(|binding| binding["agent"].as_str())
+            .unwrap_or_default();
+        println!(
+            "{} fired at {} (team={}, tier={}, agent={agent})",
+            timer.name,
+            timer.fired_at.unwrap_or(timer.fire_at),
+            timer.labels["team"],
+            timer.labels["tier"],
+        );
+        fired.insert(timer.name.clone());
+        for node in NODES {
+            let unblocked = node.after.contains(&timer.name.as_str())
+                && node
+                    .after
+                    .iter()
+                    .all(|dependency| fired.contains(*dependency));
+            if unblocked {
+                kernel.schedule(node_spec(&root, node)).await?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! `minoots.admin.v1.KernelAdmin`, the operator surface of a running kernel: its status and
+//! leadership, per-tenant timer counts, and interventions (force-settling a fired timer, resending
+//! retained events to a sink, compacting the store) that otherwise mean editing the store by hand.
+//! Served next to the kernel's service behind the same [`crate::grpc::authenticate`] interceptor.
+
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+use crate::grpc::{authorize, authorize_root, format_datetime, map_kernel_error, to_proto_timer};
+use crate::pb;
+use crate::pb::admin::kernel_admin_server::{KernelAdmin, KernelAdminServer};
+use crate::pb::admin::{
+    ClusterStatus, ClusterStatusRequest, CompactStoreRequest, CompactStoreResponse,
+    ForceSettleTimerRequest, Leadership, LeadershipRequest, ReplayEventsRequest,
+    ReplayEventsResponse, TenantTimerCounts, TenantTimerCountsRequest, TenantTimerCountsResponse,
+};
+use crate::{HorologyKernel, Scope, SystemEventKind, TimerCounts};
+
+pub const ADMIN_SERVICE: &str = "minoots.admin.v1.KernelAdmin";
+
+#[derive(Clone)]
+pub struct AdminService {
+    kernel: HorologyKernel,
+}
+
+impl AdminService {
+    pub fn new(kernel: HorologyKernel) -> Self {
+        Self { kernel }
+    }
+
+    pub fn into_server(self) -> KernelAdminServer<Self> {
+        KernelAdminServer::new(self)
+    }
+}
+
+#[tonic::async_trait]
+impl KernelAdmin for AdminService {
+    async fn get_cluster_status(
+        &self,
+        request: Request<ClusterStatusRequest>,
+    ) -> Result<Response<ClusterStatus>, Status> {
+        authorize_root(&request)?;
+        let counts = self.kernel.timer_counts().await;
+        let mut event_sinks = self.kernel.dead_letters().forwarders();
+        event_sinks.sort();
+        Ok(Response::new(ClusterStatus {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            active: self.kernel.is_active(),
+            store_healthy: self.kernel.store_healthy(),
+            fencing_token: self.kernel.fencing_token().unwrap_or_default(),
+            last_event_sequence: self.kernel.last_event_sequence(),
+            timers: counts.values().map(TimerCounts::total).sum(),
+            tenants: counts.len() as u64,
+            stream_subscribers: self.kernel.fanout_stats().subscribers as u64,
+            running_tasks: self.kernel.tasks().report().running.len() as u64,
+            dead_letters: self.kernel.dead_letters().stats().pending as u64,
+            event_sinks,
+        }))
+    }
+
+    async fn get_leadership(
+        &self,
+        request: Request<LeadershipRequest>,
+    ) -> Result<Response<Leadership>, Status> {
+        authorize_root(&request)?;
+        let mut leadership = Leadership {
+            active: self.kernel.is_active(),
+            fencing_token: self.kernel.fencing_token().unwrap_or_default(),
+            ..Default::default()
+        };
+        if let Some(change) = self.kernel.last_leadership_change() {
+            leadership.last_change = change.kind.name().to_string();
+            leadership.last_change_node_id = match change.kind {
+                SystemEventKind::LeadershipGained { node_id }
+                | SystemEventKind::LeadershipLost { node_id } => node_id,
+                _ => String::new(),
+            };
+            leadership.last_change_at_iso = format_datetime(change.occurred_at);
+        }
+        Ok(Response::new(leadership))
+    }
+
+    async fn list_tenant_timer_counts(
+        &self,
+        request: Request<TenantTimerCountsRequest>,
+    ) -> Result<Response<TenantTimerCountsResponse>, Status> {
+        let tenant_id = request.get_ref().tenant_id.clone();
+        if tenant_id.is_empty() {
+            authorize_root(&request)?;
+        } else {
+            authorize(&request, Scope::Admin, &tenant_id)?;
+        }
+        let tenants = self
+            .kernel
+            .timer_counts()
+            .await
+            .into_iter()
+            .filter(|(tenant, _)| tenant_id.is_empty() || *tenant == tenant_id)
+            .map(|(tenant_id, counts)| counts_to_proto(tenant_id, counts))
+            .collect();
+        Ok(Response::new(TenantTimerCountsResponse { tenants }))
+    }
+
+    async fn force_settle_timer(
+        &self,
+        request: Request<ForceSettleTimerRequest>,
+    ) -> Result<Response<pb::Timer>, Status> {
+        authorize(&request, Scope::Admin, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let timer_id = Uuid::parse_str(&payload.timer_id)
+            .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
+        let failure = Some(payload.failure_reason).filter(|reason| !reason.is_empty());
+        let timer = self
+            .kernel
+            .force_settle(&payload.tenant_id, timer_id, failure)
+            .await
+            .map_err(map_kernel_error)?
+            .ok_or_else(|| Status::not_found("timer not found"))?;
+        Ok(Response::new(to_proto_timer(timer)?))
+    }
+
+    async fn replay_events(
+        &self,
+        request: Request<ReplayEventsRequest>,
+    ) -> Result<Response<ReplayEventsResponse>, Status> {
+        authorize_root(&request)?;
+        let payload = request.into_inner();
+        let tenant_id = payload.tenant_id;
+        let mut events = self
+            .kernel
+            .replay_events(payload.after_sequence, |tenant| {
+                tenant_id.is_empty() || tenant == tenant_id
+            })
+            .map_err(map_kernel_error)?;
+        if payload.limit > 0 {
+            events.truncate(payload.limit as usize);
+        }
+        let replayed = events.len() as u64;
+        let last_sequence = events
+            .last()
+            .map_or(payload.after_sequence, |event| event.sequence);
+        if !self.kernel.dead_letters().redeliver(&payload.sink, events) {
+            return Err(Status::not_found(format!(
+                "no event sink `{}` is running",
+                payload.sink
+            )));
+        }
+        Ok(Response::new(ReplayEventsResponse {
+            replayed,
+            last_sequence,
+        }))
+    }
+
+    async fn compact_store(
+        &self,
+        request: Request<CompactStoreRequest>,
+    ) -> Result<Response<CompactStoreResponse>, Status> {
+        authorize_root(&request)?;
+        let dropped = self
+            .kernel
+            .compact_store()
+            .await
+            .map_err(map_kernel_error)?;
+        Ok(Response::new(CompactStoreResponse {
+            compacted: dropped.is_some(),
+            dropped_timers: dropped.unwrap_or_default() as u64,
+        }))
+    }
+}
+
+fn counts_to_proto(tenant_id: String, counts: TimerCounts) -> TenantTimerCounts {
+    TenantTimerCounts {
+        tenant_id,
+        scheduled: counts.scheduled,
+        armed: counts.armed,
+        fired: counts.fired,
+        cancelled: counts.cancelled,
+        missed: counts.missed,
+        acknowledged: counts.acknowledged,
+        failed: counts.failed,
+        settled: counts.settled,
+        total: counts.total(),
+    }
+}
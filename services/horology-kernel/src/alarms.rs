@@ -0,0 +1,240 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Tenant operations whose per-minute rate is watched for runaway loops.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulingOp {
+    Schedule,
+    Cancel,
+}
+
+impl SchedulingOp {
+    pub fn name(self) -> &'static str {
+        match self {
+            SchedulingOp::Schedule => "schedule",
+            SchedulingOp::Cancel => "cancel",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlarmConfig {
+    /// A minute is anomalous once its count reaches this multiple of the tenant's baseline.
+    pub spike_factor: f64,
+    /// Minutes quieter than this never alarm, so small tenants do not page anyone.
+    pub min_events_per_minute: u64,
+    /// Weight of the latest minute in the exponentially weighted baseline.
+    pub baseline_weight: f64,
+    /// Throttle a tenant's schedules after a schedule spike instead of only alerting.
+    pub auto_throttle: bool,
+    /// While throttled, schedules per minute are capped at this multiple of the baseline.
+    pub throttle_factor: f64,
+    pub throttle_duration: Duration,
+}
+
+impl Default for AlarmConfig {
+    fn default() -> Self {
+        Self {
+            spike_factor: 10.0,
+            min_events_per_minute: 60,
+            baseline_weight: 0.2,
+            auto_throttle: false,
+            throttle_factor: 2.0,
+            throttle_duration: Duration::minutes(10),
+        }
+    }
+}
+
+/// An anomalous minute for one tenant and operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spike {
+    pub tenant_id: String,
+    pub operation: SchedulingOp,
+    pub per_minute: u64,
+    pub baseline_per_minute: f64,
+    /// Whether the spike put the tenant under a schedule throttle.
+    pub throttled: bool,
+}
+
+#[derive(Default)]
+struct Window {
+    minute: i64,
+    count: u64,
+    /// `None` until a full minute has been observed.
+    baseline: Option<f64>,
+    alarmed: bool,
+}
+
+impl Window {
+    /// Folds finished minutes into the baseline; idle minutes count as zero.
+    fn advance(&mut self, minute: i64, weight: f64) {
+        if minute <= self.minute {
+            return;
+        }
+        if self.minute != 0 {
+            let mut baseline = match self.baseline {
+                Some(baseline) => weight * self.count as f64 + (1.0 - weight) * baseline,
+                None => self.count as f64,
+            };
+            // Past an hour of silence the remaining decay is negligible.
+            for _ in 1..(minute - self.minute).min(60) {
+                baseline *= 1.0 - weight;
+            }
+            self.baseline = Some(baseline);
+        }
+        self.minute = minute;
+        self.count = 0;
+        self.alarmed = false;
+    }
+}
+
+struct Throttle {
+    until: DateTime<Utc>,
+    per_minute: u64,
+}
+
+#[derive(Default)]
+struct AlarmTable {
+    windows: HashMap<(String, SchedulingOp), Window>,
+    throttles: HashMap<String, Throttle>,
+}
+
+/// Per-tenant rate-of-change detector for schedules and cancels.
+#[derive(Clone, Default)]
+pub struct RateAlarms {
+    config: AlarmConfig,
+    table: Arc<Mutex<AlarmTable>>,
+}
+
+impl RateAlarms {
+    pub fn new(config: AlarmConfig) -> Self {
+        Self {
+            config,
+            table: Arc::default(),
+        }
+    }
+
+    /// Counts one operation and returns a spike the first time a minute crosses the threshold.
+    pub fn record(
+        &self,
+        tenant_id: &str,
+        operation: SchedulingOp,
+        at: DateTime<Utc>,
+    ) -> Option<Spike> {
+        let minute = minute_of(at);
+        let mut table = self.table();
+        let window = table
+            .windows
+            .entry((tenant_id.to_string(), operation))
+            .or_default();
+        window.advance(minute, self.config.baseline_weight);
+        window.count += 1;
+        let baseline = window.baseline?;
+        if window.alarmed
+            || window.count < self.config.min_events_per_minute
+            || (window.count as f64) < self.config.spike_factor * baseline.max(1.0)
+        {
+            return None;
+        }
+        window.alarmed = true;
+        let per_minute = window.count;
+        let throttled = self.config.auto_throttle && operation == SchedulingOp::Schedule;
+        if throttled {
+            table.throttles.insert(
+                tenant_id.to_string(),
+                Throttle {
+                    until: at + self.config.throttle_duration,
+                    per_minute: (baseline.max(1.0) * self.config.throttle_factor).ceil() as u64,
+                },
+            );
+        }
+        Some(Spike {
+            tenant_id: tenant_id.to_string(),
+            operation,
+            per_minute,
+            baseline_per_minute: baseline,
+            throttled,
+        })
+    }
+
+    /// Checks a schedule against an active throttle, returning how long to wait when it is over
+    /// the cap.
+    pub fn check_throttle(&self, tenant_id: &str, at: DateTime<Utc>) -> Result<(), Duration> {
+        let mut table = self.table();
+        let Some(throttle) = table.throttles.get(tenant_id) else {
+            return Ok(());
+        };
+        if throttle.until <= at {
+            table.throttles.remove(tenant_id);
+            return Ok(());
+        }
+        let cap = throttle.per_minute;
+        let minute = minute_of(at);
+        let used = table
+            .windows
+            .get(&(tenant_id.to_string(), SchedulingOp::Schedule))
+            .filter(|window| window.minute == minute)
+            .map_or(0, |window| window.count);
+        if used < cap {
+            return Ok(());
+        }
+        let next_minute = DateTime::from_timestamp((minute + 1) * 60, 0).unwrap_or(at);
+        Err(next_minute - at)
+    }
+
+    fn table(&self) -> std::sync::MutexGuard<'_, AlarmTable> {
+        self.table.lock().expect("rate alarms poisoned")
+    }
+}
+
+fn minute_of(at: DateTime<Utc>) -> i64 {
+    at.timestamp().div_euclid(60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alarms_once_per_spiking_minute_and_throttles_schedules() {
+        let alarms = RateAlarms::new(AlarmConfig {
+            min_events_per_minute: 20,
+            auto_throttle: true,
+            ..AlarmConfig::default()
+        });
+        let start = DateTime::from_timestamp(1_700_000_040, 0).unwrap();
+        for minute in 0..5 {
+            let at = start + Duration::minutes(minute);
+            for _ in 0..2 {
+                assert_eq!(alarms.record("tenant-a", SchedulingOp::Schedule, at), None);
+            }
+        }
+
+        let burst = start + Duration::minutes(5);
+        let spikes: Vec<_> = (0..40)
+            .filter_map(|_| alarms.record("tenant-a", SchedulingOp::Schedule, burst))
+            .collect();
+        assert_eq!(spikes.len(), 1);
+        assert_eq!(spikes[0].per_minute, 20);
+        assert!(spikes[0].throttled);
+        assert!(alarms.check_throttle("tenant-a", burst).is_err());
+        assert!(alarms.check_throttle("tenant-b", burst).is_ok());
+
+        // The cap is twice the pre-spike baseline of two schedules per minute.
+        let later = burst + Duration::minutes(1);
+        assert!(alarms.check_throttle("tenant-a", later).is_ok());
+        for _ in 0..4 {
+            alarms.record("tenant-a", SchedulingOp::Schedule, later);
+        }
+        assert!(alarms.check_throttle("tenant-a", later).is_err());
+        assert!(alarms
+            .check_throttle("tenant-a", burst + Duration::minutes(11))
+            .is_ok());
+    }
+}
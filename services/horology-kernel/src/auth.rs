@@ -0,0 +1,329 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::persistence::{AuthStore, StoreError};
+
+const SECRET_PREFIX: &str = "mnt_";
+
+/// Operations an API token may perform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    Schedule,
+    Cancel,
+    /// Get, list, and wait on timers and query the tenant's history, usage, and stats.
+    Read,
+    Stream,
+    Admin,
+}
+
+/// Metadata of an issued token. The secret is only returned once, at issue or rotation time.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub tenant_id: String,
+    pub scopes: Vec<Scope>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub rotated_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// A token as an [`AuthStore`] keeps it: metadata plus the hex SHA-256 of its current secret,
+/// `None` once revoked.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredApiToken {
+    #[serde(flatten)]
+    pub token: ApiToken,
+    pub secret_sha256: Option<String>,
+}
+
+/// The caller a request was authenticated as.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Principal {
+    pub token_id: Option<Uuid>,
+    /// The identity provider's subject (`sub`) for callers authenticated by a
+    /// [`CredentialVerifier`]; `None` for API tokens.
+    pub subject: Option<String>,
+    /// `None` for the operator root token, which may act on every tenant.
+    pub tenant_id: Option<String>,
+    pub scopes: Vec<Scope>,
+}
+
+impl Principal {
+    fn root() -> Self {
+        Self {
+            token_id: None,
+            subject: None,
+            tenant_id: None,
+            scopes: vec![
+                Scope::Schedule,
+                Scope::Cancel,
+                Scope::Read,
+                Scope::Stream,
+                Scope::Admin,
+            ],
+        }
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.tenant_id.is_none()
+    }
+
+    /// Identifies the caller in audit trails and rate limits: `root`, the token id, or the subject.
+    pub fn label(&self) -> String {
+        match (&self.token_id, &self.subject) {
+            _ if self.is_root() => "root".to_string(),
+            (Some(id), _) => id.to_string(),
+            (None, Some(subject)) => subject.clone(),
+            (None, None) => String::new(),
+        }
+    }
+
+    pub fn can_access(&self, tenant_id: &str) -> bool {
+        self.tenant_id.as_deref().is_none_or(|own| own == tenant_id)
+    }
+
+    pub fn allows(&self, scope: Scope, tenant_id: &str) -> bool {
+        self.can_access(tenant_id) && self.scopes.contains(&scope)
+    }
+}
+
+#[derive(Default)]
+struct TokenTable {
+    tokens: HashMap<Uuid, ApiToken>,
+    /// SHA-256 of each live secret. Plaintext secrets are never stored.
+    hashes: HashMap<[u8; 32], Uuid>,
+    root_hash: Option<[u8; 32]>,
+    verifier: Option<Arc<dyn CredentialVerifier>>,
+    store: Option<Arc<dyn AuthStore>>,
+}
+
+/// Authenticates bearer credentials the store did not issue, such as JWTs from an identity
+/// provider. Called only for credentials that match no API token.
+pub trait CredentialVerifier: Send + Sync {
+    fn verify(&self, credential: &str) -> Option<Principal>;
+}
+
+/// Tenant-scoped API tokens with issue/rotate/revoke lifecycle.
+#[derive(Clone, Default)]
+pub struct TokenStore {
+    table: Arc<Mutex<TokenTable>>,
+}
+
+impl TokenStore {
+    /// Registers the operator secret that bootstraps token administration for every tenant.
+    pub fn set_root_secret(&self, secret: &str) {
+        self.table().root_hash = Some(hash_secret(secret));
+    }
+
+    /// Accepts credentials `verifier` vouches for alongside issued tokens, and enforces
+    /// authentication like a root secret does.
+    pub fn set_verifier(&self, verifier: Arc<dyn CredentialVerifier>) {
+        self.table().verifier = Some(verifier);
+    }
+
+    /// Authentication is enforced once a root secret or a verifier is configured; until then
+    /// anonymous callers are accepted so existing deployments keep working.
+    pub fn is_enforced(&self) -> bool {
+        let table = self.table();
+        table.root_hash.is_some() || table.verifier.is_some()
+    }
+
+    /// Loads every token `store` holds and writes later changes through it on [`Self::persist`].
+    /// Returns how many tokens were loaded.
+    pub async fn attach_store(&self, store: Arc<dyn AuthStore>) -> Result<usize, StoreError> {
+        let stored = store.load_tokens().await?;
+        let mut table = self.table();
+        for record in &stored {
+            let id = record.token.id;
+            table.hashes.retain(|_, token_id| *token_id != id);
+            if let Some(hash) = record.secret_sha256.as_deref().and_then(decode_hash) {
+                table.hashes.insert(hash, id);
+            }
+            table.tokens.insert(id, record.token.clone());
+        }
+        table.store = Some(store);
+        Ok(stored.len())
+    }
+
+    /// Writes the token's current state to the attached store, if any. Call after each issue,
+    /// rotation, or revocation; until it succeeds the change only lives in memory.
+    pub async fn persist(&self, token_id: Uuid) -> Result<(), StoreError> {
+        let (store, record) = {
+            let table = self.table();
+            let Some(store) = table.store.clone() else {
+                return Ok(());
+            };
+            let Some(token) = table.tokens.get(&token_id).cloned() else {
+                return Ok(());
+            };
+            let secret_sha256 = table
+                .hashes
+                .iter()
+                .find(|(_, id)| **id == token_id)
+                .map(|(hash, _)| encode_hash(hash));
+            (
+                store,
+                StoredApiToken {
+                    token,
+                    secret_sha256,
+                },
+            )
+        };
+        store.save_token(&record).await
+    }
+
+    pub fn issue(
+        &self,
+        tenant_id: &str,
+        scopes: Vec<Scope>,
+        description: Option<String>,
+    ) -> (ApiToken, String) {
+        let secret = generate_secret();
+        let token = ApiToken {
+            id: Uuid::new_v4(),
+            tenant_id: tenant_id.to_string(),
+            scopes,
+            description,
+            created_at: Utc::now(),
+            rotated_at: None,
+            revoked_at: None,
+        };
+        let mut table = self.table();
+        table.hashes.insert(hash_secret(&secret), token.id);
+        table.tokens.insert(token.id, token.clone());
+        (token, secret)
+    }
+
+    /// Replaces a live token's secret; the previous secret stops working immediately.
+    pub fn rotate(&self, tenant_id: &str, token_id: Uuid) -> Option<(ApiToken, String)> {
+        let secret = generate_secret();
+        let mut table = self.table();
+        let token = table
+            .tokens
+            .get_mut(&token_id)
+            .filter(|token| token.tenant_id == tenant_id && token.revoked_at.is_none())?;
+        token.rotated_at = Some(Utc::now());
+        let token = token.clone();
+        table.hashes.retain(|_, id| *id != token_id);
+        table.hashes.insert(hash_secret(&secret), token_id);
+        Some((token, secret))
+    }
+
+    pub fn revoke(&self, tenant_id: &str, token_id: Uuid) -> Option<ApiToken> {
+        let mut table = self.table();
+        let token = table
+            .tokens
+            .get_mut(&token_id)
+            .filter(|token| token.tenant_id == tenant_id && token.revoked_at.is_none())?;
+        token.revoked_at = Some(Utc::now());
+        let token = token.clone();
+        table.hashes.retain(|_, id| *id != token_id);
+        Some(token)
+    }
+
+    pub fn list(&self, tenant_id: &str) -> Vec<ApiToken> {
+        let mut tokens: Vec<_> = self
+            .table()
+            .tokens
+            .values()
+            .filter(|token| token.tenant_id == tenant_id)
+            .cloned()
+            .collect();
+        tokens.sort_by_key(|token| token.created_at);
+        tokens
+    }
+
+    pub fn authenticate(&self, secret: &str) -> Option<Principal> {
+        let hash = hash_secret(secret);
+        let table = self.table();
+        if table.root_hash == Some(hash) {
+            return Some(Principal::root());
+        }
+        match table.hashes.get(&hash).and_then(|id| table.tokens.get(id)) {
+            Some(token) => Some(Principal {
+                token_id: Some(token.id),
+                subject: None,
+                tenant_id: Some(token.tenant_id.clone()),
+                scopes: token.scopes.clone(),
+            }),
+            None => {
+                let verifier = table.verifier.clone()?;
+                // Verification may be slow (signature checks); the table is not held meanwhile.
+                drop(table);
+                verifier.verify(secret)
+            }
+        }
+    }
+
+    fn table(&self) -> std::sync::MutexGuard<'_, TokenTable> {
+        self.table.lock().expect("token store poisoned")
+    }
+}
+
+fn generate_secret() -> String {
+    format!(
+        "{SECRET_PREFIX}{}{}",
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+fn hash_secret(secret: &str) -> [u8; 32] {
+    Sha256::digest(secret.as_bytes()).into()
+}
+
+fn encode_hash(hash: &[u8; 32]) -> String {
+    hash.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hash(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut hash = [0; 32];
+    for (index, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok()?;
+    }
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_and_revocation_invalidate_old_secrets() {
+        let store = TokenStore::default();
+        let (token, secret) = store.issue("tenant-a", vec![Scope::Schedule], None);
+        let principal = store
+            .authenticate(&secret)
+            .expect("issued token authenticates");
+        assert!(principal.allows(Scope::Schedule, "tenant-a"));
+        assert!(!principal.allows(Scope::Cancel, "tenant-a"));
+        assert!(!principal.allows(Scope::Schedule, "tenant-b"));
+
+        assert!(store.rotate("tenant-b", token.id).is_none());
+        let (_, rotated) = store.rotate("tenant-a", token.id).expect("rotate");
+        assert!(store.authenticate(&secret).is_none());
+        assert!(store.authenticate(&rotated).is_some());
+
+        assert!(store.revoke("tenant-a", token.id).is_some());
+        assert!(store.authenticate(&rotated).is_none());
+        assert!(store.list("tenant-a")[0].revoked_at.is_some());
+
+        store.set_root_secret("operator");
+        assert!(store
+            .authenticate("operator")
+            .expect("root")
+            .allows(Scope::Admin, "tenant-b"));
+    }
+}
@@ -0,0 +1,118 @@
+//! Optional backpressure from event consumers onto the dispatch loop. Every sink (gRPC streams and
+//! the NATS bridges behind them, the embedded orchestrator, the event log) reads the kernel's
+//! broadcast channel, so events the slowest of them has not read yet measure downstream lag. While
+//! lag is at or above [`BackpressureConfig::high_watermark`], each due fire is held until lag falls
+//! to the low watermark or the timer's allowance runs out: what is left of its accuracy budget, or
+//! [`BackpressureConfig::max_delay_ms`] for timers without one.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How often a held fire rechecks consumer lag.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackpressureConfig {
+    /// Unread events at which fires start being held; the channel holds at most 1024.
+    pub high_watermark: usize,
+    /// Unread events at which held fires are released.
+    pub low_watermark: usize,
+    /// Longest hold for a timer without an accuracy budget.
+    pub max_delay_ms: u64,
+}
+
+impl BackpressureConfig {
+    /// Releases at half the high watermark and holds budget-less timers for at most a second.
+    pub fn new(high_watermark: usize) -> Self {
+        Self {
+            high_watermark,
+            low_watermark: high_watermark / 2,
+            max_delay_ms: 1_000,
+        }
+    }
+
+    /// How much longer a fire due at `due_at` may be held at `now`.
+    pub fn allowance(
+        &self,
+        due_at: DateTime<Utc>,
+        accuracy_budget_ms: Option<u64>,
+        now: DateTime<Utc>,
+    ) -> Duration {
+        let late_ms = (now - due_at).num_milliseconds().max(0) as u64;
+        let cap_ms = accuracy_budget_ms.unwrap_or(self.max_delay_ms);
+        Duration::from_millis(cap_ms.saturating_sub(late_ms))
+    }
+}
+
+/// Backpressure applied since startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackpressureStats {
+    /// Fires held because consumers lagged.
+    pub fires_delayed: u64,
+    pub delay_ms_total: u64,
+    /// Held fires released while consumers still lagged because their allowance ran out.
+    pub allowance_exhausted: u64,
+    /// Unread events when the dispatch loop last looked.
+    pub current_lag: u64,
+    pub max_lag: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct BackpressureMetrics {
+    inner: Arc<Mutex<BackpressureStats>>,
+}
+
+impl BackpressureMetrics {
+    pub fn snapshot(&self) -> BackpressureStats {
+        *self.lock()
+    }
+
+    pub(crate) fn observe_lag(&self, lag: usize) {
+        let mut stats = self.lock();
+        stats.current_lag = lag as u64;
+        stats.max_lag = stats.max_lag.max(lag as u64);
+    }
+
+    pub(crate) fn record_hold(&self, held: Duration, exhausted: bool) {
+        let mut stats = self.lock();
+        stats.fires_delayed += 1;
+        stats.delay_ms_total = stats.delay_ms_total.saturating_add(held.as_millis() as u64);
+        if exhausted {
+            stats.allowance_exhausted += 1;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BackpressureStats> {
+        self.inner.lock().expect("backpressure metrics poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowance_is_what_is_left_of_the_budget() {
+        let config = BackpressureConfig::new(100);
+        assert_eq!(config.low_watermark, 50);
+        let due_at = Utc::now();
+        let later = |ms| due_at + chrono::Duration::milliseconds(ms);
+        assert_eq!(
+            config.allowance(due_at, Some(40), later(15)),
+            Duration::from_millis(25)
+        );
+        assert_eq!(
+            config.allowance(due_at, Some(40), later(60)),
+            Duration::ZERO
+        );
+        assert_eq!(
+            config.allowance(due_at, None, due_at),
+            Duration::from_millis(1_000)
+        );
+    }
+}
@@ -1,26 +1,256 @@
-use horology_kernel::grpc::HorologyKernelService;
-use horology_kernel::pb::horology_kernel_server::HorologyKernelServer;
-use horology_kernel::{HorologyKernel, SchedulerConfig, TimerSpec};
-use std::{collections::HashMap, net::SocketAddr};
-use tokio::signal;
+use horology_kernel::admin::AdminService;
+use horology_kernel::fanout::RecvError;
+use horology_kernel::grpc::{self, HorologyKernelService};
+use horology_kernel::health::HealthService;
+use horology_kernel::jwt::{JwtConfig, JwtVerifier};
+use horology_kernel::pb::reflection::server_reflection_server::ServerReflectionServer;
+use horology_kernel::rate_limit::RateLimitLayer;
+use horology_kernel::reflection::ReflectionService;
+use horology_kernel::rest;
+use horology_kernel::standby::{PrimaryForwarder, StandbyFollower};
+use horology_kernel::tail::{self, EventLine};
+use horology_kernel::{
+    BackpressureConfig, BatchingTimerStore, EventHistory, EventRetention, FileAuthStore,
+    FileDeliveryStore, FileStoreOptions, FileTenantPolicyStore, FileTimerStore, FileUsageStore,
+    HorologyKernel, SchedulerConfig, ShutdownCoordinator, StoreError, StreamLimits, SystemEvent,
+    SystemEventKind, TaskRegistry, TenantPolicyStore, TenantQuota, TerminalRetention, TimerEvent,
+    TimerSpec, TimerStore, UsageRecord, WriteBatchConfig,
+};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    signal,
+    sync::{broadcast, oneshot},
+    task::JoinHandle,
+};
+use tonic::service::interceptor::InterceptedService;
 use tonic::transport::Server;
-use tracing::{error, info};
+use tower_layer::Layer;
+use tracing::{error, info, warn};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
     info!("Starting horology kernel");
+    let tail_events = std::env::args().any(|arg| arg == "--tail-events");
 
-    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let mut config = SchedulerConfig::default();
+    if let Ok(scheme) = std::env::var("MINOOTS_TIMER_ID_SCHEME") {
+        config.id_scheme = scheme.parse().map_err(anyhow::Error::msg)?;
+    }
+    info!(id_scheme = ?config.id_scheme, "timer id scheme");
+    if let Ok(policy) = std::env::var("MINOOTS_MISSED_FIRE_POLICY") {
+        config.missed_fire_policy = policy.parse().map_err(anyhow::Error::msg)?;
+    }
+    let quota_limit = |name: &str| {
+        std::env::var(name)
+            .ok()
+            .map(|value| value.parse::<u64>())
+            .transpose()
+    };
+    if let Some(max_duration_ms) = quota_limit("MINOOTS_MAX_DURATION_MS")? {
+        config.max_duration_ms = Some(max_duration_ms);
+    }
+    config.quotas = TenantQuota {
+        max_active_timers: quota_limit("MINOOTS_QUOTA_MAX_ACTIVE_TIMERS")?,
+        max_schedules_per_minute: quota_limit("MINOOTS_QUOTA_MAX_SCHEDULES_PER_MINUTE")?,
+        max_total_duration_ms: quota_limit("MINOOTS_QUOTA_MAX_TOTAL_DURATION_MS")?,
+        max_requests_per_second: quota_limit("MINOOTS_QUOTA_MAX_REQUESTS_PER_SECOND")?,
+        request_burst: quota_limit("MINOOTS_QUOTA_REQUEST_BURST")?,
+    };
+    config.stream_limits = StreamLimits {
+        max_events_per_second: quota_limit("MINOOTS_STREAM_MAX_EVENTS_PER_SECOND")?,
+        max_bytes_per_second: quota_limit("MINOOTS_STREAM_MAX_BYTES_PER_SECOND")?,
+        max_event_bytes: quota_limit("MINOOTS_STREAM_MAX_EVENT_BYTES")?,
+    };
+    if let Some(capacity) = quota_limit("MINOOTS_SUBSCRIBER_BUFFER")? {
+        config.subscriber_buffer.capacity = capacity.max(1) as usize;
+    }
+    if let Ok(policy) = std::env::var("MINOOTS_SUBSCRIBER_DROP_POLICY") {
+        config.subscriber_buffer.drop_policy = policy.parse().map_err(anyhow::Error::msg)?;
+    }
+    info!(subscriber_buffer = ?config.subscriber_buffer, "buffering events per subscriber");
+    if let Some(high_watermark) = quota_limit("MINOOTS_BACKPRESSURE_HIGH_WATERMARK")? {
+        let mut backpressure = BackpressureConfig::new(high_watermark as usize);
+        if let Some(low_watermark) = quota_limit("MINOOTS_BACKPRESSURE_LOW_WATERMARK")? {
+            backpressure.low_watermark = low_watermark as usize;
+        }
+        if let Some(max_delay_ms) = quota_limit("MINOOTS_BACKPRESSURE_MAX_DELAY_MS")? {
+            backpressure.max_delay_ms = max_delay_ms;
+        }
+        info!(?backpressure, "holding fires while event consumers lag");
+        config.backpressure = Some(backpressure);
+    }
+    if let Some(days) = quota_limit("MINOOTS_EVENT_RETENTION_DAYS")? {
+        let mut retention = EventRetention::days(days);
+        if let Some(max_events) = quota_limit("MINOOTS_EVENT_RETENTION_MAX_EVENTS")? {
+            retention.max_events_per_tenant = max_events as usize;
+        }
+        config.event_history = Some(match std::env::var("MINOOTS_EVENT_HISTORY_PATH") {
+            Ok(path) => {
+                info!(%path, ?retention, "retaining event history on disk");
+                EventHistory::open(path, retention)?
+            }
+            Err(_) => {
+                info!(?retention, "retaining event history in memory");
+                EventHistory::new(retention)
+            }
+        });
+    }
+    let terminal_retention = TerminalRetention {
+        max_age: quota_limit("MINOOTS_MEMORY_TERMINAL_RETENTION_SECS")?.map(Duration::from_secs),
+        max_count: quota_limit("MINOOTS_MEMORY_TERMINAL_MAX")?.map(|count| count as usize),
+    };
+    let evicting = terminal_retention != TerminalRetention::default();
+    if evicting {
+        info!(?terminal_retention, "evicting finished timers from memory");
+        config.terminal_retention = Some(terminal_retention);
+    }
+    // Wall-clock steps beyond this re-arm pending timers; 0 stops checking fires against it.
+    let drift_threshold_ms = quota_limit("MINOOTS_CLOCK_DRIFT_THRESHOLD_MS")?.unwrap_or(1_000);
+    config.drift_threshold =
+        (drift_threshold_ms > 0).then(|| Duration::from_millis(drift_threshold_ms));
+    config.alarms.auto_throttle = std::env::var("MINOOTS_SPIKE_AUTO_THROTTLE")
+        .is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"));
+    let standby_of = std::env::var("MINOOTS_STANDBY_OF").ok();
+    config.standby = standby_of.is_some();
+    // Learners are read replicas: they replicate like a standby but never take over.
+    let learner = std::env::var("MINOOTS_STANDBY_LEARNER")
+        .is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"));
+    if learner && standby_of.is_none() {
+        anyhow::bail!("MINOOTS_STANDBY_LEARNER needs MINOOTS_STANDBY_OF");
+    }
+    if learner && std::env::var("MINOOTS_K8S_LEASE").is_ok() {
+        anyhow::bail!("a learner never campaigns; unset MINOOTS_K8S_LEASE");
+    }
+    let store_path = std::env::var("MINOOTS_STORE_PATH").ok();
+    let flush_interval_ms = quota_limit("MINOOTS_STORE_FLUSH_INTERVAL_MS")?;
+    let batch_size = quota_limit("MINOOTS_STORE_BATCH_SIZE")?;
+    let kernel = match &store_path {
+        Some(path) => {
+            let options = FileStoreOptions {
+                terminal_retention: quota_limit("MINOOTS_STORE_TERMINAL_RETENTION_SECS")?
+                    .map(Duration::from_secs),
+                compact_every: quota_limit("MINOOTS_STORE_COMPACT_EVERY")?
+                    .map(|records| records.max(1) as usize),
+            };
+            info!(%path, ?options, "persisting timers to file store");
+            let mut store: Arc<dyn TimerStore> =
+                Arc::new(FileTimerStore::open_with(path, options)?);
+            if flush_interval_ms.is_some() || batch_size.is_some() {
+                let mut batch = WriteBatchConfig::default();
+                if let Some(flush_interval_ms) = flush_interval_ms {
+                    batch.flush_interval = Duration::from_millis(flush_interval_ms);
+                }
+                if let Some(batch_size) = batch_size {
+                    batch.max_batch = batch_size.max(1) as usize;
+                }
+                info!(?batch, "batching store writes");
+                store = Arc::new(BatchingTimerStore::new(store, batch));
+            }
+            HorologyKernel::with_store(config, store)
+        }
+        None => HorologyKernel::new(config),
+    };
+    // Operators promoting standbys by hand raise this with every failover.
+    if let Some(token) = quota_limit("MINOOTS_FENCING_TOKEN")? {
+        info!(token, "fencing fires");
+        kernel.set_fencing_token(token);
+    }
+    let policy_task = match std::env::var("MINOOTS_TENANT_POLICY_PATH") {
+        Ok(path) => {
+            let store = FileTenantPolicyStore::new(&path);
+            let loaded = kernel.reload_tenant_policies(&store).await?;
+            info!(%path, loaded, "loaded tenant policies; send SIGHUP to reload");
+            let reload_every = Duration::from_secs(
+                std::env::var("MINOOTS_TENANT_POLICY_RELOAD_SECS")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(30),
+            );
+            let mut reload_signal = signal::unix::signal(signal::unix::SignalKind::hangup())?;
+            let policy_kernel = kernel.clone();
+            Some(kernel.tasks().spawn("tenant-policy-reload", async move {
+                let mut interval = tokio::time::interval(reload_every);
+                interval.tick().await;
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {}
+                        _ = reload_signal.recv() => {}
+                    }
+                    reload_policies(&policy_kernel, &store).await;
+                }
+            }))
+        }
+        Err(_) => None,
+    };
     let mut events = kernel.subscribe();
     let grpc_addr: SocketAddr = std::env::var("KERNEL_GRPC_ADDR")
         .or_else(|_| std::env::var("KERNEL_GRPC_URL"))
         .unwrap_or_else(|_| "0.0.0.0:50051".to_string())
         .parse()?;
-    let grpc_service = HorologyKernelService::new(kernel.clone());
+    if let Ok(secret) = std::env::var("MINOOTS_ADMIN_TOKEN") {
+        kernel.tokens().set_root_secret(&secret);
+        info!("API token authentication enforced");
+    }
+    if let Ok(path) = std::env::var("MINOOTS_API_TOKEN_STORE_PATH") {
+        let loaded = kernel
+            .tokens()
+            .attach_store(Arc::new(FileAuthStore::new(&path)))
+            .await?;
+        info!(%path, loaded, "loaded API tokens");
+    }
+    if let Ok(path) = std::env::var("MINOOTS_DELIVERY_STORE_PATH") {
+        let loaded = kernel
+            .deliveries()
+            .attach_store(Arc::new(FileDeliveryStore::new(&path)))
+            .await?;
+        info!(%path, loaded, "loaded delivery receipts");
+    }
+    if let Ok(path) = std::env::var("MINOOTS_USAGE_STORE_PATH") {
+        let loaded = kernel
+            .usage()
+            .attach_store(Arc::new(FileUsageStore::new(&path)))
+            .await?;
+        info!(%path, loaded, "loaded usage records");
+    }
+    if let Ok(path) = std::env::var("MINOOTS_WEBHOOK_STORE_PATH") {
+        let loaded = kernel.webhooks().attach_file(&path)?;
+        info!(%path, loaded, "loaded event webhooks");
+    }
+    if std::env::var("MINOOTS_WEBHOOK_ALLOW_PRIVATE").as_deref() == Ok("1") {
+        kernel.webhooks().allow_private_destinations(true);
+        warn!("event webhooks and embedded orchestrator actions may reach private and loopback addresses");
+    }
+    let jwks_task = match JwtConfig::from_env()? {
+        Some(config) => {
+            let verifier = JwtVerifier::new(config);
+            // A provider that is down at boot only delays JWT callers; the refresh task retries.
+            match verifier.refresh().await {
+                Ok(keys) => info!(keys, "JWT authentication enforced"),
+                Err(error) => {
+                    error!(%error, "failed to load JWKS; JWTs are refused until it loads")
+                }
+            }
+            kernel.tokens().set_verifier(Arc::new(verifier.clone()));
+            Some(kernel.tasks().spawn("jwks-refresh", async move {
+                verifier.run().await;
+            }))
+        }
+        None => None,
+    };
+    if kernel.tokens().is_enforced() {
+        warn_plaintext_listener("gRPC", grpc_addr);
+    }
+    let mut grpc_service = HorologyKernelService::new(kernel.clone());
+    if let Some(primary) = &standby_of {
+        // Until promoted, schedules and cancels sent to the standby are answered by the primary.
+        grpc_service = grpc_service.forward_to_primary(PrimaryForwarder::new(primary.clone())?);
+    }
+    // One set of buckets for the gRPC API and the REST gateway.
+    let rate_limits = RateLimitLayer::new(kernel.clone());
 
     // Spawn a demo timer if running in local dev mode.
-    if std::env::var("MINOOTS_BOOT_DEMO").is_ok() {
+    if std::env::var("MINOOTS_BOOT_DEMO").is_ok() && kernel.is_active() {
         info!("Scheduling demo timer");
         kernel
             .schedule(TimerSpec {
@@ -33,37 +263,625 @@ async fn main() -> anyhow::Result<()> {
                 labels: HashMap::new(),
                 action_bundle: None,
                 agent_binding: None,
+                ..Default::default()
             })
             .await?;
     }
 
-    let event_task = tokio::spawn(async move {
+    let color = tail::color_enabled();
+    let event_task = kernel.tasks().spawn("event-log", async move {
         loop {
             match events.recv().await {
-                Ok(event) => info!(?event, "timer event"),
-                Err(err) => {
-                    tracing::warn!(?err, "event channel closed");
+                Ok(event) if tail_events => {
+                    println!(
+                        "{}",
+                        EventLine::from(&event).render(chrono::Utc::now(), color)
+                    );
+                }
+                Ok(event) => log_event(&event),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "event log fell behind; events dropped");
+                }
+                Err(RecvError::Closed) => {
+                    warn!("event channel closed");
                     break;
                 }
             }
         }
     });
 
-    info!(%grpc_addr, "Starting horology kernel gRPC server");
-    Server::builder()
-        .add_service(HorologyKernelServer::new(grpc_service))
-        .serve_with_shutdown(grpc_addr, async {
-            signal::ctrl_c()
+    let usage_period = Duration::from_secs(
+        std::env::var("MINOOTS_USAGE_PERIOD_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(3600),
+    );
+    let usage_kernel = kernel.clone();
+    let usage_task = kernel.tasks().spawn("usage-meter", async move {
+        let mut interval = tokio::time::interval(usage_period);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            log_usage(usage_kernel.usage().close_period().await);
+        }
+    });
+
+    let standby_task = match standby_of {
+        Some(primary) if learner => {
+            let follower =
+                StandbyFollower::new(primary, std::env::var("MINOOTS_STANDBY_TOKEN").ok());
+            let learner_kernel = kernel.clone();
+            info!("Starting as a learner; it follows the primary and is never promoted");
+            Some(kernel.tasks().spawn("standby-follower", async move {
+                follower.run(learner_kernel).await
+            }))
+        }
+        Some(primary) => {
+            let mut promote_signal =
+                signal::unix::signal(signal::unix::SignalKind::user_defined1())?;
+            let follower =
+                StandbyFollower::new(primary, std::env::var("MINOOTS_STANDBY_TOKEN").ok());
+            let standby_kernel = kernel.clone();
+            info!("Starting as a warm standby; send SIGUSR1 to promote");
+            Some(kernel.tasks().spawn("standby-follower", async move {
+                tokio::join!(follower.run(standby_kernel.clone()), async {
+                    if promote_signal.recv().await.is_some() {
+                        standby_kernel.promote();
+                    }
+                });
+            }))
+        }
+        None => None,
+    };
+
+    #[cfg(feature = "embedded-orchestrator")]
+    let orchestrator_task = std::env::var("MINOOTS_EMBEDDED_ORCHESTRATOR")
+        .is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .then(|| {
+            info!("Executing timer actions in-process");
+            let orchestrator_kernel = kernel.clone();
+            kernel.tasks().spawn("embedded-orchestrator", async move {
+                // Actions follow the event webhooks' MINOOTS_WEBHOOK_ALLOW_PRIVATE setting.
+                horology_kernel::orchestrator::EmbeddedOrchestrator::new(
+                    orchestrator_kernel.webhooks().clone(),
+                )
+                .run(orchestrator_kernel)
                 .await
-                .expect("failed to listen for shutdown signal");
+            })
+        });
+
+    #[cfg(feature = "kubernetes")]
+    let (lease_elector, kube_tasks) = {
+        use horology_kernel::kubernetes::{
+            EventRecorder, KubeClient, LeaseElector, PodIdentity, DEFAULT_API_URL,
+        };
+        let client = KubeClient::new(
+            std::env::var("MINOOTS_K8S_API_URL").unwrap_or_else(|_| DEFAULT_API_URL.to_string()),
+        );
+        let identity = PodIdentity::from_env();
+        let mut tasks = Vec::new();
+        if std::env::var("MINOOTS_K8S_EVENTS")
+            .is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        {
+            let recorder = EventRecorder::new(client.clone(), identity.clone());
+            let system_events = kernel.subscribe_system();
+            tasks.push(kernel.tasks().spawn("k8s-event-recorder", async move {
+                recorder.run(system_events).await
+            }));
+        }
+        let elector = std::env::var("MINOOTS_K8S_LEASE").ok().map(|lease| {
+            info!(%lease, node_id = identity.node_id(), "campaigning for leadership lease");
+            Arc::new(LeaseElector::new(client, identity, lease))
+        });
+        if let Some(elector) = elector.clone() {
+            let elector_kernel = kernel.clone();
+            tasks.push(kernel.tasks().spawn("k8s-lease-elector", async move {
+                elector.run(elector_kernel).await
+            }));
+        }
+        (elector, tasks)
+    };
+
+    let ops_webhook = std::env::var("MINOOTS_OPS_WEBHOOK_URL")
+        .ok()
+        .map(|url| spawn_ops_webhook(kernel.tasks(), url, kernel.subscribe_system()));
+    let event_sinks = spawn_event_sinks(&kernel)?;
+
+    let heartbeat_task = match store_path {
+        Some(_) => {
+            let report = kernel.restore_from_store().await?;
+            for entry in &report.entries {
+                warn!(
+                    timer_id = %entry.timer_id,
+                    tenant_id = %entry.tenant_id,
+                    fire_at = %entry.fire_at,
+                    late_by_ms = entry.late_by_ms,
+                    "timer fell due while the kernel was down"
+                );
+            }
+            let heartbeat_kernel = kernel.clone();
+            Some(kernel.tasks().spawn("store-heartbeat", async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(5));
+                loop {
+                    interval.tick().await;
+                    if let Err(error) = heartbeat_kernel.heartbeat().await {
+                        warn!(?error, "failed to record store heartbeat");
+                    }
+                }
+            }))
+        }
+        None => None,
+    };
+
+    // A pending timer this far past due lost its fire; 0 turns the detector off.
+    let stuck_grace_ms = quota_limit("MINOOTS_STUCK_TIMER_GRACE_MS")?.unwrap_or(5_000);
+    let stuck_timer_task = (stuck_grace_ms > 0).then(|| {
+        let detector_kernel = kernel.clone();
+        kernel.tasks().spawn("stuck-timer-detector", async move {
+            let grace = Duration::from_millis(stuck_grace_ms);
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            loop {
+                interval.tick().await;
+                detector_kernel.recover_stuck_timers(grace).await;
+            }
         })
-        .await
-        .map_err(|error| {
-            error!(?error, "gRPC server error");
-            anyhow::anyhow!(error)
-        })?;
+    });
+    let drift_task = (drift_threshold_ms > 0).then(|| {
+        let drift_kernel = kernel.clone();
+        kernel.tasks().spawn("clock-drift-detector", async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            loop {
+                interval.tick().await;
+                drift_kernel.check_clock_drift().await;
+            }
+        })
+    });
+    let eviction_task = evicting.then(|| {
+        let eviction_kernel = kernel.clone();
+        kernel.tasks().spawn("terminal-eviction", async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(10));
+            loop {
+                interval.tick().await;
+                let evicted = eviction_kernel.evict_terminal_timers().await;
+                if evicted > 0 {
+                    info!(evicted, "evicted finished timers from memory");
+                }
+            }
+        })
+    });
+
+    // Optional REST/JSON gateway over the same service, for clients without a gRPC stack.
+    let http_gateway = match std::env::var("KERNEL_HTTP_ADDR") {
+        Ok(addr) => {
+            let http_addr: SocketAddr = addr.parse()?;
+            let server = axum::Server::try_bind(&http_addr)?;
+            if kernel.tokens().is_enforced() {
+                warn_plaintext_listener("REST", http_addr);
+            }
+            info!(%http_addr, "Starting horology kernel REST gateway");
+            let router = rest::router(
+                grpc_service.clone(),
+                kernel.tokens().clone(),
+                rate_limits.clone(),
+            );
+            let (stop_tx, stop_rx) = oneshot::channel::<()>();
+            let task = kernel.tasks().spawn(
+                "http-gateway",
+                server
+                    .serve(router.into_make_service())
+                    .with_graceful_shutdown(async {
+                        let _ = stop_rx.await;
+                    }),
+            );
+            Some((stop_tx, task))
+        }
+        Err(_) => None,
+    };
+
+    info!(%grpc_addr, "Starting horology kernel gRPC server");
+    let (server_stop_tx, server_stop_rx) = oneshot::channel::<()>();
+    let mut server_task = kernel.tasks().spawn(
+        "grpc-server",
+        Server::builder()
+            // Rate limiting runs inside authentication so it can key buckets by principal.
+            .add_service(InterceptedService::new(
+                rate_limits.layer(grpc_service.into_server()),
+                grpc::authenticate(kernel.tokens().clone()),
+            ))
+            .add_service(InterceptedService::new(
+                AdminService::new(kernel.clone()).into_server(),
+                grpc::authenticate(kernel.tokens().clone()),
+            ))
+            // Probes carry no token; reflection needs one whenever the kernel's API does.
+            .add_service(HealthService::new(kernel.clone()).into_server())
+            .add_service(ServerReflectionServer::with_interceptor(
+                ReflectionService::new(),
+                grpc::authenticate(kernel.tokens().clone()),
+            ))
+            .serve_with_shutdown(grpc_addr, async {
+                let _ = server_stop_rx.await;
+            }),
+    );
+    kernel.emit_system(SystemEventKind::KernelStarted);
+
+    tokio::select! {
+        result = signal::ctrl_c() => result.expect("failed to listen for shutdown signal"),
+        result = &mut server_task => {
+            event_task.abort();
+            for (_, task) in &event_sinks {
+                task.abort();
+            }
+            usage_task.abort();
+            if let Some(task) = heartbeat_task {
+                task.abort();
+            }
+            if let Some(task) = stuck_timer_task {
+                task.abort();
+            }
+            if let Some(task) = drift_task {
+                task.abort();
+            }
+            if let Some(task) = eviction_task {
+                task.abort();
+            }
+            return match result? {
+                Ok(()) => Ok(()),
+                Err(error) => {
+                    error!(?error, "gRPC server error");
+                    Err(anyhow::anyhow!(error))
+                }
+            };
+        }
+    }
 
     info!("Shutting down horology kernel");
-    event_task.abort();
+    let mut coordinator = ShutdownCoordinator::new();
+    // Ingress first so no new timers arrive while the rest of the kernel winds down.
+    coordinator.register("grpc-server", Duration::from_secs(10), async move {
+        let _ = server_stop_tx.send(());
+        match server_task.await {
+            Ok(Err(error)) => error!(?error, "gRPC server error during shutdown"),
+            Err(error) => error!(?error, "gRPC server task failed"),
+            Ok(Ok(())) => {}
+        }
+    });
+    if let Some((stop_tx, task)) = http_gateway {
+        // Open event streams end when the kernel drains, so this waits at most for in-flight requests.
+        coordinator.register("http-gateway", Duration::from_secs(10), async move {
+            let _ = stop_tx.send(());
+            match task.await {
+                Ok(Err(error)) => error!(?error, "REST gateway error during shutdown"),
+                Err(error) => error!(?error, "REST gateway task failed"),
+                Ok(Ok(())) => {}
+            }
+        });
+    }
+    if let Some(task) = standby_task {
+        coordinator.register("standby-follower", Duration::from_secs(2), async move {
+            task.abort();
+            let _ = task.await;
+        });
+    }
+    #[cfg(feature = "embedded-orchestrator")]
+    if let Some(task) = orchestrator_task {
+        coordinator.register(
+            "embedded-orchestrator",
+            Duration::from_secs(5),
+            async move {
+                task.abort();
+                let _ = task.await;
+            },
+        );
+    }
+    // Steps down, lets an in-flight fire land, and leaves a flushed snapshot for the next leader
+    // before the lease is given up.
+    let drain_kernel = kernel.clone();
+    coordinator.register("kernel-drain", Duration::from_secs(10), async move {
+        if let Err(error) = drain_kernel.shutdown().await {
+            error!(%error, "failed to drain the kernel");
+        }
+    });
+    // After the drain, so the sinks forward every event up to the final snapshot.
+    coordinator.register("event-sinks", Duration::from_secs(10), async move {
+        let mut tasks = Vec::new();
+        for (stop_tx, task) in event_sinks {
+            let _ = stop_tx.send(());
+            tasks.push(task);
+        }
+        for task in tasks {
+            let _ = task.await;
+        }
+    });
+    #[cfg(feature = "kubernetes")]
+    coordinator.register("kubernetes", Duration::from_secs(5), async move {
+        for task in kube_tasks {
+            task.abort();
+            let _ = task.await;
+        }
+        if let Some(elector) = lease_elector {
+            if let Err(error) = elector.release().await {
+                warn!(%error, "failed to release leadership lease");
+            }
+        }
+    });
+    // The dispatch loop ends with the runtime; stop the periodic roll-up and flush the usage
+    // accumulated since the last period so it is not lost.
+    let flush_kernel = kernel.clone();
+    coordinator.register("usage-meter", Duration::from_secs(5), async move {
+        usage_task.abort();
+        let _ = usage_task.await;
+        log_usage(flush_kernel.usage().close_period().await);
+    });
+    if let Some(task) = heartbeat_task {
+        coordinator.register("store-heartbeat", Duration::from_secs(2), async move {
+            task.abort();
+            let _ = task.await;
+        });
+    }
+    if let Some(task) = stuck_timer_task {
+        coordinator.register("stuck-timer-detector", Duration::from_secs(2), async move {
+            task.abort();
+            let _ = task.await;
+        });
+    }
+    if let Some(task) = drift_task {
+        coordinator.register("clock-drift-detector", Duration::from_secs(2), async move {
+            task.abort();
+            let _ = task.await;
+        });
+    }
+    if let Some(task) = eviction_task {
+        coordinator.register("terminal-eviction", Duration::from_secs(2), async move {
+            task.abort();
+            let _ = task.await;
+        });
+    }
+    if let Some(task) = jwks_task {
+        coordinator.register("jwks-refresh", Duration::from_secs(2), async move {
+            task.abort();
+            let _ = task.await;
+        });
+    }
+    if let Some(task) = policy_task {
+        coordinator.register("tenant-policy-reload", Duration::from_secs(2), async move {
+            task.abort();
+            let _ = task.await;
+        });
+    }
+    coordinator.register("event-log", Duration::from_secs(2), async move {
+        event_task.abort();
+        let _ = event_task.await;
+    });
+    // Last, so operational events raised while stopping the other components still go out.
+    if let Some((stop_tx, task)) = ops_webhook {
+        coordinator.register("ops-webhook", Duration::from_secs(5), async move {
+            let _ = stop_tx.send(());
+            let _ = task.await;
+        });
+    }
+    coordinator.shutdown().await;
     Ok(())
 }
+
+/// Swaps in the store's current tenant policies, keeping the previous ones if the store is
+/// unreadable or malformed.
+async fn reload_policies(kernel: &HorologyKernel, store: &dyn TenantPolicyStore) {
+    match kernel.reload_tenant_policies(store).await {
+        Ok(loaded) => info!(loaded, "reloaded tenant policies"),
+        Err(error) => warn!(%error, "failed to reload tenant policies; keeping previous ones"),
+    }
+}
+
+fn log_usage(closed: Result<Vec<UsageRecord>, StoreError>) {
+    match closed {
+        Ok(records) => {
+            for record in records {
+                info!(?record, "usage record");
+            }
+        }
+        Err(error) => warn!(%error, "failed to persist usage records"),
+    }
+}
+
+/// Starts a forwarder for the webhook sink and for each event sink configured through the
+/// environment, each on its own subscription, returning their stop signals and tasks.
+fn spawn_event_sinks(
+    kernel: &HorologyKernel,
+) -> anyhow::Result<Vec<(oneshot::Sender<()>, JoinHandle<()>)>> {
+    use horology_kernel::sinks::{self, webhook::WebhookSink};
+    use horology_kernel::{EventSink, RetryPolicy};
+
+    let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    let mut retry = RetryPolicy::default();
+    if let Some(attempts) = var("MINOOTS_SINK_MAX_ATTEMPTS") {
+        retry.max_attempts = attempts.parse::<u32>()?.max(1);
+    }
+
+    let configured = std::iter::once::<Box<dyn EventSink>>(Box::new(
+        WebhookSink::new(kernel.webhooks().clone()).with_ledger(kernel.deliveries().clone()),
+    ));
+    #[cfg(feature = "cloud-sinks")]
+    let configured = configured.chain(cloud_sinks(&var)?);
+
+    Ok(configured
+        .map(|sink| {
+            info!(sink = sink.name(), "forwarding timer events");
+            let (stop_tx, stop_rx) = oneshot::channel::<()>();
+            let events = kernel.subscribe();
+            let dead_letters = kernel.dead_letters().clone();
+            let task = kernel
+                .tasks()
+                .spawn(format!("event-sink-{}", sink.name()), async move {
+                    sinks::forward(sink.as_ref(), events, retry, &dead_letters, async {
+                        let _ = stop_rx.await;
+                    })
+                    .await
+                });
+            (stop_tx, task)
+        })
+        .collect())
+}
+
+/// The Pub/Sub, SNS, and SQS sinks configured through the environment.
+#[cfg(feature = "cloud-sinks")]
+fn cloud_sinks(
+    var: &dyn Fn(&str) -> Option<String>,
+) -> anyhow::Result<Vec<Box<dyn horology_kernel::EventSink>>> {
+    use horology_kernel::sinks::{
+        aws::{AwsCredentials, SnsSink, SqsSink},
+        envelope::{Encoder, SigningKey, SinkEncoding},
+        pubsub::{PubSubSink, EMULATOR_URL},
+    };
+    use horology_kernel::EventSink;
+
+    let credentials = || {
+        AwsCredentials::from_env().ok_or_else(|| {
+            anyhow::anyhow!(
+                "AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY are required by the SNS and SQS sinks"
+            )
+        })
+    };
+    let encoding = match var("MINOOTS_SINK_ENCODING") {
+        Some(encoding) => encoding.parse::<SinkEncoding>()?,
+        None => SinkEncoding::Json,
+    };
+    let signing_key = var("MINOOTS_SINK_SIGNING_KEY").map(|secret| {
+        let key_id = var("MINOOTS_SINK_SIGNING_KEY_ID").unwrap_or_else(|| "default".to_string());
+        SigningKey::new(key_id, secret.into_bytes())
+    });
+    if signing_key.is_some() && encoding != SinkEncoding::Protobuf {
+        anyhow::bail!("MINOOTS_SINK_SIGNING_KEY needs MINOOTS_SINK_ENCODING=protobuf");
+    }
+    let encoder = Encoder::new(encoding, signing_key);
+
+    let mut configured: Vec<Box<dyn EventSink>> = Vec::new();
+    if let Some(topic) = var("MINOOTS_PUBSUB_TOPIC") {
+        let url = var("MINOOTS_PUBSUB_URL").unwrap_or_else(|| EMULATOR_URL.to_string());
+        let token_file = var("MINOOTS_PUBSUB_TOKEN_FILE").map(std::path::PathBuf::from);
+        configured.push(Box::new(
+            PubSubSink::new(&url, &topic, token_file).with_encoder(encoder.clone()),
+        ));
+    }
+    if let Some(topic_arn) = var("MINOOTS_SNS_TOPIC_ARN") {
+        let url = var("MINOOTS_SNS_URL")
+            .ok_or_else(|| anyhow::anyhow!("MINOOTS_SNS_TOPIC_ARN needs MINOOTS_SNS_URL"))?;
+        configured.push(Box::new(
+            SnsSink::new(&url, topic_arn, credentials()?)?.with_encoder(encoder.clone()),
+        ));
+    }
+    if let Some(queue_url) = var("MINOOTS_SQS_QUEUE_URL") {
+        let region = var("AWS_REGION")
+            .or_else(|| var("AWS_DEFAULT_REGION"))
+            .ok_or_else(|| anyhow::anyhow!("MINOOTS_SQS_QUEUE_URL needs AWS_REGION"))?;
+        configured.push(Box::new(
+            SqsSink::new(&queue_url, region, credentials()?)?.with_encoder(encoder.clone()),
+        ));
+    }
+    Ok(configured)
+}
+
+/// Forwards operational events to `url` as JSON POSTs until told to stop, then drains what is left.
+fn spawn_ops_webhook(
+    tasks: &TaskRegistry,
+    url: String,
+    mut events: broadcast::Receiver<SystemEvent>,
+) -> (oneshot::Sender<()>, JoinHandle<()>) {
+    let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+    let task = tasks.spawn("ops-webhook", async move {
+        let client = hyper::Client::new();
+        loop {
+            let event = tokio::select! {
+                event = events.recv() => event,
+                _ = &mut stop_rx => {
+                    while let Ok(event) = events.try_recv() {
+                        deliver_ops_event(&client, &url, &event).await;
+                    }
+                    break;
+                }
+            };
+            match event {
+                Ok(event) => deliver_ops_event(&client, &url, &event).await,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "ops webhook fell behind; events dropped")
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+    (stop_tx, task)
+}
+
+async fn deliver_ops_event(
+    client: &hyper::Client<hyper::client::HttpConnector>,
+    url: &str,
+    event: &SystemEvent,
+) {
+    let result = async {
+        let request = hyper::Request::post(url)
+            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .body(hyper::Body::from(serde_json::to_vec(event)?))?;
+        let response = client.request(request).await?;
+        anyhow::ensure!(
+            response.status().is_success(),
+            "ops webhook responded with {}",
+            response.status()
+        );
+        Ok(())
+    }
+    .await;
+    if let Err(error) = result {
+        warn!(
+            ?error,
+            kind = event.kind.name(),
+            "failed to deliver ops event"
+        );
+    }
+}
+
+fn log_event(event: &TimerEvent) {
+    match event {
+        TimerEvent::Scheduled(timer) => {
+            info!(timer_id = %timer.id, tenant_id = %timer.tenant_id, fire_at = %timer.fire_at, "timer scheduled")
+        }
+        TimerEvent::PreFire { timer, fires_in_ms } => {
+            info!(timer_id = %timer.id, tenant_id = %timer.tenant_id, fires_in_ms, "timer about to fire")
+        }
+        TimerEvent::Rescheduled {
+            timer,
+            previous_fire_at,
+        } => {
+            info!(timer_id = %timer.id, tenant_id = %timer.tenant_id, %previous_fire_at, fire_at = %timer.fire_at, "timer rescheduled")
+        }
+        TimerEvent::Fired(timer) => {
+            info!(timer_id = %timer.id, tenant_id = %timer.tenant_id, "timer fired")
+        }
+        TimerEvent::Cancelled { timer, reason } => {
+            info!(timer_id = %timer.id, tenant_id = %timer.tenant_id, ?reason, "timer cancelled")
+        }
+        TimerEvent::Missed { timer, late_by_ms } => {
+            warn!(timer_id = %timer.id, tenant_id = %timer.tenant_id, late_by_ms, "timer missed during downtime")
+        }
+        TimerEvent::Acknowledged(timer) => {
+            info!(timer_id = %timer.id, tenant_id = %timer.tenant_id, "deadline acknowledged")
+        }
+        TimerEvent::Failed(timer) => {
+            warn!(timer_id = %timer.id, tenant_id = %timer.tenant_id, reason = ?timer.failure_reason, "timer failed")
+        }
+        TimerEvent::Settled(timer) => {
+            info!(timer_id = %timer.id, tenant_id = %timer.tenant_id, "timer settled")
+        }
+    }
+}
+
+/// The kernel serves plaintext only; TLS and client certificates are terminated by a sidecar in
+/// front of it (`KERNEL_TLS_TERMINATED=1`). Without one, bearer tokens cross the network in clear.
+fn warn_plaintext_listener(listener: &str, addr: SocketAddr) {
+    let terminated = std::env::var("KERNEL_TLS_TERMINATED").is_ok_and(|value| value == "1");
+    if !addr.ip().is_loopback() && !terminated {
+        warn!(
+            %addr,
+            "{listener} listener is plaintext on a non-loopback address; terminate TLS in a sidecar \
+             and set KERNEL_TLS_TERMINATED=1"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use horology_kernel::legacy;
+use horology_kernel::pb::horology_kernel_client::HorologyKernelClient;
+use horology_kernel::pb::ImportLegacyTimersRequest;
+use tonic::metadata::AsciiMetadataValue;
+
+const BATCH_SIZE: usize = 500;
+
+/// Imports timers exported from the control plane's repository (a JSON array of `TimerRecord`s or
+/// one per line) into a running kernel. Ids and fire times are preserved and records the kernel
+/// already has are skipped, so the import can be re-run after a partial failure.
+///
+/// Usage: `minoots-import --file <records.json> [--addr http://127.0.0.1:50051]`. Sends
+/// `MINOOTS_ADMIN_TOKEN` as the operator token when set.
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut addr =
+        std::env::var("KERNEL_GRPC_URL").unwrap_or_else(|_| "http://127.0.0.1:50051".to_string());
+    let mut file = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--addr" => {
+                addr = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--addr needs a value"))?
+            }
+            "--file" => {
+                file = Some(
+                    args.next()
+                        .ok_or_else(|| anyhow::anyhow!("--file needs a value"))?,
+                )
+            }
+            other => anyhow::bail!("unknown argument: {other}"),
+        }
+    }
+    let file = file.ok_or_else(|| anyhow::anyhow!("--file is required"))?;
+    if !addr.contains("://") {
+        addr = format!("http://{addr}");
+    }
+    // Parsed locally so large exports go out in batches that fit a gRPC message.
+    let records = legacy::parse_records(&std::fs::read_to_string(&file)?)?;
+    let authorization: Option<AsciiMetadataValue> = std::env::var("MINOOTS_ADMIN_TOKEN")
+        .ok()
+        .map(|token| format!("Bearer {token}").parse())
+        .transpose()?;
+
+    let mut client = HorologyKernelClient::connect(addr).await?;
+    let (mut imported, mut already_present, mut skipped) = (0, 0, 0);
+    for batch in records.chunks(BATCH_SIZE) {
+        let mut request = tonic::Request::new(ImportLegacyTimersRequest {
+            records_json: serde_json::to_string(batch)?,
+        });
+        if let Some(authorization) = &authorization {
+            request
+                .metadata_mut()
+                .insert("authorization", authorization.clone());
+        }
+        let report = client
+            .import_legacy_timers(request)
+            .await
+            .map_err(|status| anyhow::anyhow!("{}", status.message()))?
+            .into_inner();
+        for timer in &report.imported {
+            println!(
+                "imported  {}  {}  {}",
+                timer.id, timer.fire_at_iso, timer.name
+            );
+        }
+        for rejection in &report.rejected {
+            eprintln!("rejected  {}  {}", rejection.id, rejection.reason);
+        }
+        imported += report.imported.len();
+        already_present += report.already_present.len();
+        skipped += report.skipped_terminal.len();
+    }
+    println!(
+        "{} records: {imported} imported, {already_present} already present, {skipped} fired or cancelled",
+        records.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use horology_kernel::pb::horology_kernel_client::HorologyKernelClient;
+use horology_kernel::pb::{TimerListRequest, TimerSortOrder, TimerStatus};
+use tonic::metadata::AsciiMetadataValue;
+
+const PAGE_SIZE: u32 = 500;
+
+/// Lists a tenant's timers, optionally filtered with the `ListTimers` query syntax.
+///
+/// Usage: `minoots-list --tenant <tenant-id> [--addr http://127.0.0.1:50051] [--include-projects]
+/// [--query "status in (scheduled,armed) AND label.env=prod AND fire_at < now+1h"]
+/// [--label key=value]... [--name-prefix <prefix>] [--sort fire_at|-fire_at|created_at|-created_at|name]
+/// [--token <token>]`. Without `--token`, sends `MINOOTS_API_TOKEN` or else `MINOOTS_ADMIN_TOKEN`
+/// when set.
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut addr =
+        std::env::var("KERNEL_GRPC_URL").unwrap_or_else(|_| "http://127.0.0.1:50051".to_string());
+    let mut tenant_id = None;
+    let mut token = None;
+    let mut query = String::new();
+    let mut include_projects = false;
+    let mut label_selectors = Vec::new();
+    let mut name_prefix = String::new();
+    let mut sort_order = TimerSortOrder::Unspecified;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--addr" => {
+                addr = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--addr needs a value"))?
+            }
+            "--tenant" => {
+                tenant_id = Some(
+                    args.next()
+                        .ok_or_else(|| anyhow::anyhow!("--tenant needs a value"))?,
+                )
+            }
+            "--token" => {
+                token = Some(
+                    args.next()
+                        .ok_or_else(|| anyhow::anyhow!("--token needs a value"))?,
+                )
+            }
+            "--query" => {
+                query = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--query needs a value"))?
+            }
+            "--label" => label_selectors.push(
+                args.next()
+                    .ok_or_else(|| anyhow::anyhow!("--label needs a value"))?,
+            ),
+            "--name-prefix" => {
+                name_prefix = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--name-prefix needs a value"))?
+            }
+            "--sort" => {
+                sort_order = match args.next().as_deref() {
+                    Some("fire_at") => TimerSortOrder::FireAtAsc,
+                    Some("-fire_at") => TimerSortOrder::FireAtDesc,
+                    Some("created_at") => TimerSortOrder::CreatedAtAsc,
+                    Some("-created_at") => TimerSortOrder::CreatedAtDesc,
+                    Some("name") => TimerSortOrder::Name,
+                    other => anyhow::bail!("unknown --sort order: {}", other.unwrap_or_default()),
+                }
+            }
+            "--include-projects" => include_projects = true,
+            other => anyhow::bail!("unknown argument: {other}"),
+        }
+    }
+    let tenant_id = tenant_id.ok_or_else(|| anyhow::anyhow!("--tenant is required"))?;
+    if !addr.contains("://") {
+        addr = format!("http://{addr}");
+    }
+    let authorization: Option<AsciiMetadataValue> = token
+        .or_else(|| std::env::var("MINOOTS_API_TOKEN").ok())
+        .or_else(|| std::env::var("MINOOTS_ADMIN_TOKEN").ok())
+        .map(|token| format!("Bearer {token}").parse())
+        .transpose()?;
+
+    let mut client = HorologyKernelClient::connect(addr).await?;
+    let mut request = TimerListRequest {
+        tenant_id,
+        include_projects,
+        query,
+        label_selectors,
+        name_prefix,
+        sort_order: sort_order as i32,
+        page_size: PAGE_SIZE,
+        ..Default::default()
+    };
+    // Pages come from one snapshot, so the walk is consistent even while timers change.
+    loop {
+        let mut call = tonic::Request::new(request.clone());
+        if let Some(authorization) = &authorization {
+            call.metadata_mut()
+                .insert("authorization", authorization.clone());
+        }
+        let page = client
+            .list_timers(call)
+            .await
+            .map_err(|status| anyhow::anyhow!("{}", status.message()))?
+            .into_inner();
+        for timer in page.timers {
+            let status = TimerStatus::try_from(timer.status)
+                .map(|status| {
+                    status
+                        .as_str_name()
+                        .trim_start_matches("TIMER_STATUS_")
+                        .to_lowercase()
+                })
+                .unwrap_or_default();
+            println!(
+                "{}  {:<9}  {}  {}",
+                timer.id, status, timer.fire_at_iso, timer.name
+            );
+        }
+        if page.next_page_token.is_empty() {
+            break;
+        }
+        request.page_token = page.next_page_token;
+    }
+    Ok(())
+}
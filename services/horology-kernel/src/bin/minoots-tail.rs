@@ -0,0 +1,86 @@
+use horology_kernel::pb::horology_kernel_client::HorologyKernelClient;
+use horology_kernel::pb::TimerEventStreamRequest;
+use horology_kernel::tail::{self, EventLine};
+use tonic::metadata::AsciiMetadataValue;
+
+/// Live tail of a running kernel's lifecycle events.
+///
+/// Usage: `minoots-tail --tenant <tenant-id> [--addr http://127.0.0.1:50051] [--token <token>]
+/// [--include-projects] [--resume-from <sequence>]`. Without `--token`, sends `MINOOTS_API_TOKEN`
+/// or else `MINOOTS_ADMIN_TOKEN` when set. Operators tail every tenant with `--tenant __all__`.
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut addr =
+        std::env::var("KERNEL_GRPC_URL").unwrap_or_else(|_| "http://127.0.0.1:50051".to_string());
+    let mut tenant_id = None;
+    let mut token = None;
+    let mut include_projects = false;
+    let mut resume_from_sequence = 0;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--addr" => {
+                addr = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--addr needs a value"))?
+            }
+            "--tenant" => {
+                tenant_id = Some(
+                    args.next()
+                        .ok_or_else(|| anyhow::anyhow!("--tenant needs a value"))?,
+                )
+            }
+            "--token" => {
+                token = Some(
+                    args.next()
+                        .ok_or_else(|| anyhow::anyhow!("--token needs a value"))?,
+                )
+            }
+            "--include-projects" => include_projects = true,
+            "--resume-from" => {
+                resume_from_sequence = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--resume-from needs a value"))?
+                    .parse()?
+            }
+            other => anyhow::bail!("unknown argument: {other}"),
+        }
+    }
+    let tenant_id = tenant_id.ok_or_else(|| anyhow::anyhow!("--tenant is required"))?;
+    if !addr.contains("://") {
+        addr = format!("http://{addr}");
+    }
+    let authorization: Option<AsciiMetadataValue> = token
+        .or_else(|| std::env::var("MINOOTS_API_TOKEN").ok())
+        .or_else(|| std::env::var("MINOOTS_ADMIN_TOKEN").ok())
+        .map(|token| format!("Bearer {token}").parse())
+        .transpose()?;
+
+    let mut client = HorologyKernelClient::connect(addr).await?;
+    let mut request = tonic::Request::new(TimerEventStreamRequest {
+        tenant_id,
+        topics: vec![],
+        include_projects,
+        resume_from_sequence,
+        ..Default::default()
+    });
+    if let Some(authorization) = authorization {
+        request
+            .metadata_mut()
+            .insert("authorization", authorization);
+    }
+    let mut events = client
+        .stream_timer_events(request)
+        .await
+        .map_err(|status| anyhow::anyhow!("{}", status.message()))?
+        .into_inner();
+
+    let color = tail::color_enabled();
+    while let Some(event) = events.message().await? {
+        if let Some(line) = EventLine::from_proto(&event) {
+            println!("{}", line.render(chrono::Utc::now(), color));
+        }
+    }
+    Ok(())
+}
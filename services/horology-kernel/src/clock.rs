@@ -0,0 +1,199 @@
+//! Time source of the kernel. Timers and events are stamped with [`Clock::now`], and the dispatch
+//! loop sleeps on [`Clock::sleep_until`], so tests can swap in a [`MockClock`] and advance time by
+//! hand instead of sleeping through it.
+//!
+//! Deadlines are monotonic, so a wall-clock step (an NTP correction, a VM resuming) does not move
+//! them; [`DriftDetector`] notices the step so the kernel can re-arm timers against it.
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::{sync::watch, time::Instant};
+
+#[async_trait]
+pub trait Clock: Send + Sync + Debug {
+    /// Wall-clock time stamped on timers and events.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Monotonic time the dispatch queue orders its deadlines by.
+    fn instant(&self) -> Instant;
+
+    /// Resolves once [`Clock::instant`] reaches `deadline`.
+    async fn sleep_until(&self, deadline: Instant);
+
+    /// [`Clock::sleep_until`] for timers with sub-millisecond deadlines.
+    async fn sleep_until_precisely(&self, deadline: Instant) {
+        self.sleep_until(deadline).await;
+    }
+}
+
+/// The real clock: [`Utc::now`] and the Tokio timer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+
+    async fn sleep_until(&self, deadline: Instant) {
+        tokio::time::sleep_until(deadline).await;
+    }
+
+    /// Sleeps to within a millisecond of `deadline`, then yields until it passes; the Tokio timer
+    /// on its own only resolves whole milliseconds.
+    async fn sleep_until_precisely(&self, deadline: Instant) {
+        if let Some(coarse) = deadline.checked_sub(Duration::from_millis(1)) {
+            tokio::time::sleep_until(coarse).await;
+        }
+        while Instant::now() < deadline {
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+/// Clock that only moves when told to. Sleepers wake as soon as [`MockClock::advance`] carries
+/// the clock past their deadline.
+#[derive(Debug)]
+pub struct MockClock {
+    start: (DateTime<Utc>, Instant),
+    elapsed: watch::Sender<Duration>,
+    wall_step: Mutex<chrono::Duration>,
+}
+
+impl MockClock {
+    /// A clock standing at `now`.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            start: (now, Instant::now()),
+            elapsed: watch::channel(Duration::ZERO).0,
+            wall_step: Mutex::new(chrono::Duration::zero()),
+        }
+    }
+
+    /// Moves the clock forward by `by`, waking every sleeper whose deadline it passes.
+    pub fn advance(&self, by: Duration) {
+        self.elapsed.send_modify(|elapsed| *elapsed += by);
+    }
+
+    /// Steps the wall clock alone, as an NTP correction would, leaving monotonic time and every
+    /// sleeper where they are.
+    pub fn step_wall_clock(&self, by: chrono::Duration) {
+        *self.wall_step.lock().expect("mock clock poisoned") += by;
+    }
+
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.borrow()
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new(Utc::now())
+    }
+}
+
+#[async_trait]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.start.0
+            + chrono::Duration::from_std(self.elapsed()).unwrap_or_default()
+            + *self.wall_step.lock().expect("mock clock poisoned")
+    }
+
+    fn instant(&self) -> Instant {
+        self.start.1 + self.elapsed()
+    }
+
+    async fn sleep_until(&self, deadline: Instant) {
+        let mut elapsed = self.elapsed.subscribe();
+        // The sender lives as long as the clock, which outlives every borrow of it.
+        let _ = elapsed
+            .wait_for(|elapsed| self.start.1 + *elapsed >= deadline)
+            .await;
+    }
+}
+
+/// A wall-clock step seen by [`crate::HorologyKernel::check_clock_drift`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockJump {
+    /// How far the wall clock moved beyond monotonic time; negative when it stepped back.
+    pub jump_ms: i64,
+    /// Pending timers re-armed against the stepped wall clock.
+    pub rearmed: usize,
+}
+
+/// Tracks the offset between wall and monotonic time. The offset only changes when the wall clock
+/// is stepped; gradual NTP slewing between two observations stays far below any useful threshold.
+#[derive(Clone, Debug)]
+pub(crate) struct DriftDetector {
+    anchor: Arc<Mutex<(DateTime<Utc>, Instant)>>,
+}
+
+impl DriftDetector {
+    pub(crate) fn new(clock: &dyn Clock) -> Self {
+        Self {
+            anchor: Arc::new(Mutex::new((clock.now(), clock.instant()))),
+        }
+    }
+
+    /// How far the wall clock moved beyond monotonic time since the previous observation.
+    pub(crate) fn observe(&self, clock: &dyn Clock) -> chrono::Duration {
+        let (now, instant) = (clock.now(), clock.instant());
+        let mut anchor = self.anchor.lock().expect("drift detector poisoned");
+        let elapsed =
+            chrono::Duration::from_std(instant.duration_since(anchor.1)).unwrap_or_default();
+        let jump = now - anchor.0 - elapsed;
+        *anchor = (now, instant);
+        jump
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sleepers_wake_once_the_clock_passes_their_deadline() {
+        let clock = std::sync::Arc::new(MockClock::default());
+        let started = clock.now();
+        let deadline = clock.instant() + Duration::from_secs(60);
+        let sleeper = tokio::spawn({
+            let clock = clock.clone();
+            async move { clock.sleep_until(deadline).await }
+        });
+
+        clock.advance(Duration::from_secs(59));
+        tokio::task::yield_now().await;
+        assert!(!sleeper.is_finished());
+        clock.advance(Duration::from_secs(1));
+        tokio::time::timeout(Duration::from_secs(1), sleeper)
+            .await
+            .expect("sleeper woke")
+            .unwrap();
+        assert_eq!(clock.now() - started, chrono::Duration::seconds(60));
+    }
+
+    #[test]
+    fn only_wall_clock_steps_count_as_drift() {
+        let clock = MockClock::default();
+        let drift = DriftDetector::new(&clock);
+
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(drift.observe(&clock), chrono::Duration::zero());
+        clock.step_wall_clock(chrono::Duration::seconds(-5));
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(drift.observe(&clock), chrono::Duration::seconds(-5));
+        assert_eq!(drift.observe(&clock), chrono::Duration::zero());
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+    clock::{Clock, SystemClock},
+    persistence::{DeliveryStore, StoreError},
+};
+
+/// Receipts kept per subscriber; recording past it drops that subscriber's oldest receipt.
+pub const MAX_RECEIPTS_PER_SUBSCRIBER: usize = 10_000;
+
+/// Subscribers tracked at once; a new one past it drops every receipt of the subscriber that
+/// received nothing for longest.
+pub const MAX_SUBSCRIBERS: usize = 1_024;
+
+/// Record of a fired event being handed to one subscriber.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryReceipt {
+    pub subscriber_id: String,
+    /// Position of the event within the subscriber's stream.
+    pub sequence: u64,
+    pub delivered_at: DateTime<Utc>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+/// Ledger of which subscribers received each fired timer and which of them acknowledged it.
+/// Receipts are bounded per subscriber and, once [`Self::attach_store`] ran, written through to
+/// a [`DeliveryStore`] so acknowledgements survive restarts.
+#[derive(Clone)]
+pub struct DeliveryLedger {
+    inner: Arc<Mutex<Ledger>>,
+    clock: Arc<dyn Clock>,
+}
+
+#[derive(Default)]
+struct Ledger {
+    receipts: HashMap<Uuid, Vec<DeliveryReceipt>>,
+    subscribers: HashMap<String, Subscriber>,
+    /// Bumped on every delivery; orders subscribers by their latest one.
+    deliveries: u64,
+    /// Sends each timer's changed receipts to the store's writer task, in order.
+    writer: Option<mpsc::UnboundedSender<(Uuid, Vec<DeliveryReceipt>)>>,
+}
+
+#[derive(Default)]
+struct Subscriber {
+    /// Timer of each receipt the subscriber holds, oldest first.
+    timers: VecDeque<Uuid>,
+    last_delivery: u64,
+}
+
+impl Default for DeliveryLedger {
+    fn default() -> Self {
+        Self::new(Arc::new(SystemClock))
+    }
+}
+
+impl DeliveryLedger {
+    /// Ledger stamping receipts with `clock`.
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            inner: Arc::default(),
+            clock,
+        }
+    }
+
+    /// Loads every receipt `store` holds and writes later changes through it from a background
+    /// task. Returns how many timers had receipts.
+    pub async fn attach_store(&self, store: Arc<dyn DeliveryStore>) -> Result<usize, StoreError> {
+        let mut stored = store.load_receipts().await?;
+        let (sender, mut changes) = mpsc::unbounded_channel::<(Uuid, Vec<DeliveryReceipt>)>();
+        tokio::spawn(async move {
+            while let Some((timer_id, receipts)) = changes.recv().await {
+                if let Err(error) = store.save_receipts(timer_id, &receipts).await {
+                    tracing::warn!(%error, %timer_id, "failed to persist delivery receipts");
+                }
+            }
+        });
+        let loaded = stored.len();
+        let mut receipts: Vec<(Uuid, DeliveryReceipt)> = stored
+            .drain()
+            .flat_map(|(timer_id, receipts)| receipts.into_iter().map(move |r| (timer_id, r)))
+            .collect();
+        receipts.sort_by_key(|(_, receipt)| receipt.delivered_at);
+        let mut ledger = self.lock();
+        for (timer_id, receipt) in receipts {
+            ledger.insert(timer_id, receipt);
+        }
+        ledger.writer = Some(sender);
+        Ok(loaded)
+    }
+
+    pub fn record(&self, timer_id: Uuid, subscriber_id: &str, sequence: u64) {
+        let receipt = DeliveryReceipt {
+            subscriber_id: subscriber_id.to_string(),
+            sequence,
+            delivered_at: self.clock.now(),
+            acknowledged_at: None,
+        };
+        let mut ledger = self.lock();
+        for changed in ledger.insert(timer_id, receipt) {
+            ledger.write(changed);
+        }
+    }
+
+    /// Marks the subscriber's receipt as acknowledged. Returns `false` when the subscriber never
+    /// received the timer.
+    pub fn acknowledge(&self, timer_id: Uuid, subscriber_id: &str) -> bool {
+        let mut ledger = self.lock();
+        let Some(receipt) = ledger.receipts.get_mut(&timer_id).and_then(|entries| {
+            entries
+                .iter_mut()
+                .find(|r| r.subscriber_id == subscriber_id)
+        }) else {
+            return false;
+        };
+        if receipt.acknowledged_at.is_none() {
+            receipt.acknowledged_at = Some(self.clock.now());
+            ledger.write(timer_id);
+        }
+        true
+    }
+
+    /// Whether any subscriber has acknowledged the timer's fire.
+    pub fn is_acknowledged(&self, timer_id: Uuid) -> bool {
+        self.lock().receipts.get(&timer_id).is_some_and(|receipts| {
+            receipts
+                .iter()
+                .any(|receipt| receipt.acknowledged_at.is_some())
+        })
+    }
+
+    /// Drops the timer's receipts, e.g. before it fires again or once it is evicted.
+    pub fn forget(&self, timer_id: Uuid) {
+        let mut ledger = self.lock();
+        let Some(receipts) = ledger.receipts.remove(&timer_id) else {
+            return;
+        };
+        for receipt in receipts {
+            if let Some(subscriber) = ledger.subscribers.get_mut(&receipt.subscriber_id) {
+                subscriber.timers.retain(|id| *id != timer_id);
+                if subscriber.timers.is_empty() {
+                    ledger.subscribers.remove(&receipt.subscriber_id);
+                }
+            }
+        }
+        ledger.write(timer_id);
+    }
+
+    /// Drops every receipt the subscriber holds.
+    pub fn forget_subscriber(&self, subscriber_id: &str) {
+        let mut ledger = self.lock();
+        for changed in ledger.remove_subscriber(subscriber_id) {
+            ledger.write(changed);
+        }
+    }
+
+    pub fn receipts(&self, timer_id: Uuid) -> Vec<DeliveryReceipt> {
+        self.lock()
+            .receipts
+            .get(&timer_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Ledger> {
+        self.inner.lock().expect("delivery ledger poisoned")
+    }
+}
+
+impl Ledger {
+    /// Adds the receipt, dropping receipts past the per-subscriber and subscriber caps. Returns
+    /// the timers whose receipts changed.
+    fn insert(&mut self, timer_id: Uuid, receipt: DeliveryReceipt) -> Vec<Uuid> {
+        let mut changed = vec![timer_id];
+        if !self.subscribers.contains_key(&receipt.subscriber_id)
+            && self.subscribers.len() >= MAX_SUBSCRIBERS
+        {
+            let idle = self
+                .subscribers
+                .iter()
+                .min_by_key(|(_, subscriber)| subscriber.last_delivery)
+                .map(|(id, _)| id.clone());
+            if let Some(idle) = idle {
+                changed.extend(self.remove_subscriber(&idle));
+            }
+        }
+        self.deliveries += 1;
+        let subscriber = self
+            .subscribers
+            .entry(receipt.subscriber_id.clone())
+            .or_default();
+        subscriber.last_delivery = self.deliveries;
+        subscriber.timers.push_back(timer_id);
+        let oldest = (subscriber.timers.len() > MAX_RECEIPTS_PER_SUBSCRIBER)
+            .then(|| subscriber.timers.pop_front())
+            .flatten();
+        if let Some(oldest) = oldest {
+            self.remove_receipt(oldest, &receipt.subscriber_id);
+            changed.push(oldest);
+        }
+        self.receipts.entry(timer_id).or_default().push(receipt);
+        changed
+    }
+
+    fn remove_subscriber(&mut self, subscriber_id: &str) -> Vec<Uuid> {
+        let Some(subscriber) = self.subscribers.remove(subscriber_id) else {
+            return Vec::new();
+        };
+        let mut timers: Vec<Uuid> = subscriber.timers.into();
+        timers.sort_unstable();
+        timers.dedup();
+        for timer_id in &timers {
+            if let Some(receipts) = self.receipts.get_mut(timer_id) {
+                receipts.retain(|receipt| receipt.subscriber_id != subscriber_id);
+                if receipts.is_empty() {
+                    self.receipts.remove(timer_id);
+                }
+            }
+        }
+        timers
+    }
+
+    /// Removes the subscriber's earliest receipt for the timer.
+    fn remove_receipt(&mut self, timer_id: Uuid, subscriber_id: &str) {
+        let Some(receipts) = self.receipts.get_mut(&timer_id) else {
+            return;
+        };
+        if let Some(index) = receipts
+            .iter()
+            .position(|receipt| receipt.subscriber_id == subscriber_id)
+        {
+            receipts.remove(index);
+        }
+        if receipts.is_empty() {
+            self.receipts.remove(&timer_id);
+        }
+    }
+
+    fn write(&self, timer_id: Uuid) {
+        if let Some(writer) = &self.writer {
+            let receipts = self.receipts.get(&timer_id).cloned().unwrap_or_default();
+            // The writer task only stops with the runtime, when nothing is left to persist.
+            let _ = writer.send((timer_id, receipts));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::MockClock, persistence::FileDeliveryStore};
+
+    #[test]
+    fn receipts_are_bounded_per_subscriber_and_use_the_clock() {
+        let clock = Arc::new(MockClock::default());
+        let ledger = DeliveryLedger::new(clock.clone());
+        let first = Uuid::new_v4();
+        ledger.record(first, "worker", 1);
+        ledger.record(first, "audit", 1);
+        assert_eq!(ledger.receipts(first)[0].delivered_at, clock.now());
+        for sequence in 2..=MAX_RECEIPTS_PER_SUBSCRIBER as u64 + 1 {
+            ledger.record(Uuid::new_v4(), "worker", sequence);
+        }
+        let kept = ledger.receipts(first);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].subscriber_id, "audit");
+
+        ledger.forget_subscriber("audit");
+        assert!(ledger.receipts(first).is_empty());
+    }
+
+    #[tokio::test]
+    async fn file_store_restores_receipts_and_acknowledgements() {
+        let path =
+            std::env::temp_dir().join(format!("minoots-deliveries-{}.jsonl", Uuid::new_v4()));
+        let ledger = DeliveryLedger::default();
+        assert_eq!(
+            ledger
+                .attach_store(Arc::new(FileDeliveryStore::new(&path)))
+                .await
+                .unwrap(),
+            0
+        );
+        let (acked, forgotten) = (Uuid::new_v4(), Uuid::new_v4());
+        ledger.record(acked, "worker", 1);
+        ledger.record(forgotten, "worker", 2);
+        assert!(ledger.acknowledge(acked, "worker"));
+        ledger.forget(forgotten);
+        // Writes go through a background task; wait for its four lines to land.
+        for _ in 0..100 {
+            let written = std::fs::read_to_string(&path).unwrap_or_default();
+            if written.lines().count() == 4 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let restarted = DeliveryLedger::default();
+        assert_eq!(
+            restarted
+                .attach_store(Arc::new(FileDeliveryStore::new(&path)))
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(restarted.is_acknowledged(acked));
+        assert!(restarted.receipts(forgotten).is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use tokio::{sync::Notify, time::Instant};
+use uuid::Uuid;
+
+/// What a queue entry does when its deadline passes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EntryKind {
+    Fire,
+    /// Pre-fire notice `lead` ahead of the fire due at `fire_deadline`.
+    PreFire {
+        lead: Duration,
+        fire_deadline: Instant,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Entry {
+    pub deadline: Instant,
+    pub timer_id: Uuid,
+    /// The timer's `fire_at` when queued; a reschedule leaves entries with an older one stale.
+    pub fire_at: DateTime<Utc>,
+    pub kind: EntryKind,
+    /// Approach the deadline by yielding rather than trusting the millisecond timer.
+    pub precise: bool,
+    /// The timer's priority; orders entries that come due in the same pass.
+    pub priority: u32,
+    seq: u64,
+    /// Sequence number of the [`TimerQueue::push_all`] that queued the entry; only entries of a
+    /// timer's latest one are live.
+    generation: u64,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    /// Earlier deadlines first; ties keep insertion order.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.deadline, self.seq).cmp(&(other.deadline, other.seq))
+    }
+}
+
+/// Heaps at most this large are never compacted.
+const MIN_COMPACT_LEN: usize = 64;
+
+#[derive(Default)]
+struct Queued {
+    heap: BinaryHeap<Reverse<Entry>>,
+    /// Latest generation of each queued timer and how many of its entries are still in the heap.
+    live: HashMap<Uuid, (u64, usize)>,
+    /// Live entries in the heap; the rest were replaced or forgotten.
+    live_entries: usize,
+}
+
+impl Queued {
+    fn is_live(&self, entry: &Entry) -> bool {
+        self.live
+            .get(&entry.timer_id)
+            .is_some_and(|(generation, _)| *generation == entry.generation)
+    }
+
+    fn retire(&mut self, timer_id: Uuid) {
+        if let Some((_, remaining)) = self.live.remove(&timer_id) {
+            self.live_entries -= remaining;
+        }
+    }
+
+    /// Drops replaced and forgotten entries once they outnumber live ones, which keeps the heap
+    /// within twice the live entries however often timers are re-armed.
+    fn compact(&mut self) {
+        let stale = self.heap.len() - self.live_entries;
+        if self.heap.len() > MIN_COMPACT_LEN && stale > self.live_entries {
+            let live = std::mem::take(&mut self.live);
+            self.heap.retain(|Reverse(entry)| {
+                live.get(&entry.timer_id)
+                    .is_some_and(|(g, _)| *g == entry.generation)
+            });
+            self.live = live;
+        }
+    }
+}
+
+/// Deadline-ordered queue drained by the kernel's single dispatch loop, replacing one sleeping task
+/// per timer. Re-arming a timer replaces its queued entries and [`TimerQueue::forget`] drops them;
+/// replaced entries are skipped when they come due and compacted away once they outnumber live
+/// ones.
+#[derive(Clone, Default)]
+pub(crate) struct TimerQueue {
+    queued: Arc<Mutex<Queued>>,
+    seq: Arc<AtomicU64>,
+    wake: Arc<Notify>,
+    started: Arc<AtomicBool>,
+}
+
+impl TimerQueue {
+    /// Queues one entry for `timer_id` in place of any it had.
+    #[cfg(test)]
+    pub fn push(
+        &self,
+        deadline: Instant,
+        timer_id: Uuid,
+        fire_at: DateTime<Utc>,
+        kind: EntryKind,
+        precise: bool,
+        priority: u32,
+    ) {
+        self.push_all(timer_id, fire_at, priority, [(deadline, kind, precise)]);
+    }
+
+    /// Queues `(deadline, kind, precise)` entries for `timer_id` in place of any it had.
+    pub fn push_all(
+        &self,
+        timer_id: Uuid,
+        fire_at: DateTime<Utc>,
+        priority: u32,
+        entries: impl IntoIterator<Item = (Instant, EntryKind, bool)>,
+    ) {
+        let generation = self.seq.fetch_add(1, AtomicOrdering::Relaxed);
+        let mut queued = self.queued();
+        queued.retire(timer_id);
+        let mut count = 0;
+        for (deadline, kind, precise) in entries {
+            queued.heap.push(Reverse(Entry {
+                deadline,
+                timer_id,
+                fire_at,
+                kind,
+                precise,
+                priority,
+                seq: self.seq.fetch_add(1, AtomicOrdering::Relaxed),
+                generation,
+            }));
+            count += 1;
+        }
+        queued.live.insert(timer_id, (generation, count));
+        queued.live_entries += count;
+        queued.compact();
+        drop(queued);
+        // Stores a permit when the loop is busy, so a push racing its next wait is never lost.
+        self.wake.notify_one();
+    }
+
+    /// Drops the entries queued for `timer_id`, e.g. once it is cancelled.
+    pub fn forget(&self, timer_id: Uuid) {
+        let mut queued = self.queued();
+        queued.retire(timer_id);
+        queued.compact();
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.queued().heap.len()
+    }
+
+    /// Returns `true` exactly once, for whoever should start the dispatch loop.
+    pub fn claim_driver(&self) -> bool {
+        !self.started.swap(true, AtomicOrdering::AcqRel)
+    }
+
+    /// Gives up the claim of a dispatch loop that stopped, so the next [`TimerQueue::claim_driver`]
+    /// starts a new one.
+    pub fn release_driver(&self) {
+        self.started.store(false, AtomicOrdering::Release);
+    }
+
+    pub fn peek(&self) -> Option<Entry> {
+        self.queued()
+            .heap
+            .peek()
+            .map(|Reverse(entry)| entry.clone())
+    }
+
+    /// Removes every entry due at `now` and returns the live ones, highest priority first and
+    /// earliest first within a priority.
+    pub fn pop_due(&self, now: Instant) -> Vec<Entry> {
+        let mut queued = self.queued();
+        let mut due = Vec::new();
+        while queued
+            .heap
+            .peek()
+            .is_some_and(|Reverse(entry)| entry.deadline <= now)
+        {
+            let Reverse(entry) = queued.heap.pop().expect("peeked");
+            if !queued.is_live(&entry) {
+                continue;
+            }
+            queued.live_entries -= 1;
+            if let Some((_, remaining)) = queued.live.get_mut(&entry.timer_id) {
+                *remaining -= 1;
+                if *remaining == 0 {
+                    queued.live.remove(&entry.timer_id);
+                }
+            }
+            due.push(entry);
+        }
+        // Stable, so equal priorities keep deadline order.
+        due.sort_by_key(|entry| Reverse(entry.priority));
+        due
+    }
+
+    pub async fn changed(&self) {
+        self.wake.notified().await;
+    }
+
+    fn queued(&self) -> std::sync::MutexGuard<'_, Queued> {
+        self.queued.lock().expect("timer queue poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn pops_due_entries_in_deadline_order() {
+        let queue = TimerQueue::default();
+        let now = Instant::now();
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let fire_at = Utc::now();
+        queue.push(
+            now + Duration::from_millis(30),
+            a,
+            fire_at,
+            EntryKind::Fire,
+            false,
+            0,
+        );
+        queue.push(
+            now + Duration::from_millis(10),
+            b,
+            fire_at,
+            EntryKind::Fire,
+            false,
+            0,
+        );
+        queue.push(
+            now + Duration::from_millis(10),
+            c,
+            fire_at,
+            EntryKind::Fire,
+            true,
+            0,
+        );
+
+        assert_eq!(queue.peek().map(|entry| entry.timer_id), Some(b));
+        let due: Vec<_> = queue
+            .pop_due(now + Duration::from_millis(20))
+            .into_iter()
+            .map(|entry| entry.timer_id)
+            .collect();
+        assert_eq!(due, vec![b, c]);
+        assert_eq!(queue.peek().map(|entry| entry.timer_id), Some(a));
+        assert!(queue.claim_driver());
+        assert!(!queue.claim_driver());
+    }
+
+    #[tokio::test]
+    async fn orders_entries_due_together_by_priority() {
+        let queue = TimerQueue::default();
+        let now = Instant::now();
+        let (low, high, urgent) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let fire_at = Utc::now();
+        queue.push(now, low, fire_at, EntryKind::Fire, false, 0);
+        queue.push(
+            now + Duration::from_millis(5),
+            high,
+            fire_at,
+            EntryKind::Fire,
+            false,
+            10,
+        );
+        queue.push(now, urgent, fire_at, EntryKind::Fire, false, 10);
+
+        let due: Vec<_> = queue
+            .pop_due(now + Duration::from_millis(10))
+            .into_iter()
+            .map(|entry| entry.timer_id)
+            .collect();
+        assert_eq!(due, vec![urgent, high, low]);
+    }
+
+    #[tokio::test]
+    async fn rearming_replaces_entries_and_keeps_the_heap_bounded() {
+        let queue = TimerQueue::default();
+        let now = Instant::now();
+        let fire_at = Utc::now();
+        let (chatty, cancelled) = (Uuid::new_v4(), Uuid::new_v4());
+        let far = now + Duration::from_secs(3_600);
+        queue.push(far, cancelled, fire_at, EntryKind::Fire, false, 0);
+        queue.forget(cancelled);
+        for step in 0..1_000 {
+            let deadline = far + Duration::from_millis(step);
+            let pre_fire = EntryKind::PreFire {
+                lead: Duration::from_millis(1),
+                fire_deadline: deadline,
+            };
+            queue.push_all(
+                chatty,
+                fire_at,
+                0,
+                [
+                    (deadline - Duration::from_millis(1), pre_fire, false),
+                    (deadline, EntryKind::Fire, false),
+                ],
+            );
+        }
+        assert!(queue.len() <= 2 * MIN_COMPACT_LEN, "{}", queue.len());
+
+        let due = queue.pop_due(far + Duration::from_secs(10));
+        assert_eq!(due.len(), 2);
+        assert!(due.iter().all(|entry| entry.timer_id == chatty));
+        assert_eq!(due[1].deadline, far + Duration::from_millis(999));
+        assert_eq!(queue.len(), 0);
+    }
+}
@@ -0,0 +1,50 @@
+//! Eviction of finished timers from the kernel's in-memory map. Fired, cancelled, missed,
+//! acknowledged, and failed timers otherwise stay in memory forever; once evicted they are only
+//! reachable through the [`crate::TimerStore`], which [`crate::HorologyKernel::get`] falls back to.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::TimerInstance;
+
+/// How many finished timers the kernel keeps in memory, and for how long. Unset fields do not
+/// limit anything.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TerminalRetention {
+    /// Evict timers this long after they finished.
+    pub max_age: Option<Duration>,
+    /// Keep at most this many finished timers, evicting the ones that finished first.
+    pub max_count: Option<usize>,
+}
+
+impl TerminalRetention {
+    /// Finished timers among `timers` that fall outside the retention at `now`.
+    pub(crate) fn expired<'a>(
+        &self,
+        timers: impl Iterator<Item = &'a TimerInstance>,
+        now: DateTime<Utc>,
+    ) -> Vec<Uuid> {
+        let mut finished: Vec<(DateTime<Utc>, Uuid)> = timers
+            .filter(|timer| timer.is_terminal())
+            .map(|timer| (timer.finished_at(), timer.id))
+            .collect();
+        finished.sort_unstable();
+        let over_count = self
+            .max_count
+            .map_or(0, |max| finished.len().saturating_sub(max));
+        let cutoff = self
+            .max_age
+            .and_then(|age| chrono::Duration::from_std(age).ok())
+            .map(|age| now - age);
+        finished
+            .into_iter()
+            .enumerate()
+            .take_while(|(index, (finished_at, _))| {
+                *index < over_count || cutoff.is_some_and(|cutoff| *finished_at < cutoff)
+            })
+            .map(|(_, (_, id))| id)
+            .collect()
+    }
+}
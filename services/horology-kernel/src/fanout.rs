@@ -0,0 +1,377 @@
+//! Delivery of timer events to in-process subscribers. Each subscription has its own bounded
+//! buffer, so a slow consumer only loses its own events, under its own [`DropPolicy`], and never
+//! silently: the consumer gets [`RecvError::Lagged`] before its next event and operators see the
+//! totals in [`FanoutStats`].
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll, Waker},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{RecordedEvent, TimerEvent};
+
+/// What a full subscriber buffer does with the next event.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DropPolicy {
+    /// Evicts the oldest buffered event, keeping the subscriber current.
+    #[default]
+    DropOldest,
+    /// Discards the new event, keeping what the subscriber already has.
+    DropNewest,
+    /// Closes the subscription; the subscriber drains its buffer and must resubscribe.
+    Disconnect,
+}
+
+impl std::str::FromStr for DropPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "oldest" | "drop_oldest" => Ok(DropPolicy::DropOldest),
+            "newest" | "drop_newest" => Ok(DropPolicy::DropNewest),
+            "disconnect" => Ok(DropPolicy::Disconnect),
+            other => Err(format!("unknown drop policy `{other}`")),
+        }
+    }
+}
+
+/// Size and overflow handling of one subscriber's buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriberBuffer {
+    pub capacity: usize,
+    pub drop_policy: DropPolicy,
+}
+
+impl Default for SubscriberBuffer {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            drop_policy: DropPolicy::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum RecvError {
+    #[error("subscriber fell behind; {0} events were dropped")]
+    Lagged(u64),
+    #[error("subscription closed")]
+    Closed,
+}
+
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum TryRecvError {
+    #[error("no event buffered")]
+    Empty,
+    #[error("subscriber fell behind; {0} events were dropped")]
+    Lagged(u64),
+    #[error("subscription closed")]
+    Closed,
+}
+
+/// Subscriber buffers across the kernel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct FanoutStats {
+    pub subscribers: usize,
+    /// Events waiting in all buffers.
+    pub buffered: usize,
+    /// Events waiting in the fullest buffer.
+    pub max_buffered: usize,
+    /// Events lost to full buffers since startup.
+    pub events_dropped: u64,
+    /// Subscriptions closed by [`DropPolicy::Disconnect`] since startup.
+    pub disconnected: u64,
+}
+
+#[derive(Default)]
+struct Buffer {
+    events: VecDeque<RecordedEvent>,
+    /// Drops not yet reported to the subscriber.
+    unreported: u64,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl Buffer {
+    fn close(&mut self) {
+        self.closed = true;
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+struct Subscriber {
+    config: SubscriberBuffer,
+    buffer: Mutex<Buffer>,
+    dropped: AtomicU64,
+}
+
+impl Subscriber {
+    fn buffer(&self) -> std::sync::MutexGuard<'_, Buffer> {
+        self.buffer.lock().expect("subscriber buffer poisoned")
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    subscribers: Mutex<Vec<Weak<Subscriber>>>,
+    dropped: AtomicU64,
+    disconnected: AtomicU64,
+}
+
+impl Shared {
+    fn subscribers(&self) -> std::sync::MutexGuard<'_, Vec<Weak<Subscriber>>> {
+        self.subscribers.lock().expect("fanout poisoned")
+    }
+}
+
+/// Subscriptions end, once drained, when the kernel is dropped.
+impl Drop for Shared {
+    fn drop(&mut self) {
+        for subscriber in self.subscribers().iter().filter_map(Weak::upgrade) {
+            subscriber.buffer().close();
+        }
+    }
+}
+
+/// Publishing side, shared by every clone of the kernel.
+#[derive(Clone)]
+pub(crate) struct EventFanout {
+    shared: Arc<Shared>,
+}
+
+impl EventFanout {
+    pub(crate) fn new() -> Self {
+        Self {
+            shared: Arc::default(),
+        }
+    }
+
+    pub(crate) fn subscribe(&self, config: SubscriberBuffer) -> EventSubscription {
+        let subscriber = Arc::new(Subscriber {
+            config: SubscriberBuffer {
+                capacity: config.capacity.max(1),
+                ..config
+            },
+            buffer: Mutex::default(),
+            dropped: AtomicU64::new(0),
+        });
+        self.shared.subscribers().push(Arc::downgrade(&subscriber));
+        EventSubscription { subscriber }
+    }
+
+    /// Buffers `event` for every open subscription, pruning dropped ones.
+    pub(crate) fn publish(&self, event: &RecordedEvent) {
+        let mut subscribers = self.shared.subscribers();
+        subscribers.retain(|subscriber| {
+            let Some(subscriber) = subscriber.upgrade() else {
+                return false;
+            };
+            let mut buffer = subscriber.buffer();
+            if buffer.closed {
+                return false;
+            }
+            if buffer.events.len() >= subscriber.config.capacity {
+                subscriber.dropped.fetch_add(1, Ordering::Relaxed);
+                self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                buffer.unreported += 1;
+                match subscriber.config.drop_policy {
+                    DropPolicy::DropOldest => {
+                        buffer.events.pop_front();
+                    }
+                    DropPolicy::DropNewest => return true,
+                    DropPolicy::Disconnect => {
+                        self.shared.disconnected.fetch_add(1, Ordering::Relaxed);
+                        buffer.close();
+                        return false;
+                    }
+                }
+            }
+            buffer.events.push_back(event.clone());
+            if let Some(waker) = buffer.waker.take() {
+                waker.wake();
+            }
+            true
+        });
+    }
+
+    pub(crate) fn stats(&self) -> FanoutStats {
+        let mut stats = FanoutStats {
+            events_dropped: self.shared.dropped.load(Ordering::Relaxed),
+            disconnected: self.shared.disconnected.load(Ordering::Relaxed),
+            ..Default::default()
+        };
+        for subscriber in self.shared.subscribers().iter().filter_map(Weak::upgrade) {
+            let buffered = subscriber.buffer().events.len();
+            stats.subscribers += 1;
+            stats.buffered += buffered;
+            stats.max_buffered = stats.max_buffered.max(buffered);
+        }
+        stats
+    }
+}
+
+/// One subscriber's stream of timer events, buffered per [`SubscriberBuffer`]. Only events
+/// published after subscribing are received.
+pub struct EventSubscription {
+    subscriber: Arc<Subscriber>,
+}
+
+impl EventSubscription {
+    /// The next event. Reports [`RecvError::Lagged`] once for any events dropped since the last
+    /// call, and [`RecvError::Closed`] once the buffer is drained after a disconnect or after the
+    /// kernel was dropped.
+    pub async fn recv(&mut self) -> Result<TimerEvent, RecvError> {
+        self.recv_recorded().await.map(|recorded| recorded.event)
+    }
+
+    /// [`EventSubscription::recv`] with the event's sequence number and emission time.
+    pub async fn recv_recorded(&mut self) -> Result<RecordedEvent, RecvError> {
+        std::future::poll_fn(|cx| self.poll_recv_recorded(cx)).await
+    }
+
+    pub fn try_recv(&mut self) -> Result<TimerEvent, TryRecvError> {
+        self.try_recv_recorded().map(|recorded| recorded.event)
+    }
+
+    /// [`EventSubscription::try_recv`] with the event's sequence number and emission time.
+    pub fn try_recv_recorded(&mut self) -> Result<RecordedEvent, TryRecvError> {
+        let mut buffer = self.subscriber.buffer();
+        if buffer.unreported > 0 {
+            return Err(TryRecvError::Lagged(std::mem::take(&mut buffer.unreported)));
+        }
+        match buffer.events.pop_front() {
+            Some(recorded) => Ok(recorded),
+            None if buffer.closed => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// [`EventSubscription::recv`] for hand-written streams.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<TimerEvent, RecvError>> {
+        self.poll_recv_recorded(cx)
+            .map(|result| result.map(|recorded| recorded.event))
+    }
+
+    /// [`EventSubscription::recv_recorded`] for hand-written streams.
+    pub fn poll_recv_recorded(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<RecordedEvent, RecvError>> {
+        let mut buffer = self.subscriber.buffer();
+        if buffer.unreported > 0 {
+            return Poll::Ready(Err(RecvError::Lagged(std::mem::take(
+                &mut buffer.unreported,
+            ))));
+        }
+        match buffer.events.pop_front() {
+            Some(event) => Poll::Ready(Ok(event)),
+            None if buffer.closed => Poll::Ready(Err(RecvError::Closed)),
+            None => {
+                buffer.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Events waiting to be received.
+    pub fn buffered(&self) -> usize {
+        self.subscriber.buffer().events.len()
+    }
+
+    /// Events this subscription lost to a full buffer.
+    pub fn dropped(&self) -> u64 {
+        self.subscriber.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str) -> RecordedEvent {
+        RecordedEvent {
+            sequence: 1,
+            recorded_at: chrono::Utc::now(),
+            event: TimerEvent::Scheduled(crate::TimerInstance::from_spec(
+                &crate::TimerSpec {
+                    name: Some(name.into()),
+                    ..Default::default()
+                },
+                uuid::Uuid::new_v4(),
+                chrono::Utc::now(),
+                chrono::Utc::now(),
+                Default::default(),
+            )),
+        }
+    }
+
+    fn name(event: TimerEvent) -> String {
+        match event {
+            TimerEvent::Scheduled(timer) => timer.name,
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn full_buffers_drop_per_policy_and_report_the_lag() {
+        let fanout = EventFanout::new();
+        let buffer = |drop_policy| SubscriberBuffer {
+            capacity: 2,
+            drop_policy,
+        };
+        let mut oldest = fanout.subscribe(buffer(DropPolicy::DropOldest));
+        let mut newest = fanout.subscribe(buffer(DropPolicy::DropNewest));
+        let mut disconnect = fanout.subscribe(buffer(DropPolicy::Disconnect));
+        for name in ["a", "b", "c"] {
+            fanout.publish(&event(name));
+        }
+
+        assert!(matches!(oldest.try_recv(), Err(TryRecvError::Lagged(1))));
+        assert_eq!(name(oldest.try_recv().unwrap()), "b");
+        assert_eq!(name(oldest.try_recv().unwrap()), "c");
+        assert!(matches!(newest.try_recv(), Err(TryRecvError::Lagged(1))));
+        assert_eq!(name(newest.try_recv().unwrap()), "a");
+        assert_eq!(name(newest.try_recv().unwrap()), "b");
+        assert!(matches!(newest.try_recv(), Err(TryRecvError::Empty)));
+        assert!(matches!(
+            disconnect.try_recv(),
+            Err(TryRecvError::Lagged(1))
+        ));
+        assert_eq!(name(disconnect.try_recv().unwrap()), "a");
+        assert_eq!(name(disconnect.try_recv().unwrap()), "b");
+        assert!(matches!(disconnect.try_recv(), Err(TryRecvError::Closed)));
+
+        let stats = fanout.stats();
+        assert_eq!(stats.subscribers, 2);
+        assert_eq!(stats.events_dropped, 3);
+        assert_eq!(stats.disconnected, 1);
+        drop(oldest);
+        assert_eq!(fanout.stats().subscribers, 1);
+    }
+
+    #[tokio::test]
+    async fn subscriptions_close_with_the_kernel() {
+        let fanout = EventFanout::new();
+        let mut subscription = fanout.subscribe(SubscriberBuffer::default());
+        let receiver = tokio::spawn(async move {
+            let first = subscription.recv().await.map(name);
+            (first, subscription.recv().await.map(name))
+        });
+        tokio::task::yield_now().await;
+        fanout.publish(&event("last"));
+        drop(fanout);
+        let (first, second) = receiver.await.unwrap();
+        assert_eq!(first.as_deref(), Ok("last"));
+        assert_eq!(second, Err(RecvError::Closed));
+    }
+}
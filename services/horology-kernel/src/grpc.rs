@@ -1,28 +1,100 @@
+//! The kernel's one implementation of `minoots.timer.v1.HorologyKernel`. Everything else that
+//! speaks the API goes through [`HorologyKernelService`] rather than a parallel implementation:
+//! the REST gateway calls its methods, the admin service reuses its authorization and
+//! conversions, and standbys follow a primary through its client. Wire types are the proto's ISO
+//! and JSON strings, converted here. Breaking contract changes belong in a new proto package
+//! (`minoots.timer.v2`) served beside v1, not in v1 itself.
+
+use std::collections::{HashMap, VecDeque};
 use std::pin::Pin;
 
 use futures_core::Stream;
-use tokio_stream::{wrappers::BroadcastStream, StreamExt};
-use tonic::{Request, Response, Status};
+use prost::Message;
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::BroadcastStream, wrappers::ReceiverStream, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+use uuid::Uuid;
+
+use crate::fanout::RecvError;
+use crate::pb::horology_kernel_server::{
+    HorologyKernel as HorologyKernelApi, HorologyKernelServer,
+};
+use crate::pb::{
+    self, AccuracyBudgetStatsRequest, AcknowledgeDeliveryRequest, AcknowledgeTimerRequest,
+    ApiDescriptorRequest, ApplyManifestRequest, BackpressureStatsRequest, DeliveryStatusRequest,
+    FireGapReportRequest, ImportLegacyTimersRequest, IssueApiTokenRequest, KickWatchdogRequest,
+    ListApiTokensRequest, ListEventWebhooksRequest, ListStreamSubscribersRequest, ListTasksRequest,
+    PreviewScheduleRequest, QueryEventsRequest, RegisterEventWebhookRequest,
+    ReplayDeadLettersRequest, ReportTimerExecutionRequest, RevokeApiTokenRequest,
+    RevokeSessionRequest, RotateApiTokenRequest, ScheduleTimersBatchRequest, SnoozeTimerRequest,
+    SystemEventStreamRequest, TenantUsageRequest, TimerCancelRequest, TimerEventStreamRequest,
+    TimerGetRequest, TimerListRequest, TimerScheduleRequest, TimerStatsRequest, TimerUpdateRequest,
+    TimerWaitRequest, WatchTimersRequest,
+};
+use crate::query::{parse_status, Comparison, QueryError};
+use crate::standby::PrimaryForwarder;
+use crate::{
+    ActionReport, Admission, ApiToken, BudgetOutcome, EventFilter, EventQuery, EventSubscription,
+    EventWebhook, ExecutionReport, FailureReason, FireGapReport, GapOutcome, HorologyKernel,
+    KernelError, MissedFirePolicy, PageRequest, PageTokenError, Principal, RecordedEvent,
+    Reschedule, Scope, StreamGovernor, StreamLimits, StreamMeter, SystemEvent, TaskInfo, TaskState,
+    TenantScope, ThrottleNotice, ThrottleReason, TimerCounts, TimerEvent, TimerFilter,
+    TimerInstance, TimerKind, TimerManifest, TimerOrder, TimerOutcome, TimerSpec, TimerStatus,
+    TokenStore, UsageCounters, WebhookError, EVENT_KINDS, MAX_BATCH_SIZE,
+};
 
-use crate::pb::horology_kernel_server::{HorologyKernel as HorologyKernelApi, HorologyKernelServer};
-use crate::pb::{self, TimerCancelRequest, TimerEventStreamRequest, TimerGetRequest, TimerListRequest, TimerScheduleRequest};
-use crate::{HorologyKernel, KernelError, TimerEvent, TimerInstance, TimerSpec, TimerStatus};
+/// Pseudo tenant id that selects every tenant; only the operator token may use it.
+pub const ALL_TENANTS: &str = "__all__";
 
 pub type TimerEventStream = Pin<Box<dyn Stream<Item = Result<pb::TimerEvent, Status>> + Send + 'static>>;
+pub type SystemEventStream = Pin<Box<dyn Stream<Item = Result<pb::SystemEvent, Status>> + Send + 'static>>;
+pub type WatchTimersStream = Pin<Box<dyn Stream<Item = Result<pb::WatchTimersUpdate, Status>> + Send + 'static>>;
+pub type ScheduleAckStream = Pin<Box<dyn Stream<Item = Result<pb::ScheduleTimerStreamAck, Status>> + Send + 'static>>;
+
+/// A streamed schedule request, numbered in arrival order, or the error that ended the stream.
+type StreamedSpec = Result<(u64, Result<TimerSpec, Status>), Status>;
 
 #[derive(Clone)]
 pub struct HorologyKernelService {
     kernel: HorologyKernel,
+    primary: Option<PrimaryForwarder>,
 }
 
 impl HorologyKernelService {
     pub fn new(kernel: HorologyKernel) -> Self {
-        Self { kernel }
+        Self { kernel, primary: None }
+    }
+
+    /// Proxies `ScheduleTimer` and `CancelTimer` to `primary` while the kernel is a standby,
+    /// instead of failing them with `UNAVAILABLE`, and `GetTimer` and `ListTimers` unless the
+    /// caller allows a stale answer from the standby's replicated copy.
+    pub fn forward_to_primary(mut self, primary: PrimaryForwarder) -> Self {
+        self.primary = Some(primary);
+        self
+    }
+
+    /// The primary to forward writes to, while this kernel is a standby that has one.
+    fn standby_primary(&self) -> Option<&PrimaryForwarder> {
+        self.primary.as_ref().filter(|_| !self.kernel.is_active())
+    }
+
+    pub(crate) fn kernel(&self) -> &HorologyKernel {
+        &self.kernel
     }
 
     pub fn into_server(self) -> HorologyKernelServer<Self> {
         HorologyKernelServer::new(self)
     }
+
+    /// A token change that fails to persist would be lost on restart, so the RPC reports it.
+    async fn persist_token(&self, token_id: Uuid) -> Result<(), Status> {
+        self.kernel
+            .tokens()
+            .persist(token_id)
+            .await
+            .map_err(|error| Status::unavailable(format!("token change not persisted: {error}")))
+    }
 }
 
 #[tonic::async_trait]
@@ -31,6 +103,10 @@ impl HorologyKernelApi for HorologyKernelService {
         &self,
         request: Request<TimerScheduleRequest>,
     ) -> Result<Response<pb::TimerScheduleResponse>, Status> {
+        authorize(&request, Scope::Schedule, &request.get_ref().tenant_id)?;
+        if let Some(primary) = self.standby_primary() {
+            return primary.schedule_timer(request).await;
+        }
         let spec = request.into_inner();
         let timer_spec = convert_schedule_request(spec)?;
         let timer = self
@@ -43,18 +119,150 @@ impl HorologyKernelApi for HorologyKernelService {
         }))
     }
 
+    #[allow(clippy::result_large_err)]
+    async fn schedule_timers_batch(
+        &self,
+        request: Request<ScheduleTimersBatchRequest>,
+    ) -> Result<Response<pb::ScheduleTimersBatchResponse>, Status> {
+        if request.get_ref().items.len() > MAX_BATCH_SIZE {
+            return Err(map_kernel_error(KernelError::BatchTooLarge { max: MAX_BATCH_SIZE }));
+        }
+        let mut results: Vec<Option<Result<pb::Timer, Status>>> = Vec::new();
+        let mut specs = Vec::new();
+        let mut positions = Vec::new();
+        for item in &request.get_ref().items {
+            let spec = authorize(&request, Scope::Schedule, &item.tenant_id)
+                .and_then(|_| convert_schedule_request(item.clone()));
+            match spec {
+                Ok(spec) => {
+                    positions.push(results.len());
+                    specs.push(spec);
+                    results.push(None);
+                }
+                Err(status) => results.push(Some(Err(status))),
+            }
+        }
+        let scheduled = self
+            .kernel
+            .schedule_batch(specs)
+            .await
+            .map_err(map_kernel_error)?;
+        for (position, result) in positions.into_iter().zip(scheduled) {
+            results[position] = Some(result.map_err(map_kernel_error).and_then(to_proto_timer));
+        }
+        let results = results
+            .into_iter()
+            .map(|result| {
+                let result = match result.expect("every item has a result") {
+                    Ok(timer) => pb::schedule_timers_batch_result::Result::Timer(timer),
+                    Err(status) => pb::schedule_timers_batch_result::Result::Error(batch_item_error(status)),
+                };
+                pb::ScheduleTimersBatchResult { result: Some(result) }
+            })
+            .collect();
+        Ok(Response::new(pb::ScheduleTimersBatchResponse { results }))
+    }
+
+    type ScheduleTimerStreamStream = ScheduleAckStream;
+
+    #[allow(clippy::result_large_err)]
+    async fn schedule_timer_stream(
+        &self,
+        request: Request<Streaming<TimerScheduleRequest>>,
+    ) -> Result<Response<Self::ScheduleTimerStreamStream>, Status> {
+        let principal = request.extensions().get::<Principal>().cloned();
+        let mut inbound = request.into_inner();
+        let (specs_tx, mut specs_rx) = mpsc::channel::<StreamedSpec>(MAX_BATCH_SIZE);
+        let (acks_tx, acks_rx) = mpsc::channel(MAX_BATCH_SIZE);
+        // Validation runs ahead of the store: while one batch is written, the next requests are
+        // authorized, converted, and queued for the following batch.
+        let read = async move {
+            let mut index = 0;
+            loop {
+                let spec = match inbound.message().await {
+                    Ok(Some(item)) => allows(principal.as_ref(), Scope::Schedule, &item.tenant_id)
+                        .and_then(|_| convert_schedule_request(item))
+                        .map(|spec| (index, Ok(spec)))
+                        .or_else(|status| Ok((index, Err(status)))),
+                    Ok(None) => break,
+                    Err(status) => Err(status),
+                };
+                let failed = spec.is_err();
+                if specs_tx.send(spec).await.is_err() || failed {
+                    break;
+                }
+                index += 1;
+            }
+        };
+        let kernel = self.kernel.clone();
+        let write = async move {
+            while let Some(first) = specs_rx.recv().await {
+                let mut queued = vec![first];
+                while queued.len() < MAX_BATCH_SIZE {
+                    match specs_rx.try_recv() {
+                        Ok(spec) => queued.push(spec),
+                        Err(_) => break,
+                    }
+                }
+                let mut items = Vec::new();
+                let mut failure = None;
+                for spec in queued {
+                    match spec {
+                        Ok(item) => items.push(item),
+                        Err(status) => {
+                            failure = Some(status);
+                            break;
+                        }
+                    }
+                }
+                let acks = match schedule_streamed(&kernel, items).await {
+                    Ok(acks) => acks,
+                    Err(status) => vec![Err(status)],
+                };
+                for ack in acks {
+                    let failed = ack.is_err();
+                    if acks_tx.send(ack).await.is_err() || failed {
+                        return;
+                    }
+                }
+                if let Some(status) = failure {
+                    let _ = acks_tx.send(Err(status)).await;
+                    return;
+                }
+            }
+        };
+        self.kernel.tasks().spawn("schedule-stream", async move {
+            tokio::join!(read, write);
+        });
+        Ok(Response::new(Box::pin(ReceiverStream::new(acks_rx))))
+    }
+
     async fn cancel_timer(
         &self,
         request: Request<TimerCancelRequest>,
     ) -> Result<Response<pb::Timer>, Status> {
+        authorize(&request, Scope::Cancel, &request.get_ref().tenant_id)?;
+        if let Some(primary) = self.standby_primary() {
+            return primary.cancel_timer(request).await;
+        }
+        if !self.kernel.is_active() {
+            return Err(standby_status());
+        }
         let payload = request.into_inner();
         let id = uuid::Uuid::parse_str(&payload.timer_id)
             .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
 
         let result = self
             .kernel
-            .cancel(&payload.tenant_id, id, optional_string(payload.reason), optional_string(payload.requested_by))
-            .await;
+            .cancel_at_version(
+                &payload.tenant_id,
+                id,
+                expected_state_version(payload.expected_state_version),
+                optional_string(payload.reason),
+                optional_string(payload.requested_by),
+            )
+            .await
+            .map_err(map_kernel_error)?;
 
         match result {
             Some(timer) => Ok(Response::new(to_proto_timer(timer)?)),
@@ -62,10 +270,139 @@ impl HorologyKernelApi for HorologyKernelService {
         }
     }
 
+    async fn revoke_session(
+        &self,
+        request: Request<RevokeSessionRequest>,
+    ) -> Result<Response<pb::RevokeSessionResponse>, Status> {
+        authorize(&request, Scope::Cancel, &request.get_ref().tenant_id)?;
+        if !self.kernel.is_active() {
+            return Err(standby_status());
+        }
+        let payload = request.into_inner();
+        if payload.session_id.is_empty() {
+            return Err(Status::invalid_argument("session_id is required"));
+        }
+        let cancelled = self
+            .kernel
+            .revoke_session(&payload.tenant_id, &payload.session_id, optional_string(payload.requested_by))
+            .await
+            .into_iter()
+            .map(to_proto_timer)
+            .collect::<Result<Vec<_>, Status>>()?;
+        Ok(Response::new(pb::RevokeSessionResponse { cancelled }))
+    }
+
+    async fn snooze_timer(
+        &self,
+        request: Request<SnoozeTimerRequest>,
+    ) -> Result<Response<pb::Timer>, Status> {
+        authorize(&request, Scope::Schedule, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let id = uuid::Uuid::parse_str(&payload.timer_id)
+            .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
+        let timer = self
+            .kernel
+            .snooze(&payload.tenant_id, id, std::time::Duration::from_millis(payload.delay_ms))
+            .await
+            .map_err(map_kernel_error)?;
+        match timer {
+            Some(timer) => Ok(Response::new(to_proto_timer(timer)?)),
+            None => Err(Status::not_found("timer not found")),
+        }
+    }
+
+    async fn kick_watchdog(
+        &self,
+        request: Request<KickWatchdogRequest>,
+    ) -> Result<Response<pb::Timer>, Status> {
+        authorize(&request, Scope::Schedule, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let id = uuid::Uuid::parse_str(&payload.timer_id)
+            .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
+        let timer = self
+            .kernel
+            .kick_watchdog(&payload.tenant_id, id)
+            .await
+            .map_err(map_kernel_error)?;
+        match timer {
+            Some(timer) => Ok(Response::new(to_proto_timer(timer)?)),
+            None => Err(Status::not_found("timer not found")),
+        }
+    }
+
+    async fn acknowledge_timer(
+        &self,
+        request: Request<AcknowledgeTimerRequest>,
+    ) -> Result<Response<pb::Timer>, Status> {
+        authorize(&request, Scope::Schedule, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let id = uuid::Uuid::parse_str(&payload.timer_id)
+            .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
+        let timer = self
+            .kernel
+            .acknowledge(&payload.tenant_id, id)
+            .await
+            .map_err(map_kernel_error)?;
+        match timer {
+            Some(timer) => Ok(Response::new(to_proto_timer(timer)?)),
+            None => Err(Status::not_found("timer not found")),
+        }
+    }
+
+    async fn report_timer_execution(
+        &self,
+        request: Request<ReportTimerExecutionRequest>,
+    ) -> Result<Response<pb::Timer>, Status> {
+        authorize(&request, Scope::Schedule, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let id = uuid::Uuid::parse_str(&payload.timer_id)
+            .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
+        let actions = payload.actions.into_iter().map(action_report_from_proto).collect();
+        let timer = self
+            .kernel
+            .report_execution(&payload.tenant_id, id, actions)
+            .await
+            .map_err(map_kernel_error)?;
+        match timer {
+            Some(timer) => Ok(Response::new(to_proto_timer(timer)?)),
+            None => Err(Status::not_found("timer not found")),
+        }
+    }
+
+    async fn update_timer(
+        &self,
+        request: Request<TimerUpdateRequest>,
+    ) -> Result<Response<pb::Timer>, Status> {
+        authorize(&request, Scope::Schedule, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let id = uuid::Uuid::parse_str(&payload.timer_id)
+            .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
+        let to = match payload.change {
+            Some(pb::timer_update_request::Change::FireTimeIso(iso)) => Reschedule::FireAt(parse_iso_datetime(&iso)?),
+            Some(pb::timer_update_request::Change::DelayDeltaMs(delta)) => chrono::TimeDelta::try_milliseconds(delta)
+                .map(Reschedule::Delta)
+                .ok_or_else(|| Status::invalid_argument("delay_delta_ms is out of range"))?,
+            None => return Err(Status::invalid_argument("fire_time_iso or delay_delta_ms is required")),
+        };
+        let timer = self
+            .kernel
+            .reschedule_at_version(&payload.tenant_id, id, to, expected_state_version(payload.expected_state_version))
+            .await
+            .map_err(map_kernel_error)?;
+        match timer {
+            Some(timer) => Ok(Response::new(to_proto_timer(timer)?)),
+            None => Err(Status::not_found("timer not found")),
+        }
+    }
+
     async fn get_timer(
         &self,
         request: Request<TimerGetRequest>,
     ) -> Result<Response<pb::Timer>, Status> {
+        authorize(&request, Scope::Read, &request.get_ref().tenant_id)?;
+        if let Some(primary) = self.standby_primary().filter(|_| !request.get_ref().allow_stale) {
+            return primary.get_timer(request).await;
+        }
         let payload = request.into_inner();
         let id = uuid::Uuid::parse_str(&payload.timer_id)
             .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
@@ -76,56 +413,1081 @@ impl HorologyKernelApi for HorologyKernelService {
         }
     }
 
+    #[allow(clippy::result_large_err)]
     async fn list_timers(
         &self,
         request: Request<TimerListRequest>,
     ) -> Result<Response<pb::TimerListResponse>, Status> {
+        if request.get_ref().tenant_id == ALL_TENANTS {
+            authorize_root(&request)?;
+        } else {
+            authorize(&request, Scope::Read, &request.get_ref().tenant_id)?;
+        }
+        if let Some(primary) = self.standby_primary().filter(|_| !request.get_ref().allow_stale) {
+            return primary.list_timers(request).await;
+        }
         let payload = request.into_inner();
-        let timers = self.kernel.list(&payload.tenant_id).await;
-        let timers = timers
+        let page = PageRequest {
+            listing: listing_fingerprint(&payload),
+            page_size: payload.page_size as usize,
+            page_token: optional_string(payload.page_token.clone()),
+        };
+        let mut filter = TimerFilter::parse(&payload.query, self.kernel.now())
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+        if !payload.statuses.is_empty() {
+            let statuses = payload
+                .statuses
+                .iter()
+                .map(|status| {
+                    parse_status(status)
+                        .ok_or_else(|| Status::invalid_argument(format!("unknown status `{status}`")))
+                })
+                .collect::<Result<Vec<_>, Status>>()?;
+            filter.restrict_statuses(statuses);
+        }
+        for selector in &payload.label_selectors {
+            filter
+                .labels
+                .push(selector.parse().map_err(|error: QueryError| Status::invalid_argument(error.message))?);
+        }
+        if !payload.name_prefix.is_empty() {
+            filter.name_prefix = Some(payload.name_prefix);
+        }
+        for (value, comparison, field) in [
+            (&payload.fire_at_from_iso, Comparison::Ge, "fire_at_from_iso"),
+            (&payload.fire_at_until_iso, Comparison::Lt, "fire_at_until_iso"),
+        ] {
+            if !value.is_empty() {
+                let bound = parse_iso_datetime(value)
+                    .map_err(|_| Status::invalid_argument(format!("{field} must be RFC3339")))?;
+                filter.fire_at.push((comparison, bound));
+            }
+        }
+        let order = timer_order_from_proto(payload.sort_order);
+        let scope = tenant_scope(payload.tenant_id, payload.include_projects);
+        let page = self
+            .kernel
+            .list_page(&scope, &filter, order, page)
+            .await
+            .map_err(map_kernel_error)?;
+        let timers = page
+            .timers
             .into_iter()
             .map(to_proto_timer)
             .collect::<Result<Vec<_>, Status>>()?;
         Ok(Response::new(pb::TimerListResponse {
             timers,
-            next_page_token: String::new(),
+            next_page_token: page.next_page_token.unwrap_or_default(),
+            snapshot_at_iso: format_datetime(page.snapshot_at),
+            total_size: page.total as u64,
         }))
     }
 
+    #[allow(clippy::result_large_err)]
+    async fn query_events(
+        &self,
+        request: Request<QueryEventsRequest>,
+    ) -> Result<Response<pb::QueryEventsResponse>, Status> {
+        authorize(&request, Scope::Read, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        if let Some(unknown) = payload.types.iter().find(|kind| !EVENT_KINDS.contains(&kind.as_str())) {
+            return Err(Status::invalid_argument(format!("unknown event type `{unknown}`")));
+        }
+        let query = EventQuery {
+            timer_id: optional_string(payload.timer_id)
+                .map(|id| Uuid::parse_str(&id).map_err(|_| Status::invalid_argument("timer_id must be a valid UUID")))
+                .transpose()?,
+            kinds: payload.types,
+            since: optional_string(payload.since_iso).map(|value| parse_iso_datetime(&value)).transpose()?,
+            until: optional_string(payload.until_iso).map(|value| parse_iso_datetime(&value)).transpose()?,
+            after_sequence: (payload.after_sequence > 0).then_some(payload.after_sequence),
+            limit: (payload.limit > 0).then_some(payload.limit as usize),
+        };
+        let events = self
+            .kernel
+            .query_events(&payload.tenant_id, &query)
+            .map_err(map_kernel_error)?
+            .into_iter()
+            .map(|recorded| {
+                Ok(pb::RecordedTimerEvent {
+                    sequence: recorded.sequence,
+                    recorded_at_iso: format_datetime(recorded.recorded_at),
+                    event: Some(pb::TimerEvent { sequence: recorded.sequence, ..event_to_proto(recorded.event)? }),
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+        Ok(Response::new(pb::QueryEventsResponse { events }))
+    }
+
+    async fn wait_timer(
+        &self,
+        request: Request<TimerWaitRequest>,
+    ) -> Result<Response<pb::Timer>, Status> {
+        authorize(&request, Scope::Read, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let id = uuid::Uuid::parse_str(&payload.timer_id)
+            .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
+        match self.kernel.wait(&payload.tenant_id, id).await {
+            TimerOutcome::Fired(timer)
+            | TimerOutcome::Cancelled(timer)
+            | TimerOutcome::Missed(timer)
+            | TimerOutcome::Acknowledged(timer)
+            | TimerOutcome::Failed(timer) => {
+                Ok(Response::new(to_proto_timer(timer)?))
+            }
+            TimerOutcome::NotFound => Err(Status::not_found("timer not found")),
+        }
+    }
+
+    async fn preview_schedule(
+        &self,
+        request: Request<PreviewScheduleRequest>,
+    ) -> Result<Response<pb::PreviewScheduleResponse>, Status> {
+        let tenant_id = request.get_ref().spec.as_ref().map(|spec| spec.tenant_id.as_str()).unwrap_or_default();
+        authorize(&request, Scope::Read, tenant_id)?;
+        let payload = request.into_inner();
+        let spec = payload
+            .spec
+            .ok_or_else(|| Status::invalid_argument("spec is required"))?;
+        let count = payload.count.max(1) as usize;
+        let fire_times = self
+            .kernel
+            .preview(&convert_schedule_request(spec)?, count)
+            .map_err(map_kernel_error)?;
+        Ok(Response::new(pb::PreviewScheduleResponse {
+            fire_times_iso: fire_times.into_iter().map(format_datetime).collect(),
+        }))
+    }
+
+    async fn get_timer_stats(
+        &self,
+        request: Request<TimerStatsRequest>,
+    ) -> Result<Response<pb::TimerStats>, Status> {
+        if request.get_ref().tenant_id == ALL_TENANTS {
+            authorize_root(&request)?;
+        } else {
+            authorize(&request, Scope::Read, &request.get_ref().tenant_id)?;
+        }
+        let payload = request.into_inner();
+        if payload.tenant_id.is_empty() {
+            return Err(Status::invalid_argument("tenant_id is required"));
+        }
+        let scope = tenant_scope(payload.tenant_id, payload.include_projects);
+        let stats = self.kernel.timer_stats(&scope, &payload.label_keys).await;
+        Ok(Response::new(pb::TimerStats {
+            counts: Some(status_counts_to_proto(stats.counts)),
+            next_fire_at_iso: stats.next_fire_at.map(format_datetime).unwrap_or_default(),
+            horizon_iso: stats.horizon.map(format_datetime).unwrap_or_default(),
+            labels: stats
+                .labels
+                .into_iter()
+                .map(|((key, value), counts)| pb::LabelTimerStats {
+                    key,
+                    value,
+                    counts: Some(status_counts_to_proto(counts)),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn get_accuracy_budget_stats(
+        &self,
+        request: Request<AccuracyBudgetStatsRequest>,
+    ) -> Result<Response<pb::AccuracyBudgetStats>, Status> {
+        authorize(&request, Scope::Read, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        if payload.tenant_id.is_empty() {
+            return Err(Status::invalid_argument("tenant_id is required"));
+        }
+        let stats = self.kernel.budget_stats(&payload.tenant_id);
+        Ok(Response::new(pb::AccuracyBudgetStats {
+            evaluated: stats.evaluated,
+            violations: stats.violations,
+            max_overrun_ms: stats.max_overrun_ms,
+            violation_rate: stats.violation_rate(),
+        }))
+    }
+
+    async fn get_tenant_usage(
+        &self,
+        request: Request<TenantUsageRequest>,
+    ) -> Result<Response<pb::TenantUsageResponse>, Status> {
+        authorize(&request, Scope::Read, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        if payload.tenant_id.is_empty() {
+            return Err(Status::invalid_argument("tenant_id is required"));
+        }
+        let usage = self.kernel.usage();
+        let (period_start, current) = usage.current(&payload.tenant_id);
+        let records = usage
+            .records(&payload.tenant_id)
+            .into_iter()
+            .map(|record| pb::UsageRecord {
+                tenant_id: record.tenant_id,
+                period_start_iso: format_datetime(record.period_start),
+                period_end_iso: format_datetime(record.period_end),
+                counters: Some(usage_to_proto(record.counters)),
+            })
+            .collect();
+        Ok(Response::new(pb::TenantUsageResponse {
+            current_period_start_iso: format_datetime(period_start),
+            current: Some(usage_to_proto(current)),
+            records,
+        }))
+    }
+
+    async fn get_delivery_status(
+        &self,
+        request: Request<DeliveryStatusRequest>,
+    ) -> Result<Response<pb::DeliveryStatusResponse>, Status> {
+        authorize(&request, Scope::Read, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let id = uuid::Uuid::parse_str(&payload.timer_id)
+            .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
+        let receipts = self
+            .kernel
+            .delivery_status(&payload.tenant_id, id)
+            .await
+            .ok_or_else(|| Status::not_found("timer not found"))?;
+        Ok(Response::new(pb::DeliveryStatusResponse {
+            timer_id: payload.timer_id,
+            receipts: receipts
+                .into_iter()
+                .map(|receipt| pb::DeliveryReceipt {
+                    subscriber_id: receipt.subscriber_id,
+                    sequence: receipt.sequence,
+                    delivered_at_iso: format_datetime(receipt.delivered_at),
+                    acknowledged_at_iso: receipt
+                        .acknowledged_at
+                        .map(format_datetime)
+                        .unwrap_or_default(),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn acknowledge_delivery(
+        &self,
+        request: Request<AcknowledgeDeliveryRequest>,
+    ) -> Result<Response<pb::AcknowledgeDeliveryResponse>, Status> {
+        authorize(&request, Scope::Stream, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        if payload.subscriber_id.is_empty() {
+            return Err(Status::invalid_argument("subscriber_id is required"));
+        }
+        let id = uuid::Uuid::parse_str(&payload.timer_id)
+            .map_err(|_| Status::invalid_argument("timer_id must be a valid UUID"))?;
+        let acknowledged = self
+            .kernel
+            .acknowledge_delivery(&payload.tenant_id, id, &payload.subscriber_id)
+            .await;
+        Ok(Response::new(pb::AcknowledgeDeliveryResponse { acknowledged }))
+    }
+
     type StreamTimerEventsStream = TimerEventStream;
 
     async fn stream_timer_events(
         &self,
         request: Request<TimerEventStreamRequest>,
     ) -> Result<Response<Self::StreamTimerEventsStream>, Status> {
+        if request.get_ref().tenant_id == ALL_TENANTS {
+            authorize_root(&request)?;
+        } else {
+            authorize(&request, Scope::Stream, &request.get_ref().tenant_id)?;
+        }
+        let principal = principal_label(&request);
         let payload = request.into_inner();
         let tenant_id = payload.tenant_id;
         if tenant_id.is_empty() {
             return Err(Status::invalid_argument("tenant_id is required"));
         }
 
-        let tenant_filter = if tenant_id == "__all__" {
-            None
+        // All-tenant feeds are operator-only (e.g. the standby follower) and are not capped.
+        let (tenant_filter, limits) = if tenant_id == ALL_TENANTS {
+            (None, StreamLimits::default())
         } else {
-            Some(tenant_id.clone())
+            (Some(tenant_scope(tenant_id, payload.include_projects)), self.kernel.stream_limits())
         };
 
-        let receiver = self.kernel.subscribe();
-        let stream = BroadcastStream::new(receiver)
-            .filter_map(move |event| match event {
-                Ok(event)
-                    if tenant_filter
-                        .as_ref()
-                        .map(|tenant| event_belongs_to_tenant(&event, tenant))
-                        .unwrap_or(true) => Some(event_to_proto(event)),
-                Ok(_) => None,
-                Err(_) => Some(Err(Status::aborted("event channel closed"))),
-            });
+        let filter = event_filter_from_proto(&payload.topics, &payload.label_selectors, payload.name_prefix)?;
+
+        let (replay, events) = if payload.resume_from_sequence > 0 {
+            let kernel = &self.kernel;
+            kernel
+                .subscribe_after(payload.resume_from_sequence, |tenant_id| {
+                    tenant_filter.as_ref().is_none_or(|scope| kernel.in_scope(scope, tenant_id))
+                })
+                .map_err(map_kernel_error)?
+        } else {
+            (Vec::new(), self.kernel.subscribe())
+        };
+
+        let meter = self.kernel.stream_metrics().open(&principal, &payload.subscriber_id);
+        let feed = SubscriberFeed {
+            replay: replay.into(),
+            events: Some(events),
+            tenant_filter,
+            filter,
+            kernel: self.kernel.clone(),
+            // Streams without an id are recorded under the caller, so they can still acknowledge.
+            subscriber_id: optional_string(payload.subscriber_id).unwrap_or(principal),
+            sequence: 0,
+            governor: StreamGovernor::new(limits),
+            meter,
+            queued: None,
+            snapshot_versions: HashMap::new(),
+        };
+
+        Ok(Response::new(Box::pin(feed)))
+    }
+
+    type WatchTimersStream = WatchTimersStream;
+
+    #[allow(clippy::result_large_err)]
+    async fn watch_timers(
+        &self,
+        request: Request<WatchTimersRequest>,
+    ) -> Result<Response<Self::WatchTimersStream>, Status> {
+        if request.get_ref().tenant_id == ALL_TENANTS {
+            authorize_root(&request)?;
+        } else {
+            authorize(&request, Scope::Read, &request.get_ref().tenant_id)?;
+            authorize(&request, Scope::Stream, &request.get_ref().tenant_id)?;
+        }
+        let principal = principal_label(&request);
+        let payload = request.into_inner();
+        if payload.tenant_id.is_empty() {
+            return Err(Status::invalid_argument("tenant_id is required"));
+        }
+        let limits = if payload.tenant_id == ALL_TENANTS {
+            StreamLimits::default()
+        } else {
+            self.kernel.stream_limits()
+        };
+        let scope = tenant_scope(payload.tenant_id, payload.include_projects);
+        let events = event_filter_from_proto(&[], &payload.label_selectors, String::new())?;
+        let mut filter = TimerFilter {
+            labels: events.labels.clone(),
+            ..Default::default()
+        };
+        filter.restrict_statuses(vec![TimerStatus::Scheduled, TimerStatus::Armed]);
+
+        // Subscribing before the snapshot is read means no change is lost in between; changes the
+        // snapshot already holds are recognised by state_version and skipped.
+        let subscription = self.kernel.subscribe();
+        let snapshot = self.kernel.list_matching(&scope, &filter, TimerOrder::default()).await;
+        let snapshot_versions = snapshot.iter().map(|timer| (timer.id, timer.state_version)).collect();
+        let mut updates = snapshot
+            .into_iter()
+            .map(|timer| to_proto_timer(timer).map(pb::watch_timers_update::Update::Timer))
+            .collect::<Result<Vec<_>, Status>>()?;
+        let timers = updates.len() as u64;
+        updates.push(pb::watch_timers_update::Update::SnapshotComplete(pb::WatchSnapshotComplete { timers }));
+
+        let feed = SubscriberFeed {
+            replay: VecDeque::new(),
+            events: Some(subscription),
+            tenant_filter: (scope != TenantScope::All).then_some(scope),
+            filter: events,
+            kernel: self.kernel.clone(),
+            subscriber_id: principal.clone(),
+            sequence: 0,
+            governor: StreamGovernor::new(limits),
+            meter: self.kernel.stream_metrics().open(&principal, ""),
+            queued: None,
+            snapshot_versions,
+        };
+        let snapshot = tokio_stream::iter(updates).map(|update| Ok(pb::WatchTimersUpdate { update: Some(update) }));
+        let changes = feed.map(|event| {
+            event.map(|event| pb::WatchTimersUpdate {
+                update: Some(pb::watch_timers_update::Update::Event(event)),
+            })
+        });
+        Ok(Response::new(Box::pin(snapshot.chain(changes))))
+    }
+
+    type StreamSystemEventsStream = SystemEventStream;
+
+    #[allow(clippy::result_large_err)]
+    async fn stream_system_events(
+        &self,
+        request: Request<SystemEventStreamRequest>,
+    ) -> Result<Response<Self::StreamSystemEventsStream>, Status> {
+        authorize_root(&request)?;
+        let stream = BroadcastStream::new(self.kernel.subscribe_system()).map(|event| match event {
+            Ok(event) => system_event_to_proto(event),
+            Err(_) => Err(Status::aborted("system event channel closed")),
+        });
 
         Ok(Response::new(Box::pin(stream)))
     }
+
+    async fn issue_api_token(
+        &self,
+        request: Request<IssueApiTokenRequest>,
+    ) -> Result<Response<pb::IssuedApiToken>, Status> {
+        authorize(&request, Scope::Admin, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        if payload.tenant_id.is_empty() {
+            return Err(Status::invalid_argument("tenant_id is required"));
+        }
+        let scopes = payload
+            .scopes()
+            .map(scope_from_proto)
+            .collect::<Result<Vec<_>, Status>>()?;
+        if scopes.is_empty() {
+            return Err(Status::invalid_argument("at least one scope is required"));
+        }
+        let (token, secret) = self
+            .kernel
+            .tokens()
+            .issue(&payload.tenant_id, scopes, optional_string(payload.description));
+        self.persist_token(token.id).await?;
+        Ok(Response::new(pb::IssuedApiToken {
+            token: Some(token_to_proto(token)),
+            secret,
+        }))
+    }
+
+    async fn rotate_api_token(
+        &self,
+        request: Request<RotateApiTokenRequest>,
+    ) -> Result<Response<pb::IssuedApiToken>, Status> {
+        authorize(&request, Scope::Admin, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let (token, secret) = self
+            .kernel
+            .tokens()
+            .rotate(&payload.tenant_id, parse_token_id(&payload.token_id)?)
+            .ok_or_else(|| Status::not_found("token not found"))?;
+        self.persist_token(token.id).await?;
+        Ok(Response::new(pb::IssuedApiToken {
+            token: Some(token_to_proto(token)),
+            secret,
+        }))
+    }
+
+    async fn revoke_api_token(
+        &self,
+        request: Request<RevokeApiTokenRequest>,
+    ) -> Result<Response<pb::ApiToken>, Status> {
+        authorize(&request, Scope::Admin, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        let token = self
+            .kernel
+            .tokens()
+            .revoke(&payload.tenant_id, parse_token_id(&payload.token_id)?)
+            .ok_or_else(|| Status::not_found("token not found"))?;
+        self.persist_token(token.id).await?;
+        Ok(Response::new(token_to_proto(token)))
+    }
+
+    async fn list_api_tokens(
+        &self,
+        request: Request<ListApiTokensRequest>,
+    ) -> Result<Response<pb::ListApiTokensResponse>, Status> {
+        authorize(&request, Scope::Admin, &request.get_ref().tenant_id)?;
+        let tokens = self
+            .kernel
+            .tokens()
+            .list(&request.get_ref().tenant_id)
+            .into_iter()
+            .map(token_to_proto)
+            .collect();
+        Ok(Response::new(pb::ListApiTokensResponse { tokens }))
+    }
+
+    async fn register_event_webhook(
+        &self,
+        request: Request<RegisterEventWebhookRequest>,
+    ) -> Result<Response<pb::RegisteredEventWebhook>, Status> {
+        authorize(&request, Scope::Admin, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        if payload.tenant_id.is_empty() {
+            return Err(Status::invalid_argument("tenant_id is required"));
+        }
+        let filter = event_filter_from_proto(&payload.topics, &payload.label_selectors, payload.name_prefix)?;
+        let (webhook, secret) = self
+            .kernel
+            .webhooks()
+            .register(&payload.tenant_id, &payload.url, filter, optional_string(payload.description))
+            .await
+            .map_err(|error| match error {
+                WebhookError::InvalidUrl(_)
+                | WebhookError::HttpsUnsupported(_)
+                | WebhookError::Unresolvable(_)
+                | WebhookError::ForbiddenDestination { .. } => Status::invalid_argument(error.to_string()),
+                WebhookError::TooMany { .. } => Status::resource_exhausted(error.to_string()),
+                WebhookError::Persist(_) => Status::internal(error.to_string()),
+            })?;
+        Ok(Response::new(pb::RegisteredEventWebhook {
+            webhook: Some(webhook_to_proto(webhook)),
+            secret,
+        }))
+    }
+
+    async fn list_event_webhooks(
+        &self,
+        request: Request<ListEventWebhooksRequest>,
+    ) -> Result<Response<pb::ListEventWebhooksResponse>, Status> {
+        authorize(&request, Scope::Admin, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        if payload.tenant_id.is_empty() {
+            return Err(Status::invalid_argument("tenant_id is required"));
+        }
+        let webhooks = self.kernel.webhooks().list(&payload.tenant_id).into_iter().map(webhook_to_proto).collect();
+        Ok(Response::new(pb::ListEventWebhooksResponse { webhooks }))
+    }
+
+    async fn get_fire_gap_report(
+        &self,
+        request: Request<FireGapReportRequest>,
+    ) -> Result<Response<pb::FireGapReport>, Status> {
+        authorize_root(&request)?;
+        let report = self
+            .kernel
+            .fire_gap_report()
+            .ok_or_else(|| Status::not_found("kernel has not restored from a store"))?;
+        Ok(Response::new(fire_gap_report_to_proto(report)))
+    }
+
+    async fn list_stream_subscribers(
+        &self,
+        request: Request<ListStreamSubscribersRequest>,
+    ) -> Result<Response<pb::ListStreamSubscribersResponse>, Status> {
+        authorize_root(&request)?;
+        let now = chrono::Utc::now();
+        let subscribers = self
+            .kernel
+            .stream_metrics()
+            .snapshot()
+            .into_iter()
+            .map(|stats| pb::StreamSubscriber {
+                bytes_per_second: stats.bytes_per_second(now),
+                principal: stats.principal,
+                subscriber_id: stats.subscriber_id,
+                first_connected_at_iso: format_datetime(stats.first_connected_at),
+                active_streams: stats.active_streams,
+                events_delivered: stats.events_delivered,
+                bytes_delivered: stats.bytes_delivered,
+                events_dropped: stats.events_dropped,
+                bytes_dropped: stats.bytes_dropped,
+                throttle_notices: stats.throttle_notices,
+                events_lagged: stats.events_lagged,
+                last_event_at_iso: stats.last_event_at.map(format_datetime).unwrap_or_default(),
+            })
+            .collect();
+        Ok(Response::new(pb::ListStreamSubscribersResponse { subscribers }))
+    }
+
+    async fn get_backpressure_stats(
+        &self,
+        request: Request<BackpressureStatsRequest>,
+    ) -> Result<Response<pb::BackpressureStats>, Status> {
+        authorize_root(&request)?;
+        let config = self.kernel.backpressure();
+        let stats = self.kernel.backpressure_stats();
+        Ok(Response::new(pb::BackpressureStats {
+            enabled: config.is_some(),
+            high_watermark: config.map(|config| config.high_watermark as u64).unwrap_or_default(),
+            low_watermark: config.map(|config| config.low_watermark as u64).unwrap_or_default(),
+            max_delay_ms: config.map(|config| config.max_delay_ms).unwrap_or_default(),
+            current_lag: stats.current_lag,
+            max_lag: stats.max_lag,
+            fires_delayed: stats.fires_delayed,
+            delay_ms_total: stats.delay_ms_total,
+            allowance_exhausted: stats.allowance_exhausted,
+        }))
+    }
+
+    async fn replay_dead_letters(
+        &self,
+        request: Request<ReplayDeadLettersRequest>,
+    ) -> Result<Response<pb::ReplayDeadLettersResponse>, Status> {
+        authorize_root(&request)?;
+        let payload = request.into_inner();
+        let limit = match payload.limit {
+            0 => usize::MAX,
+            limit => limit as usize,
+        };
+        let dead_letters = self.kernel.dead_letters();
+        let replayed = dead_letters.replay(optional_string(payload.sink).as_deref(), limit);
+        let stats = dead_letters.stats();
+        Ok(Response::new(pb::ReplayDeadLettersResponse {
+            replayed: replayed as u64,
+            remaining: stats.pending as u64,
+            parked_total: stats.parked,
+            discarded_total: stats.discarded,
+        }))
+    }
+
+    async fn list_tasks(
+        &self,
+        request: Request<ListTasksRequest>,
+    ) -> Result<Response<pb::ListTasksResponse>, Status> {
+        authorize_root(&request)?;
+        let report = self.kernel.tasks().report();
+        Ok(Response::new(pb::ListTasksResponse {
+            running: report.running.into_iter().map(task_to_proto).collect(),
+            recently_ended: report.recently_ended.into_iter().map(task_to_proto).collect(),
+            spawned: report.counts.spawned,
+            completed: report.counts.completed,
+            panicked: report.counts.panicked,
+            aborted: report.counts.aborted,
+        }))
+    }
+
+    async fn import_legacy_timers(
+        &self,
+        request: Request<ImportLegacyTimersRequest>,
+    ) -> Result<Response<pb::ImportLegacyTimersResponse>, Status> {
+        authorize_root(&request)?;
+        let records = crate::legacy::parse_records(&request.into_inner().records_json)
+            .map_err(|error| map_kernel_error(error.into()))?;
+        let report = self.kernel.import_legacy(records).await.map_err(map_kernel_error)?;
+        Ok(Response::new(pb::ImportLegacyTimersResponse {
+            imported: report
+                .imported
+                .into_iter()
+                .map(to_proto_timer)
+                .collect::<Result<Vec<_>, Status>>()?,
+            already_present: report.already_present.iter().map(Uuid::to_string).collect(),
+            skipped_terminal: report.skipped_terminal.iter().map(Uuid::to_string).collect(),
+            rejected: report
+                .rejected
+                .into_iter()
+                .map(|(id, reason)| pb::LegacyImportRejection {
+                    id: id.to_string(),
+                    reason,
+                })
+                .collect(),
+        }))
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn apply_manifest(
+        &self,
+        request: Request<ApplyManifestRequest>,
+    ) -> Result<Response<pb::ApplyManifestResponse>, Status> {
+        authorize(&request, Scope::Schedule, &request.get_ref().tenant_id)?;
+        authorize(&request, Scope::Cancel, &request.get_ref().tenant_id)?;
+        let payload = request.into_inner();
+        if payload.tenant_id.is_empty() {
+            return Err(Status::invalid_argument("tenant_id is required"));
+        }
+        if payload.requested_by.is_empty() {
+            return Err(Status::invalid_argument("requested_by is required"));
+        }
+        let manifest = TimerManifest::from_json(&payload.manifest_json)
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+        let report = self
+            .kernel
+            .apply_manifest(&payload.tenant_id, &payload.requested_by, &manifest)
+            .await
+            .map_err(map_kernel_error)?;
+        let convert = |timers: Vec<TimerInstance>| {
+            timers.into_iter().map(to_proto_timer).collect::<Result<Vec<_>, Status>>()
+        };
+        Ok(Response::new(pb::ApplyManifestResponse {
+            created: convert(report.created)?,
+            updated: convert(report.updated)?,
+            cancelled: convert(report.cancelled)?,
+            unchanged: convert(report.unchanged)?,
+        }))
+    }
+
+    async fn get_api_descriptor(
+        &self,
+        request: Request<ApiDescriptorRequest>,
+    ) -> Result<Response<pb::ApiDescriptor>, Status> {
+        authorize_root(&request)?;
+        let digest = Sha256::digest(pb::FILE_DESCRIPTOR_SET);
+        Ok(Response::new(pb::ApiDescriptor {
+            file_descriptor_set: pb::FILE_DESCRIPTOR_SET.to_vec(),
+            openapi_json: pb::OPENAPI_JSON.to_string(),
+            kernel_version: env!("CARGO_PKG_VERSION").to_string(),
+            descriptor_sha256: digest.iter().map(|byte| format!("{byte:02x}")).collect(),
+        }))
+    }
+}
+
+/// Tonic interceptor that resolves `authorization: Bearer <secret>` against the token store and
+/// attaches the [`Principal`] to the request. Anonymous requests are only let through while the
+/// store has no root secret, i.e. while authentication is not enforced.
+#[allow(clippy::result_large_err)]
+pub fn authenticate(
+    tokens: TokenStore,
+) -> impl FnMut(Request<()>) -> Result<Request<()>, Status> + Clone {
+    move |mut request: Request<()>| {
+        let header = request
+            .metadata()
+            .get("authorization")
+            .map(|value| value.to_str().unwrap_or_default().to_string());
+        match header {
+            Some(header) => {
+                let secret = header
+                    .strip_prefix("Bearer ")
+                    .ok_or_else(|| Status::unauthenticated("authorization must be a bearer token"))?;
+                let principal = tokens
+                    .authenticate(secret.trim())
+                    .ok_or_else(|| Status::unauthenticated("invalid or revoked token"))?;
+                request.extensions_mut().insert(principal);
+            }
+            None if tokens.is_enforced() => {
+                return Err(Status::unauthenticated("missing bearer token"));
+            }
+            None => {}
+        }
+        Ok(request)
+    }
+}
+
+/// Checks the authenticated principal against the tenant and the required scope. Requests served
+/// without the [`authenticate`] interceptor carry no principal and are allowed.
+#[allow(clippy::result_large_err)]
+pub(crate) fn authorize<T>(request: &Request<T>, scope: Scope, tenant_id: &str) -> Result<(), Status> {
+    allows(request.extensions().get::<Principal>(), scope, tenant_id)
+}
+
+/// [`authorize`] for a principal taken off a request that has since been consumed.
+#[allow(clippy::result_large_err)]
+fn allows(principal: Option<&Principal>, scope: Scope, tenant_id: &str) -> Result<(), Status> {
+    let Some(principal) = principal else {
+        return Ok(());
+    };
+    if principal.allows(scope, tenant_id) {
+        Ok(())
+    } else {
+        Err(Status::permission_denied("token does not grant this operation for the tenant"))
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn event_filter_from_proto(
+    topics: &[String],
+    label_selectors: &[String],
+    name_prefix: String,
+) -> Result<EventFilter, Status> {
+    let mut filter = EventFilter::default();
+    for topic in topics {
+        filter.add_topic(topic).map_err(|error| Status::invalid_argument(error.message))?;
+    }
+    for selector in label_selectors {
+        filter
+            .labels
+            .push(selector.parse().map_err(|error: QueryError| Status::invalid_argument(error.message))?);
+    }
+    filter.name_prefix = optional_string(name_prefix);
+    Ok(filter)
+}
+
+fn webhook_to_proto(webhook: EventWebhook) -> pb::EventWebhook {
+    pb::EventWebhook {
+        webhook_id: webhook.id.to_string(),
+        tenant_id: webhook.tenant_id,
+        url: webhook.url,
+        topics: webhook.filter.kinds.iter().map(|kind| format!("timer.{kind}")).collect(),
+        label_selectors: webhook
+            .filter
+            .labels
+            .iter()
+            .map(|label| format!("{}{}{}", label.key, if label.negated { "!=" } else { "=" }, label.value))
+            .collect(),
+        name_prefix: webhook.filter.name_prefix.unwrap_or_default(),
+        description: webhook.description.unwrap_or_default(),
+        created_at_iso: format_datetime(webhook.created_at),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+pub(crate) fn authorize_root<T>(request: &Request<T>) -> Result<(), Status> {
+    match request.extensions().get::<Principal>() {
+        Some(principal) if !principal.is_root() => {
+            Err(Status::permission_denied("operation requires the operator token"))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_token_id(value: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(value).map_err(|_| Status::invalid_argument("token_id must be a valid UUID"))
+}
+
+#[allow(clippy::result_large_err)]
+fn scope_from_proto(scope: pb::TokenScope) -> Result<Scope, Status> {
+    match scope {
+        pb::TokenScope::Schedule => Ok(Scope::Schedule),
+        pb::TokenScope::Cancel => Ok(Scope::Cancel),
+        pb::TokenScope::Read => Ok(Scope::Read),
+        pb::TokenScope::Stream => Ok(Scope::Stream),
+        pb::TokenScope::Admin => Ok(Scope::Admin),
+        pb::TokenScope::Unspecified => Err(Status::invalid_argument("token scope must be specified")),
+    }
+}
+
+fn scope_to_proto(scope: Scope) -> pb::TokenScope {
+    match scope {
+        Scope::Schedule => pb::TokenScope::Schedule,
+        Scope::Cancel => pb::TokenScope::Cancel,
+        Scope::Read => pb::TokenScope::Read,
+        Scope::Stream => pb::TokenScope::Stream,
+        Scope::Admin => pb::TokenScope::Admin,
+    }
+}
+
+fn token_to_proto(token: ApiToken) -> pb::ApiToken {
+    pb::ApiToken {
+        token_id: token.id.to_string(),
+        tenant_id: token.tenant_id,
+        scopes: token.scopes.into_iter().map(|scope| scope_to_proto(scope) as i32).collect(),
+        description: token.description.unwrap_or_default(),
+        created_at_iso: format_datetime(token.created_at),
+        rotated_at_iso: token.rotated_at.map(format_datetime).unwrap_or_default(),
+        revoked_at_iso: token.revoked_at.map(format_datetime).unwrap_or_default(),
+    }
+}
+
+fn fire_gap_report_to_proto(report: FireGapReport) -> pb::FireGapReport {
+    pb::FireGapReport {
+        downtime_started_at_iso: report.downtime_started_at.map(format_datetime).unwrap_or_default(),
+        restored_at_iso: format_datetime(report.restored_at),
+        restored: report.restored,
+        entries: report
+            .entries
+            .into_iter()
+            .map(|entry| pb::FireGapEntry {
+                timer_id: entry.timer_id.to_string(),
+                tenant_id: entry.tenant_id,
+                fire_at_iso: format_datetime(entry.fire_at),
+                late_by_ms: entry.late_by_ms,
+                outcome: match entry.outcome {
+                    GapOutcome::FiredLate => pb::GapOutcome::FiredLate,
+                    GapOutcome::Missed => pb::GapOutcome::Missed,
+                } as i32,
+            })
+            .collect(),
+    }
+}
+
+/// Delivers one subscriber's timer events under its [`StreamGovernor`], recording deliveries,
+/// usage, and throughput for the events it lets through and interleaving throttle notices.
+struct SubscriberFeed {
+    /// Retained events a resuming subscriber missed, sent before live ones.
+    replay: VecDeque<RecordedEvent>,
+    /// `None` once the subscription closed and the stream ended.
+    events: Option<EventSubscription>,
+    tenant_filter: Option<TenantScope>,
+    filter: EventFilter,
+    kernel: HorologyKernel,
+    /// Name the stream's fired events are recorded under in the delivery ledger.
+    subscriber_id: String,
+    /// Position of the last delivered event within this stream.
+    sequence: u64,
+    governor: StreamGovernor,
+    meter: StreamMeter,
+    /// Event held back while the throttle notice preceding it is sent.
+    queued: Option<pb::TimerEvent>,
+    /// State versions of the timers a `WatchTimers` snapshot sent; events at or below them are
+    /// already reflected and skipped. Empty for plain event streams.
+    snapshot_versions: HashMap<Uuid, u64>,
+}
+
+impl SubscriberFeed {
+    fn admit(&mut self, recorded: RecordedEvent) -> Option<Result<pb::TimerEvent, Status>> {
+        let event = recorded.event;
+        let timer = event.timer();
+        if let Some(&version) = self.snapshot_versions.get(&timer.id) {
+            // Snapshots do not carry pre-fire notices, so they never count as already reflected.
+            if timer.state_version <= version && !matches!(event, TimerEvent::PreFire { .. }) {
+                return None;
+            }
+            if timer.state_version > version {
+                self.snapshot_versions.remove(&timer.id);
+            }
+        }
+        if let Some(scope) = &self.tenant_filter {
+            if !self.kernel.in_scope(scope, event_tenant_id(&event)) {
+                return None;
+            }
+        }
+        if !self.filter.matches(&event) {
+            return None;
+        }
+        let tenant_id = event_tenant_id(&event).to_string();
+        let fired = match &event {
+            TimerEvent::Fired(timer) => Some(timer.id),
+            _ => None,
+        };
+        let message = match event_to_proto(event) {
+            Ok(message) => pb::TimerEvent { sequence: recorded.sequence, ..message },
+            Err(status) => return Some(Err(status)),
+        };
+        let bytes = message.encoded_len() as u64;
+        let (deliver, notice) = match self.governor.admit(bytes) {
+            Admission::Deliver { notice } => (true, notice),
+            Admission::Drop { notice } => (false, notice),
+        };
+        if deliver {
+            self.sequence += 1;
+            if let Some(timer_id) = fired {
+                self.kernel.deliveries().record(timer_id, &self.subscriber_id, self.sequence);
+            }
+            self.kernel.usage().record_event_bytes(&tenant_id, bytes);
+            self.meter.delivered(bytes);
+        } else {
+            self.meter.dropped(bytes);
+        }
+        let Some(notice) = notice else {
+            return deliver.then_some(Ok(message));
+        };
+        if deliver {
+            self.queued = Some(message);
+        }
+        Some(Ok(self.notify(notice)))
+    }
+
+    fn notify(&self, notice: ThrottleNotice) -> pb::TimerEvent {
+        self.meter.notified();
+        pb::TimerEvent {
+            event: Some(pb::timer_event::Event::Throttled(pb::StreamThrottled {
+                reason: notice.reason.name().to_string(),
+                dropped_events: notice.dropped_events,
+                dropped_bytes: notice.dropped_bytes,
+            })),
+            sequence: 0,
+        }
+    }
+}
+
+impl Stream for SubscriberFeed {
+    type Item = Result<pb::TimerEvent, Status>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Option<Self::Item>> {
+        if let Some(message) = self.queued.take() {
+            return std::task::Poll::Ready(Some(Ok(message)));
+        }
+        loop {
+            if let Some(recorded) = self.replay.pop_front() {
+                if let Some(item) = self.admit(recorded) {
+                    return std::task::Poll::Ready(Some(item));
+                }
+                continue;
+            }
+            let Some(events) = self.events.as_mut() else {
+                return std::task::Poll::Ready(None);
+            };
+            match std::task::ready!(events.poll_recv_recorded(cx)) {
+                Ok(event) => {
+                    if let Some(item) = self.admit(event) {
+                        return std::task::Poll::Ready(Some(item));
+                    }
+                }
+                // The subscriber's buffer overflowed before the stream could send these.
+                Err(RecvError::Lagged(dropped_events)) => {
+                    self.meter.lagged(dropped_events);
+                    let notice = ThrottleNotice {
+                        reason: ThrottleReason::Lagged,
+                        dropped_events,
+                        dropped_bytes: 0,
+                    };
+                    return std::task::Poll::Ready(Some(Ok(self.notify(notice))));
+                }
+                Err(RecvError::Closed) => {
+                    self.events = None;
+                    return std::task::Poll::Ready(Some(Err(Status::aborted("event channel closed"))));
+                }
+            }
+        }
+    }
+}
+
+/// Schedules one batch of a [`HorologyKernelApi::schedule_timer_stream`] and returns its acks in
+/// stream order. Fails as a whole, scheduling nothing, when the kernel rejects the batch.
+#[allow(clippy::result_large_err)]
+async fn schedule_streamed(
+    kernel: &HorologyKernel,
+    items: Vec<(u64, Result<TimerSpec, Status>)>,
+) -> Result<Vec<Result<pb::ScheduleTimerStreamAck, Status>>, Status> {
+    let mut specs = Vec::new();
+    let mut results = Vec::new();
+    for (index, spec) in items {
+        match spec {
+            Ok(spec) => {
+                specs.push(spec);
+                results.push((index, None));
+            }
+            Err(status) => results.push((index, Some(Err(status)))),
+        }
+    }
+    let mut scheduled = kernel
+        .schedule_batch(specs)
+        .await
+        .map_err(map_kernel_error)?
+        .into_iter();
+    Ok(results
+        .into_iter()
+        .map(|(index, result)| {
+            let result = result.unwrap_or_else(|| {
+                scheduled
+                    .next()
+                    .expect("every spec has a result")
+                    .map_err(map_kernel_error)
+                    .and_then(to_proto_timer)
+            });
+            let result = match result {
+                Ok(timer) => pb::schedule_timer_stream_ack::Result::Timer(timer),
+                Err(status) => pb::schedule_timer_stream_ack::Result::Error(batch_item_error(status)),
+            };
+            Ok(pb::ScheduleTimerStreamAck { index, result: Some(result) })
+        })
+        .collect())
+}
+
+fn batch_item_error(status: Status) -> pb::BatchItemError {
+    pb::BatchItemError {
+        code: status.code() as i32,
+        message: status.message().to_string(),
+    }
+}
+
+/// Names the caller for per-subscriber metrics: its token id or subject, `root`, or `anonymous`.
+fn principal_label<T>(request: &Request<T>) -> String {
+    match request.extensions().get::<Principal>() {
+        Some(principal) => principal.label(),
+        None => "anonymous".to_string(),
+    }
+}
+
+fn task_to_proto(task: TaskInfo) -> pb::TaskInfo {
+    pb::TaskInfo {
+        id: task.id,
+        name: task.name,
+        state: match task.state {
+            TaskState::Running => pb::TaskState::Running,
+            TaskState::Completed => pb::TaskState::Completed,
+            TaskState::Panicked => pb::TaskState::Panicked,
+            TaskState::Aborted => pb::TaskState::Aborted,
+        } as i32,
+        started_at_iso: format_datetime(task.started_at),
+        polls: task.polls,
+        last_polled_at_iso: task.last_polled_at.map(format_datetime).unwrap_or_default(),
+        busy_since_iso: task.busy_since.map(format_datetime).unwrap_or_default(),
+        ended_at_iso: task.ended_at.map(format_datetime).unwrap_or_default(),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn system_event_to_proto(event: SystemEvent) -> Result<pb::SystemEvent, Status> {
+    Ok(pb::SystemEvent {
+        subject: event.subject.clone(),
+        kind: event.kind.name().to_string(),
+        occurred_at_iso: format_datetime(event.occurred_at),
+        payload_json: serialize_json(serde_json::to_value(&event.kind).ok())?,
+    })
 }
 
+#[allow(clippy::result_large_err)]
 fn convert_schedule_request(request: TimerScheduleRequest) -> Result<TimerSpec, Status> {
     if request.tenant_id.is_empty() {
         return Err(Status::invalid_argument("tenant_id is required"));
@@ -134,12 +1496,18 @@ fn convert_schedule_request(request: TimerScheduleRequest) -> Result<TimerSpec,
         return Err(Status::invalid_argument("requested_by is required"));
     }
 
-    let (duration_ms, fire_at) = match request.schedule_time {
+    let (duration_ms, duration_us, fire_at) = match request.schedule_time {
         Some(pb::timer_schedule_request::ScheduleTime::DurationMs(duration)) => {
             if duration == 0 {
                 return Err(Status::invalid_argument("duration_ms must be greater than zero"));
             }
-            (duration, None)
+            (duration, None, None)
+        }
+        Some(pb::timer_schedule_request::ScheduleTime::DurationUs(duration)) => {
+            if duration == 0 {
+                return Err(Status::invalid_argument("duration_us must be greater than zero"));
+            }
+            (duration / 1_000, Some(duration), None)
         }
         Some(pb::timer_schedule_request::ScheduleTime::FireTimeIso(iso)) => {
             let fire_at = parse_iso_datetime(&iso)?;
@@ -150,11 +1518,11 @@ fn convert_schedule_request(request: TimerScheduleRequest) -> Result<TimerSpec,
             let duration = (fire_at - now)
                 .to_std()
                 .map_err(|_| Status::invalid_argument("fire_time must be in the future"))?;
-            (duration.as_millis() as u64, Some(fire_at))
+            (duration.as_millis() as u64, None, Some(fire_at))
         }
         None => {
             return Err(Status::invalid_argument(
-                "either duration_ms or fire_time must be provided",
+                "one of duration_ms, duration_us or fire_time must be provided",
             ))
         }
     };
@@ -164,16 +1532,149 @@ fn convert_schedule_request(request: TimerScheduleRequest) -> Result<TimerSpec,
         requested_by: request.requested_by,
         name: optional_string(request.name),
         duration_ms,
+        duration_us,
         fire_at,
         metadata: parse_optional_json_string(request.metadata_json)?,
         labels: request.labels,
         action_bundle: parse_optional_json_string(request.action_bundle_json)?,
         agent_binding: parse_optional_json_string(request.agent_binding_json)?,
+        accuracy_budget_ms: (request.accuracy_budget_ms > 0).then_some(request.accuracy_budget_ms),
+        pre_fire_notice_ms: (request.pre_fire_notice_ms > 0).then_some(request.pre_fire_notice_ms),
+        idempotency_key: optional_string(request.idempotency_key),
+        missed_fire_policy: missed_fire_policy_from_proto(request.missed_fire_policy, request.missed_fire_grace_ms),
+        session_id: optional_string(request.session_id),
+        kind: timer_kind_from_proto(request.watchdog_interval_ms, request.deadline, request.deadline_grace_ms)?,
+        priority: request.priority,
     };
 
     Ok(spec)
 }
 
+/// Identifies a listing by everything in the request except its page fields, so a page token is
+/// only accepted for the query that issued it.
+fn listing_fingerprint(request: &TimerListRequest) -> String {
+    let listing = TimerListRequest {
+        page_size: 0,
+        page_token: String::new(),
+        ..request.clone()
+    };
+    let digest = Sha256::digest(listing.encode_to_vec());
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn timer_order_from_proto(order: i32) -> TimerOrder {
+    match pb::TimerSortOrder::try_from(order) {
+        Ok(pb::TimerSortOrder::FireAtDesc) => TimerOrder::FireAtDesc,
+        Ok(pb::TimerSortOrder::CreatedAtAsc) => TimerOrder::CreatedAtAsc,
+        Ok(pb::TimerSortOrder::CreatedAtDesc) => TimerOrder::CreatedAtDesc,
+        Ok(pb::TimerSortOrder::Name) => TimerOrder::Name,
+        _ => TimerOrder::FireAtAsc,
+    }
+}
+
+fn missed_fire_policy_from_proto(policy: i32, grace_ms: u64) -> Option<MissedFirePolicy> {
+    match pb::MissedFirePolicy::try_from(policy) {
+        Ok(pb::MissedFirePolicy::FireImmediately) => Some(MissedFirePolicy::FireImmediately),
+        Ok(pb::MissedFirePolicy::Skip) => Some(MissedFirePolicy::SkipAndMarkMissed),
+        Ok(pb::MissedFirePolicy::FireWithGrace) => Some(MissedFirePolicy::FireWithGrace { grace_ms }),
+        _ => None,
+    }
+}
+
+pub(crate) fn missed_fire_policy_to_proto(policy: Option<MissedFirePolicy>) -> (pb::MissedFirePolicy, u64) {
+    match policy {
+        None => (pb::MissedFirePolicy::Unspecified, 0),
+        Some(MissedFirePolicy::FireImmediately) => (pb::MissedFirePolicy::FireImmediately, 0),
+        Some(MissedFirePolicy::SkipAndMarkMissed) => (pb::MissedFirePolicy::Skip, 0),
+        Some(MissedFirePolicy::FireWithGrace { grace_ms }) => (pb::MissedFirePolicy::FireWithGrace, grace_ms),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn timer_kind_from_proto(watchdog_interval_ms: u64, deadline: bool, deadline_grace_ms: u64) -> Result<TimerKind, Status> {
+    match (watchdog_interval_ms, deadline) {
+        (0, false) => Ok(TimerKind::OneShot),
+        (interval_ms, false) => Ok(TimerKind::Watchdog { interval_ms }),
+        (0, true) => Ok(TimerKind::Deadline { grace_ms: deadline_grace_ms }),
+        _ => Err(Status::invalid_argument("a timer cannot be both a watchdog and a deadline")),
+    }
+}
+
+fn failure_reason_to_proto(reason: FailureReason) -> pb::TimerFailureReason {
+    let message = reason.to_string();
+    let code = reason.code().to_string();
+    match reason {
+        FailureReason::DeadlineExceeded { grace_ms } => pb::TimerFailureReason {
+            code,
+            message,
+            grace_ms,
+            ..Default::default()
+        },
+        FailureReason::ActionsFailed { failed, total, error } => pb::TimerFailureReason {
+            code,
+            message,
+            failed_actions: failed,
+            total_actions: total,
+            error: error.unwrap_or_default(),
+            ..Default::default()
+        },
+        FailureReason::FailedByOperator { reason } => pb::TimerFailureReason {
+            code,
+            message,
+            error: reason,
+            ..Default::default()
+        },
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn failure_reason_from_proto(reason: pb::TimerFailureReason) -> Result<FailureReason, Status> {
+    match reason.code.as_str() {
+        "deadline_exceeded" => Ok(FailureReason::DeadlineExceeded { grace_ms: reason.grace_ms }),
+        "actions_failed" => Ok(FailureReason::ActionsFailed {
+            failed: reason.failed_actions,
+            total: reason.total_actions,
+            error: optional_string(reason.error),
+        }),
+        "failed_by_operator" => Ok(FailureReason::FailedByOperator { reason: reason.error }),
+        code => Err(Status::invalid_argument(format!("unsupported failure reason `{code}`"))),
+    }
+}
+
+fn action_report_from_proto(action: pb::ActionResult) -> ActionReport {
+    ActionReport {
+        action_id: action.action_id,
+        success: action.success,
+        duration_ms: action.duration_ms,
+        error: optional_string(action.error),
+    }
+}
+
+fn execution_to_proto(execution: ExecutionReport) -> pb::ExecutionResult {
+    pb::ExecutionResult {
+        actions: execution
+            .actions
+            .into_iter()
+            .map(|action| pb::ActionResult {
+                action_id: action.action_id,
+                success: action.success,
+                duration_ms: action.duration_ms,
+                error: action.error.unwrap_or_default(),
+                ..Default::default()
+            })
+            .collect(),
+        completed_at_iso: format_datetime(execution.completed_at),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn execution_from_proto(execution: pb::ExecutionResult) -> Result<ExecutionReport, Status> {
+    Ok(ExecutionReport {
+        actions: execution.actions.into_iter().map(action_report_from_proto).collect(),
+        completed_at: parse_iso_datetime(&execution.completed_at_iso)?,
+    })
+}
+
 fn optional_string(value: String) -> Option<String> {
     if value.is_empty() {
         None
@@ -182,7 +1683,9 @@ fn optional_string(value: String) -> Option<String> {
     }
 }
 
-fn to_proto_timer(timer: TimerInstance) -> Result<pb::Timer, Status> {
+#[allow(clippy::result_large_err)]
+pub(crate) fn to_proto_timer(timer: TimerInstance) -> Result<pb::Timer, Status> {
+    let (missed_fire_policy, missed_fire_grace_ms) = missed_fire_policy_to_proto(timer.missed_fire_policy);
     Ok(pb::Timer {
         id: timer.id.to_string(),
         tenant_id: timer.tenant_id,
@@ -206,65 +1709,328 @@ fn to_proto_timer(timer: TimerInstance) -> Result<pb::Timer, Status> {
         action_bundle_json: serialize_json(timer.action_bundle)?,
         agent_binding_json: serialize_json(timer.agent_binding)?,
         labels: timer.labels,
+        accuracy_budget_ms: timer.accuracy_budget_ms.unwrap_or_default(),
+        fire_latency_ms: timer.fire_latency_ms.unwrap_or_default(),
+        duration_us: timer.duration_us.unwrap_or_default(),
+        fire_latency_us: timer.fire_latency_us.unwrap_or_default(),
+        budget_outcome: timer
+            .budget_outcome
+            .map(budget_outcome_to_proto)
+            .unwrap_or(pb::BudgetOutcome::Unspecified) as i32,
+        pre_fire_notice_ms: timer.pre_fire_notice_ms.unwrap_or_default(),
+        idempotency_key: timer.idempotency_key.unwrap_or_default(),
+        missed_fire_policy: missed_fire_policy as i32,
+        missed_fire_grace_ms,
+        session_id: timer.session_id.unwrap_or_default(),
+        snooze_count: timer.snooze_count,
+        watchdog_interval_ms: match timer.kind {
+            TimerKind::Watchdog { interval_ms } => interval_ms,
+            TimerKind::OneShot | TimerKind::Deadline { .. } => 0,
+        },
+        last_kicked_at_iso: timer.last_kicked_at.map(format_datetime).unwrap_or_default(),
+        priority: timer.priority,
+        deadline: matches!(timer.kind, TimerKind::Deadline { .. }),
+        deadline_grace_ms: match timer.kind {
+            TimerKind::Deadline { grace_ms } => grace_ms,
+            TimerKind::OneShot | TimerKind::Watchdog { .. } => 0,
+        },
+        acknowledged_at_iso: timer.acknowledged_at.map(format_datetime).unwrap_or_default(),
+        failed_at_iso: timer.failed_at.map(format_datetime).unwrap_or_default(),
+        failure_reason: timer.failure_reason.map(failure_reason_to_proto),
+        state_version: timer.state_version,
+        settled_at_iso: timer.settled_at.map(format_datetime).unwrap_or_default(),
+        pre_fire_sent_for_iso: timer.pre_fire_sent_for.map(format_datetime).unwrap_or_default(),
+        execution: timer.execution.map(execution_to_proto),
+    })
+}
+
+/// Inverse of the server's timer encoding, used by standbys replicating from an active kernel.
+#[allow(clippy::result_large_err)]
+pub fn timer_from_proto(timer: pb::Timer) -> Result<TimerInstance, Status> {
+    let optional_datetime = |value: &str| {
+        if value.is_empty() {
+            Ok(None)
+        } else {
+            parse_iso_datetime(value).map(Some)
+        }
+    };
+    let status = match pb::TimerStatus::try_from(timer.status) {
+        Ok(pb::TimerStatus::Scheduled) => TimerStatus::Scheduled,
+        Ok(pb::TimerStatus::Armed) => TimerStatus::Armed,
+        Ok(pb::TimerStatus::Fired) => TimerStatus::Fired,
+        Ok(pb::TimerStatus::Cancelled) => TimerStatus::Cancelled,
+        Ok(pb::TimerStatus::Missed) => TimerStatus::Missed,
+        Ok(pb::TimerStatus::Acknowledged) => TimerStatus::Acknowledged,
+        Ok(pb::TimerStatus::Failed) => TimerStatus::Failed,
+        Ok(pb::TimerStatus::Settled) => TimerStatus::Settled,
+        _ => return Err(Status::invalid_argument("unsupported timer status")),
+    };
+    Ok(TimerInstance {
+        schema_version: crate::TIMER_SCHEMA_VERSION,
+        id: Uuid::parse_str(&timer.id).map_err(|_| Status::invalid_argument("timer id must be a valid UUID"))?,
+        tenant_id: timer.tenant_id,
+        requested_by: timer.requested_by,
+        name: timer.name,
+        duration_ms: timer.duration_ms,
+        duration_us: (timer.duration_us > 0).then_some(timer.duration_us),
+        created_at: parse_iso_datetime(&timer.created_at_iso)?,
+        fire_at: parse_iso_datetime(&timer.fire_at_iso)?,
+        status,
+        metadata: parse_optional_json_string(timer.metadata_json)?,
+        labels: timer.labels,
+        action_bundle: parse_optional_json_string(timer.action_bundle_json)?,
+        agent_binding: parse_optional_json_string(timer.agent_binding_json)?,
+        fired_at: optional_datetime(&timer.fired_at_iso)?,
+        cancelled_at: optional_datetime(&timer.cancelled_at_iso)?,
+        cancel_reason: optional_string(timer.cancel_reason),
+        cancelled_by: optional_string(timer.cancelled_by),
+        accuracy_budget_ms: (timer.accuracy_budget_ms > 0).then_some(timer.accuracy_budget_ms),
+        fire_latency_ms: (!timer.fired_at_iso.is_empty()).then_some(timer.fire_latency_ms),
+        fire_latency_us: (!timer.fired_at_iso.is_empty()).then_some(timer.fire_latency_us),
+        budget_outcome: match pb::BudgetOutcome::try_from(timer.budget_outcome) {
+            Ok(pb::BudgetOutcome::WithinBudget) => Some(BudgetOutcome::WithinBudget),
+            Ok(pb::BudgetOutcome::OverBudget) => Some(BudgetOutcome::OverBudget),
+            _ => None,
+        },
+        pre_fire_notice_ms: (timer.pre_fire_notice_ms > 0).then_some(timer.pre_fire_notice_ms),
+        pre_fire_sent_for: optional_datetime(&timer.pre_fire_sent_for_iso)?,
+        idempotency_key: optional_string(timer.idempotency_key),
+        missed_fire_policy: missed_fire_policy_from_proto(timer.missed_fire_policy, timer.missed_fire_grace_ms),
+        session_id: optional_string(timer.session_id),
+        snooze_count: timer.snooze_count,
+        kind: timer_kind_from_proto(timer.watchdog_interval_ms, timer.deadline, timer.deadline_grace_ms)?,
+        last_kicked_at: optional_datetime(&timer.last_kicked_at_iso)?,
+        priority: timer.priority,
+        acknowledged_at: optional_datetime(&timer.acknowledged_at_iso)?,
+        failed_at: optional_datetime(&timer.failed_at_iso)?,
+        failure_reason: timer.failure_reason.map(failure_reason_from_proto).transpose()?,
+        settled_at: optional_datetime(&timer.settled_at_iso)?,
+        execution: timer.execution.map(execution_from_proto).transpose()?,
+        state_version: timer.state_version,
     })
 }
 
+#[allow(clippy::result_large_err)]
+pub fn event_from_proto(event: pb::TimerEvent) -> Result<Option<TimerEvent>, Status> {
+    let missing = || Status::invalid_argument("event is missing its timer");
+    Ok(match event.event {
+        Some(pb::timer_event::Event::Scheduled(scheduled)) => Some(TimerEvent::Scheduled(timer_from_proto(
+            scheduled.timer.ok_or_else(missing)?,
+        )?)),
+        Some(pb::timer_event::Event::Fired(fired)) => {
+            Some(TimerEvent::Fired(timer_from_proto(fired.timer.ok_or_else(missing)?)?))
+        }
+        Some(pb::timer_event::Event::Cancelled(cancelled)) => Some(TimerEvent::Cancelled {
+            timer: timer_from_proto(cancelled.timer.ok_or_else(missing)?)?,
+            reason: optional_string(cancelled.reason),
+        }),
+        Some(pb::timer_event::Event::PreFire(notice)) => Some(TimerEvent::PreFire {
+            timer: timer_from_proto(notice.timer.ok_or_else(missing)?)?,
+            fires_in_ms: notice.fires_in_ms,
+        }),
+        Some(pb::timer_event::Event::Rescheduled(rescheduled)) => Some(TimerEvent::Rescheduled {
+            timer: timer_from_proto(rescheduled.timer.ok_or_else(missing)?)?,
+            previous_fire_at: parse_iso_datetime(&rescheduled.previous_fire_time_iso)?,
+        }),
+        Some(pb::timer_event::Event::Missed(missed)) => Some(TimerEvent::Missed {
+            timer: timer_from_proto(missed.timer.ok_or_else(missing)?)?,
+            late_by_ms: missed.late_by_ms,
+        }),
+        Some(pb::timer_event::Event::Acknowledged(acknowledged)) => Some(TimerEvent::Acknowledged(timer_from_proto(
+            acknowledged.timer.ok_or_else(missing)?,
+        )?)),
+        Some(pb::timer_event::Event::Failed(failed)) => {
+            Some(TimerEvent::Failed(timer_from_proto(failed.timer.ok_or_else(missing)?)?))
+        }
+        Some(pb::timer_event::Event::Settled(settled)) => {
+            Some(TimerEvent::Settled(timer_from_proto(settled.timer.ok_or_else(missing)?)?))
+        }
+        // Throttle notices are addressed to the stream's subscriber, not timer state changes.
+        Some(pb::timer_event::Event::Throttled(_)) | None => None,
+    })
+}
+
+fn budget_outcome_to_proto(outcome: BudgetOutcome) -> pb::BudgetOutcome {
+    match outcome {
+        BudgetOutcome::WithinBudget => pb::BudgetOutcome::WithinBudget,
+        BudgetOutcome::OverBudget => pb::BudgetOutcome::OverBudget,
+    }
+}
+
 fn status_to_proto(status: TimerStatus) -> pb::TimerStatus {
     match status {
         TimerStatus::Scheduled => pb::TimerStatus::Scheduled,
         TimerStatus::Armed => pb::TimerStatus::Armed,
         TimerStatus::Fired => pb::TimerStatus::Fired,
         TimerStatus::Cancelled => pb::TimerStatus::Cancelled,
+        TimerStatus::Missed => pb::TimerStatus::Missed,
+        TimerStatus::Acknowledged => pb::TimerStatus::Acknowledged,
+        TimerStatus::Failed => pb::TimerStatus::Failed,
+        TimerStatus::Settled => pb::TimerStatus::Settled,
+    }
+}
+
+fn usage_to_proto(counters: UsageCounters) -> pb::UsageCounters {
+    pb::UsageCounters {
+        timers_scheduled: counters.timers_scheduled,
+        fires_delivered: counters.fires_delivered,
+        action_executions: counters.action_executions,
+        event_bytes_published: counters.event_bytes_published,
+    }
+}
+
+fn status_counts_to_proto(counts: TimerCounts) -> pb::TimerStatusCounts {
+    pb::TimerStatusCounts {
+        scheduled: counts.scheduled,
+        armed: counts.armed,
+        fired: counts.fired,
+        cancelled: counts.cancelled,
+        missed: counts.missed,
+        acknowledged: counts.acknowledged,
+        failed: counts.failed,
+        settled: counts.settled,
+        total: counts.total(),
     }
 }
 
-fn event_to_proto(event: TimerEvent) -> Result<pb::TimerEvent, Status> {
+#[allow(clippy::result_large_err)]
+pub fn event_to_proto(event: TimerEvent) -> Result<pb::TimerEvent, Status> {
     match event {
         TimerEvent::Scheduled(timer) => Ok(pb::TimerEvent {
             event: Some(pb::timer_event::Event::Scheduled(pb::TimerScheduled {
                 timer: Some(to_proto_timer(timer)?),
             })),
+            sequence: 0,
         }),
         TimerEvent::Fired(timer) => Ok(pb::TimerEvent {
             event: Some(pb::timer_event::Event::Fired(pb::TimerFired {
                 timer: Some(to_proto_timer(timer)?),
                 result: None,
             })),
+            sequence: 0,
         }),
         TimerEvent::Cancelled { timer, reason } => Ok(pb::TimerEvent {
             event: Some(pb::timer_event::Event::Cancelled(pb::TimerCancelled {
                 timer: Some(to_proto_timer(timer)?),
                 reason: reason.unwrap_or_default(),
             })),
+            sequence: 0,
+        }),
+        TimerEvent::PreFire { timer, fires_in_ms } => Ok(pb::TimerEvent {
+            event: Some(pb::timer_event::Event::PreFire(pb::TimerPreFire {
+                timer: Some(to_proto_timer(timer)?),
+                fires_in_ms,
+            })),
+            sequence: 0,
+        }),
+        TimerEvent::Rescheduled { timer, previous_fire_at } => Ok(pb::TimerEvent {
+            event: Some(pb::timer_event::Event::Rescheduled(pb::TimerRescheduled {
+                timer: Some(to_proto_timer(timer)?),
+                previous_fire_time_iso: format_datetime(previous_fire_at),
+            })),
+            sequence: 0,
+        }),
+        TimerEvent::Missed { timer, late_by_ms } => Ok(pb::TimerEvent {
+            event: Some(pb::timer_event::Event::Missed(pb::TimerMissed {
+                timer: Some(to_proto_timer(timer)?),
+                late_by_ms,
+            })),
+            sequence: 0,
+        }),
+        TimerEvent::Acknowledged(timer) => Ok(pb::TimerEvent {
+            event: Some(pb::timer_event::Event::Acknowledged(pb::TimerAcknowledged {
+                timer: Some(to_proto_timer(timer)?),
+            })),
+            sequence: 0,
+        }),
+        TimerEvent::Failed(timer) => Ok(pb::TimerEvent {
+            event: Some(pb::timer_event::Event::Failed(pb::TimerFailed {
+                timer: Some(to_proto_timer(timer)?),
+            })),
+            sequence: 0,
+        }),
+        TimerEvent::Settled(timer) => Ok(pb::TimerEvent {
+            event: Some(pb::timer_event::Event::Settled(pb::TimerSettled {
+                timer: Some(to_proto_timer(timer)?),
+            })),
+            sequence: 0,
         }),
     }
 }
 
-fn event_belongs_to_tenant(event: &TimerEvent, tenant_id: &str) -> bool {
+fn event_tenant_id(event: &TimerEvent) -> &str {
     match event {
-        TimerEvent::Scheduled(timer) => timer.tenant_id == tenant_id,
-        TimerEvent::Fired(timer) => timer.tenant_id == tenant_id,
-        TimerEvent::Cancelled { timer, .. } => timer.tenant_id == tenant_id,
+        TimerEvent::Scheduled(timer) => &timer.tenant_id,
+        TimerEvent::Fired(timer) => &timer.tenant_id,
+        TimerEvent::Cancelled { timer, .. } => &timer.tenant_id,
+        TimerEvent::PreFire { timer, .. } => &timer.tenant_id,
+        TimerEvent::Rescheduled { timer, .. } => &timer.tenant_id,
+        TimerEvent::Missed { timer, .. } => &timer.tenant_id,
+        TimerEvent::Acknowledged(timer) => &timer.tenant_id,
+        TimerEvent::Failed(timer) => &timer.tenant_id,
+        TimerEvent::Settled(timer) => &timer.tenant_id,
     }
 }
 
-fn map_kernel_error(error: KernelError) -> Status {
+fn tenant_scope(tenant_id: String, include_projects: bool) -> TenantScope {
+    if tenant_id == ALL_TENANTS {
+        TenantScope::All
+    } else if include_projects {
+        TenantScope::Organization(tenant_id)
+    } else {
+        TenantScope::Tenant(tenant_id)
+    }
+}
+
+fn standby_status() -> Status {
+    Status::unavailable("kernel is a standby and does not accept writes")
+}
+
+pub(crate) fn map_kernel_error(error: KernelError) -> Status {
     match error {
         KernelError::InvalidDuration => Status::invalid_argument("duration must be greater than zero"),
         KernelError::InvalidFireTime => Status::invalid_argument("fire_at must be in the future"),
+        error @ KernelError::LimitExceeded { .. } => Status::invalid_argument(error.to_string()),
+        KernelError::Standby => standby_status(),
+        error @ KernelError::RateLimited { .. } => Status::resource_exhausted(error.to_string()),
+        error @ KernelError::QuotaExceeded { .. } => Status::resource_exhausted(error.to_string()),
+        KernelError::Store(error) => Status::unavailable(error.to_string()),
+        error @ KernelError::Manifest(_) => Status::invalid_argument(error.to_string()),
+        error @ KernelError::NotPending => Status::failed_precondition(error.to_string()),
+        error @ KernelError::NotSnoozable => Status::failed_precondition(error.to_string()),
+        error @ KernelError::NotWatchdog => Status::failed_precondition(error.to_string()),
+        error @ KernelError::NotDeadline => Status::failed_precondition(error.to_string()),
+        error @ KernelError::NotFired => Status::failed_precondition(error.to_string()),
+        error @ KernelError::EventHistoryDisabled => Status::failed_precondition(error.to_string()),
+        error @ KernelError::EventsExpired { .. } => Status::out_of_range(error.to_string()),
+        error @ KernelError::SequenceAhead { .. } => Status::out_of_range(error.to_string()),
+        error @ KernelError::PageToken(PageTokenError::TooLarge { .. }) => {
+            Status::failed_precondition(error.to_string())
+        }
+        error @ KernelError::PageToken(_) => Status::invalid_argument(error.to_string()),
+        error @ KernelError::LegacyImport(_) => Status::invalid_argument(error.to_string()),
+        error @ KernelError::BatchTooLarge { .. } => Status::invalid_argument(error.to_string()),
+        error @ KernelError::StateVersionConflict { .. } => Status::aborted(error.to_string()),
     }
 }
 
+/// Timers start at state version 1, so 0 (the proto default) means "no expectation".
+fn expected_state_version(value: u64) -> Option<u64> {
+    (value != 0).then_some(value)
+}
+
+#[allow(clippy::result_large_err)]
 fn parse_iso_datetime(value: &str) -> Result<chrono::DateTime<chrono::Utc>, Status> {
     chrono::DateTime::parse_from_rfc3339(value)
         .map(|dt| dt.with_timezone(&chrono::Utc))
         .map_err(|_| Status::invalid_argument("fire_time_iso must be RFC3339"))
 }
 
-fn format_datetime(value: chrono::DateTime<chrono::Utc>) -> String {
+pub(crate) fn format_datetime(value: chrono::DateTime<chrono::Utc>) -> String {
     value.to_rfc3339()
 }
 
+#[allow(clippy::result_large_err)]
 fn parse_optional_json_string(value: String) -> Result<Option<serde_json::Value>, Status> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -275,6 +2041,7 @@ fn parse_optional_json_string(value: String) -> Result<Option<serde_json::Value>
         .map_err(|error| Status::invalid_argument(format!("invalid json payload: {error}")))
 }
 
+#[allow(clippy::result_large_err)]
 fn serialize_json(value: Option<serde_json::Value>) -> Result<String, Status> {
     match value {
         Some(inner) => serde_json::to_string(&inner)
@@ -0,0 +1,94 @@
+//! `grpc.health.v1.Health`, for Kubernetes gRPC probes and load balancers. The kernel's service
+//! (and the empty name, meaning the whole server) is `SERVING` while the kernel is active and its
+//! last store write succeeded, and `NOT_SERVING` on a standby or while writes fail, so probes
+//! route traffic to the leader only.
+
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio_stream::{wrappers::IntervalStream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use crate::pb::health::health_check_response::ServingStatus;
+use crate::pb::health::health_server::{Health, HealthServer};
+use crate::pb::health::{HealthCheckRequest, HealthCheckResponse};
+use crate::HorologyKernel;
+
+pub const KERNEL_SERVICE: &str = "minoots.timer.v1.HorologyKernel";
+pub const HEALTH_SERVICE: &str = "grpc.health.v1.Health";
+
+/// How often `Watch` re-evaluates the status; changes are sent, repeats are not.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+pub type HealthWatchStream =
+    Pin<Box<dyn Stream<Item = Result<HealthCheckResponse, Status>> + Send + 'static>>;
+
+#[derive(Clone)]
+pub struct HealthService {
+    kernel: HorologyKernel,
+}
+
+impl HealthService {
+    pub fn new(kernel: HorologyKernel) -> Self {
+        Self { kernel }
+    }
+
+    pub fn into_server(self) -> HealthServer<Self> {
+        HealthServer::new(self)
+    }
+
+    /// `None` for services this server does not host.
+    fn status(&self, service: &str) -> Option<ServingStatus> {
+        match service {
+            "" | KERNEL_SERVICE if self.kernel.is_active() && self.kernel.store_healthy() => {
+                Some(ServingStatus::Serving)
+            }
+            "" | KERNEL_SERVICE => Some(ServingStatus::NotServing),
+            HEALTH_SERVICE
+            | crate::admin::ADMIN_SERVICE
+            | crate::reflection::REFLECTION_SERVICE => Some(ServingStatus::Serving),
+            _ => None,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Health for HealthService {
+    async fn check(
+        &self,
+        request: Request<HealthCheckRequest>,
+    ) -> Result<Response<HealthCheckResponse>, Status> {
+        let service = request.into_inner().service;
+        let status = self
+            .status(&service)
+            .ok_or_else(|| Status::not_found(format!("unknown service `{service}`")))?;
+        Ok(Response::new(HealthCheckResponse {
+            status: status.into(),
+        }))
+    }
+
+    type WatchStream = HealthWatchStream;
+
+    #[allow(clippy::result_large_err)]
+    async fn watch(
+        &self,
+        request: Request<HealthCheckRequest>,
+    ) -> Result<Response<Self::WatchStream>, Status> {
+        let service = request.into_inner().service;
+        let health = self.clone();
+        let mut last = None;
+        let updates =
+            IntervalStream::new(tokio::time::interval(WATCH_INTERVAL)).filter_map(move |_| {
+                let status = health
+                    .status(&service)
+                    .unwrap_or(ServingStatus::ServiceUnknown);
+                (last.replace(status) != Some(status)).then(|| {
+                    Ok(HealthCheckResponse {
+                        status: status.into(),
+                    })
+                })
+            });
+        Ok(Response::new(Box::pin(updates)))
+    }
+}
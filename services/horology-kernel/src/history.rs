@@ -0,0 +1,367 @@
+//! Retained log of the timer events the kernel emitted, per tenant, so "what happened to timer X
+//! last Tuesday" can be answered without external event storage. Events older than the retention
+//! window or beyond the per-tenant cap are dropped. With a file, events also survive restarts: each
+//! one is appended as a JSON line, and the file is compacted to the retained events when opened.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{StoreError, TimerEvent};
+
+/// Most events one query returns.
+pub const MAX_QUERY_LIMIT: usize = 1_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventRetention {
+    pub max_age_ms: u64,
+    pub max_events_per_tenant: usize,
+}
+
+impl EventRetention {
+    /// Keeps `days` of events, at most 10,000 per tenant.
+    pub fn days(days: u64) -> Self {
+        Self {
+            max_age_ms: days * 24 * 60 * 60 * 1_000,
+            max_events_per_tenant: 10_000,
+        }
+    }
+
+    fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - chrono::Duration::milliseconds(self.max_age_ms.min(i64::MAX as u64) as i64)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Kernel-wide, increasing with every event the kernel emits, so one tenant's events skip the
+    /// numbers of other tenants'. Resume a query or an event stream after the last one seen.
+    pub sequence: u64,
+    pub recorded_at: DateTime<Utc>,
+    pub event: TimerEvent,
+}
+
+/// Filters for [`EventHistory::query`]; empty fields match everything.
+#[derive(Clone, Debug, Default)]
+pub struct EventQuery {
+    pub timer_id: Option<Uuid>,
+    /// Event types as named by [`TimerEvent::kind`], e.g. `fired`.
+    pub kinds: Vec<String>,
+    /// Inclusive.
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive.
+    pub until: Option<DateTime<Utc>>,
+    pub after_sequence: Option<u64>,
+    /// Defaults to 100 and is capped at [`MAX_QUERY_LIMIT`].
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+struct Log {
+    last_sequence: u64,
+    tenants: HashMap<String, VecDeque<RecordedEvent>>,
+    /// Per tenant, the highest sequence retention has dropped.
+    trimmed_through: HashMap<String, u64>,
+    file: Option<File>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EventHistory {
+    retention: EventRetention,
+    log: Arc<Mutex<Log>>,
+}
+
+impl EventHistory {
+    /// In-memory history, lost on restart.
+    pub fn new(retention: EventRetention) -> Self {
+        Self {
+            retention,
+            log: Arc::new(Mutex::new(Log::default())),
+        }
+    }
+
+    /// History persisted to a JSON-lines file, keeping what the file retains from earlier runs.
+    pub fn open(path: impl AsRef<Path>, retention: EventRetention) -> Result<Self, StoreError> {
+        let path = path.as_ref();
+        let history = Self::new(retention);
+        let cutoff = retention.cutoff(Utc::now());
+        let mut log = Log::default();
+        match File::open(path) {
+            Ok(file) => {
+                for line in BufReader::new(file).lines() {
+                    let line = line?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let recorded: RecordedEvent = serde_json::from_str(&line)?;
+                    log.last_sequence = log.last_sequence.max(recorded.sequence);
+                    history.retain(&mut log, recorded, cutoff);
+                }
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        let compacted = path.with_extension("compact");
+        {
+            let mut file = File::create(&compacted)?;
+            let mut retained: Vec<&RecordedEvent> = log.tenants.values().flatten().collect();
+            retained.sort_by_key(|recorded| recorded.sequence);
+            for recorded in retained {
+                write_line(&mut file, recorded)?;
+            }
+            file.sync_all()?;
+        }
+        std::fs::rename(&compacted, path)?;
+        log.file = Some(OpenOptions::new().append(true).open(path)?);
+        *history.lock() = log;
+        Ok(history)
+    }
+
+    pub fn retention(&self) -> EventRetention {
+        self.retention
+    }
+
+    /// Sequence of the latest recorded event, including those recorded by earlier runs; the kernel
+    /// numbers its events on from here.
+    pub fn last_sequence(&self) -> u64 {
+        self.lock().last_sequence
+    }
+
+    pub(crate) fn record(&self, recorded: &RecordedEvent) {
+        let mut log = self.lock();
+        log.last_sequence = log.last_sequence.max(recorded.sequence);
+        if let Some(file) = log.file.as_mut() {
+            if let Err(error) = write_line(file, recorded) {
+                tracing::warn!(%error, "failed to persist event history");
+            }
+        }
+        let cutoff = self.retention.cutoff(recorded.recorded_at);
+        self.retain(&mut log, recorded.clone(), cutoff);
+    }
+
+    /// Retained events after `after_sequence` of every tenant `include` accepts, in sequence order.
+    /// Fails with the highest dropped sequence when retention already dropped one of them.
+    pub(crate) fn replay(
+        &self,
+        after_sequence: u64,
+        include: impl Fn(&str) -> bool,
+    ) -> Result<Vec<RecordedEvent>, u64> {
+        let log = self.lock();
+        let trimmed_through = log
+            .trimmed_through
+            .iter()
+            .filter(|(tenant_id, _)| include(tenant_id))
+            .map(|(_, sequence)| *sequence)
+            .max()
+            .unwrap_or_default();
+        if trimmed_through > after_sequence {
+            return Err(trimmed_through);
+        }
+        let mut events: Vec<RecordedEvent> = log
+            .tenants
+            .iter()
+            .filter(|(tenant_id, _)| include(tenant_id))
+            .flat_map(|(_, events)| events.iter())
+            .filter(|recorded| recorded.sequence > after_sequence)
+            .cloned()
+            .collect();
+        events.sort_by_key(|recorded| recorded.sequence);
+        Ok(events)
+    }
+
+    /// A tenant's retained events matching `query`, oldest first.
+    pub fn query(&self, tenant_id: &str, query: &EventQuery) -> Vec<RecordedEvent> {
+        let cutoff = self.retention.cutoff(Utc::now());
+        let limit = query.limit.unwrap_or(100).clamp(1, MAX_QUERY_LIMIT);
+        let log = self.lock();
+        let Some(events) = log.tenants.get(tenant_id) else {
+            return Vec::new();
+        };
+        events
+            .iter()
+            .filter(|recorded| recorded.recorded_at >= cutoff)
+            .filter(|recorded| {
+                query
+                    .after_sequence
+                    .is_none_or(|after| recorded.sequence > after)
+            })
+            .filter(|recorded| {
+                query
+                    .since
+                    .is_none_or(|since| recorded.recorded_at >= since)
+            })
+            .filter(|recorded| query.until.is_none_or(|until| recorded.recorded_at < until))
+            .filter(|recorded| {
+                query
+                    .timer_id
+                    .is_none_or(|id| recorded.event.timer().id == id)
+            })
+            .filter(|recorded| {
+                query.kinds.is_empty()
+                    || query.kinds.iter().any(|kind| kind == recorded.event.kind())
+            })
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Appends `recorded` to its tenant's events, then drops what falls outside the retention.
+    fn retain(&self, log: &mut Log, recorded: RecordedEvent, cutoff: DateTime<Utc>) {
+        let tenant_id = recorded.event.timer().tenant_id.clone();
+        let events = log.tenants.entry(tenant_id.clone()).or_default();
+        events.push_back(recorded);
+        let mut trimmed = None;
+        while events.len() > self.retention.max_events_per_tenant
+            || events
+                .front()
+                .is_some_and(|recorded| recorded.recorded_at < cutoff)
+        {
+            trimmed = events.pop_front().map(|recorded| recorded.sequence);
+        }
+        if let Some(sequence) = trimmed {
+            let through = log.trimmed_through.entry(tenant_id).or_default();
+            *through = (*through).max(sequence);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Log> {
+        self.log.lock().expect("event history poisoned")
+    }
+}
+
+fn write_line(file: &mut File, recorded: &RecordedEvent) -> Result<(), StoreError> {
+    let mut line = serde_json::to_vec(recorded)?;
+    line.push(b'\n');
+    file.write_all(&line)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HorologyKernel, KernelError, SchedulerConfig, TimerSpec};
+
+    #[tokio::test]
+    async fn retained_events_survive_a_reopen_within_the_cap() {
+        let path = std::env::temp_dir().join(format!("minoots-events-{}.jsonl", Uuid::new_v4()));
+        let retention = EventRetention {
+            max_events_per_tenant: 3,
+            ..EventRetention::days(1)
+        };
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            event_history: Some(EventHistory::open(&path, retention).unwrap()),
+            ..Default::default()
+        });
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+        let first = kernel.schedule(spec.clone()).await.unwrap();
+        let second = kernel.schedule(spec).await.unwrap();
+        kernel
+            .cancel("tenant-a", first.id, None, None)
+            .await
+            .expect("cancelled");
+        kernel
+            .cancel("tenant-a", second.id, None, None)
+            .await
+            .expect("cancelled");
+
+        let reopened = EventHistory::open(&path, retention).unwrap();
+        let all = reopened.query("tenant-a", &EventQuery::default());
+        let kinds: Vec<_> = all.iter().map(|recorded| recorded.event.kind()).collect();
+        assert_eq!(kinds, ["scheduled", "cancelled", "cancelled"]);
+        assert!(all
+            .windows(2)
+            .all(|pair| pair[0].sequence < pair[1].sequence));
+
+        let first_only = reopened.query(
+            "tenant-a",
+            &EventQuery {
+                timer_id: Some(first.id),
+                kinds: vec!["cancelled".into()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(first_only.len(), 1);
+        assert_eq!(first_only[0].event.timer().id, first.id);
+        let after = reopened.query(
+            "tenant-a",
+            &EventQuery {
+                after_sequence: Some(all[1].sequence),
+                ..Default::default()
+            },
+        );
+        assert_eq!(after.len(), 1);
+        assert!(reopened
+            .query("tenant-b", &EventQuery::default())
+            .is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn resumed_subscriptions_replay_what_they_missed_exactly_once() {
+        let path = std::env::temp_dir().join(format!("minoots-events-{}.jsonl", Uuid::new_v4()));
+        let retention = EventRetention {
+            max_events_per_tenant: 2,
+            ..EventRetention::days(1)
+        };
+        let config = || SchedulerConfig {
+            event_history: Some(EventHistory::open(&path, retention).unwrap()),
+            ..Default::default()
+        };
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+        let kernel = HorologyKernel::new(config());
+        for _ in 0..3 {
+            kernel.schedule(spec.clone()).await.unwrap();
+        }
+        assert_eq!(kernel.last_event_sequence(), 3);
+
+        // A restarted kernel numbers on from the persisted history.
+        let kernel = HorologyKernel::new(config());
+        assert_eq!(kernel.last_event_sequence(), 3);
+        let (replay, mut live) = kernel.subscribe_after(2, |_| true).unwrap();
+        let replayed: Vec<_> = replay.iter().map(|recorded| recorded.sequence).collect();
+        assert_eq!(replayed, [3]);
+        kernel.schedule(spec).await.unwrap();
+        assert_eq!(live.recv_recorded().await.unwrap().sequence, 4);
+        assert!(kernel.subscribe_after(4, |_| true).unwrap().0.is_empty());
+
+        assert!(matches!(
+            kernel.subscribe_after(1, |_| true),
+            Err(KernelError::EventsExpired {
+                after_sequence: 1,
+                trimmed_through: 2
+            })
+        ));
+        assert!(kernel
+            .subscribe_after(1, |tenant_id| tenant_id != "tenant-a")
+            .unwrap()
+            .0
+            .is_empty());
+        assert!(matches!(
+            kernel.subscribe_after(5, |_| true),
+            Err(KernelError::SequenceAhead {
+                requested: 5,
+                latest: 4
+            })
+        ));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,76 @@
+//! Invariant checks over the kernel's pending timers. A pending timer well past its due time means a
+//! fire was lost: the dispatch loop panicked (taking the queue's claim with it), or the timer's
+//! queue entry went missing. [`crate::HorologyKernel::recover_stuck_timers`] finds such timers and
+//! recovers them rather than letting them wait forever.
+
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How a stuck timer was recovered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    /// The dispatch loop had stopped; it was restarted and the timer queued on it again.
+    Rearmed,
+    /// The dispatch loop was running but had not fired the timer, so it was fired directly.
+    Fired,
+}
+
+impl RecoveryAction {
+    pub fn name(self) -> &'static str {
+        match self {
+            RecoveryAction::Rearmed => "rearmed",
+            RecoveryAction::Fired => "fired",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveredStuckTimer {
+    pub timer_id: Uuid,
+    pub tenant_id: String,
+    /// How far past its due time the timer was when found.
+    pub overdue_ms: u64,
+    pub action: RecoveryAction,
+}
+
+/// Stuck-timer recoveries since startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StuckTimerStats {
+    pub scans: u64,
+    pub rearmed: u64,
+    pub fired: u64,
+    /// Times a stopped dispatch loop was restarted.
+    pub dispatch_restarts: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct StuckTimerMetrics {
+    inner: Arc<Mutex<StuckTimerStats>>,
+}
+
+impl StuckTimerMetrics {
+    pub fn snapshot(&self) -> StuckTimerStats {
+        *self.lock()
+    }
+
+    pub(crate) fn record_scan(&self, recovered: &[RecoveredStuckTimer], restarted: bool) {
+        let mut stats = self.lock();
+        stats.scans += 1;
+        for timer in recovered {
+            match timer.action {
+                RecoveryAction::Rearmed => stats.rearmed += 1,
+                RecoveryAction::Fired => stats.fired += 1,
+            }
+        }
+        if restarted {
+            stats.dispatch_restarts += 1;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StuckTimerStats> {
+        self.inner.lock().expect("stuck timer metrics poisoned")
+    }
+}
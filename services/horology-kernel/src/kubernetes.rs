@@ -0,0 +1,492 @@
+//! Kubernetes integration: publishes the kernel's leadership as a `coordination.k8s.io/v1` Lease,
+//! promotes a standby when it acquires the lease, and records Kubernetes Events for leadership
+//! transitions and quota violations.
+//!
+//! The API server is reached over plain HTTP, normally a `kubectl proxy` sidecar on
+//! `http://127.0.0.1:8001` that handles service-account authentication and TLS.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hyper::{client::HttpConnector, Body, Client, Method, Request, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::{HorologyKernel, SystemEvent, SystemEventKind};
+
+pub const DEFAULT_API_URL: &str = "http://127.0.0.1:8001";
+const COMPONENT: &str = "horology-kernel";
+
+/// Who this kernel is, taken from the downward API (`POD_NAME`, `POD_NAMESPACE`, `NODE_NAME`
+/// exposed as env vars via `fieldRef`), falling back to `HOSTNAME` outside a pod.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PodIdentity {
+    pub pod_name: String,
+    pub namespace: String,
+    pub node_name: Option<String>,
+}
+
+impl PodIdentity {
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok().filter(|value| !value.is_empty()))
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            pod_name: lookup("POD_NAME")
+                .or_else(|| lookup("HOSTNAME"))
+                .unwrap_or_else(|| COMPONENT.to_string()),
+            namespace: lookup("POD_NAMESPACE").unwrap_or_else(|| "default".to_string()),
+            node_name: lookup("NODE_NAME"),
+        }
+    }
+
+    /// Lease holder identity and the `node_id` of leadership events.
+    pub fn node_id(&self) -> &str {
+        &self.pod_name
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum KubeError {
+    #[error("kubernetes API request failed: {0}")]
+    Transport(#[from] hyper::Error),
+    #[error("kubernetes API responded with {status}: {body}")]
+    Status { status: StatusCode, body: String },
+    #[error("invalid kubernetes API payload: {0}")]
+    Payload(#[from] serde_json::Error),
+}
+
+/// Minimal JSON client for the handful of API calls the kernel makes.
+#[derive(Clone)]
+pub struct KubeClient {
+    base_url: String,
+    client: Client<HttpConnector>,
+}
+
+impl KubeClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client: Client::new(),
+        }
+    }
+
+    /// Sends `body` to `path`; `Ok(None)` means 404.
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Option<Value>, KubeError> {
+        let request = Request::builder()
+            .method(method)
+            .uri(format!("{}{path}", self.base_url))
+            .header("content-type", "application/json")
+            .header("accept", "application/json")
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .expect("valid kubernetes request");
+        let response = self.client.request(request).await?;
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await?;
+        if status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(KubeError::Status {
+                status,
+                body: String::from_utf8_lossy(&bytes).into_owned(),
+            });
+        }
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaseSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder_identity: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_duration_seconds: Option<i64>,
+    /// `MicroTime` strings, which must carry exactly six fractional digits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acquire_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renew_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_transitions: Option<i64>,
+}
+
+fn micro_time(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string()
+}
+
+fn parse_micro_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// What a candidate does with the lease it just read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum LeaseDecision {
+    Renew,
+    Acquire,
+    Follow { holder: String },
+}
+
+fn decide(spec: &LeaseSpec, identity: &str, now: DateTime<Utc>) -> LeaseDecision {
+    let holder = spec.holder_identity.clone().unwrap_or_default();
+    if holder == identity {
+        return LeaseDecision::Renew;
+    }
+    let expires_at = spec
+        .renew_time
+        .as_deref()
+        .and_then(parse_micro_time)
+        .map(|renewed| {
+            renewed + chrono::Duration::seconds(spec.lease_duration_seconds.unwrap_or_default())
+        });
+    if holder.is_empty() || expires_at.is_none_or(|expires_at| expires_at <= now) {
+        LeaseDecision::Acquire
+    } else {
+        LeaseDecision::Follow { holder }
+    }
+}
+
+/// Leader election over a Lease object. The holder keeps renewing it; a standby that acquires it
+/// promotes itself, replacing the manual `SIGUSR1` promotion. Each acquisition fences the kernel
+/// with the lease's transition count, so a deposed leader cannot record a fire the new one owns.
+pub struct LeaseElector {
+    client: KubeClient,
+    identity: PodIdentity,
+    lease_name: String,
+    lease_duration: Duration,
+    /// Transitions of the lease when this kernel last held it, plus one so it is never 0.
+    term: AtomicU64,
+}
+
+impl LeaseElector {
+    pub fn new(client: KubeClient, identity: PodIdentity, lease_name: impl Into<String>) -> Self {
+        Self {
+            client,
+            identity,
+            lease_name: lease_name.into(),
+            lease_duration: Duration::from_secs(15),
+            term: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_lease_duration(mut self, lease_duration: Duration) -> Self {
+        self.lease_duration = lease_duration;
+        self
+    }
+
+    /// Fencing token of the latest acquisition; 0 before this kernel first held the lease.
+    pub fn term(&self) -> u64 {
+        self.term.load(Ordering::SeqCst)
+    }
+
+    fn path(&self) -> String {
+        format!(
+            "/apis/coordination.k8s.io/v1/namespaces/{}/leases/{}",
+            self.identity.namespace, self.lease_name
+        )
+    }
+
+    /// Campaigns for the lease until the task is dropped, emitting `leadership_gained` and
+    /// `leadership_lost` on transitions.
+    pub async fn run(&self, kernel: HorologyKernel) {
+        let retry = self.lease_duration / 3;
+        let mut leading = false;
+        let mut renewed_at: Option<tokio::time::Instant> = None;
+        loop {
+            let held = match self.try_acquire_or_renew().await {
+                Ok(held) => {
+                    if held {
+                        renewed_at = Some(tokio::time::Instant::now());
+                    }
+                    held
+                }
+                Err(error) => {
+                    warn!(%error, lease = %self.lease_name, "failed to update leadership lease");
+                    // Without the API server we cannot renew, but the lease stays ours until it
+                    // would expire.
+                    leading && renewed_at.is_some_and(|at| at.elapsed() < self.lease_duration)
+                }
+            };
+            if held != leading {
+                leading = held;
+                let node_id = self.identity.node_id().to_string();
+                if held {
+                    info!(%node_id, term = self.term(), lease = %self.lease_name, "acquired leadership lease");
+                    kernel.set_fencing_token(self.term());
+                    kernel.promote();
+                    kernel.emit_system(SystemEventKind::LeadershipGained { node_id });
+                } else {
+                    warn!(%node_id, lease = %self.lease_name, "lost leadership lease");
+                    kernel.emit_system(SystemEventKind::LeadershipLost { node_id });
+                }
+            }
+            tokio::time::sleep(retry).await;
+        }
+    }
+
+    /// Reads the lease and renews or takes it over when allowed. Returns whether this kernel holds
+    /// it afterwards; a conflicting concurrent write counts as not holding it.
+    pub async fn try_acquire_or_renew(&self) -> Result<bool, KubeError> {
+        let now = Utc::now();
+        let current = self.client.request(Method::GET, &self.path(), None).await?;
+        let Some(mut lease) = current else {
+            let spec = LeaseSpec {
+                holder_identity: Some(self.identity.node_id().to_string()),
+                lease_duration_seconds: Some(self.lease_duration.as_secs() as i64),
+                acquire_time: Some(micro_time(now)),
+                renew_time: Some(micro_time(now)),
+                lease_transitions: Some(0),
+            };
+            self.term.store(1, Ordering::SeqCst);
+            let body = json!({
+                "apiVersion": "coordination.k8s.io/v1",
+                "kind": "Lease",
+                "metadata": { "name": self.lease_name, "namespace": self.identity.namespace },
+                "spec": spec,
+            });
+            let path = format!(
+                "/apis/coordination.k8s.io/v1/namespaces/{}/leases",
+                self.identity.namespace
+            );
+            return conflict_as_false(self.client.request(Method::POST, &path, Some(&body)).await);
+        };
+        let mut spec: LeaseSpec = serde_json::from_value(lease["spec"].take()).unwrap_or_default();
+        match decide(&spec, self.identity.node_id(), now) {
+            LeaseDecision::Follow { .. } => return Ok(false),
+            LeaseDecision::Renew => {}
+            LeaseDecision::Acquire => {
+                spec.holder_identity = Some(self.identity.node_id().to_string());
+                spec.acquire_time = Some(micro_time(now));
+                spec.lease_transitions = Some(spec.lease_transitions.unwrap_or_default() + 1);
+            }
+        }
+        spec.renew_time = Some(micro_time(now));
+        spec.lease_duration_seconds = Some(self.lease_duration.as_secs() as i64);
+        self.term.store(
+            spec.lease_transitions.unwrap_or_default().max(0) as u64 + 1,
+            Ordering::SeqCst,
+        );
+        // The read's resourceVersion stays in metadata, so a concurrent writer makes this a 409.
+        lease["spec"] = serde_json::to_value(&spec)?;
+        conflict_as_false(
+            self.client
+                .request(Method::PUT, &self.path(), Some(&lease))
+                .await,
+        )
+    }
+
+    /// Gives the lease up on shutdown so a standby can take over without waiting for expiry.
+    pub async fn release(&self) -> Result<(), KubeError> {
+        let Some(mut lease) = self.client.request(Method::GET, &self.path(), None).await? else {
+            return Ok(());
+        };
+        if lease["spec"]["holderIdentity"] != self.identity.node_id() {
+            return Ok(());
+        }
+        lease["spec"]["holderIdentity"] = Value::Null;
+        self.client
+            .request(Method::PUT, &self.path(), Some(&lease))
+            .await?;
+        Ok(())
+    }
+}
+
+fn conflict_as_false(result: Result<Option<Value>, KubeError>) -> Result<bool, KubeError> {
+    match result {
+        Ok(_) => Ok(true),
+        Err(KubeError::Status {
+            status: StatusCode::CONFLICT,
+            ..
+        }) => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+/// Records system events that operators watch for as Kubernetes Events on the kernel's pod.
+pub struct EventRecorder {
+    client: KubeClient,
+    identity: PodIdentity,
+}
+
+impl EventRecorder {
+    pub fn new(client: KubeClient, identity: PodIdentity) -> Self {
+        Self { client, identity }
+    }
+
+    pub async fn run(&self, mut events: broadcast::Receiver<SystemEvent>) {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    let Some(body) = self.event_body(&event) else {
+                        continue;
+                    };
+                    let path = format!("/api/v1/namespaces/{}/events", self.identity.namespace);
+                    if let Err(error) = self.client.request(Method::POST, &path, Some(&body)).await
+                    {
+                        warn!(%error, kind = event.kind.name(), "failed to record kubernetes event");
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(
+                        skipped,
+                        "kubernetes event recorder fell behind; events dropped"
+                    )
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    fn event_body(&self, event: &SystemEvent) -> Option<Value> {
+        let (reason, kind, message) = match &event.kind {
+            SystemEventKind::LeadershipGained { node_id } => (
+                "LeadershipGained",
+                "Normal",
+                format!("{node_id} became the active horology kernel"),
+            ),
+            SystemEventKind::LeadershipLost { node_id } => (
+                "LeadershipLost",
+                "Warning",
+                format!("{node_id} lost the leadership lease"),
+            ),
+            SystemEventKind::QuotaExceeded { tenant_id, quota } => (
+                "QuotaExceeded",
+                "Warning",
+                format!("tenant {tenant_id} exceeded its {quota} quota"),
+            ),
+            SystemEventKind::SchedulingSpike {
+                tenant_id,
+                operation,
+                per_minute,
+                throttled: true,
+                ..
+            } => (
+                "TenantThrottled",
+                "Warning",
+                format!("tenant {tenant_id} throttled after {per_minute} {operation}s per minute"),
+            ),
+            SystemEventKind::RecoveredStuckTimer {
+                timer_id,
+                overdue_ms,
+                action,
+                ..
+            } => (
+                "RecoveredStuckTimer",
+                "Warning",
+                format!("timer {timer_id} was stuck {overdue_ms}ms past due and was {action}"),
+            ),
+            SystemEventKind::Fenced {
+                fencing_token,
+                current_token,
+            } => (
+                "Fenced",
+                "Warning",
+                format!("fenced off at term {fencing_token} by term {current_token}; stepped down to standby"),
+            ),
+            SystemEventKind::ClockJumped { jump_ms, rearmed } => (
+                "ClockJumped",
+                "Warning",
+                format!("wall clock stepped {jump_ms}ms; re-armed {rearmed} pending timers"),
+            ),
+            _ => return None,
+        };
+        let at = event
+            .occurred_at
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        Some(json!({
+            "apiVersion": "v1",
+            "kind": "Event",
+            "metadata": {
+                "generateName": format!("{}.", self.identity.pod_name),
+                "namespace": self.identity.namespace,
+            },
+            "involvedObject": {
+                "apiVersion": "v1",
+                "kind": "Pod",
+                "name": self.identity.pod_name,
+                "namespace": self.identity.namespace,
+            },
+            "reason": reason,
+            "message": message,
+            "type": kind,
+            "source": { "component": COMPONENT, "host": self.identity.node_name },
+            "firstTimestamp": at,
+            "lastTimestamp": at,
+            "count": 1,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_prefers_downward_api_fields() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        let pod = PodIdentity::from_lookup(env(&[
+            ("POD_NAME", "kernel-0"),
+            ("POD_NAMESPACE", "timers"),
+            ("NODE_NAME", "node-a"),
+            ("HOSTNAME", "ignored"),
+        ]));
+        assert_eq!(pod.node_id(), "kernel-0");
+        assert_eq!(pod.namespace, "timers");
+        assert_eq!(pod.node_name.as_deref(), Some("node-a"));
+        let bare = PodIdentity::from_lookup(env(&[("HOSTNAME", "laptop")]));
+        assert_eq!(bare.node_id(), "laptop");
+        assert_eq!(bare.namespace, "default");
+    }
+
+    #[test]
+    fn takes_over_only_expired_or_vacant_leases() {
+        let now = Utc::now();
+        let held_by = |holder: &str, renewed_secs_ago: i64| LeaseSpec {
+            holder_identity: Some(holder.to_string()),
+            lease_duration_seconds: Some(15),
+            renew_time: Some(micro_time(
+                now - chrono::Duration::seconds(renewed_secs_ago),
+            )),
+            ..LeaseSpec::default()
+        };
+        assert_eq!(
+            decide(&held_by("kernel-0", 5), "kernel-0", now),
+            LeaseDecision::Renew
+        );
+        assert_eq!(
+            decide(&held_by("kernel-1", 5), "kernel-0", now),
+            LeaseDecision::Follow {
+                holder: "kernel-1".into()
+            }
+        );
+        assert_eq!(
+            decide(&held_by("kernel-1", 20), "kernel-0", now),
+            LeaseDecision::Acquire
+        );
+        assert_eq!(
+            decide(&LeaseSpec::default(), "kernel-0", now),
+            LeaseDecision::Acquire
+        );
+        assert_eq!(micro_time(now).split('.').nth(1).map(str::len), Some(7));
+    }
+}
@@ -0,0 +1,145 @@
+//! Migration of timers created through the control plane before it scheduled on the kernel. Records
+//! use the control plane's `TimerRecord` JSON layout and are imported with their original ids and
+//! fire times, so an import can be re-run without creating duplicates.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{TimerInstance, TimerKind, TimerSpec};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum LegacyImportError {
+    #[error("legacy timer records are malformed: {0}")]
+    Malformed(String),
+    #[error("legacy timer record on line {line} is malformed: {message}")]
+    MalformedLine { line: usize, message: String },
+}
+
+/// One control-plane `TimerRecord`; fields the kernel derives itself (fire latency, budget outcome)
+/// are ignored.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyTimerRecord {
+    pub id: Uuid,
+    pub tenant_id: String,
+    pub requested_by: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub fire_at: DateTime<Utc>,
+    /// `scheduled`, `armed`, `fired`, `cancelled`, `failed`, or `missed`.
+    pub status: String,
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    #[serde(default)]
+    pub action_bundle: Option<serde_json::Value>,
+    #[serde(default)]
+    pub agent_binding: Option<serde_json::Value>,
+    #[serde(default)]
+    pub accuracy_budget_ms: Option<u64>,
+    #[serde(default)]
+    pub pre_fire_notice_ms: Option<u64>,
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub watchdog_interval_ms: Option<u64>,
+    #[serde(default)]
+    pub priority: Option<u32>,
+    #[serde(default)]
+    pub deadline_grace_ms: Option<u64>,
+}
+
+impl LegacyTimerRecord {
+    /// Only timers that have not fired or been cancelled are migrated.
+    pub fn is_pending(&self) -> bool {
+        matches!(self.status.as_str(), "scheduled" | "armed")
+    }
+
+    pub fn to_spec(&self) -> TimerSpec {
+        TimerSpec {
+            tenant_id: self.tenant_id.clone(),
+            requested_by: self.requested_by.clone(),
+            name: Some(self.name.clone()),
+            duration_ms: (self.fire_at - self.created_at).num_milliseconds().max(0) as u64,
+            duration_us: None,
+            fire_at: Some(self.fire_at),
+            metadata: self.metadata.clone(),
+            labels: self.labels.clone(),
+            action_bundle: self.action_bundle.clone(),
+            agent_binding: self.agent_binding.clone(),
+            accuracy_budget_ms: self.accuracy_budget_ms,
+            pre_fire_notice_ms: self.pre_fire_notice_ms,
+            idempotency_key: self.idempotency_key.clone(),
+            missed_fire_policy: None,
+            session_id: self.session_id.clone(),
+            kind: match (self.watchdog_interval_ms, self.deadline_grace_ms) {
+                (Some(interval_ms), _) if interval_ms > 0 => TimerKind::Watchdog { interval_ms },
+                (_, Some(grace_ms)) => TimerKind::Deadline { grace_ms },
+                _ => TimerKind::OneShot,
+            },
+            priority: self.priority.unwrap_or_default(),
+        }
+    }
+}
+
+/// Parses an export of the control plane's repository: either a JSON array of records or one
+/// record per line.
+pub fn parse_records(document: &str) -> Result<Vec<LegacyTimerRecord>, LegacyImportError> {
+    if document.trim_start().starts_with('[') {
+        return serde_json::from_str(document)
+            .map_err(|error| LegacyImportError::Malformed(error.to_string()));
+    }
+    document
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|error| LegacyImportError::MalformedLine {
+                line: index + 1,
+                message: error.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Outcome of [`crate::HorologyKernel::import_legacy`], by record.
+#[derive(Clone, Debug, Default)]
+pub struct ImportReport {
+    pub imported: Vec<TimerInstance>,
+    /// Already known to the kernel by id or idempotency key, e.g. from an earlier run.
+    pub already_present: Vec<Uuid>,
+    /// Fired, cancelled, or failed in the control plane; nothing left to schedule.
+    pub skipped_terminal: Vec<Uuid>,
+    /// Records the kernel's spec limits reject, with the reason.
+    pub rejected: Vec<(Uuid, String)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_arrays_and_json_lines() {
+        let record = r#"{"id": "6f1c2f43-7c1e-4c5a-9d59-0b3c8f1d2e4a", "tenantId": "acme", "requestedBy": "cli", "name": "renewal", "durationMs": 60000, "createdAt": "2025-01-01T00:00:00Z", "fireAt": "2025-01-01T00:01:00Z", "status": "scheduled", "fireLatencyMs": 3}"#;
+        let from_lines = parse_records(&format!("{record}\n\n{record}\n")).unwrap();
+        assert_eq!(from_lines.len(), 2);
+        let from_array = parse_records(&format!("[{record}]")).unwrap();
+        assert_eq!(from_array[0], from_lines[0]);
+        let spec = from_array[0].to_spec();
+        assert_eq!(spec.duration_ms, 60_000);
+        assert_eq!(spec.name.as_deref(), Some("renewal"));
+        assert!(from_array[0].is_pending());
+
+        assert!(matches!(
+            parse_records(&format!("{record}\n{{\"id\": 1}}")),
+            Err(LegacyImportError::MalformedLine { line: 2, .. })
+        ));
+    }
+}
@@ -1,27 +1,228 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::{sync::broadcast, sync::RwLock};
-use tracing::Instrument;
+use tokio::{sync::broadcast, sync::oneshot, sync::watch};
 use uuid::Uuid;
 
+#[cfg(feature = "grpc")]
 pub mod pb {
     tonic::include_proto!("minoots.timer.v1");
+
+    /// Serialized `FileDescriptorSet` of `timer.proto`, emitted by the build.
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("minoots_timer_descriptor");
+    /// OpenAPI 3 document derived from [`FILE_DESCRIPTOR_SET`] by the build.
+    pub const OPENAPI_JSON: &str = include_str!(concat!(env!("OUT_DIR"), "/minoots_timer_openapi.json"));
+
+    /// `minoots.admin.v1`, served by [`crate::admin::AdminService`].
+    pub mod admin {
+        tonic::include_proto!("minoots.admin.v1");
+    }
+
+    /// Serialized `FileDescriptorSet` of `admin.proto` and the kernel proto it imports.
+    pub const ADMIN_FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("minoots_admin_descriptor");
+
+    /// `grpc.health.v1`, served by [`crate::health::HealthService`].
+    pub mod health {
+        tonic::include_proto!("grpc.health.v1");
+    }
+
+    /// `grpc.reflection.v1`, served by [`crate::reflection::ReflectionService`].
+    pub mod reflection {
+        tonic::include_proto!("grpc.reflection.v1");
+    }
+
+    /// Serialized `FileDescriptorSet` of the health and reflection protos.
+    pub const STANDARD_FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("grpc_standard_descriptor");
 }
 
+#[cfg(feature = "grpc")]
+pub mod admin;
+pub mod alarms;
+pub mod auth;
+pub mod backpressure;
+pub mod clock;
+pub mod delivery;
+mod dispatch;
+pub mod eviction;
+pub mod fanout;
+#[cfg(feature = "grpc")]
 pub mod grpc;
+#[cfg(feature = "grpc")]
+pub mod health;
+pub mod history;
+pub mod invariants;
+#[cfg(feature = "grpc")]
+pub mod jwt;
+#[cfg(feature = "kubernetes")]
+pub mod kubernetes;
+pub mod legacy;
+pub mod limits;
+pub mod manifest;
+pub mod metering;
+pub mod ops;
+pub mod pagination;
+#[cfg(feature = "embedded-orchestrator")]
+pub mod orchestrator;
+pub mod persistence;
+pub mod query;
+pub mod quota;
+#[cfg(feature = "grpc")]
+pub mod reflection;
+#[cfg(feature = "grpc")]
+pub mod rate_limit;
+#[cfg(feature = "grpc")]
+pub mod rest;
+pub mod shutdown;
+mod shards;
+pub mod sinks;
+pub mod slo;
+pub mod streams;
+#[cfg(feature = "grpc")]
+pub mod standby;
+pub mod tail;
+pub mod tasks;
+pub mod tenancy;
+
+pub use alarms::{AlarmConfig, RateAlarms, SchedulingOp, Spike};
+pub use auth::{ApiToken, CredentialVerifier, Principal, Scope, StoredApiToken, TokenStore};
+pub use backpressure::{BackpressureConfig, BackpressureMetrics, BackpressureStats};
+pub use clock::{Clock, ClockJump, MockClock, SystemClock};
+use clock::DriftDetector;
+pub use delivery::{DeliveryLedger, DeliveryReceipt, MAX_RECEIPTS_PER_SUBSCRIBER, MAX_SUBSCRIBERS};
+use dispatch::{EntryKind, TimerQueue};
+use shards::{AllShards, TimerMap, TimerShards};
+pub use eviction::TerminalRetention;
+use fanout::EventFanout;
+pub use fanout::{DropPolicy, EventSubscription, FanoutStats, SubscriberBuffer};
+use manifest::ManifestStep;
+pub use history::{EventHistory, EventQuery, EventRetention, RecordedEvent};
+pub use invariants::{RecoveredStuckTimer, RecoveryAction, StuckTimerMetrics, StuckTimerStats};
+pub use legacy::{ImportReport, LegacyImportError, LegacyTimerRecord};
+pub use limits::SpecLimits;
+pub use manifest::{ApplyReport, ManifestError, TimerManifest, MANIFEST_LABEL};
+pub use metering::{UsageCounters, UsageMeter, UsageRecord, DEFAULT_USAGE_RETENTION};
+pub use ops::{SystemEvent, SystemEventKind, SYSTEM_SUBJECT};
+pub use pagination::{
+    ListSnapshots, PageRequest, PageTokenError, TimerPage, MAX_SNAPSHOTS_PER_TENANT, MAX_SNAPSHOT_TIMERS,
+};
+pub use persistence::{
+    AuthStore, BatchingTimerStore, DeliveryStore, FencedWrite, FileAuthStore, FileDeliveryStore, FileStoreOptions,
+    FileTenantPolicyStore, FileTimerStore, FileUsageStore, FireGapEntry, FireGapReport, GapOutcome, InMemoryAuthStore,
+    InMemoryDeliveryStore, InMemoryTenantPolicyStore, InMemoryTimerStore, InMemoryUsageStore, MissedFirePolicy,
+    StoreError, TenantPolicyStore, TimerStore, UsageStore, WriteBatchConfig,
+};
+pub use query::{EventFilter, QueryError, TimerFilter, TimerOrder};
+pub use quota::{QuotaKind, QuotaViolation, RequestRateLimiter, ScheduleRateLimiter, TenantQuota};
+pub use shutdown::{DrainReport, ShutdownCoordinator, StopOutcome};
+pub use sinks::{
+    DeadLetter, DeadLetterQueue, DeadLetterStats, EventSink, EventWebhook, RetryPolicy, SinkError, WebhookError,
+    WebhookRegistry,
+};
+pub use slo::{BudgetOutcome, BudgetStats, BudgetTracker};
+pub use streams::{
+    Admission, StreamGovernor, StreamLimits, StreamMeter, StreamMetrics, SubscriberStats, ThrottleNotice,
+    ThrottleReason,
+};
+pub use tasks::{TaskCounts, TaskInfo, TaskRegistry, TaskReport, TaskState};
+pub use tenancy::{TenantDirectory, TenantPolicy, TenantScope};
 
 #[derive(Clone, Debug)]
 pub struct SchedulerConfig {
     pub max_duration_ms: Option<u64>,
+    /// Initial organization/project hierarchy and per-tenant policies.
+    pub tenants: TenantDirectory,
+    pub id_scheme: TimerIdScheme,
+    pub limits: SpecLimits,
+    /// Start as a warm standby: timers are hydrated through [`HorologyKernel::apply`] and their
+    /// sleep tasks armed, but nothing fires and schedules are rejected until
+    /// [`HorologyKernel::promote`].
+    pub standby: bool,
+    /// Per-tenant schedule/cancel spike detection and optional auto-throttling.
+    pub alarms: AlarmConfig,
+    /// Default handling of timers that came due while the kernel was down; timers may override it.
+    pub missed_fire_policy: MissedFirePolicy,
+    /// Default per-tenant quotas; a tenant's [`TenantPolicy::quota`] overrides them.
+    pub quotas: TenantQuota,
+    /// Caps applied to each timer event stream subscriber.
+    pub stream_limits: StreamLimits,
+    /// Hold fires while event consumers lag; off when `None`.
+    pub backpressure: Option<BackpressureConfig>,
+    /// Retained per-tenant event log behind [`HorologyKernel::query_events`]; off when `None`.
+    pub event_history: Option<EventHistory>,
+    /// Finished timers kept in memory by [`HorologyKernel::evict_terminal_timers`]; all of them
+    /// when `None`.
+    pub terminal_retention: Option<TerminalRetention>,
+    /// Time source for timestamps and the dispatch loop; tests swap in a [`MockClock`].
+    pub clock: Arc<dyn Clock>,
+    /// Wall-clock steps larger than this re-arm pending timers against the new wall time; fires
+    /// are never checked against the wall clock when `None`.
+    pub drift_threshold: Option<Duration>,
+    /// Locks the in-memory timer table is split across, so operations on different timers run in
+    /// parallel.
+    pub timer_shards: usize,
+    /// Buffer each event subscriber gets unless it asks for its own.
+    pub subscriber_buffer: SubscriberBuffer,
 }
 
 impl Default for SchedulerConfig {
     fn default() -> Self {
         Self {
             max_duration_ms: Some(1000 * 60 * 60 * 24 * 30), // 30 days
+            tenants: TenantDirectory::default(),
+            id_scheme: TimerIdScheme::default(),
+            limits: SpecLimits::default(),
+            standby: false,
+            alarms: AlarmConfig::default(),
+            missed_fire_policy: MissedFirePolicy::default(),
+            quotas: TenantQuota::default(),
+            stream_limits: StreamLimits::default(),
+            backpressure: None,
+            event_history: None,
+            terminal_retention: None,
+            clock: Arc::new(SystemClock),
+            drift_threshold: Some(Duration::from_secs(1)),
+            timer_shards: 16,
+            subscriber_buffer: SubscriberBuffer::default(),
+        }
+    }
+}
+
+/// How new timer ids are generated. `UuidV7` ids embed the creation time in their leading bits,
+/// so they sort by creation order (like ULIDs) while staying valid UUIDs on the wire.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerIdScheme {
+    #[default]
+    UuidV4,
+    UuidV7,
+}
+
+impl TimerIdScheme {
+    pub fn generate(self) -> Uuid {
+        match self {
+            TimerIdScheme::UuidV4 => Uuid::new_v4(),
+            TimerIdScheme::UuidV7 => Uuid::now_v7(),
+        }
+    }
+}
+
+impl std::str::FromStr for TimerIdScheme {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "v4" | "uuidv4" => Ok(TimerIdScheme::UuidV4),
+            "v7" | "uuidv7" => Ok(TimerIdScheme::UuidV7),
+            other => Err(format!("unknown timer id scheme `{other}`")),
         }
     }
 }
@@ -32,6 +233,60 @@ pub enum KernelError {
     InvalidDuration,
     #[error("fire_at must be in the future")]
     InvalidFireTime,
+    #[error("`{key}` {reason}")]
+    LimitExceeded { key: String, reason: String },
+    #[error("kernel is a standby and does not accept writes")]
+    Standby,
+    #[error("tenant {tenant_id} is throttled after a scheduling spike; retry in {retry_after_ms}ms")]
+    RateLimited {
+        tenant_id: String,
+        retry_after_ms: u64,
+    },
+    #[error(transparent)]
+    Store(#[from] StoreError),
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+    #[error("timer has already fired or been cancelled")]
+    NotPending,
+    #[error("only fired timers that no subscriber has acknowledged can be snoozed")]
+    NotSnoozable,
+    #[error("only watchdog timers can be kicked")]
+    NotWatchdog,
+    #[error("only deadline timers can be acknowledged")]
+    NotDeadline,
+    #[error("only fired timers can report an execution or be settled")]
+    NotFired,
+    #[error(transparent)]
+    PageToken(#[from] PageTokenError),
+    #[error(transparent)]
+    LegacyImport(#[from] LegacyImportError),
+    #[error("event history is not enabled on this kernel")]
+    EventHistoryDisabled,
+    #[error("events after sequence {after_sequence} are no longer retained (dropped through {trimmed_through})")]
+    EventsExpired {
+        after_sequence: u64,
+        trimmed_through: u64,
+    },
+    #[error("sequence {requested} is ahead of the latest event, {latest}")]
+    SequenceAhead { requested: u64, latest: u64 },
+    #[error("a batch may schedule at most {max} timers")]
+    BatchTooLarge { max: usize },
+    #[error("tenant {tenant_id} exceeded its {quota} quota of {limit}")]
+    QuotaExceeded {
+        tenant_id: String,
+        quota: QuotaKind,
+        limit: u64,
+    },
+    #[error("timer is at state version {actual}, not the expected {expected}")]
+    StateVersionConflict { expected: u64, actual: u64 },
+}
+
+/// New fire time for [`HorologyKernel::reschedule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reschedule {
+    FireAt(DateTime<Utc>),
+    /// Shifts the current fire time; a negative delta pulls it in.
+    Delta(chrono::Duration),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -41,44 +296,386 @@ pub enum TimerStatus {
     Armed,
     Fired,
     Cancelled,
+    /// Came due during downtime and was skipped under its [`MissedFirePolicy`].
+    Missed,
+    /// A deadline timer acknowledged before its deadline passed.
+    Acknowledged,
+    /// Ended without succeeding; `failure_reason` says why.
+    Failed,
+    /// Fired, and the orchestrator reported every action of its bundle succeeded.
+    Settled,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// How a timer comes due.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimerKind {
+    /// Fires once at `fire_at`.
+    #[default]
+    OneShot,
+    /// Dead-man switch: fires only if [`HorologyKernel::kick_watchdog`] is not called before
+    /// `fire_at`, and each kick moves `fire_at` to `interval_ms` after the kick.
+    Watchdog { interval_ms: u64 },
+    /// SLA deadline: must be settled with [`HorologyKernel::acknowledge`] by `fire_at` plus
+    /// `grace_ms`, or it fails with [`FailureReason::DeadlineExceeded`] instead of firing.
+    Deadline { grace_ms: u64 },
+}
+
+impl TimerKind {
+    /// How long after `fire_at` the timer comes due.
+    fn grace(self) -> Duration {
+        match self {
+            TimerKind::Deadline { grace_ms } => Duration::from_millis(grace_ms),
+            TimerKind::OneShot | TimerKind::Watchdog { .. } => Duration::ZERO,
+        }
+    }
+}
+
+/// Why a timer ended [`TimerStatus::Failed`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum FailureReason {
+    /// A deadline timer was not acknowledged within `grace_ms` of its `fire_at`.
+    DeadlineExceeded { grace_ms: u64 },
+    /// `failed` of the `total` actions the orchestrator ran for a fired timer failed; `error` is
+    /// the first failure's.
+    ActionsFailed {
+        failed: u32,
+        total: u32,
+        error: Option<String>,
+    },
+    /// An operator settled the fired timer as failed through the admin API.
+    FailedByOperator { reason: String },
+}
+
+impl FailureReason {
+    /// Stable machine-readable code, e.g. `deadline_exceeded`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            FailureReason::DeadlineExceeded { .. } => "deadline_exceeded",
+            FailureReason::ActionsFailed { .. } => "actions_failed",
+            FailureReason::FailedByOperator { .. } => "failed_by_operator",
+        }
+    }
+}
+
+impl std::fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailureReason::DeadlineExceeded { grace_ms } => {
+                write!(f, "not acknowledged within {grace_ms}ms of its deadline")
+            }
+            FailureReason::ActionsFailed {
+                failed,
+                total,
+                error,
+            } => {
+                write!(f, "{failed} of {total} actions failed")?;
+                match error {
+                    Some(error) => write!(f, ": {error}"),
+                    None => Ok(()),
+                }
+            }
+            FailureReason::FailedByOperator { reason } => write!(f, "failed by an operator: {reason}"),
+        }
+    }
+}
+
+/// Outcome of one action of a fired timer's bundle, as reported by the orchestrator.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionReport {
+    pub action_id: String,
+    pub success: bool,
+    /// Time spent on the action, retries included.
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// What the orchestrator made of a fired timer's action bundle; see
+/// [`HorologyKernel::report_execution`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    pub actions: Vec<ActionReport>,
+    /// When the report reached the kernel.
+    pub completed_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TimerSpec {
     pub tenant_id: String,
     pub requested_by: String,
     pub name: Option<String>,
     pub duration_ms: u64,
+    /// Microsecond duration; takes precedence over `duration_ms` when set.
+    #[serde(default)]
+    pub duration_us: Option<u64>,
     pub fire_at: Option<DateTime<Utc>>,
     pub metadata: Option<serde_json::Value>,
     pub labels: HashMap<String, String>,
     pub action_bundle: Option<serde_json::Value>,
     pub agent_binding: Option<serde_json::Value>,
+    /// Maximum acceptable delay between `fire_at` and the actual fire, for SLO reporting.
+    #[serde(default)]
+    pub accuracy_budget_ms: Option<u64>,
+    /// Emit [`TimerEvent::PreFire`] this long before `fire_at`.
+    #[serde(default)]
+    pub pre_fire_notice_ms: Option<u64>,
+    /// Client-chosen key; re-sending it for the same tenant returns the timer it first created.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Overrides [`SchedulerConfig::missed_fire_policy`] for this timer.
+    #[serde(default)]
+    pub missed_fire_policy: Option<MissedFirePolicy>,
+    /// Agent session the timer belongs to; [`HorologyKernel::revoke_session`] cancels them together.
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub kind: TimerKind,
+    /// Timers that come due together fire, persist, and emit their events in descending priority.
+    #[serde(default)]
+    pub priority: u32,
+}
+
+/// Current serialized layout of [`TimerInstance`]. Bump when a change cannot be expressed with
+/// serde defaults and add a fixture for the previous version under `tests/fixtures`.
+pub const TIMER_SCHEMA_VERSION: u32 = 1;
+
+/// Most specs [`HorologyKernel::schedule_batch`] accepts in one call.
+pub const MAX_BATCH_SIZE: usize = 1_000;
+
+fn initial_schema_version() -> u32 {
+    1
+}
+
+/// How many timers a tenant holds in each status.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TimerCounts {
+    pub scheduled: u64,
+    pub armed: u64,
+    pub fired: u64,
+    pub cancelled: u64,
+    pub missed: u64,
+    pub acknowledged: u64,
+    pub failed: u64,
+    pub settled: u64,
+}
+
+impl TimerCounts {
+    pub fn add(&mut self, status: &TimerStatus) {
+        let count = match status {
+            TimerStatus::Scheduled => &mut self.scheduled,
+            TimerStatus::Armed => &mut self.armed,
+            TimerStatus::Fired => &mut self.fired,
+            TimerStatus::Cancelled => &mut self.cancelled,
+            TimerStatus::Missed => &mut self.missed,
+            TimerStatus::Acknowledged => &mut self.acknowledged,
+            TimerStatus::Failed => &mut self.failed,
+            TimerStatus::Settled => &mut self.settled,
+        };
+        *count += 1;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.scheduled
+            + self.armed
+            + self.fired
+            + self.cancelled
+            + self.missed
+            + self.acknowledged
+            + self.failed
+            + self.settled
+    }
+}
+
+/// Aggregate view of a tenant's timers for dashboards, see [`HorologyKernel::timer_stats`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TimerStats {
+    pub counts: TimerCounts,
+    /// Soonest and furthest fire among scheduled and armed timers; `None` when none are pending.
+    pub next_fire_at: Option<DateTime<Utc>>,
+    pub horizon: Option<DateTime<Utc>>,
+    /// Counts per `(label key, value)`, for the requested label keys.
+    pub labels: BTreeMap<(String, String), TimerCounts>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TimerInstance {
+    /// Layout version the instance was written with; payloads predating the tag are version 1.
+    #[serde(default = "initial_schema_version")]
+    pub schema_version: u32,
     pub id: Uuid,
     pub tenant_id: String,
     pub requested_by: String,
     pub name: String,
     pub duration_ms: u64,
+    /// Set when the timer was requested with sub-millisecond resolution (`duration_us` or a
+    /// `fire_at` with sub-millisecond digits); such timers fire on a precise final approach.
+    #[serde(default)]
+    pub duration_us: Option<u64>,
     pub created_at: DateTime<Utc>,
     pub fire_at: DateTime<Utc>,
     pub status: TimerStatus,
+    #[serde(default)]
     pub metadata: Option<serde_json::Value>,
+    #[serde(default)]
     pub labels: HashMap<String, String>,
+    #[serde(default)]
     pub action_bundle: Option<serde_json::Value>,
+    #[serde(default)]
     pub agent_binding: Option<serde_json::Value>,
+    #[serde(default)]
     pub fired_at: Option<DateTime<Utc>>,
+    #[serde(default)]
     pub cancelled_at: Option<DateTime<Utc>>,
+    #[serde(default)]
     pub cancel_reason: Option<String>,
+    #[serde(default)]
     pub cancelled_by: Option<String>,
+    #[serde(default)]
+    pub accuracy_budget_ms: Option<u64>,
+    /// Delay between `fire_at` and the actual fire.
+    #[serde(default)]
+    pub fire_latency_ms: Option<u64>,
+    /// Same delay in microseconds, reporting the precision actually achieved.
+    #[serde(default)]
+    pub fire_latency_us: Option<u64>,
+    #[serde(default)]
+    pub budget_outcome: Option<BudgetOutcome>,
+    #[serde(default)]
+    pub pre_fire_notice_ms: Option<u64>,
+    /// `fire_at` whose pre-fire notice already went out, so re-arming the same fire (e.g. after a
+    /// wall-clock step) does not send a second one.
+    #[serde(default)]
+    pub pre_fire_sent_for: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    #[serde(default)]
+    pub missed_fire_policy: Option<MissedFirePolicy>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Times the timer was re-armed with [`HorologyKernel::snooze`] after firing.
+    #[serde(default)]
+    pub snooze_count: u32,
+    #[serde(default)]
+    pub kind: TimerKind,
+    /// Last [`HorologyKernel::kick_watchdog`] call on a watchdog timer.
+    #[serde(default)]
+    pub last_kicked_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub priority: u32,
+    /// When a deadline timer was acknowledged.
+    #[serde(default)]
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub failed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub failure_reason: Option<FailureReason>,
+    /// When a fired timer's execution report settled it.
+    #[serde(default)]
+    pub settled_at: Option<DateTime<Utc>>,
+    /// Per-action results of a fired timer, once the orchestrator reported them.
+    #[serde(default)]
+    pub execution: Option<ExecutionReport>,
+    /// Starts at 1 and increases with every state change, so a caller can make a cancel or
+    /// reschedule conditional on the copy it last read. Timers persisted before it existed read as 0.
+    #[serde(default)]
+    pub state_version: u64,
 }
 
 impl TimerInstance {
+    /// A pending timer for `spec`, created at `created_at` and due `duration` later at `fire_at`.
+    fn from_spec(
+        spec: &TimerSpec,
+        id: Uuid,
+        created_at: DateTime<Utc>,
+        fire_at: DateTime<Utc>,
+        duration: Duration,
+    ) -> Self {
+        let precise = spec.duration_us.is_some()
+            || spec
+                .fire_at
+                .is_some_and(|at| at.timestamp_subsec_nanos() % 1_000_000 != 0);
+        TimerInstance {
+            schema_version: TIMER_SCHEMA_VERSION,
+            id,
+            tenant_id: spec.tenant_id.clone(),
+            requested_by: spec.requested_by.clone(),
+            name: spec
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("timer-{}", created_at.timestamp_millis())),
+            duration_ms: duration.as_millis() as u64,
+            duration_us: precise.then_some(duration.as_micros() as u64),
+            created_at,
+            fire_at,
+            status: TimerStatus::Scheduled,
+            metadata: spec.metadata.clone(),
+            labels: spec.labels.clone(),
+            action_bundle: spec.action_bundle.clone(),
+            agent_binding: spec.agent_binding.clone(),
+            fired_at: None,
+            cancelled_at: None,
+            cancel_reason: None,
+            cancelled_by: None,
+            accuracy_budget_ms: spec.accuracy_budget_ms,
+            fire_latency_ms: None,
+            fire_latency_us: None,
+            budget_outcome: None,
+            pre_fire_notice_ms: spec.pre_fire_notice_ms,
+            pre_fire_sent_for: None,
+            idempotency_key: spec.idempotency_key.clone(),
+            missed_fire_policy: spec.missed_fire_policy,
+            session_id: spec.session_id.clone(),
+            snooze_count: 0,
+            kind: spec.kind,
+            last_kicked_at: None,
+            priority: spec.priority,
+            acknowledged_at: None,
+            failed_at: None,
+            failure_reason: None,
+            settled_at: None,
+            execution: None,
+            state_version: 1,
+        }
+    }
+
+    /// Fails when the caller expected another version than the timer's current one.
+    fn check_state_version(&self, expected: Option<u64>) -> Result<(), KernelError> {
+        match expected {
+            Some(expected) if expected != self.state_version => Err(KernelError::StateVersionConflict {
+                expected,
+                actual: self.state_version,
+            }),
+            _ => Ok(()),
+        }
+    }
+
     fn is_terminal(&self) -> bool {
-        matches!(self.status, TimerStatus::Fired | TimerStatus::Cancelled)
+        matches!(
+            self.status,
+            TimerStatus::Fired
+                | TimerStatus::Cancelled
+                | TimerStatus::Missed
+                | TimerStatus::Acknowledged
+                | TimerStatus::Failed
+                | TimerStatus::Settled
+        )
+    }
+
+    /// When a terminal timer finished; missed timers have no timestamp of their own.
+    fn finished_at(&self) -> DateTime<Utc> {
+        self.fired_at
+            .or(self.cancelled_at)
+            .or(self.acknowledged_at)
+            .or(self.failed_at)
+            .unwrap_or(self.fire_at)
+    }
+
+    /// When the dispatch loop acts on the timer: `fire_at`, or the end of a deadline's grace window.
+    fn due_at(&self) -> DateTime<Utc> {
+        self.fire_at + chrono::Duration::from_std(self.kind.grace()).unwrap_or_default()
     }
 }
 
@@ -86,189 +683,2187 @@ impl TimerInstance {
 #[serde(tag = "type", content = "data")]
 pub enum TimerEvent {
     Scheduled(TimerInstance),
+    /// Heads-up emitted `pre_fire_notice_ms` before the fire, or straight away when the timer was
+    /// scheduled with less lead time than the notice.
+    PreFire {
+        timer: TimerInstance,
+        fires_in_ms: u64,
+    },
     Fired(TimerInstance),
     Cancelled {
         timer: TimerInstance,
         reason: Option<String>,
     },
+    /// The timer came due during downtime and was skipped rather than fired late.
+    Missed {
+        timer: TimerInstance,
+        late_by_ms: u64,
+    },
+    /// The timer's fire time moved; `timer` carries the new `fire_at`.
+    Rescheduled {
+        timer: TimerInstance,
+        previous_fire_at: DateTime<Utc>,
+    },
+    /// A deadline timer was acknowledged in time.
+    Acknowledged(TimerInstance),
+    /// A deadline timer passed its grace window unacknowledged, or the actions of a fired timer
+    /// failed; `timer` carries the `failure_reason`.
+    Failed(TimerInstance),
+    /// Every action of a fired timer succeeded; `timer` carries the `execution` report.
+    Settled(TimerInstance),
 }
 
-#[derive(Clone)]
-struct KernelState {
-    timers: Arc<RwLock<HashMap<Uuid, TimerInstance>>>,
-    event_tx: broadcast::Sender<TimerEvent>,
-    config: SchedulerConfig,
+impl TimerEvent {
+    /// Snake-case event type, e.g. `pre_fire`; the names [`EventQuery::kinds`] filters on.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TimerEvent::Scheduled(_) => "scheduled",
+            TimerEvent::PreFire { .. } => "pre_fire",
+            TimerEvent::Fired(_) => "fired",
+            TimerEvent::Cancelled { .. } => "cancelled",
+            TimerEvent::Missed { .. } => "missed",
+            TimerEvent::Rescheduled { .. } => "rescheduled",
+            TimerEvent::Acknowledged(_) => "acknowledged",
+            TimerEvent::Failed(_) => "failed",
+            TimerEvent::Settled(_) => "settled",
+        }
+    }
+
+    pub fn timer(&self) -> &TimerInstance {
+        match self {
+            TimerEvent::Scheduled(timer)
+            | TimerEvent::Fired(timer)
+            | TimerEvent::Acknowledged(timer)
+            | TimerEvent::Failed(timer)
+            | TimerEvent::Settled(timer)
+            | TimerEvent::PreFire { timer, .. }
+            | TimerEvent::Cancelled { timer, .. }
+            | TimerEvent::Missed { timer, .. }
+            | TimerEvent::Rescheduled { timer, .. } => timer,
+        }
+    }
 }
 
-#[derive(Clone)]
-pub struct HorologyKernel {
-    state: KernelState,
+/// Every [`TimerEvent::kind`].
+pub const EVENT_KINDS: &[&str] = &[
+    "scheduled",
+    "pre_fire",
+    "fired",
+    "cancelled",
+    "missed",
+    "rescheduled",
+    "acknowledged",
+    "failed",
+    "settled",
+];
+
+/// Terminal outcome of a timer, resolved by [`HorologyKernel::wait`].
+#[derive(Clone, Debug)]
+pub enum TimerOutcome {
+    Fired(TimerInstance),
+    Cancelled(TimerInstance),
+    Missed(TimerInstance),
+    Acknowledged(TimerInstance),
+    Failed(TimerInstance),
+    NotFound,
 }
 
-impl HorologyKernel {
-    pub fn new(config: SchedulerConfig) -> Self {
-        let (event_tx, _rx) = broadcast::channel(1024);
-        Self {
-            state: KernelState {
-                timers: Arc::new(RwLock::new(HashMap::new())),
-                event_tx,
-                config,
-            },
+impl TimerOutcome {
+    fn from_terminal(timer: TimerInstance) -> Self {
+        match timer.status {
+            TimerStatus::Cancelled => TimerOutcome::Cancelled(timer),
+            TimerStatus::Missed => TimerOutcome::Missed(timer),
+            TimerStatus::Acknowledged => TimerOutcome::Acknowledged(timer),
+            TimerStatus::Failed => TimerOutcome::Failed(timer),
+            _ => TimerOutcome::Fired(timer),
         }
     }
+}
 
-    pub fn subscribe(&self) -> broadcast::Receiver<TimerEvent> {
-        self.state.event_tx.subscribe()
-    }
+type WaiterRegistry = Arc<Mutex<HashMap<Uuid, Vec<oneshot::Sender<TimerOutcome>>>>>;
+/// Timer created for each `(tenant_id, idempotency_key)`.
+type IdempotencyIndex = Arc<Mutex<HashMap<(String, String), Uuid>>>;
 
-    pub async fn schedule(&self, spec: TimerSpec) -> Result<TimerInstance, KernelError> {
-        let now = Utc::now();
-        let delay = if let Some(ts) = spec.fire_at {
-            if ts <= now {
-                return Err(KernelError::InvalidFireTime);
-            }
-            (ts - now)
-                .to_std()
-                .map_err(|_| KernelError::InvalidFireTime)?
-        } else {
-            if spec.duration_ms == 0 {
-                return Err(KernelError::InvalidDuration);
-            }
-            Duration::from_millis(spec.duration_ms)
-        };
+#[derive(Clone)]
+struct KernelState {
+    timers: TimerShards,
+    waiters: WaiterRegistry,
+    idempotency: IdempotencyIndex,
+    tenants: Arc<Mutex<TenantDirectory>>,
+    usage: UsageMeter,
+    budgets: BudgetTracker,
+    alarms: RateAlarms,
+    schedule_rates: ScheduleRateLimiter,
+    deliveries: DeliveryLedger,
+    tokens: TokenStore,
+    events: EventFanout,
+    /// Sequence of the latest published event, held while publishing so every subscriber receives
+    /// events in sequence order.
+    sequence: Arc<Mutex<u64>>,
+    system_tx: broadcast::Sender<SystemEvent>,
+    /// `false` while the kernel is a warm standby.
+    active: Arc<watch::Sender<bool>>,
+    store: Arc<dyn TimerStore>,
+    gap_report: Arc<Mutex<Option<FireGapReport>>>,
+    queue: TimerQueue,
+    tasks: TaskRegistry,
+    snapshots: ListSnapshots,
+    streams: StreamMetrics,
+    backpressure: BackpressureMetrics,
+    dead_letters: DeadLetterQueue,
+    webhooks: WebhookRegistry,
+    /// The running dispatch loop, if one has been started.
+    dispatch: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    stuck: StuckTimerMetrics,
+    /// Leadership term fires are recorded under; 0 until one is set, leaving fires unfenced.
+    fencing_token: Arc<AtomicU64>,
+    /// Whether the last store write succeeded.
+    store_healthy: Arc<AtomicBool>,
+    /// The latest system event that changed leadership.
+    leadership: Arc<Mutex<Option<SystemEvent>>>,
+    drift: DriftDetector,
+    config: SchedulerConfig,
+}
 
-        let duration_ms = delay.as_millis() as u64;
-        if let Some(max) = self.state.config.max_duration_ms {
-            if duration_ms > max {
-                return Err(KernelError::InvalidDuration);
-            }
-        }
+impl KernelState {
+    fn now(&self) -> DateTime<Utc> {
+        self.config.clock.now()
+    }
 
-        let chrono_delay =
-            chrono::Duration::from_std(delay).map_err(|_| KernelError::InvalidFireTime)?;
-        let fire_at = spec.fire_at.unwrap_or_else(|| now + chrono_delay);
+    /// Resolves every pending waiter for a timer that just reached a terminal state.
+    fn notify_waiters(&self, timer: &TimerInstance) {
+        let waiters = self
+            .waiters
+            .lock()
+            .expect("waiter registry poisoned")
+            .remove(&timer.id);
+        for waiter in waiters.into_iter().flatten() {
+            let _ = waiter.send(TimerOutcome::from_terminal(timer.clone()));
+        }
+    }
 
-        let timer = TimerInstance {
-            id: Uuid::new_v4(),
-            tenant_id: spec.tenant_id.clone(),
-            requested_by: spec.requested_by.clone(),
-            name: spec
-                .name
-                .unwrap_or_else(|| format!("timer-{}", now.timestamp_millis())),
-            duration_ms,
-            created_at: now,
-            fire_at,
-            status: TimerStatus::Scheduled,
-            metadata: spec.metadata.clone(),
-            labels: spec.labels.clone(),
-            action_bundle: spec.action_bundle.clone(),
-            agent_binding: spec.agent_binding.clone(),
-            fired_at: None,
-            cancelled_at: None,
-            cancel_reason: None,
-            cancelled_by: None,
+    /// Numbers the event, records it in the retained history, if any, and buffers it for every
+    /// subscriber.
+    fn publish(&self, event: TimerEvent) {
+        let mut sequence = self.sequence.lock().expect("event sequence poisoned");
+        *sequence += 1;
+        let recorded = RecordedEvent {
+            sequence: *sequence,
+            recorded_at: self.now(),
+            event,
         };
-
-        {
-            let mut timers = self.state.timers.write().await;
-            timers.insert(timer.id, timer.clone());
+        if let Some(history) = &self.config.event_history {
+            history.record(&recorded);
         }
+        self.events.publish(&recorded);
+    }
 
-        let _ = self
-            .state
-            .event_tx
-            .send(TimerEvent::Scheduled(timer.clone()));
+    fn emit_system(&self, kind: SystemEventKind) {
+        let event = SystemEvent::new(kind, self.now());
+        if event.kind.is_leadership_change() {
+            *self.leadership.lock().expect("leadership record poisoned") = Some(event.clone());
+        }
+        let _ = self.system_tx.send(event);
+    }
 
-        self.spawn_fire_task(timer.clone());
+    fn idempotency(&self) -> std::sync::MutexGuard<'_, HashMap<(String, String), Uuid>> {
+        self.idempotency.lock().expect("idempotency index poisoned")
+    }
 
-        Ok(timer)
+    /// Indexes the timer under its idempotency key, if it has one.
+    fn remember_key(&self, timer: &TimerInstance) {
+        if let Some(key) = &timer.idempotency_key {
+            self.idempotency()
+                .insert((timer.tenant_id.clone(), key.clone()), timer.id);
+        }
     }
 
-    pub async fn cancel(
+    /// The timer previously created with this tenant's idempotency key.
+    fn replay<G: std::ops::Deref<Target = TimerMap>>(
         &self,
+        timers: &AllShards<G>,
         tenant_id: &str,
-        timer_id: Uuid,
-        reason: Option<String>,
-        cancelled_by: Option<String>,
+        key: Option<&String>,
     ) -> Option<TimerInstance> {
-        let mut timers = self.state.timers.write().await;
-        let entry = timers.get_mut(&timer_id)?;
-        if entry.tenant_id != tenant_id {
-            return None;
-        }
+        let id = *self
+            .idempotency()
+            .get(&(tenant_id.to_string(), key?.clone()))?;
+        timers.get(&id).cloned()
+    }
 
-        if entry.is_terminal() {
-            return Some(entry.clone());
+    /// Feeds the spike detector and raises `scheduling_spike` when a tenant's rate jumps.
+    fn record_rate(&self, tenant_id: &str, operation: SchedulingOp, at: DateTime<Utc>) {
+        if let Some(spike) = self.alarms.record(tenant_id, operation, at) {
+            tracing::warn!(
+                tenant_id,
+                operation = operation.name(),
+                per_minute = spike.per_minute,
+                baseline_per_minute = spike.baseline_per_minute,
+                throttled = spike.throttled,
+                "tenant scheduling spike"
+            );
+            self.emit_system(SystemEventKind::SchedulingSpike {
+                tenant_id: spike.tenant_id,
+                operation: operation.name().to_string(),
+                per_minute: spike.per_minute,
+                baseline_per_minute: spike.baseline_per_minute.round() as u64,
+                throttled: spike.throttled,
+            });
         }
+    }
 
-        entry.status = TimerStatus::Cancelled;
-        entry.cancelled_at = Some(Utc::now());
-        entry.cancel_reason = reason.clone();
-        entry.cancelled_by = cancelled_by;
-        let snapshot = entry.clone();
-        drop(timers);
-
-        let _ = self.state.event_tx.send(TimerEvent::Cancelled {
-            timer: snapshot.clone(),
-            reason,
+    /// Queues a timer's fire (and pre-fire notice, unless already sent for this `fire_at`) `delay`
+    /// from now, when it is due (see [`TimerInstance::due_at`]), starting the dispatch loop on first
+    /// use.
+    fn arm(&self, timer: &TimerInstance, delay: Duration) {
+        let deadline = self.config.clock.instant() + delay;
+        let precise = timer.duration_us.is_some();
+        let notice = timer
+            .pre_fire_notice_ms
+            .map(Duration::from_millis)
+            .filter(|_| !delay.is_zero() && timer.pre_fire_sent_for != Some(timer.fire_at));
+        let pre_fire = notice.map(|notice| {
+            let lead = notice.min(delay);
+            let kind = EntryKind::PreFire {
+                lead,
+                fire_deadline: deadline,
+            };
+            (deadline - lead, kind, false)
         });
-        Some(snapshot)
+        // Replaces whatever was queued for the timer, so re-arming never piles up entries.
+        self.queue.push_all(
+            timer.id,
+            timer.fire_at,
+            timer.priority,
+            pre_fire
+                .into_iter()
+                .chain([(deadline, EntryKind::Fire, precise)]),
+        );
+        self.start_dispatch();
     }
 
-    pub async fn get(&self, tenant_id: &str, timer_id: Uuid) -> Option<TimerInstance> {
-        let timers = self.state.timers.read().await;
-        timers
-            .get(&timer_id)
-            .filter(|t| t.tenant_id == tenant_id)
-            .cloned()
+    /// Starts the dispatch loop unless one is running.
+    fn start_dispatch(&self) {
+        if self.queue.claim_driver() {
+            let handle = self.tasks.spawn("timer-dispatch", self.clone().drive());
+            *self.dispatch.lock().expect("dispatch handle poisoned") = Some(handle);
+        }
     }
 
-    pub async fn list(&self, tenant_id: &str) -> Vec<TimerInstance> {
-        let timers = self.state.timers.read().await;
-        let mut timers: Vec<_> = timers
-            .values()
-            .filter(|t| t.tenant_id == tenant_id)
-            .cloned()
-            .collect();
-        timers.sort_by_key(|t| t.fire_at);
-        timers
+    /// Single dispatch loop: sleeps until the earliest queued deadline and fires everything due.
+    async fn drive(self) {
+        loop {
+            let Some(next) = self.queue.peek() else {
+                self.queue.changed().await;
+                continue;
+            };
+            let reached = async {
+                if next.precise {
+                    self.config.clock.sleep_until_precisely(next.deadline).await;
+                } else {
+                    self.config.clock.sleep_until(next.deadline).await;
+                }
+            };
+            tokio::select! {
+                _ = reached => {}
+                // An earlier deadline may have been queued; re-evaluate the head.
+                _ = self.queue.changed() => continue,
+            }
+            // Standbys keep timers queued but hold fires until promotion.
+            if self.active.subscribe().wait_for(|active| *active).await.is_err() {
+                return;
+            }
+            for entry in self.queue.pop_due(self.config.clock.instant()) {
+                match entry.kind {
+                    EntryKind::Fire => {
+                        if self.defer_early_fire(entry.timer_id, entry.fire_at).await {
+                            continue;
+                        }
+                        self.hold_for_consumers(entry.timer_id).await;
+                        self.fire(entry.timer_id, entry.fire_at).await
+                    }
+                    // A notice that comes due together with its fire (e.g. after promotion) is moot.
+                    EntryKind::PreFire {
+                        lead,
+                        fire_deadline,
+                    } if self.config.clock.instant() < fire_deadline => {
+                        self.emit_pre_fire(entry.timer_id, entry.fire_at, lead).await
+                    }
+                    EntryKind::PreFire { .. } => {}
+                }
+            }
+        }
     }
 
-    fn spawn_fire_task(&self, timer: TimerInstance) {
-        let state = self.state.clone();
-        let span = tracing::info_span!("timer_fire_task", timer_id = %timer.id, tenant_id = %timer.tenant_id);
-        tokio::spawn(
-            async move {
-                let duration = Duration::from_millis(timer.duration_ms);
-                tokio::time::sleep(duration).await;
+    /// Re-arms a fire whose monotonic deadline came up while the wall clock still puts it more than
+    /// the drift threshold in the future, i.e. the wall clock stepped back after it was armed.
+    async fn defer_early_fire(&self, timer_id: Uuid, fire_at: DateTime<Utc>) -> bool {
+        let Some(threshold) = self.config.drift_threshold else {
+            return false;
+        };
+        let timers = self.timers.shard(&timer_id).read().await;
+        let Some(timer) = timers
+            .get(&timer_id)
+            .filter(|timer| !timer.is_terminal() && timer.fire_at == fire_at)
+        else {
+            return false;
+        };
+        let remaining = (timer.due_at() - self.now()).to_std().unwrap_or_default();
+        if remaining <= threshold {
+            return false;
+        }
+        tracing::warn!(
+            timer_id = %timer_id,
+            remaining_ms = remaining.as_millis() as u64,
+            "wall clock is behind the timer's deadline; deferring its fire"
+        );
+        self.arm(timer, remaining);
+        true
+    }
 
-                let mut timers = state.timers.write().await;
-                let entry = match timers.get_mut(&timer.id) {
-                    Some(entry) => entry,
-                    None => return,
-                };
+    /// With backpressure on, holds a due fire while event consumers lag, for at most the timer's
+    /// allowance.
+    async fn hold_for_consumers(&self, timer_id: Uuid) {
+        let Some(config) = self.config.backpressure else {
+            return;
+        };
+        let lag = self.events.stats().max_buffered;
+        self.backpressure.observe_lag(lag);
+        if lag < config.high_watermark {
+            return;
+        }
+        let allowance = match self.timers.shard(&timer_id).read().await.get(&timer_id) {
+            Some(timer) if !timer.is_terminal() => {
+                config.allowance(timer.due_at(), timer.accuracy_budget_ms, self.now())
+            }
+            _ => return,
+        };
+        let started = self.config.clock.instant();
+        let deadline = started + allowance;
+        let released = loop {
+            let now = self.config.clock.instant();
+            if now >= deadline {
+                break false;
+            }
+            let poll = now + (deadline - now).min(backpressure::POLL_INTERVAL);
+            self.config.clock.sleep_until(poll).await;
+            let lag = self.events.stats().max_buffered;
+            self.backpressure.observe_lag(lag);
+            if lag <= config.low_watermark {
+                break true;
+            }
+        };
+        let held = self.config.clock.instant() - started;
+        self.backpressure.record_hold(held, !released);
+        if !released {
+            tracing::warn!(
+                %timer_id,
+                held_ms = held.as_millis() as u64,
+                lag = self.events.stats().max_buffered,
+                "event consumers still lagging; firing at the end of the timer's allowance"
+            );
+        }
+    }
 
-                if entry.is_terminal() {
-                    return;
-                }
+    /// Fires the timer unless it reached a terminal state or was rescheduled away from `fire_at`.
+    /// A deadline timer that comes due was never acknowledged, so it fails instead.
+    async fn fire(&self, timer_id: Uuid, fire_at: DateTime<Utc>) {
+        let mut timers = self.timers.shard(&timer_id).write().await;
+        let entry = match timers.get_mut(&timer_id) {
+            Some(entry) if !entry.is_terminal() && entry.fire_at == fire_at => entry,
+            _ => return,
+        };
+        let fired_at = self.now();
+        let mut snapshot = entry.clone();
+        snapshot.state_version += 1;
+        if let TimerKind::Deadline { grace_ms } = snapshot.kind {
+            snapshot.status = TimerStatus::Failed;
+            snapshot.failed_at = Some(fired_at);
+            snapshot.failure_reason = Some(FailureReason::DeadlineExceeded { grace_ms });
+        } else {
+            let latency = fired_at - snapshot.fire_at;
+            let latency_ms = latency.num_milliseconds().max(0) as u64;
+            snapshot.status = TimerStatus::Fired;
+            snapshot.fired_at = Some(fired_at);
+            snapshot.fire_latency_ms = Some(latency_ms);
+            snapshot.fire_latency_us = latency.num_microseconds().map(|us| us.max(0) as u64);
+            snapshot.budget_outcome = snapshot
+                .accuracy_budget_ms
+                .map(|budget| self.budgets.record(&snapshot.tenant_id, latency_ms, budget));
+        }
+        if let Err(stored) = self.commit_fire(&snapshot).await {
+            let Some(stored) = stored else {
+                return;
+            };
+            *entry = stored.clone();
+            drop(timers);
+            // Fired elsewhere: adopt that outcome without emitting a second event.
+            if stored.is_terminal() {
+                self.notify_waiters(&stored);
+            } else {
+                let delay = (stored.due_at() - self.now()).to_std().unwrap_or_default();
+                self.arm(&stored, delay);
+            }
+            return;
+        }
+        *entry = snapshot.clone();
+        drop(timers);
 
-                entry.status = TimerStatus::Fired;
-                entry.fired_at = Some(Utc::now());
-                let snapshot = entry.clone();
-                drop(timers);
+        self.notify_waiters(&snapshot);
+        if snapshot.status == TimerStatus::Failed {
+            self.publish(TimerEvent::Failed(snapshot));
+        } else {
+            self.usage.record_fired(&snapshot.tenant_id);
+            self.publish(TimerEvent::Fired(snapshot));
+        }
+    }
 
-                let _ = state.event_tx.send(TimerEvent::Fired(snapshot));
+    /// Persists a fire. Without a fencing token this is a plain write, and a failed one only raises
+    /// `store_degraded`. With a token the write is conditional, so only one fire of a timer state is
+    /// ever durable; the fire must not go ahead when it fails. `Err(Some(stored))` means another
+    /// kernel already moved the timer on. `Err(None)` means this kernel was fenced off by a newer
+    /// leader and stepped down to standby, re-queueing the timer for a later promotion, or the store
+    /// failed and the timer stays pending for stuck-timer recovery to retry.
+    async fn commit_fire(&self, fired: &TimerInstance) -> Result<(), Option<TimerInstance>> {
+        let fencing_token = self.fencing_token.load(Ordering::SeqCst);
+        if fencing_token == 0 {
+            let _ = self.persist(fired).await;
+            return Ok(());
+        }
+        match self.store.upsert_fenced(fired, fencing_token).await {
+            Ok(FencedWrite::Written) => Ok(()),
+            Ok(FencedWrite::Superseded(stored)) => {
+                tracing::info!(timer_id = %fired.id, "timer state already recorded by another kernel");
+                Err(Some(*stored))
             }
-            .instrument(span),
-        );
+            Ok(FencedWrite::Fenced { current }) => {
+                tracing::warn!(
+                    timer_id = %fired.id,
+                    fencing_token,
+                    current,
+                    "fenced off by a newer leader; stepping down to standby"
+                );
+                if self.active.send_replace(false) {
+                    self.emit_system(SystemEventKind::Fenced {
+                        fencing_token,
+                        current_token: current,
+                    });
+                }
+                let delay = (fired.due_at() - self.now()).to_std().unwrap_or_default();
+                self.arm(fired, delay);
+                Err(None)
+            }
+            Err(error) => {
+                tracing::error!(timer_id = %fired.id, %error, "failed to record fire");
+                self.emit_system(SystemEventKind::StoreDegraded {
+                    reason: error.to_string(),
+                });
+                Err(None)
+            }
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Publishes a pre-fire notice unless the timer has meanwhile reached a terminal state, and
+    /// records it on the timer so a re-arm does not repeat it. Standbys stay quiet, matching how
+    /// they hold fires.
+    async fn emit_pre_fire(&self, timer_id: Uuid, fire_at: DateTime<Utc>, fires_in: Duration) {
+        if !*self.active.borrow() {
+            return;
+        }
+        let mut timers = self.timers.shard(&timer_id).write().await;
+        let Some(entry) = timers
+            .get_mut(&timer_id)
+            .filter(|timer| !timer.is_terminal() && timer.fire_at == fire_at)
+        else {
+            return;
+        };
+        let mut timer = entry.clone();
+        timer.pre_fire_sent_for = Some(fire_at);
+        timer.state_version += 1;
+        // Persisted so a restarted or promoted kernel does not send the notice again. A failed
+        // write is already reported as `store_degraded`, and only risks that repeat, so the notice
+        // still goes out.
+        let _ = self.persist(&timer).await;
+        *entry = timer.clone();
+        drop(timers);
+        self.publish(TimerEvent::PreFire {
+            timer,
+            fires_in_ms: fires_in.as_millis() as u64,
+        });
+    }
 
-    #[tokio::test]
+    /// Writes a timer through to the store, raising `store_degraded` when the write fails.
+    async fn persist(&self, timer: &TimerInstance) -> Result<(), StoreError> {
+        let result = self.store.upsert(timer).await;
+        self.store_healthy.store(result.is_ok(), Ordering::Relaxed);
+        if let Err(error) = &result {
+            tracing::error!(timer_id = %timer.id, %error, "failed to persist timer");
+            self.emit_system(SystemEventKind::StoreDegraded {
+                reason: error.to_string(),
+            });
+        }
+        result
+    }
+
+    /// [`KernelState::persist`] for several timers in one store write.
+    async fn persist_many(&self, timers: &[TimerInstance]) -> Result<(), StoreError> {
+        if timers.is_empty() {
+            return Ok(());
+        }
+        let result = self.store.upsert_many(timers).await;
+        self.store_healthy.store(result.is_ok(), Ordering::Relaxed);
+        if let Err(error) = &result {
+            tracing::error!(timers = timers.len(), %error, "failed to persist timer batch");
+            self.emit_system(SystemEventKind::StoreDegraded {
+                reason: error.to_string(),
+            });
+        }
+        result
+    }
+}
+
+#[derive(Clone)]
+pub struct HorologyKernel {
+    state: KernelState,
+}
+
+impl HorologyKernel {
+    pub fn new(config: SchedulerConfig) -> Self {
+        Self::with_store(config, Arc::new(InMemoryTimerStore::default()))
+    }
+
+    /// Builds a kernel that writes every timer transition through to `store`. Call
+    /// [`HorologyKernel::restore_from_store`] to pick up timers persisted by a previous run.
+    pub fn with_store(config: SchedulerConfig, store: Arc<dyn TimerStore>) -> Self {
+        let (system_tx, _rx) = broadcast::channel(64);
+        let tenants = Arc::new(Mutex::new(config.tenants.clone()));
+        Self {
+            state: KernelState {
+                timers: TimerShards::new(config.timer_shards),
+                waiters: Arc::new(Mutex::new(HashMap::new())),
+                idempotency: Arc::new(Mutex::new(HashMap::new())),
+                tenants: tenants.clone(),
+                usage: UsageMeter::new(DEFAULT_USAGE_RETENTION, config.clock.clone()),
+                budgets: BudgetTracker::default(),
+                alarms: RateAlarms::new(config.alarms.clone()),
+                schedule_rates: ScheduleRateLimiter::default(),
+                deliveries: DeliveryLedger::new(config.clock.clone()),
+                tokens: TokenStore::default(),
+                events: EventFanout::new(),
+                sequence: Arc::new(Mutex::new(
+                    config
+                        .event_history
+                        .as_ref()
+                        .map_or(0, EventHistory::last_sequence),
+                )),
+                system_tx,
+                active: Arc::new(watch::channel(!config.standby).0),
+                store,
+                gap_report: Arc::new(Mutex::new(None)),
+                queue: TimerQueue::default(),
+                tasks: TaskRegistry::default(),
+                snapshots: ListSnapshots::default(),
+                streams: StreamMetrics::default(),
+                backpressure: BackpressureMetrics::default(),
+                dead_letters: DeadLetterQueue::default(),
+                webhooks: WebhookRegistry::with_tenants(tenants),
+                dispatch: Arc::new(Mutex::new(None)),
+                stuck: StuckTimerMetrics::default(),
+                fencing_token: Arc::new(AtomicU64::new(0)),
+                store_healthy: Arc::new(AtomicBool::new(true)),
+                leadership: Arc::default(),
+                drift: DriftDetector::new(config.clock.as_ref()),
+                config,
+            },
+        }
+    }
+
+    /// Timer events from now on, buffered per [`SchedulerConfig::subscriber_buffer`].
+    pub fn subscribe(&self) -> EventSubscription {
+        self.subscribe_with(self.state.config.subscriber_buffer)
+    }
+
+    /// [`HorologyKernel::subscribe`] with a buffer sized for this subscriber.
+    pub fn subscribe_with(&self, buffer: SubscriberBuffer) -> EventSubscription {
+        self.state.events.subscribe(buffer)
+    }
+
+    /// Retained events after `after_sequence` of the tenants `include` accepts, and a subscription
+    /// picking up right after them, so a reconnecting consumer misses and repeats nothing. Needs
+    /// [`SchedulerConfig::event_history`].
+    pub fn subscribe_after(
+        &self,
+        after_sequence: u64,
+        include: impl Fn(&str) -> bool,
+    ) -> Result<(Vec<RecordedEvent>, EventSubscription), KernelError> {
+        let history = self
+            .state
+            .config
+            .event_history
+            .as_ref()
+            .ok_or(KernelError::EventHistoryDisabled)?;
+        // Holding the sequence keeps events from being published between the replay and the
+        // subscription.
+        let latest = self.state.sequence.lock().expect("event sequence poisoned");
+        if after_sequence > *latest {
+            return Err(KernelError::SequenceAhead {
+                requested: after_sequence,
+                latest: *latest,
+            });
+        }
+        let replay = history
+            .replay(after_sequence, include)
+            .map_err(|trimmed_through| KernelError::EventsExpired {
+                after_sequence,
+                trimmed_through,
+            })?;
+        let subscription = self.subscribe();
+        drop(latest);
+        Ok((replay, subscription))
+    }
+
+    /// Sequence of the latest timer event; 0 before the first.
+    pub fn last_event_sequence(&self) -> u64 {
+        *self.state.sequence.lock().expect("event sequence poisoned")
+    }
+
+    /// Buffered and dropped events across every subscriber.
+    pub fn fanout_stats(&self) -> FanoutStats {
+        self.state.events.stats()
+    }
+
+    /// Operational events about the kernel itself, published under [`SYSTEM_SUBJECT`].
+    pub fn subscribe_system(&self) -> broadcast::Receiver<SystemEvent> {
+        self.state.system_tx.subscribe()
+    }
+
+    pub fn emit_system(&self, kind: SystemEventKind) {
+        self.state.emit_system(kind);
+    }
+
+    /// Reloads persisted timers, re-arms the pending ones, and reports those whose fire time fell
+    /// inside the downtime window (between the last heartbeat and now). Overdue timers fire
+    /// immediately or are marked missed, according to their [`MissedFirePolicy`].
+    pub async fn restore_from_store(&self) -> Result<FireGapReport, KernelError> {
+        let downtime_started_at = self.state.store.last_heartbeat().await?;
+        let persisted = self.state.store.load_all().await?;
+        let restored_at = self.state.now();
+        let mut entries = Vec::new();
+        let mut pending = Vec::new();
+        let mut missed = Vec::new();
+        {
+            let mut timers = self.state.timers.write().await;
+            for mut timer in persisted.iter().cloned() {
+                if !timer.is_terminal() {
+                    if timer.due_at() <= restored_at {
+                        let late_by_ms = (restored_at - timer.due_at()).num_milliseconds() as u64;
+                        let outcome = timer
+                            .missed_fire_policy
+                            .unwrap_or(self.state.config.missed_fire_policy)
+                            .outcome(late_by_ms);
+                        entries.push(FireGapEntry {
+                            timer_id: timer.id,
+                            tenant_id: timer.tenant_id.clone(),
+                            fire_at: timer.fire_at,
+                            late_by_ms,
+                            outcome,
+                        });
+                        if outcome == GapOutcome::Missed {
+                            timer.status = TimerStatus::Missed;
+                            timer.state_version += 1;
+                            let _ = self.state.persist(&timer).await;
+                            missed.push((timer.clone(), late_by_ms));
+                        }
+                    }
+                    if !timer.is_terminal() {
+                        pending.push(timer.clone());
+                    }
+                }
+                self.state.remember_key(&timer);
+                timers.insert(timer.id, timer);
+            }
+        }
+        for (timer, late_by_ms) in missed {
+            self.state.publish(TimerEvent::Missed { timer, late_by_ms });
+        }
+        for timer in pending {
+            let delay = (timer.due_at() - restored_at).to_std().unwrap_or_default();
+            self.state.arm(&timer, delay);
+        }
+        entries.sort_by_key(|entry| entry.fire_at);
+
+        let report = FireGapReport {
+            downtime_started_at,
+            restored_at,
+            restored: persisted.len() as u64,
+            entries,
+        };
+        tracing::info!(
+            restored = report.restored,
+            overdue = report.entries.len(),
+            downtime_started_at = ?report.downtime_started_at,
+            "restored timers from store"
+        );
+        self.emit_system(SystemEventKind::RestoreCompleted {
+            timers: report.restored,
+            overdue: report.entries.len() as u64,
+        });
+        *self.state.gap_report.lock().expect("gap report poisoned") = Some(report.clone());
+        Ok(report)
+    }
+
+    /// Fire-gap report from the most recent restore, if any.
+    pub fn fire_gap_report(&self) -> Option<FireGapReport> {
+        self.state
+            .gap_report
+            .lock()
+            .expect("gap report poisoned")
+            .clone()
+    }
+
+    /// Marks the kernel as alive in the store so the next restore can bound its downtime window.
+    pub async fn heartbeat(&self) -> Result<(), KernelError> {
+        Ok(self.state.store.record_heartbeat(self.state.now()).await?)
+    }
+
+    /// Winds the kernel down before the process exits. It steps down to standby, so writes are
+    /// refused with [`KernelError::Standby`] and clients move to the next leader, then waits for a
+    /// fire that is being recorded and stops the dispatch loop. Every in-memory timer is written to
+    /// the store as a final snapshot (through [`TimerStore::upsert_fenced`] when fencing, so a newer
+    /// leader's writes are kept), the store is flushed, and a last heartbeat is recorded. Release
+    /// any leadership lease after this returns.
+    pub async fn shutdown(&self) -> Result<DrainReport, KernelError> {
+        self.state.active.send_replace(false);
+        self.emit_system(SystemEventKind::ShutdownStarted);
+        // A fire holds the write lock while it is recorded, so none is half-done once we have it.
+        let timers = self.state.timers.write().await;
+        if let Some(dispatch) = self
+            .state
+            .dispatch
+            .lock()
+            .expect("dispatch handle poisoned")
+            .take()
+        {
+            dispatch.abort();
+        }
+        let snapshot: Vec<TimerInstance> = timers.values().cloned().collect();
+        drop(timers);
+
+        match self.fencing_token() {
+            Some(token) => {
+                for timer in &snapshot {
+                    if let FencedWrite::Fenced { current } =
+                        self.state.store.upsert_fenced(timer, token).await?
+                    {
+                        tracing::warn!(current, "fenced off while writing the final snapshot");
+                        break;
+                    }
+                }
+            }
+            None => self.state.store.upsert_many(&snapshot).await?,
+        }
+        self.state.store.flush().await?;
+        self.heartbeat().await?;
+        let report = DrainReport {
+            snapshotted: snapshot.len(),
+            pending: snapshot.iter().filter(|timer| !timer.is_terminal()).count(),
+        };
+        tracing::info!(?report, "kernel drained");
+        Ok(report)
+    }
+
+    /// Long-lived background tasks of this kernel and of the binary embedding it.
+    pub fn tasks(&self) -> &TaskRegistry {
+        &self.state.tasks
+    }
+
+    /// Events the kernel's event sinks gave up on, for operators to replay.
+    pub fn dead_letters(&self) -> &DeadLetterQueue {
+        &self.state.dead_letters
+    }
+
+    /// Tenants' event webhooks, for the webhook event sink to deliver to.
+    pub fn webhooks(&self) -> &WebhookRegistry {
+        &self.state.webhooks
+    }
+
+    /// Billable usage meter shared by the kernel and its transports.
+    pub fn usage(&self) -> &UsageMeter {
+        &self.state.usage
+    }
+
+    /// Accuracy-budget results for a tenant's fired timers.
+    pub fn budget_stats(&self, tenant_id: &str) -> BudgetStats {
+        self.state.budgets.stats(tenant_id)
+    }
+
+    /// Ledger of fired-event deliveries to stream subscribers and webhooks.
+    pub fn deliveries(&self) -> &DeliveryLedger {
+        &self.state.deliveries
+    }
+
+    /// Per-subscriber throughput of timer event streams.
+    pub fn stream_metrics(&self) -> &StreamMetrics {
+        &self.state.streams
+    }
+
+    /// Caps applied to each timer event stream.
+    pub fn stream_limits(&self) -> StreamLimits {
+        self.state.config.stream_limits
+    }
+
+    /// Backpressure settings, if the mode is on.
+    pub fn backpressure(&self) -> Option<BackpressureConfig> {
+        self.state.config.backpressure
+    }
+
+    /// Fire holds applied while event consumers lagged.
+    pub fn backpressure_stats(&self) -> BackpressureStats {
+        self.state.backpressure.snapshot()
+    }
+
+    /// Recovers pending timers more than `grace` past their due time. If the dispatch loop has
+    /// stopped (e.g. it panicked), it is restarted and the stuck timers are queued on it again;
+    /// otherwise the loop missed them and they are fired here. Each recovery raises
+    /// `recovered_stuck_timer`. Standbys hold fires, so they are not scanned. Keep `grace` above
+    /// the backpressure hold so held fires are not mistaken for lost ones.
+    pub async fn recover_stuck_timers(&self, grace: Duration) -> Vec<RecoveredStuckTimer> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+        let now = self.state.now();
+        let cutoff = now - chrono::Duration::from_std(grace).unwrap_or_default();
+        let stuck: Vec<TimerInstance> = self
+            .state
+            .timers
+            .read()
+            .await
+            .values()
+            .filter(|timer| !timer.is_terminal() && timer.due_at() < cutoff)
+            .cloned()
+            .collect();
+        let stopped = self
+            .state
+            .dispatch
+            .lock()
+            .expect("dispatch handle poisoned")
+            .as_ref()
+            .is_some_and(|handle| handle.is_finished());
+        if stopped {
+            tracing::error!("timer dispatch loop stopped; restarting it");
+            self.state.queue.release_driver();
+        }
+
+        let mut recovered = Vec::with_capacity(stuck.len());
+        for timer in stuck {
+            let action = if stopped {
+                self.state.arm(&timer, Duration::ZERO);
+                RecoveryAction::Rearmed
+            } else {
+                self.state.fire(timer.id, timer.fire_at).await;
+                RecoveryAction::Fired
+            };
+            let recovery = RecoveredStuckTimer {
+                timer_id: timer.id,
+                tenant_id: timer.tenant_id.clone(),
+                overdue_ms: (now - timer.due_at()).num_milliseconds().max(0) as u64,
+                action,
+            };
+            tracing::warn!(
+                timer_id = %recovery.timer_id,
+                tenant_id = %recovery.tenant_id,
+                overdue_ms = recovery.overdue_ms,
+                action = action.name(),
+                "recovered stuck timer"
+            );
+            self.state.emit_system(SystemEventKind::RecoveredStuckTimer {
+                timer_id: recovery.timer_id,
+                tenant_id: recovery.tenant_id.clone(),
+                overdue_ms: recovery.overdue_ms,
+                action: action.name().to_string(),
+            });
+            recovered.push(recovery);
+        }
+        if stopped {
+            // Timers queued but not yet due need the loop too.
+            self.state.start_dispatch();
+        }
+        self.state.stuck.record_scan(&recovered, stopped);
+        recovered
+    }
+
+    /// Compares the wall clock with monotonic time since the previous check. When the wall clock
+    /// stepped by more than [`SchedulerConfig::drift_threshold`] (an NTP step, a VM resuming), every
+    /// pending timer is re-armed for its fire time on the new wall clock, so a forward step fires
+    /// them on time rather than late, and `clock_jumped` is raised. Steps back are also caught at
+    /// each fire, so this only has to run often enough to bound lateness.
+    pub async fn check_clock_drift(&self) -> Option<ClockJump> {
+        let threshold = self.state.config.drift_threshold?;
+        let jump = self.state.drift.observe(self.state.config.clock.as_ref());
+        if jump.abs() <= chrono::Duration::from_std(threshold).unwrap_or_default() {
+            return None;
+        }
+        let now = self.state.now();
+        let timers = self.state.timers.read().await;
+        let mut rearmed = 0;
+        for timer in timers.values().filter(|timer| !timer.is_terminal()) {
+            self.state
+                .arm(timer, (timer.due_at() - now).to_std().unwrap_or_default());
+            rearmed += 1;
+        }
+        drop(timers);
+        let jump = ClockJump {
+            jump_ms: jump.num_milliseconds(),
+            rearmed,
+        };
+        tracing::warn!(
+            jump_ms = jump.jump_ms,
+            rearmed,
+            "wall clock stepped; re-armed pending timers"
+        );
+        self.state.emit_system(SystemEventKind::ClockJumped {
+            jump_ms: jump.jump_ms,
+            rearmed: rearmed as u64,
+        });
+        Some(jump)
+    }
+
+    /// A tenant's retained events matching `query`, oldest first.
+    pub fn query_events(
+        &self,
+        tenant_id: &str,
+        query: &EventQuery,
+    ) -> Result<Vec<RecordedEvent>, KernelError> {
+        let history = self
+            .state
+            .config
+            .event_history
+            .as_ref()
+            .ok_or(KernelError::EventHistoryDisabled)?;
+        Ok(history.query(tenant_id, query))
+    }
+
+    pub fn stuck_timer_stats(&self) -> StuckTimerStats {
+        self.state.stuck.snapshot()
+    }
+
+    pub fn is_active(&self) -> bool {
+        *self.state.active.borrow()
+    }
+
+    /// `false` while the most recent store write failed; the next successful write clears it.
+    pub fn store_healthy(&self) -> bool {
+        self.state.store_healthy.load(Ordering::Relaxed)
+    }
+
+    /// The latest promotion, lease change, or fencing this kernel saw; `None` before any.
+    pub fn last_leadership_change(&self) -> Option<SystemEvent> {
+        self.state
+            .leadership
+            .lock()
+            .expect("leadership record poisoned")
+            .clone()
+    }
+
+    /// Timers held in memory per tenant and status, without copying them out of the table.
+    pub async fn timer_counts(&self) -> BTreeMap<String, TimerCounts> {
+        let timers = self.state.timers.read().await;
+        let mut counts = BTreeMap::<String, TimerCounts>::new();
+        for timer in timers.values() {
+            match counts.get_mut(&timer.tenant_id) {
+                Some(tenant) => tenant.add(&timer.status),
+                None => {
+                    let mut tenant = TimerCounts::default();
+                    tenant.add(&timer.status);
+                    counts.insert(timer.tenant_id.clone(), tenant);
+                }
+            }
+        }
+        counts
+    }
+
+    /// Counts, pending fire horizon, and per-label counts of the timers in scope, computed under
+    /// the read lock so no timer is copied out. Labels are broken down by `label_keys`, or by
+    /// every key when it is empty.
+    pub async fn timer_stats(&self, scope: &TenantScope, label_keys: &[String]) -> TimerStats {
+        let directory = self.tenants().clone();
+        let timers = self.state.timers.read().await;
+        let mut stats = TimerStats::default();
+        for timer in timers.values().filter(|t| directory.in_scope(scope, &t.tenant_id)) {
+            stats.counts.add(&timer.status);
+            if matches!(timer.status, TimerStatus::Scheduled | TimerStatus::Armed) {
+                let fire_at = timer.fire_at;
+                stats.next_fire_at = Some(stats.next_fire_at.map_or(fire_at, |next| next.min(fire_at)));
+                stats.horizon = Some(stats.horizon.map_or(fire_at, |horizon| horizon.max(fire_at)));
+            }
+            for (key, value) in &timer.labels {
+                if label_keys.is_empty() || label_keys.contains(key) {
+                    stats
+                        .labels
+                        .entry((key.clone(), value.clone()))
+                        .or_default()
+                        .add(&timer.status);
+                }
+            }
+        }
+        stats
+    }
+
+    /// Retained events after `after_sequence` of the tenants `include` accepts, oldest first.
+    /// Needs [`SchedulerConfig::event_history`].
+    pub fn replay_events(
+        &self,
+        after_sequence: u64,
+        include: impl Fn(&str) -> bool,
+    ) -> Result<Vec<RecordedEvent>, KernelError> {
+        let history = self
+            .state
+            .config
+            .event_history
+            .as_ref()
+            .ok_or(KernelError::EventHistoryDisabled)?;
+        history
+            .replay(after_sequence, include)
+            .map_err(|trimmed_through| KernelError::EventsExpired {
+                after_sequence,
+                trimmed_through,
+            })
+    }
+
+    /// Compacts the timer store now rather than at its next scheduled compaction. Returns how
+    /// many timers were dropped, or `None` when the store does not compact.
+    pub async fn compact_store(&self) -> Result<Option<usize>, KernelError> {
+        Ok(self.state.store.compact().await?)
+    }
+
+    /// Turns a warm standby into the active kernel. A standby queues hydrated timers on the shared
+    /// dispatch queue like an active kernel but holds its dispatch loop; promotion releases it, so
+    /// fires that came due while in standby go out immediately and the rest at their deadlines.
+    pub fn promote(&self) {
+        if !self.state.active.send_replace(true) {
+            self.emit_system(SystemEventKind::StandbyPromoted);
+        }
+    }
+
+    /// Records fires from now on with [`TimerStore::upsert_fenced`] under `token`, which must grow
+    /// with every leadership change (e.g. a lease's transition count). A kernel whose fire meets a
+    /// newer token in the store steps down to standby and raises `fenced`, and a timer whose fire
+    /// the store already holds is not fired again, so a fire that races a failover is durable once.
+    /// Set it before [`HorologyKernel::promote`]; 0 turns fencing off.
+    pub fn set_fencing_token(&self, token: u64) {
+        self.state.fencing_token.store(token, Ordering::SeqCst);
+    }
+
+    pub fn fencing_token(&self) -> Option<u64> {
+        Some(self.state.fencing_token.load(Ordering::SeqCst)).filter(|token| *token > 0)
+    }
+
+    /// Current time on the kernel's [`SchedulerConfig::clock`].
+    pub fn now(&self) -> DateTime<Utc> {
+        self.state.now()
+    }
+
+    /// Resolves once the kernel is active (immediately unless it is a standby).
+    pub async fn until_active(&self) {
+        let _ = self.state.active.subscribe().wait_for(|active| *active).await;
+    }
+
+    /// Applies a lifecycle event replicated from the active kernel so a standby stays hydrated.
+    /// Applying is idempotent: re-delivered scheduled events are ignored, while reschedules and
+    /// terminal events overwrite the local copy.
+    pub async fn apply(&self, event: TimerEvent) {
+        let timer = match event {
+            TimerEvent::Scheduled(timer) => timer,
+            TimerEvent::Rescheduled { timer, .. } => {
+                let mut timers = self.state.timers.shard(&timer.id).write().await;
+                if timers.get(&timer.id).is_some_and(TimerInstance::is_terminal) {
+                    return;
+                }
+                let _ = self.state.persist(&timer).await;
+                timers.insert(timer.id, timer.clone());
+                drop(timers);
+                let delay = (timer.due_at() - self.state.now()).to_std().unwrap_or_default();
+                self.state.arm(&timer, delay);
+                return;
+            }
+            TimerEvent::Fired(timer)
+            | TimerEvent::Cancelled { timer, .. }
+            | TimerEvent::Missed { timer, .. }
+            | TimerEvent::Acknowledged(timer)
+            | TimerEvent::Failed(timer)
+            | TimerEvent::Settled(timer) => timer,
+            // The standby sends no notice itself, but records this one so it does not repeat it
+            // once promoted.
+            TimerEvent::PreFire { timer, .. } => {
+                let mut timers = self.state.timers.shard(&timer.id).write().await;
+                if !timers
+                    .get(&timer.id)
+                    .is_some_and(|local| !local.is_terminal() && local.fire_at == timer.fire_at)
+                {
+                    return;
+                }
+                let _ = self.state.persist(&timer).await;
+                timers.insert(timer.id, timer.clone());
+                drop(timers);
+                let delay = (timer.due_at() - self.state.now()).to_std().unwrap_or_default();
+                self.state.arm(&timer, delay);
+                return;
+            }
+        };
+        let mut timers = self.state.timers.shard(&timer.id).write().await;
+        if timer.is_terminal() {
+            let _ = self.state.persist(&timer).await;
+            timers.insert(timer.id, timer.clone());
+            drop(timers);
+            self.state.notify_waiters(&timer);
+            return;
+        }
+        if timers.contains_key(&timer.id) {
+            return;
+        }
+        let _ = self.state.persist(&timer).await;
+        self.state.remember_key(&timer);
+        timers.insert(timer.id, timer.clone());
+        drop(timers);
+        let delay = (timer.due_at() - self.state.now()).to_std().unwrap_or_default();
+        self.state.arm(&timer, delay);
+    }
+
+    /// Tenant API tokens used by authenticated transports.
+    pub fn tokens(&self) -> &TokenStore {
+        &self.state.tokens
+    }
+
+    /// Delivery receipts for a timer, or `None` when the timer is unknown to the tenant.
+    pub async fn delivery_status(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+    ) -> Option<Vec<DeliveryReceipt>> {
+        self.get(tenant_id, timer_id).await?;
+        Some(self.state.deliveries.receipts(timer_id))
+    }
+
+    /// Records that a subscriber finished processing a fired timer.
+    pub async fn acknowledge_delivery(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+        subscriber_id: &str,
+    ) -> bool {
+        self.get(tenant_id, timer_id).await.is_some()
+            && self.state.deliveries.acknowledge(timer_id, subscriber_id)
+    }
+
+    /// Registers a project outside the tenant policies, which can also declare projects through
+    /// [`TenantPolicy::projects`].
+    pub fn register_project(&self, organization_id: &str, project_id: &str) {
+        self.tenants().register_project(organization_id, project_id);
+    }
+
+    pub fn set_tenant_policy(&self, tenant_id: &str, policy: TenantPolicy) {
+        self.tenants().set_policy(tenant_id, policy);
+    }
+
+    /// Replaces every tenant policy with the store's current set; tenants the store no longer lists
+    /// fall back to the scheduler defaults. Applies to schedules from then on, not to timers already
+    /// pending. Returns how many policies were loaded.
+    pub async fn reload_tenant_policies(
+        &self,
+        store: &dyn TenantPolicyStore,
+    ) -> Result<usize, KernelError> {
+        let policies = store.load_policies().await?;
+        let loaded = policies.len();
+        self.tenants().replace_policies(policies);
+        Ok(loaded)
+    }
+
+    /// Effective policy for a tenant after organization inheritance.
+    pub fn tenant_policy(&self, tenant_id: &str) -> TenantPolicy {
+        self.tenants().effective_policy(tenant_id)
+    }
+
+    pub fn in_scope(&self, scope: &TenantScope, tenant_id: &str) -> bool {
+        self.tenants().in_scope(scope, tenant_id)
+    }
+
+    /// Quota limits for a tenant: its policy's quota over the scheduler-wide defaults.
+    pub fn tenant_quota(&self, tenant_id: &str) -> TenantQuota {
+        self.tenant_policy(tenant_id)
+            .quota
+            .or(&self.state.config.quotas)
+    }
+
+    /// Reports a quota violation as a system event and turns it into the error returned to the caller.
+    fn quota_exceeded(&self, tenant_id: &str, violation: QuotaViolation) -> KernelError {
+        self.emit_system(SystemEventKind::QuotaExceeded {
+            tenant_id: tenant_id.to_string(),
+            quota: violation.quota.name().to_string(),
+        });
+        KernelError::QuotaExceeded {
+            tenant_id: tenant_id.to_string(),
+            quota: violation.quota,
+            limit: violation.limit,
+        }
+    }
+
+    fn tenants(&self) -> std::sync::MutexGuard<'_, TenantDirectory> {
+        self.state.tenants.lock().expect("tenant directory poisoned")
+    }
+
+    /// Computes when `spec` would fire, applying the same validation as [`HorologyKernel::schedule`]
+    /// without creating anything. Only one-shot schedules exist today, so at most one fire time
+    /// is returned.
+    pub fn preview(
+        &self,
+        spec: &TimerSpec,
+        count: usize,
+    ) -> Result<Vec<DateTime<Utc>>, KernelError> {
+        let (fire_at, _) = self.resolve_fire_at(spec, self.state.now())?;
+        Ok(std::iter::once(fire_at).take(count).collect())
+    }
+
+    /// Validates the requested schedule and returns the fire time and effective delay.
+    fn resolve_fire_at(
+        &self,
+        spec: &TimerSpec,
+        now: DateTime<Utc>,
+    ) -> Result<(DateTime<Utc>, Duration), KernelError> {
+        self.state.config.limits.check(spec)?;
+        let delay = if let Some(ts) = spec.fire_at {
+            if ts <= now {
+                return Err(KernelError::InvalidFireTime);
+            }
+            (ts - now)
+                .to_std()
+                .map_err(|_| KernelError::InvalidFireTime)?
+        } else {
+            let delay = match spec.duration_us {
+                Some(duration_us) => Duration::from_micros(duration_us),
+                None => Duration::from_millis(spec.duration_ms),
+            };
+            if delay.is_zero() {
+                return Err(KernelError::InvalidDuration);
+            }
+            delay
+        };
+
+        self.check_max_duration(&spec.tenant_id, delay)?;
+        if let TimerKind::Watchdog { interval_ms } = spec.kind {
+            if interval_ms == 0 {
+                return Err(KernelError::InvalidDuration);
+            }
+            self.check_max_duration(&spec.tenant_id, Duration::from_millis(interval_ms))?;
+        }
+
+        let chrono_delay =
+            chrono::Duration::from_std(delay).map_err(|_| KernelError::InvalidFireTime)?;
+        Ok((spec.fire_at.unwrap_or_else(|| now + chrono_delay), delay))
+    }
+
+    fn check_max_duration(&self, tenant_id: &str, delay: Duration) -> Result<(), KernelError> {
+        let max_duration_ms = self
+            .tenant_policy(tenant_id)
+            .max_duration_ms
+            .or(self.state.config.max_duration_ms);
+        match max_duration_ms {
+            Some(max) if delay.as_millis() as u64 > max => Err(KernelError::InvalidDuration),
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn schedule(&self, spec: TimerSpec) -> Result<TimerInstance, KernelError> {
+        self.schedule_superseding(spec, &HashSet::new()).await
+    }
+
+    /// [`HorologyKernel::schedule`] that leaves the `superseded` timers, which the caller cancels
+    /// once it succeeds, out of the pending-timer quota.
+    async fn schedule_superseding(
+        &self,
+        spec: TimerSpec,
+        superseded: &HashSet<Uuid>,
+    ) -> Result<TimerInstance, KernelError> {
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        let key = spec.idempotency_key.as_ref();
+        if key.is_some() {
+            let timers = self.state.timers.read().await;
+            if let Some(existing) = self.state.replay(&timers, &spec.tenant_id, key) {
+                return Ok(existing);
+            }
+        }
+        let now = self.state.now();
+        let (timer, delay, quota) = self.prepare(&spec, now)?;
+
+        // Persist under the write lock so store writes for one timer stay ordered. Idempotency keys
+        // and pending-timer quotas span the tenant's timers, so only schedules with neither get by
+        // with locking the new timer's shard.
+        if key.is_some() || quota.limits_pending() {
+            let mut timers = self.state.timers.write().await;
+            // A concurrent request with the same key may have won the race since the check above.
+            if let Some(existing) = self.state.replay(&timers, &spec.tenant_id, key) {
+                return Ok(existing);
+            }
+            let pending = timers
+                .values()
+                .filter(|t| {
+                    t.tenant_id == timer.tenant_id
+                        && !t.is_terminal()
+                        && !superseded.contains(&t.id)
+                })
+                .map(|t| t.duration_ms);
+            if let Err(violation) = quota.check_pending(pending, timer.duration_ms) {
+                return Err(self.quota_exceeded(&timer.tenant_id, violation));
+            }
+            self.state.persist(&timer).await?;
+            self.state.remember_key(&timer);
+            timers.insert(timer.id, timer.clone());
+        } else {
+            let mut shard = self.state.timers.shard(&timer.id).write().await;
+            self.state.persist(&timer).await?;
+            shard.insert(timer.id, timer.clone());
+        }
+        self.state.usage.record_scheduled(&timer.tenant_id);
+        self.state.record_rate(&timer.tenant_id, SchedulingOp::Schedule, now);
+
+        self.state.publish(TimerEvent::Scheduled(timer.clone()));
+
+        self.state.arm(&timer, delay + timer.kind.grace());
+
+        Ok(timer)
+    }
+
+    /// Schedules up to [`MAX_BATCH_SIZE`] timers in one call. Each spec is validated and admitted
+    /// on its own, as [`HorologyKernel::schedule`] would, and gets its own result in request
+    /// order; the accepted timers are written to the store in a single batch. Fails as a whole
+    /// only for an oversized batch, a standby, or a failed store write, in which case nothing is
+    /// scheduled.
+    pub async fn schedule_batch(
+        &self,
+        specs: Vec<TimerSpec>,
+    ) -> Result<Vec<Result<TimerInstance, KernelError>>, KernelError> {
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        if specs.len() > MAX_BATCH_SIZE {
+            return Err(KernelError::BatchTooLarge {
+                max: MAX_BATCH_SIZE,
+            });
+        }
+        let mut results: Vec<Option<Result<TimerInstance, KernelError>>> =
+            specs.iter().map(|_| None).collect();
+        {
+            let timers = self.state.timers.read().await;
+            for (result, spec) in results.iter_mut().zip(&specs) {
+                let key = spec.idempotency_key.as_ref();
+                if let Some(existing) = self.state.replay(&timers, &spec.tenant_id, key) {
+                    *result = Some(Ok(existing));
+                }
+            }
+        }
+        let now = self.state.now();
+        let mut prepared = Vec::new();
+        for (index, spec) in specs.iter().enumerate() {
+            if results[index].is_some() {
+                continue;
+            }
+            match self.prepare(spec, now) {
+                Ok((timer, delay, quota)) => prepared.push((index, timer, delay, quota)),
+                Err(error) => results[index] = Some(Err(error)),
+            }
+        }
+
+        let mut accepted = Vec::new();
+        {
+            let mut timers = self.state.timers.write().await;
+            for (index, timer, delay, quota) in prepared {
+                // Also catches a key repeated earlier in this batch.
+                let key = timer.idempotency_key.as_ref();
+                if let Some(existing) = self.state.replay(&timers, &timer.tenant_id, key) {
+                    results[index] = Some(Ok(existing));
+                    continue;
+                }
+                let pending = timers
+                    .values()
+                    .filter(|t| t.tenant_id == timer.tenant_id && !t.is_terminal())
+                    .map(|t| t.duration_ms);
+                if let Err(violation) = quota.check_pending(pending, timer.duration_ms) {
+                    results[index] = Some(Err(self.quota_exceeded(&timer.tenant_id, violation)));
+                    continue;
+                }
+                self.state.remember_key(&timer);
+                timers.insert(timer.id, timer.clone());
+                accepted.push((index, timer, delay));
+            }
+            let batch: Vec<TimerInstance> =
+                accepted.iter().map(|(_, timer, _)| timer.clone()).collect();
+            if let Err(error) = self.state.persist_many(&batch).await {
+                for timer in &batch {
+                    timers.remove(&timer.id);
+                    if let Some(key) = &timer.idempotency_key {
+                        self.state
+                            .idempotency()
+                            .remove(&(timer.tenant_id.clone(), key.clone()));
+                    }
+                }
+                return Err(error.into());
+            }
+        }
+
+        for (index, timer, delay) in accepted {
+            self.state.usage.record_scheduled(&timer.tenant_id);
+            self.state
+                .record_rate(&timer.tenant_id, SchedulingOp::Schedule, now);
+            self.state.publish(TimerEvent::Scheduled(timer.clone()));
+            self.state.arm(&timer, delay + timer.kind.grace());
+            results[index] = Some(Ok(timer));
+        }
+        Ok(results
+            .into_iter()
+            .map(|result| result.expect("every spec has a result"))
+            .collect())
+    }
+
+    /// Admission checks shared by single and batch schedules: spike throttling, the spec's
+    /// limits and horizon, and the tenant's schedule rate. Returns the pending timer, its delay,
+    /// and the tenant quota its pending-timer limits are checked against.
+    fn prepare(
+        &self,
+        spec: &TimerSpec,
+        now: DateTime<Utc>,
+    ) -> Result<(TimerInstance, Duration, TenantQuota), KernelError> {
+        if let Err(retry_after) = self.state.alarms.check_throttle(&spec.tenant_id, now) {
+            return Err(KernelError::RateLimited {
+                tenant_id: spec.tenant_id.clone(),
+                retry_after_ms: retry_after.num_milliseconds().max(0) as u64,
+            });
+        }
+        let (fire_at, delay) = self.resolve_fire_at(spec, now)?;
+        let quota = self.tenant_quota(&spec.tenant_id);
+        if let Some(limit) = quota.max_schedules_per_minute {
+            if !self.state.schedule_rates.try_acquire(&spec.tenant_id, limit, now) {
+                let violation = QuotaViolation {
+                    quota: QuotaKind::ScheduleRate,
+                    limit,
+                };
+                return Err(self.quota_exceeded(&spec.tenant_id, violation));
+            }
+        }
+        let timer = TimerInstance::from_spec(
+            spec,
+            self.state.config.id_scheme.generate(),
+            now,
+            fire_at,
+            delay,
+        );
+        Ok((timer, delay, quota))
+    }
+
+    pub async fn cancel(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+        reason: Option<String>,
+        cancelled_by: Option<String>,
+    ) -> Option<TimerInstance> {
+        self.cancel_at_version(tenant_id, timer_id, None, reason, cancelled_by)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// [`HorologyKernel::cancel`] that only goes ahead while the timer is still at
+    /// `expected_state_version`, so two agents racing to mutate a timer see one succeed and the other
+    /// fail with [`KernelError::StateVersionConflict`]. `None` skips the check.
+    pub async fn cancel_at_version(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+        expected_state_version: Option<u64>,
+        reason: Option<String>,
+        cancelled_by: Option<String>,
+    ) -> Result<Option<TimerInstance>, KernelError> {
+        let mut timers = self.state.timers.shard(&timer_id).write().await;
+        let Some(entry) = timers
+            .get_mut(&timer_id)
+            .filter(|t| t.tenant_id == tenant_id)
+        else {
+            return Ok(None);
+        };
+        entry.check_state_version(expected_state_version)?;
+
+        if entry.is_terminal() {
+            return Ok(Some(entry.clone()));
+        }
+
+        let mut snapshot = entry.clone();
+        snapshot.status = TimerStatus::Cancelled;
+        snapshot.state_version += 1;
+        snapshot.cancelled_at = Some(self.state.now());
+        snapshot.cancel_reason = reason.clone();
+        snapshot.cancelled_by = cancelled_by;
+        self.state.persist(&snapshot).await?;
+        *entry = snapshot.clone();
+        drop(timers);
+        self.state.queue.forget(timer_id);
+
+        self.state.notify_waiters(&snapshot);
+        self.state.record_rate(tenant_id, SchedulingOp::Cancel, self.state.now());
+        self.state.publish(TimerEvent::Cancelled {
+            timer: snapshot.clone(),
+            reason,
+        });
+        Ok(Some(snapshot))
+    }
+
+    /// Cancels every pending timer the tenant scheduled under `session_id`, e.g. when the agent run
+    /// that owns them is aborted. Returns the timers it cancelled.
+    pub async fn revoke_session(
+        &self,
+        tenant_id: &str,
+        session_id: &str,
+        revoked_by: Option<String>,
+    ) -> Vec<TimerInstance> {
+        let ids: Vec<Uuid> = self
+            .state
+            .timers
+            .read()
+            .await
+            .values()
+            .filter(|t| {
+                t.tenant_id == tenant_id
+                    && t.session_id.as_deref() == Some(session_id)
+                    && !t.is_terminal()
+            })
+            .map(|t| t.id)
+            .collect();
+        let mut cancelled = Vec::new();
+        for id in ids {
+            let reason = format!("session {session_id} revoked");
+            if let Some(timer) = self
+                .cancel(tenant_id, id, Some(reason), revoked_by.clone())
+                .await
+                .filter(|t| t.status == TimerStatus::Cancelled)
+            {
+                cancelled.push(timer);
+            }
+        }
+        cancelled
+    }
+
+    /// Moves a pending timer's fire time, either to an absolute time or by a signed delta, keeping its
+    /// id, payload, and waiters. Returns `Ok(None)` when the tenant has no such timer.
+    pub async fn reschedule(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+        to: Reschedule,
+    ) -> Result<Option<TimerInstance>, KernelError> {
+        self.reschedule_at_version(tenant_id, timer_id, to, None).await
+    }
+
+    /// [`HorologyKernel::reschedule`] that only goes ahead while the timer is still at
+    /// `expected_state_version`; see [`HorologyKernel::cancel_at_version`].
+    pub async fn reschedule_at_version(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+        to: Reschedule,
+        expected_state_version: Option<u64>,
+    ) -> Result<Option<TimerInstance>, KernelError> {
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        let now = self.state.now();
+        let mut timers = self.state.timers.shard(&timer_id).write().await;
+        let Some(entry) = timers
+            .get_mut(&timer_id)
+            .filter(|t| t.tenant_id == tenant_id)
+        else {
+            return Ok(None);
+        };
+        entry.check_state_version(expected_state_version)?;
+        if entry.is_terminal() {
+            return Err(KernelError::NotPending);
+        }
+        let fire_at = match to {
+            Reschedule::FireAt(fire_at) => fire_at,
+            Reschedule::Delta(delta) => entry
+                .fire_at
+                .checked_add_signed(delta)
+                .ok_or(KernelError::InvalidFireTime)?,
+        };
+        let delay = (fire_at - now)
+            .to_std()
+            .ok()
+            .filter(|delay| !delay.is_zero())
+            .ok_or(KernelError::InvalidFireTime)?;
+        self.check_max_duration(tenant_id, delay)?;
+
+        let previous_fire_at = entry.fire_at;
+        let mut updated = entry.clone();
+        let duration = fire_at - updated.created_at;
+        updated.state_version += 1;
+        updated.fire_at = fire_at;
+        updated.duration_ms = duration.num_milliseconds().max(0) as u64;
+        if updated.duration_us.is_some() || fire_at.timestamp_subsec_nanos() % 1_000_000 != 0 {
+            updated.duration_us = duration.num_microseconds().map(|us| us.max(0) as u64);
+        }
+        self.state.persist(&updated).await?;
+        *entry = updated.clone();
+        drop(timers);
+
+        self.state.arm(&updated, delay + updated.kind.grace());
+        self.state.publish(TimerEvent::Rescheduled {
+            timer: updated.clone(),
+            previous_fire_at,
+        });
+        Ok(Some(updated))
+    }
+
+    /// Re-arms a fired timer `delay` from now, keeping its id, metadata, labels, and action bundle,
+    /// for reminders a human has put off. Only timers no subscriber has acknowledged yet can be
+    /// snoozed; the earlier delivery receipts are dropped so the next fire is tracked afresh.
+    /// Returns `Ok(None)` when the tenant has no such timer.
+    pub async fn snooze(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+        delay: Duration,
+    ) -> Result<Option<TimerInstance>, KernelError> {
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        if delay.is_zero() {
+            return Err(KernelError::InvalidDuration);
+        }
+        self.check_max_duration(tenant_id, delay)?;
+        let mut timers = self.state.timers.shard(&timer_id).write().await;
+        let Some(entry) = timers
+            .get_mut(&timer_id)
+            .filter(|t| t.tenant_id == tenant_id)
+        else {
+            return Ok(None);
+        };
+        let settled = self.state.deliveries.is_acknowledged(timer_id);
+        if entry.status != TimerStatus::Fired || settled {
+            return Err(KernelError::NotSnoozable);
+        }
+
+        let previous_fire_at = entry.fire_at;
+        let fire_at = self.state.now() + chrono::Duration::from_std(delay).unwrap_or_default();
+        let mut updated = entry.clone();
+        updated.status = TimerStatus::Scheduled;
+        updated.state_version += 1;
+        updated.fire_at = fire_at;
+        updated.duration_ms = (fire_at - updated.created_at).num_milliseconds().max(0) as u64;
+        updated.duration_us = None;
+        updated.fired_at = None;
+        updated.fire_latency_ms = None;
+        updated.fire_latency_us = None;
+        updated.budget_outcome = None;
+        updated.snooze_count += 1;
+        self.state.persist(&updated).await?;
+        *entry = updated.clone();
+        drop(timers);
+
+        self.state.deliveries.forget(timer_id);
+        self.state.arm(&updated, delay);
+        self.state.publish(TimerEvent::Rescheduled {
+            timer: updated.clone(),
+            previous_fire_at,
+        });
+        Ok(Some(updated))
+    }
+
+    /// Checks in with a pending watchdog timer, moving its fire time to its interval from now so it
+    /// only fires once check-ins stop. Returns `Ok(None)` when the tenant has no such timer.
+    pub async fn kick_watchdog(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+    ) -> Result<Option<TimerInstance>, KernelError> {
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        let now = self.state.now();
+        let mut timers = self.state.timers.shard(&timer_id).write().await;
+        let Some(entry) = timers
+            .get_mut(&timer_id)
+            .filter(|t| t.tenant_id == tenant_id)
+        else {
+            return Ok(None);
+        };
+        let TimerKind::Watchdog { interval_ms } = entry.kind else {
+            return Err(KernelError::NotWatchdog);
+        };
+        if entry.is_terminal() {
+            return Err(KernelError::NotPending);
+        }
+
+        let previous_fire_at = entry.fire_at;
+        let mut updated = entry.clone();
+        let fire_at = now + chrono::Duration::milliseconds(interval_ms as i64);
+        updated.state_version += 1;
+        updated.fire_at = fire_at;
+        updated.duration_ms = (fire_at - updated.created_at).num_milliseconds().max(0) as u64;
+        updated.duration_us = None;
+        updated.last_kicked_at = Some(now);
+        self.state.persist(&updated).await?;
+        *entry = updated.clone();
+        drop(timers);
+
+        self.state.arm(&updated, Duration::from_millis(interval_ms));
+        self.state.publish(TimerEvent::Rescheduled {
+            timer: updated.clone(),
+            previous_fire_at,
+        });
+        Ok(Some(updated))
+    }
+
+    /// Settles a pending deadline timer before its grace window runs out, so it never fails.
+    /// Returns `Ok(None)` when the tenant has no such timer.
+    pub async fn acknowledge(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+    ) -> Result<Option<TimerInstance>, KernelError> {
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        let mut timers = self.state.timers.shard(&timer_id).write().await;
+        let Some(entry) = timers
+            .get_mut(&timer_id)
+            .filter(|t| t.tenant_id == tenant_id)
+        else {
+            return Ok(None);
+        };
+        if !matches!(entry.kind, TimerKind::Deadline { .. }) {
+            return Err(KernelError::NotDeadline);
+        }
+        if entry.is_terminal() {
+            return Err(KernelError::NotPending);
+        }
+
+        let mut updated = entry.clone();
+        updated.status = TimerStatus::Acknowledged;
+        updated.state_version += 1;
+        updated.acknowledged_at = Some(self.state.now());
+        self.state.persist(&updated).await?;
+        *entry = updated.clone();
+        drop(timers);
+
+        self.state.notify_waiters(&updated);
+        self.state.publish(TimerEvent::Acknowledged(updated.clone()));
+        Ok(Some(updated))
+    }
+
+    /// Records the orchestrator's results for a fired timer's action bundle: the timer settles
+    /// when there is a successful result for every action in the bundle and fails with
+    /// [`FailureReason::ActionsFailed`] otherwise, counting actions without a result as failed.
+    /// Reporting on a timer that already has a report returns it unchanged, so retried reports are
+    /// harmless. Returns `Ok(None)` when the tenant has no such timer.
+    pub async fn report_execution(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+        actions: Vec<ActionReport>,
+    ) -> Result<Option<TimerInstance>, KernelError> {
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        let mut timers = self.state.timers.shard(&timer_id).write().await;
+        let Some(entry) = timers
+            .get_mut(&timer_id)
+            .filter(|t| t.tenant_id == tenant_id)
+        else {
+            return Ok(None);
+        };
+        if entry.execution.is_some() {
+            return Ok(Some(entry.clone()));
+        }
+        if entry.status != TimerStatus::Fired {
+            return Err(KernelError::NotFired);
+        }
+
+        let now = self.state.now();
+        let failures: Vec<&ActionReport> = actions.iter().filter(|action| !action.success).collect();
+        // A report that does not cover every action of the bundle cannot settle the timer.
+        let expected = entry
+            .action_bundle
+            .as_ref()
+            .and_then(|bundle| bundle.get("actions"))
+            .and_then(|actions| actions.as_array())
+            .map_or(actions.len(), Vec::len);
+        let unreported = expected.saturating_sub(actions.len());
+        let mut updated = entry.clone();
+        updated.state_version += 1;
+        if !failures.is_empty() || expected != actions.len() {
+            let error = match failures.first() {
+                Some(first) => first.error.clone(),
+                None => Some(format!("{} results reported for {expected} actions", actions.len())),
+            };
+            updated.status = TimerStatus::Failed;
+            updated.failed_at = Some(now);
+            updated.failure_reason = Some(FailureReason::ActionsFailed {
+                failed: (failures.len() + unreported) as u32,
+                total: expected.max(actions.len()) as u32,
+                error,
+            });
+        } else {
+            updated.status = TimerStatus::Settled;
+            updated.settled_at = Some(now);
+        }
+        updated.execution = Some(ExecutionReport {
+            actions,
+            completed_at: now,
+        });
+        self.state.persist(&updated).await?;
+        *entry = updated.clone();
+        drop(timers);
+
+        self.state.publish(if updated.status == TimerStatus::Settled {
+            TimerEvent::Settled(updated.clone())
+        } else {
+            TimerEvent::Failed(updated.clone())
+        });
+        Ok(Some(updated))
+    }
+
+    /// Settles a fired timer whose orchestrator never reported, as an operator intervention: the
+    /// timer settles, or fails with [`FailureReason::FailedByOperator`] when `failure` is given.
+    /// Timers that already settled or failed are returned unchanged. Returns `Ok(None)` when the
+    /// tenant has no such timer.
+    pub async fn force_settle(
+        &self,
+        tenant_id: &str,
+        timer_id: Uuid,
+        failure: Option<String>,
+    ) -> Result<Option<TimerInstance>, KernelError> {
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        let mut timers = self.state.timers.shard(&timer_id).write().await;
+        let Some(entry) = timers
+            .get_mut(&timer_id)
+            .filter(|t| t.tenant_id == tenant_id)
+        else {
+            return Ok(None);
+        };
+        match entry.status {
+            TimerStatus::Settled | TimerStatus::Failed => return Ok(Some(entry.clone())),
+            TimerStatus::Fired => {}
+            _ => return Err(KernelError::NotFired),
+        }
+
+        let now = self.state.now();
+        let mut updated = entry.clone();
+        updated.state_version += 1;
+        match failure {
+            Some(reason) => {
+                updated.status = TimerStatus::Failed;
+                updated.failed_at = Some(now);
+                updated.failure_reason = Some(FailureReason::FailedByOperator { reason });
+            }
+            None => {
+                updated.status = TimerStatus::Settled;
+                updated.settled_at = Some(now);
+            }
+        }
+        self.state.persist(&updated).await?;
+        *entry = updated.clone();
+        drop(timers);
+
+        self.state.publish(if updated.status == TimerStatus::Settled {
+            TimerEvent::Settled(updated.clone())
+        } else {
+            TimerEvent::Failed(updated.clone())
+        });
+        Ok(Some(updated))
+    }
+
+    /// Converges the tenant's manifest-owned timers onto `manifest`: schedules new entries,
+    /// replaces changed ones, and cancels timers dropped from it. Every schedule the apply needs is
+    /// validated before anything changes, and the timers it replaces or drops are only cancelled
+    /// once all of them are scheduled. A schedule refused partway, e.g. by a quota or rate limit,
+    /// cancels the ones this apply already made and leaves the previous timers in place.
+    pub async fn apply_manifest(
+        &self,
+        tenant_id: &str,
+        requested_by: &str,
+        manifest: &TimerManifest,
+    ) -> Result<ApplyReport, KernelError> {
+        manifest.validate()?;
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        let owned: Vec<_> = self
+            .state
+            .timers
+            .read()
+            .await
+            .values()
+            .filter(|t| {
+                t.tenant_id == tenant_id
+                    && t.status != TimerStatus::Cancelled
+                    && t.labels.get(MANIFEST_LABEL) == Some(&manifest.name)
+            })
+            .cloned()
+            .collect();
+        let steps = manifest::plan(manifest.specs(tenant_id, requested_by), owned);
+        let now = self.state.now();
+        for step in &steps {
+            if let ManifestStep::Create(spec) | ManifestStep::Replace { spec, .. } = step {
+                self.resolve_fire_at(spec, now)?;
+            }
+        }
+
+        // Replaced and dropped timers are cancelled after the schedules, so they do not count
+        // against the pending quota those schedules are checked with.
+        let superseded: HashSet<Uuid> = steps
+            .iter()
+            .filter_map(|step| match step {
+                ManifestStep::Replace { previous, .. } => Some(previous.id),
+                ManifestStep::Remove(timer) => Some(timer.id),
+                _ => None,
+            })
+            .collect();
+        let mut report = ApplyReport::default();
+        let mut stale = Vec::new();
+        for step in steps {
+            let scheduled = match step {
+                ManifestStep::Create(spec) => self
+                    .schedule_superseding(spec, &superseded)
+                    .await
+                    .map(|timer| report.created.push(timer)),
+                ManifestStep::Replace { previous, spec } => {
+                    stale.push((*previous, "replaced by manifest"));
+                    self.schedule_superseding(spec, &superseded)
+                        .await
+                        .map(|timer| report.updated.push(timer))
+                }
+                ManifestStep::Unchanged(timer) => {
+                    report.unchanged.push(timer);
+                    Ok(())
+                }
+                ManifestStep::Remove(timer) => {
+                    stale.push((timer, "removed from manifest"));
+                    Ok(())
+                }
+            };
+            if let Err(error) = scheduled {
+                for timer in report.created.iter().chain(&report.updated) {
+                    self.cancel(
+                        tenant_id,
+                        timer.id,
+                        Some(format!("rolled back failed apply of manifest {}", manifest.name)),
+                        Some(requested_by.to_string()),
+                    )
+                    .await;
+                }
+                return Err(error);
+            }
+        }
+        for (stale, reason) in stale {
+            let cancelled = self
+                .cancel(
+                    tenant_id,
+                    stale.id,
+                    Some(format!("{reason} {}", manifest.name)),
+                    Some(requested_by.to_string()),
+                )
+                .await;
+            if let Some(timer) = cancelled.filter(|t| t.status == TimerStatus::Cancelled) {
+                report.cancelled.push(timer);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Migrates pending control-plane timers into the kernel, keeping each record's id, creation
+    /// time, and fire time. Records whose id or idempotency key the kernel already knows are
+    /// skipped, so re-running an import is safe. Timers that came due before the import fire
+    /// immediately. Imports bypass quotas and the tenant horizon, and are not metered as schedules.
+    pub async fn import_legacy(
+        &self,
+        records: Vec<LegacyTimerRecord>,
+    ) -> Result<ImportReport, KernelError> {
+        if !self.is_active() {
+            return Err(KernelError::Standby);
+        }
+        let mut report = ImportReport::default();
+        for record in records {
+            if !record.is_pending() {
+                report.skipped_terminal.push(record.id);
+                continue;
+            }
+            let spec = record.to_spec();
+            if let Err(error) = self.state.config.limits.check(&spec) {
+                report.rejected.push((record.id, error.to_string()));
+                continue;
+            }
+            let duration = (record.fire_at - record.created_at)
+                .to_std()
+                .unwrap_or_default();
+            let timer = TimerInstance::from_spec(
+                &spec,
+                record.id,
+                record.created_at,
+                record.fire_at,
+                duration,
+            );
+            {
+                let mut timers = self.state.timers.write().await;
+                let key = spec.idempotency_key.as_ref();
+                if timers.contains_key(&timer.id)
+                    || self.state.replay(&timers, &spec.tenant_id, key).is_some()
+                {
+                    report.already_present.push(timer.id);
+                    continue;
+                }
+                self.state.persist(&timer).await?;
+                self.state.remember_key(&timer);
+                timers.insert(timer.id, timer.clone());
+            }
+            self.state.publish(TimerEvent::Scheduled(timer.clone()));
+            let delay = (timer.due_at() - self.state.now()).to_std().unwrap_or_default();
+            self.state.arm(&timer, delay);
+            report.imported.push(timer);
+        }
+        Ok(report)
+    }
+
+    /// With [`SchedulerConfig::terminal_retention`] set, finished timers evicted from memory are
+    /// looked up in the store.
+    pub async fn get(&self, tenant_id: &str, timer_id: Uuid) -> Option<TimerInstance> {
+        {
+            let timers = self.state.timers.shard(&timer_id).read().await;
+            if let Some(timer) = timers.get(&timer_id) {
+                return Some(timer.clone()).filter(|t| t.tenant_id == tenant_id);
+            }
+        }
+        self.state.config.terminal_retention?;
+        match self.state.store.get(timer_id).await {
+            Ok(timer) => timer.filter(|t| t.tenant_id == tenant_id && t.is_terminal()),
+            Err(error) => {
+                tracing::warn!(%error, %timer_id, "failed to look up evicted timer");
+                None
+            }
+        }
+    }
+
+    /// Drops finished timers outside [`SchedulerConfig::terminal_retention`] from memory, along
+    /// with their idempotency keys and delivery receipts, and returns how many were evicted. They stay in the store, so
+    /// [`HorologyKernel::get`] still finds them, but listings no longer include them and a retried
+    /// schedule with an evicted timer's key creates a new timer.
+    pub async fn evict_terminal_timers(&self) -> usize {
+        let Some(retention) = self.state.config.terminal_retention else {
+            return 0;
+        };
+        let mut timers = self.state.timers.write().await;
+        let expired = retention.expired(timers.values(), self.state.now());
+        let mut idempotency = self.state.idempotency();
+        for id in &expired {
+            if let Some(timer) = timers.remove(id) {
+                if let Some(key) = timer.idempotency_key {
+                    idempotency.remove(&(timer.tenant_id, key));
+                }
+            }
+            self.state.deliveries.forget(*id);
+        }
+        expired.len()
+    }
+
+    /// Resolves once the timer reaches a terminal state. Waiters are registered per timer so
+    /// callers never need to scan the event stream for their event.
+    pub async fn wait(&self, tenant_id: &str, timer_id: Uuid) -> TimerOutcome {
+        let receiver = {
+            let timers = self.state.timers.shard(&timer_id).read().await;
+            let timer = match timers.get(&timer_id).filter(|t| t.tenant_id == tenant_id) {
+                Some(timer) => timer,
+                None => return TimerOutcome::NotFound,
+            };
+            if timer.is_terminal() {
+                return TimerOutcome::from_terminal(timer.clone());
+            }
+            // Registering while the shard's read lock is held guarantees the terminal
+            // transition, which needs its write lock, observes this waiter. Waits that were given
+            // up, e.g. by a disconnected WaitTimer client, are dropped here, so a long-lived timer
+            // only keeps as many waiters as are still listening.
+            let (tx, rx) = oneshot::channel();
+            let mut waiters = self.state.waiters.lock().expect("waiter registry poisoned");
+            let waiters = waiters.entry(timer_id).or_default();
+            waiters.retain(|waiter| !waiter.is_closed());
+            waiters.push(tx);
+            rx
+        };
+
+        receiver.await.unwrap_or(TimerOutcome::NotFound)
+    }
+
+    pub async fn list(&self, tenant_id: &str) -> Vec<TimerInstance> {
+        self.list_scope(&TenantScope::Tenant(tenant_id.to_string())).await
+    }
+
+    /// Lists timers for a tenant or, for an organization scope, across all of its projects.
+    pub async fn list_scope(&self, scope: &TenantScope) -> Vec<TimerInstance> {
+        self.list_matching(scope, &TimerFilter::default(), TimerOrder::default()).await
+    }
+
+    /// Lists timers in scope that satisfy `filter`, sorted by `order`. Only matching timers are
+    /// cloned out of the table.
+    pub async fn list_matching(
+        &self,
+        scope: &TenantScope,
+        filter: &TimerFilter,
+        order: TimerOrder,
+    ) -> Vec<TimerInstance> {
+        let directory = self.tenants().clone();
+        let timers = self.state.timers.read().await;
+        let mut timers: Vec<_> = timers
+            .values()
+            .filter(|t| directory.in_scope(scope, &t.tenant_id) && filter.matches(t))
+            .cloned()
+            .collect();
+        order.sort(&mut timers);
+        timers
+    }
+
+    /// Lists one page of the timers in scope that satisfy `filter`. A request without a token
+    /// takes a snapshot of the whole result; its tokens page through that snapshot, so a full walk
+    /// neither skips nor repeats timers that change between requests.
+    pub async fn list_page(
+        &self,
+        scope: &TenantScope,
+        filter: &TimerFilter,
+        order: TimerOrder,
+        page: PageRequest,
+    ) -> Result<TimerPage, KernelError> {
+        if let Some(token) = page.page_token.as_deref() {
+            return Ok(self
+                .state
+                .snapshots
+                .next_page(&page.listing, token, page.page_size)?);
+        }
+        let taken_at = self.state.now();
+        let timers = self.list_matching(scope, filter, order).await;
+        let owner = match scope {
+            TenantScope::Tenant(tenant_id) | TenantScope::Organization(tenant_id) => tenant_id,
+            TenantScope::All => pagination::ALL_TENANTS_OWNER,
+        };
+        Ok(self
+            .state
+            .snapshots
+            .first_page(owner, &page.listing, timers, taken_at, page.page_size)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
     async fn schedule_and_fire_emits_events() {
         tracing_subscriber::fmt::try_init().ok();
         let kernel = HorologyKernel::new(SchedulerConfig::default());
@@ -278,82 +2873,2043 @@ mod tests {
             .schedule(TimerSpec {
                 tenant_id: "tenant-a".into(),
                 requested_by: "agent-1".into(),
-                name: Some("integration-test".into()),
-                duration_ms: 50,
-                fire_at: None,
-                metadata: None,
-                labels: HashMap::new(),
-                action_bundle: None,
-                agent_binding: None,
+                name: Some("integration-test".into()),
+                duration_ms: 50,
+                fire_at: None,
+                metadata: None,
+                labels: HashMap::new(),
+                action_bundle: None,
+                agent_binding: None,
+                ..Default::default()
+            })
+            .await
+            .expect("schedule timer");
+
+        let scheduled = events.recv().await.expect("scheduled event");
+        assert!(matches!(scheduled, TimerEvent::Scheduled(_)));
+
+        let fired = events.recv().await.expect("fired event");
+        match fired {
+            TimerEvent::Fired(fired_timer) => {
+                assert_eq!(fired_timer.id, timer.id);
+                assert_eq!(fired_timer.status, TimerStatus::Fired);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelling_prevents_fire_event() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let mut events = kernel.subscribe();
+
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                name: None,
+                duration_ms: 200,
+                fire_at: None,
+                metadata: None,
+                labels: HashMap::new(),
+                action_bundle: None,
+                agent_binding: None,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let _ = events.recv().await.expect("scheduled event");
+
+        let cancelled = kernel
+            .cancel(
+                "tenant-a",
+                timer.id,
+                Some("manual".into()),
+                Some("agent-1".into()),
+            )
+            .await
+            .expect("cancel timer");
+
+        assert_eq!(cancelled.status, TimerStatus::Cancelled);
+
+        let cancel_event = events.recv().await.expect("cancel event");
+        match cancel_event {
+            TimerEvent::Cancelled {
+                timer: cancelled_timer,
+                ..
+            } => {
+                assert_eq!(cancelled_timer.id, timer.id);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        // Ensure no fired event occurs by waiting longer than the duration
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        while let Ok(event) = events.try_recv() {
+            assert!(
+                !matches!(event, TimerEvent::Fired(_)),
+                "timer should not emit fired event after cancellation"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn wait_resolves_on_fire_and_cancel() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let spec = |duration_ms| TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            name: None,
+            duration_ms,
+            fire_at: None,
+            metadata: None,
+            labels: HashMap::new(),
+            action_bundle: None,
+            agent_binding: None,
+            ..Default::default()
+        };
+
+        let fired = kernel.schedule(spec(30)).await.unwrap();
+        match kernel.wait("tenant-a", fired.id).await {
+            TimerOutcome::Fired(timer) => assert_eq!(timer.id, fired.id),
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+        // Waiting on an already terminal timer resolves immediately.
+        assert!(matches!(
+            kernel.wait("tenant-a", fired.id).await,
+            TimerOutcome::Fired(_)
+        ));
+
+        let cancelled = kernel.schedule(spec(10_000)).await.unwrap();
+        let waiter = {
+            let kernel = kernel.clone();
+            tokio::spawn(async move { kernel.wait("tenant-a", cancelled.id).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        kernel
+            .cancel("tenant-a", cancelled.id, None, None)
+            .await
+            .expect("cancel timer");
+        assert!(matches!(
+            waiter.await.unwrap(),
+            TimerOutcome::Cancelled(_)
+        ));
+
+        assert!(matches!(
+            kernel.wait("tenant-b", cancelled.id).await,
+            TimerOutcome::NotFound
+        ));
+    }
+
+    #[tokio::test]
+    async fn abandoned_waits_do_not_accumulate() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 3_600_000,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let registered = || kernel.state.waiters.lock().unwrap()[&timer.id].len();
+        for _ in 0..5 {
+            let wait = kernel.wait("tenant-a", timer.id);
+            assert!(tokio::time::timeout(Duration::from_millis(1), wait)
+                .await
+                .is_err());
+        }
+        assert_eq!(registered(), 1);
+
+        let waiter = {
+            let kernel = kernel.clone();
+            tokio::spawn(async move { kernel.wait("tenant-a", timer.id).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(registered(), 1);
+        kernel.cancel("tenant-a", timer.id, None, None).await;
+        assert!(matches!(waiter.await.unwrap(), TimerOutcome::Cancelled(_)));
+    }
+
+    #[tokio::test]
+    async fn preview_validates_without_scheduling() {
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            max_duration_ms: Some(1_000),
+            ..SchedulerConfig::default()
+        });
+        let mut spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            name: None,
+            duration_ms: 500,
+            fire_at: None,
+            metadata: None,
+            labels: HashMap::new(),
+            action_bundle: None,
+            agent_binding: None,
+            ..Default::default()
+        };
+
+        let fire_times = kernel.preview(&spec, 5).expect("preview");
+        assert_eq!(fire_times.len(), 1);
+        assert!(fire_times[0] > Utc::now());
+        assert!(kernel.list("tenant-a").await.is_empty());
+
+        spec.duration_ms = 5_000;
+        assert!(matches!(
+            kernel.preview(&spec, 1),
+            Err(KernelError::InvalidDuration)
+        ));
+    }
+
+    #[tokio::test]
+    async fn fired_timers_are_labelled_against_accuracy_budget() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 20,
+                accuracy_budget_ms: Some(5_000),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        match kernel.wait("tenant-a", timer.id).await {
+            TimerOutcome::Fired(fired) => {
+                assert!(fired.fire_latency_ms.is_some());
+                assert_eq!(fired.budget_outcome, Some(BudgetOutcome::WithinBudget));
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+        let stats = kernel.budget_stats("tenant-a");
+        assert_eq!(stats.evaluated, 1);
+        assert_eq!(stats.violations, 0);
+    }
+
+    #[tokio::test]
+    async fn v7_ids_sort_in_creation_order() {
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            id_scheme: TimerIdScheme::UuidV7,
+            ..SchedulerConfig::default()
+        });
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let timer = kernel
+                .schedule(TimerSpec {
+                    tenant_id: "tenant-a".into(),
+                    requested_by: "agent-1".into(),
+                    duration_ms: 60_000,
+                    ..Default::default()
+                })
+                .await
+                .expect("schedule timer");
+            assert_eq!(timer.id.get_version_num(), 7);
+            ids.push(timer.id);
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+        assert_eq!("v4".parse(), Ok(TimerIdScheme::UuidV4));
+    }
+
+    #[tokio::test]
+    async fn standby_holds_fires_until_promoted() {
+        let primary = HorologyKernel::new(SchedulerConfig::default());
+        let standby = HorologyKernel::new(SchedulerConfig {
+            standby: true,
+            ..SchedulerConfig::default()
+        });
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 20,
+            ..Default::default()
+        };
+        assert!(matches!(
+            standby.schedule(spec.clone()).await,
+            Err(KernelError::Standby)
+        ));
+
+        let timer = primary.schedule(spec).await.expect("schedule on primary");
+        standby.apply(TimerEvent::Scheduled(timer.clone())).await;
+        standby.apply(TimerEvent::Scheduled(timer.clone())).await;
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let held = standby.get("tenant-a", timer.id).await.expect("hydrated");
+        assert_eq!(held.status, TimerStatus::Scheduled);
+
+        let mut events = standby.subscribe();
+        standby.promote();
+        let fired = tokio::time::timeout(Duration::from_millis(50), events.recv())
+            .await
+            .expect("fires promptly after promotion")
+            .expect("event");
+        assert!(matches!(fired, TimerEvent::Fired(t) if t.id == timer.id));
+    }
+
+    #[tokio::test]
+    async fn pre_fire_notice_precedes_fire_and_skips_cancelled_timers() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let mut events = kernel.subscribe();
+        let spec = |duration_ms| TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms,
+            pre_fire_notice_ms: Some(40),
+            ..Default::default()
+        };
+        let timer = kernel.schedule(spec(80)).await.unwrap();
+        let cancelled = kernel.schedule(spec(60)).await.unwrap();
+        kernel.cancel("tenant-a", cancelled.id, None, None).await.unwrap();
+        // Less lead time than the notice: the notice goes out straight away.
+        let short = kernel.schedule(spec(10)).await.unwrap();
+
+        let mut notices = Vec::new();
+        loop {
+            let event = tokio::time::timeout(Duration::from_millis(500), events.recv())
+                .await
+                .expect("timer fires")
+                .expect("open channel");
+            match event {
+                TimerEvent::PreFire { timer, fires_in_ms } => notices.push((timer.id, fires_in_ms)),
+                TimerEvent::Fired(fired) if fired.id == timer.id => break,
+                _ => {}
+            }
+        }
+        assert_eq!(notices, vec![(short.id, 10), (timer.id, 40)]);
+    }
+
+    #[tokio::test]
+    async fn microsecond_timers_keep_their_resolution() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_us: Some(2_250),
+                ..Default::default()
+            })
+            .await
+            .expect("schedule");
+        assert_eq!(timer.duration_us, Some(2_250));
+        assert_eq!(timer.duration_ms, 2);
+        assert_eq!(
+            (timer.fire_at - timer.created_at).num_microseconds(),
+            Some(2_250)
+        );
+
+        let fired = match kernel.wait("tenant-a", timer.id).await {
+            TimerOutcome::Fired(fired) => fired,
+            other => panic!("expected fire, got {other:?}"),
+        };
+        let latency_us = fired.fire_latency_us.expect("latency recorded");
+        assert!(fired.fired_at.unwrap() >= fired.fire_at);
+        assert_eq!(fired.fire_latency_ms, Some(latency_us / 1_000));
+
+        let coarse = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 5,
+                ..Default::default()
+            })
+            .await
+            .expect("schedule");
+        assert_eq!(coarse.duration_us, None);
+    }
+
+    #[tokio::test]
+    async fn restore_reports_timers_due_during_downtime() {
+        let store = InMemoryTimerStore::default();
+        let before =
+            HorologyKernel::with_store(SchedulerConfig::default(), Arc::new(store.clone()));
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+        let pending = before.schedule(spec.clone()).await.expect("schedule");
+        let mut overdue = before.schedule(spec).await.expect("schedule");
+        let crashed_at = Utc::now() - chrono::Duration::seconds(5);
+        store.record_heartbeat(crashed_at).await.unwrap();
+        // Pretend the outage outlasted this timer.
+        overdue.fire_at = Utc::now() - chrono::Duration::seconds(2);
+        store.upsert(&overdue).await.unwrap();
+
+        let after = HorologyKernel::with_store(SchedulerConfig::default(), Arc::new(store));
+        let mut events = after.subscribe();
+        let report = after.restore_from_store().await.expect("restore");
+        assert_eq!(report.downtime_started_at, Some(crashed_at));
+        assert_eq!(report.restored, 2);
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].timer_id, overdue.id);
+        assert_eq!(report.entries[0].outcome, GapOutcome::FiredLate);
+        assert!(report.entries[0].late_by_ms >= 2_000);
+        assert_eq!(after.fire_gap_report(), Some(report));
+
+        let fired = tokio::time::timeout(Duration::from_millis(100), events.recv())
+            .await
+            .expect("overdue timer fires on restore")
+            .expect("event");
+        assert!(matches!(fired, TimerEvent::Fired(t) if t.id == overdue.id));
+        let still_pending = after.get("tenant-a", pending.id).await.expect("restored");
+        assert_eq!(still_pending.status, TimerStatus::Scheduled);
+    }
+
+    #[tokio::test]
+    async fn missed_fire_policy_skips_or_fires_overdue_timers() {
+        let store = InMemoryTimerStore::default();
+        let before =
+            HorologyKernel::with_store(SchedulerConfig::default(), Arc::new(store.clone()));
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+        let mut skipped = before.schedule(spec.clone()).await.expect("schedule");
+        let mut within_grace = before
+            .schedule(TimerSpec {
+                missed_fire_policy: Some(MissedFirePolicy::FireWithGrace { grace_ms: 60_000 }),
+                ..spec
+            })
+            .await
+            .expect("schedule");
+        for timer in [&mut skipped, &mut within_grace] {
+            timer.fire_at = Utc::now() - chrono::Duration::seconds(2);
+            store.upsert(timer).await.unwrap();
+        }
+
+        let config = SchedulerConfig {
+            missed_fire_policy: MissedFirePolicy::SkipAndMarkMissed,
+            ..SchedulerConfig::default()
+        };
+        let after = HorologyKernel::with_store(config, Arc::new(store.clone()));
+        let mut events = after.subscribe();
+        let report = after.restore_from_store().await.expect("restore");
+        let outcome = |id| {
+            report
+                .entries
+                .iter()
+                .find(|entry| entry.timer_id == id)
+                .map(|entry| entry.outcome)
+        };
+        assert_eq!(outcome(skipped.id), Some(GapOutcome::Missed));
+        assert_eq!(outcome(within_grace.id), Some(GapOutcome::FiredLate));
+
+        let missed = events.recv().await.expect("event");
+        assert!(
+            matches!(missed, TimerEvent::Missed { timer, late_by_ms } if timer.id == skipped.id && late_by_ms >= 2_000)
+        );
+        assert!(matches!(
+            after.wait("tenant-a", skipped.id).await,
+            TimerOutcome::Missed(_)
+        ));
+        assert!(matches!(
+            after.wait("tenant-a", within_grace.id).await,
+            TimerOutcome::Fired(_)
+        ));
+        let persisted = store.load_all().await.unwrap();
+        assert!(persisted
+            .iter()
+            .any(|timer| timer.id == skipped.id && timer.status == TimerStatus::Missed));
+        assert_eq!(
+            "grace:250".parse(),
+            Ok(MissedFirePolicy::FireWithGrace { grace_ms: 250 })
+        );
+    }
+
+    #[tokio::test]
+    async fn revoking_a_session_cancels_only_its_pending_timers() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let spec = |tenant_id: &str, session_id: Option<&str>| TimerSpec {
+            tenant_id: tenant_id.into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            session_id: session_id.map(String::from),
+            ..Default::default()
+        };
+        let first = kernel
+            .schedule(spec("tenant-a", Some("run-1")))
+            .await
+            .expect("schedule");
+        let second = kernel
+            .schedule(spec("tenant-a", Some("run-1")))
+            .await
+            .expect("schedule");
+        let other_session = kernel
+            .schedule(spec("tenant-a", Some("run-2")))
+            .await
+            .expect("schedule");
+        let other_tenant = kernel
+            .schedule(spec("tenant-b", Some("run-1")))
+            .await
+            .expect("schedule");
+        kernel.cancel("tenant-a", second.id, None, None).await;
+
+        let revoked = kernel
+            .revoke_session("tenant-a", "run-1", Some("supervisor".into()))
+            .await;
+        assert_eq!(
+            revoked.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![first.id]
+        );
+        assert_eq!(
+            revoked[0].cancel_reason.as_deref(),
+            Some("session run-1 revoked")
+        );
+        for (tenant_id, id) in [("tenant-a", other_session.id), ("tenant-b", other_tenant.id)] {
+            let timer = kernel.get(tenant_id, id).await.expect("timer");
+            assert_eq!(timer.status, TimerStatus::Scheduled);
+        }
+    }
+
+    #[tokio::test]
+    async fn tenant_quotas_reject_schedules_over_the_limit() {
+        let config = SchedulerConfig {
+            quotas: TenantQuota {
+                max_active_timers: Some(1),
+                ..Default::default()
+            },
+            ..SchedulerConfig::default()
+        };
+        let kernel = HorologyKernel::new(config);
+        kernel.set_tenant_policy(
+            "tenant-b",
+            TenantPolicy {
+                quota: TenantQuota {
+                    max_active_timers: Some(5),
+                    max_schedules_per_minute: Some(2),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let mut system = kernel.subscribe_system();
+        let spec = |tenant_id: &str| TimerSpec {
+            tenant_id: tenant_id.into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+
+        let first = kernel.schedule(spec("tenant-a")).await.expect("schedule");
+        let error = kernel.schedule(spec("tenant-a")).await.unwrap_err();
+        assert!(matches!(
+            error,
+            KernelError::QuotaExceeded {
+                quota: QuotaKind::ActiveTimers,
+                limit: 1,
+                ..
+            }
+        ));
+        let event = system.recv().await.expect("system event");
+        assert_eq!(
+            event.kind,
+            SystemEventKind::QuotaExceeded {
+                tenant_id: "tenant-a".into(),
+                quota: "active_timers".into(),
+            }
+        );
+        // Finished timers no longer count against the active limit.
+        kernel.cancel("tenant-a", first.id, None, None).await;
+        kernel.schedule(spec("tenant-a")).await.expect("schedule");
+
+        // Five attempts span at most two minutes, which allow four schedules between them.
+        let mut rate_limited = false;
+        for _ in 0..5 {
+            rate_limited |= matches!(
+                kernel.schedule(spec("tenant-b")).await,
+                Err(KernelError::QuotaExceeded {
+                    quota: QuotaKind::ScheduleRate,
+                    ..
+                })
+            );
+        }
+        assert!(rate_limited);
+    }
+
+    #[tokio::test]
+    async fn paginated_listing_walks_a_consistent_snapshot() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let spec = |name: &str| TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            name: Some(name.into()),
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+        for name in ["a", "b", "c", "d"] {
+            kernel.schedule(spec(name)).await.expect("schedule");
+        }
+        let scope = TenantScope::Tenant("tenant-a".into());
+        let page = |token: Option<String>| PageRequest {
+            listing: "all".into(),
+            page_size: 2,
+            page_token: token,
+        };
+        let first = kernel
+            .list_page(&scope, &TimerFilter::default(), TimerOrder::Name, page(None))
+            .await
+            .expect("first page");
+        assert_eq!(first.total, 4);
+
+        // Changes after the first page do not shift the walk.
+        kernel.cancel("tenant-a", first.timers[0].id, None, None).await;
+        kernel.schedule(spec("a0")).await.expect("schedule");
+        let second = kernel
+            .list_page(&scope, &TimerFilter::default(), TimerOrder::Name, page(first.next_page_token))
+            .await
+            .expect("second page");
+        let names: Vec<_> = first
+            .timers
+            .iter()
+            .chain(&second.timers)
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["a", "b", "c", "d"]);
+        assert!(second.next_page_token.is_none());
+        assert_eq!(second.snapshot_at, first.snapshot_at);
+
+        let stale = kernel
+            .list_page(&scope, &TimerFilter::default(), TimerOrder::Name, page(Some("x:1".into())))
+            .await;
+        assert!(matches!(stale, Err(KernelError::PageToken(PageTokenError::Malformed))));
+    }
+
+    #[tokio::test]
+    async fn timer_stats_count_statuses_horizon_and_labels() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let spec = |duration_ms, env: &str| TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms,
+            labels: HashMap::from([
+                ("env".to_string(), env.to_string()),
+                ("team".to_string(), "core".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let fired = kernel.schedule(spec(10, "prod")).await.expect("schedule");
+        let soon = kernel.schedule(spec(60_000, "prod")).await.expect("schedule");
+        let late = kernel.schedule(spec(120_000, "dev")).await.expect("schedule");
+        kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-b".into(),
+                ..spec(1_000, "prod")
+            })
+            .await
+            .expect("schedule");
+        kernel.wait("tenant-a", fired.id).await;
+
+        let scope = TenantScope::Tenant("tenant-a".into());
+        let stats = kernel.timer_stats(&scope, &["env".to_string()]).await;
+        assert_eq!((stats.counts.fired, stats.counts.total()), (1, 3));
+        assert_eq!(stats.next_fire_at, Some(soon.fire_at));
+        assert_eq!(stats.horizon, Some(late.fire_at));
+        let prod = stats.labels[&("env".to_string(), "prod".to_string())];
+        assert_eq!((prod.fired, prod.total()), (1, 2));
+        assert_eq!(stats.labels.len(), 2);
+        assert_eq!(kernel.timer_stats(&scope, &[]).await.labels.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn snoozing_re_arms_a_fired_timer_until_it_is_acknowledged() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60_000,
+                labels: HashMap::from([("kind".to_string(), "reminder".to_string())]),
+                ..Default::default()
+            })
+            .await
+            .expect("schedule");
+        let error = kernel
+            .snooze("tenant-a", timer.id, Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, KernelError::NotSnoozable));
+        kernel
+            .reschedule(
+                "tenant-a",
+                timer.id,
+                Reschedule::FireAt(Utc::now() + chrono::Duration::milliseconds(10)),
+            )
+            .await
+            .expect("reschedule");
+        assert!(matches!(
+            kernel.wait("tenant-a", timer.id).await,
+            TimerOutcome::Fired(_)
+        ));
+
+        let snoozed = kernel
+            .snooze("tenant-a", timer.id, Duration::from_millis(20))
+            .await
+            .expect("snooze")
+            .expect("timer exists");
+        assert_eq!(snoozed.status, TimerStatus::Scheduled);
+        assert_eq!(snoozed.snooze_count, 1);
+        assert_eq!(snoozed.labels["kind"], "reminder");
+        match kernel.wait("tenant-a", timer.id).await {
+            TimerOutcome::Fired(fired) => assert_eq!(fired.snooze_count, 1),
+            other => panic!("expected the snoozed timer to fire again, got {other:?}"),
+        }
+
+        kernel.deliveries().record(timer.id, "orchestrator", 1);
+        assert!(kernel.acknowledge_delivery("tenant-a", timer.id, "orchestrator").await);
+        let error = kernel
+            .snooze("tenant-a", timer.id, Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, KernelError::NotSnoozable));
+    }
+
+    #[tokio::test]
+    async fn watchdogs_fire_only_when_kicks_stop() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let watchdog = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 80,
+                kind: TimerKind::Watchdog { interval_ms: 80 },
+                ..Default::default()
+            })
+            .await
+            .expect("schedule");
+        for _ in 0..4 {
+            tokio::time::sleep(Duration::from_millis(40)).await;
+            let kicked = kernel
+                .kick_watchdog("tenant-a", watchdog.id)
+                .await
+                .expect("kick")
+                .expect("timer exists");
+            assert_eq!(kicked.status, TimerStatus::Scheduled);
+            assert!(kicked.last_kicked_at.is_some());
+        }
+        match kernel.wait("tenant-a", watchdog.id).await {
+            TimerOutcome::Fired(fired) => {
+                let deadline = fired.last_kicked_at.expect("kicked")
+                    + chrono::Duration::milliseconds(80);
+                assert!(fired.fired_at.expect("fired") >= deadline);
+            }
+            other => panic!("expected the watchdog to fire, got {other:?}"),
+        }
+        let error = kernel.kick_watchdog("tenant-a", watchdog.id).await.unwrap_err();
+        assert!(matches!(error, KernelError::NotPending));
+
+        let one_shot = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60_000,
+                ..Default::default()
+            })
+            .await
+            .expect("schedule");
+        let error = kernel.kick_watchdog("tenant-a", one_shot.id).await.unwrap_err();
+        assert!(matches!(error, KernelError::NotWatchdog));
+        let error = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 1_000,
+                kind: TimerKind::Watchdog { interval_ms: 0 },
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(error, KernelError::InvalidDuration));
+    }
+
+    #[tokio::test]
+    async fn deadline_timers_fail_unless_acknowledged_within_grace() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let mut events = kernel.subscribe();
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "sla-monitor".into(),
+            duration_ms: 30,
+            kind: TimerKind::Deadline { grace_ms: 60 },
+            ..Default::default()
+        };
+        let met = kernel.schedule(spec.clone()).await.expect("schedule");
+        let missed = kernel.schedule(spec).await.expect("schedule");
+
+        // Past fire_at but still inside the grace window.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let acknowledged = kernel
+            .acknowledge("tenant-a", met.id)
+            .await
+            .expect("acknowledge")
+            .expect("timer exists");
+        assert_eq!(acknowledged.status, TimerStatus::Acknowledged);
+        assert!(acknowledged.acknowledged_at.is_some());
+
+        match kernel.wait("tenant-a", missed.id).await {
+            TimerOutcome::Failed(failed) => {
+                assert_eq!(
+                    failed.failure_reason,
+                    Some(FailureReason::DeadlineExceeded { grace_ms: 60 })
+                );
+                let due = failed.fire_at + chrono::Duration::milliseconds(60);
+                assert!(failed.failed_at.expect("failed") >= due);
+                assert!(failed.fired_at.is_none());
+            }
+            other => panic!("expected the deadline to fail, got {other:?}"),
+        }
+        let mut seen = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            match event {
+                TimerEvent::Acknowledged(timer) => seen.push(("acknowledged", timer.id)),
+                TimerEvent::Failed(timer) => seen.push(("failed", timer.id)),
+                TimerEvent::Fired(timer) => panic!("deadline {} fired", timer.id),
+                _ => {}
+            }
+        }
+        assert_eq!(seen, vec![("acknowledged", met.id), ("failed", missed.id)]);
+
+        let error = kernel.acknowledge("tenant-a", missed.id).await.unwrap_err();
+        assert!(matches!(error, KernelError::NotPending));
+        let one_shot = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "sla-monitor".into(),
+                duration_ms: 60_000,
+                ..Default::default()
+            })
+            .await
+            .expect("schedule");
+        let error = kernel.acknowledge("tenant-a", one_shot.id).await.unwrap_err();
+        assert!(matches!(error, KernelError::NotDeadline));
+    }
+
+    #[tokio::test]
+    async fn execution_reports_settle_or_fail_fired_timers() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let mut events = kernel.subscribe();
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 10,
+            ..Default::default()
+        };
+        let succeeded = kernel.schedule(spec.clone()).await.expect("schedule");
+        let failed = kernel.schedule(spec).await.expect("schedule");
+        let error = kernel
+            .report_execution("tenant-a", succeeded.id, Vec::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, KernelError::NotFired));
+        kernel.wait("tenant-a", succeeded.id).await;
+        kernel.wait("tenant-a", failed.id).await;
+
+        let action = |id: &str, error: Option<&str>| ActionReport {
+            action_id: id.into(),
+            success: error.is_none(),
+            duration_ms: 12,
+            error: error.map(Into::into),
+        };
+        let settled = kernel
+            .report_execution("tenant-a", succeeded.id, vec![action("notify", None)])
+            .await
+            .expect("report")
+            .expect("timer exists");
+        assert_eq!(settled.status, TimerStatus::Settled);
+        assert!(settled.settled_at.is_some());
+        assert_eq!(settled.execution.as_ref().unwrap().actions[0].duration_ms, 12);
+
+        let report = vec![
+            action("notify", None),
+            action("page", Some("HTTP 503")),
+            action("log", Some("timed out")),
+        ];
+        let failed = kernel
+            .report_execution("tenant-a", failed.id, report)
+            .await
+            .expect("report")
+            .expect("timer exists");
+        assert_eq!(failed.status, TimerStatus::Failed);
+        assert_eq!(
+            failed.failure_reason,
+            Some(FailureReason::ActionsFailed {
+                failed: 2,
+                total: 3,
+                error: Some("HTTP 503".into()),
+            })
+        );
+
+        // A report missing results for some of the bundle's actions fails the timer.
+        let partial = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 10,
+                action_bundle: Some(serde_json::json!({
+                    "actions": [{"id": "notify", "kind": "webhook"}, {"id": "rpc", "kind": "grpc"}]
+                })),
+                ..Default::default()
+            })
+            .await
+            .expect("schedule");
+        kernel.wait("tenant-a", partial.id).await;
+        let partial = kernel
+            .report_execution("tenant-a", partial.id, vec![action("notify", None)])
+            .await
+            .expect("report")
+            .expect("timer exists");
+        assert_eq!(partial.status, TimerStatus::Failed);
+        assert_eq!(
+            partial.failure_reason,
+            Some(FailureReason::ActionsFailed {
+                failed: 1,
+                total: 2,
+                error: Some("1 results reported for 2 actions".into()),
+            })
+        );
+
+        // A retried report changes nothing.
+        let again = kernel
+            .report_execution("tenant-a", failed.id, Vec::new())
+            .await
+            .expect("report")
+            .expect("timer exists");
+        assert_eq!(again.state_version, failed.state_version);
+        assert!(kernel
+            .report_execution("tenant-b", failed.id, Vec::new())
+            .await
+            .expect("report")
+            .is_none());
+
+        let mut seen = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            match event {
+                TimerEvent::Settled(timer) => seen.push(("settled", timer.id)),
+                TimerEvent::Failed(timer) => seen.push(("failed", timer.id)),
+                _ => {}
+            }
+        }
+        assert_eq!(
+            seen,
+            vec![
+                ("settled", settled.id),
+                ("failed", failed.id),
+                ("failed", partial.id)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn lagging_consumers_hold_fires_within_the_accuracy_budget() {
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            backpressure: Some(BackpressureConfig {
+                high_watermark: 1,
+                low_watermark: 0,
+                max_delay_ms: 5_000,
+            }),
+            ..Default::default()
+        });
+        // Never read until drained below, so every event counts as lag.
+        let mut slow_consumer = kernel.subscribe();
+        let spec = |accuracy_budget_ms| TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 20,
+            accuracy_budget_ms,
+            ..Default::default()
+        };
+
+        let budgeted = kernel.schedule(spec(Some(60))).await.expect("schedule");
+        let fired = match kernel.wait("tenant-a", budgeted.id).await {
+            TimerOutcome::Fired(fired) => fired,
+            other => panic!("expected a fire, got {other:?}"),
+        };
+        let latency_ms = fired.fire_latency_ms.expect("latency");
+        assert!((60..1_000).contains(&latency_ms), "held {latency_ms}ms");
+        let stats = kernel.backpressure_stats();
+        assert_eq!(stats.fires_delayed, 1);
+        assert_eq!(stats.allowance_exhausted, 1);
+        assert!(stats.max_lag >= 1);
+
+        let unbudgeted = kernel.schedule(spec(None)).await.expect("schedule");
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        while slow_consumer.try_recv().is_ok() {}
+        match kernel.wait("tenant-a", unbudgeted.id).await {
+            TimerOutcome::Fired(fired) => {
+                assert!(fired.fire_latency_ms.expect("latency") < 1_000)
+            }
+            other => panic!("expected a fire, got {other:?}"),
+        }
+        let stats = kernel.backpressure_stats();
+        assert_eq!(stats.fires_delayed, 2);
+        assert_eq!(stats.allowance_exhausted, 1, "released once consumers caught up");
+    }
+
+    #[tokio::test]
+    async fn batch_schedules_admit_each_spec_on_its_own() {
+        let store = Arc::new(InMemoryTimerStore::default());
+        let kernel = HorologyKernel::with_store(
+            SchedulerConfig {
+                quotas: TenantQuota {
+                    max_active_timers: Some(2),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            store.clone(),
+        );
+        let spec = |tenant_id: &str, duration_ms, key: Option<&str>| TimerSpec {
+            tenant_id: tenant_id.into(),
+            requested_by: "agent-1".into(),
+            duration_ms,
+            idempotency_key: key.map(Into::into),
+            ..Default::default()
+        };
+        let results = kernel
+            .schedule_batch(vec![
+                spec("tenant-a", 60_000, Some("reminder-1")),
+                spec("tenant-a", 0, None),
+                spec("tenant-a", 60_000, Some("reminder-1")),
+                spec("tenant-a", 60_000, None),
+                spec("tenant-a", 60_000, None),
+                spec("tenant-b", 60_000, None),
+            ])
+            .await
+            .expect("batch");
+        assert_eq!(results.len(), 6);
+        let first = results[0].as_ref().expect("scheduled");
+        assert!(matches!(results[1], Err(KernelError::InvalidDuration)));
+        assert_eq!(results[2].as_ref().expect("replayed").id, first.id);
+        assert!(results[3].is_ok());
+        assert!(matches!(
+            results[4],
+            Err(KernelError::QuotaExceeded {
+                quota: QuotaKind::ActiveTimers,
+                ..
+            })
+        ));
+        assert!(results[5].is_ok());
+        assert_eq!(store.load_all().await.expect("load").len(), 3);
+        assert_eq!(kernel.list("tenant-a").await.len(), 2);
+
+        let oversized = (0..=MAX_BATCH_SIZE)
+            .map(|_| spec("tenant-b", 60_000, None))
+            .collect();
+        assert!(matches!(
+            kernel.schedule_batch(oversized).await,
+            Err(KernelError::BatchTooLarge { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn stuck_timers_are_fired_or_rearmed_on_a_restarted_loop() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let mut system = kernel.subscribe_system();
+        let spec = |duration_ms| TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms,
+            ..Default::default()
+        };
+        let grace = Duration::from_secs(5);
+        // Leaves the timer's queue entry behind on its old fire time, where the loop skips it.
+        let lose_fire = |id: Uuid| {
+            let kernel = kernel.clone();
+            async move {
+                let mut timers = kernel.state.timers.write().await;
+                timers.get_mut(&id).expect("timer").fire_at =
+                    Utc::now() - chrono::Duration::seconds(10);
+            }
+        };
+
+        let lost = kernel.schedule(spec(60_000)).await.expect("schedule");
+        lose_fire(lost.id).await;
+        let recovered = kernel.recover_stuck_timers(grace).await;
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].action, RecoveryAction::Fired);
+        assert!(recovered[0].overdue_ms >= 10_000);
+        assert_eq!(
+            kernel.get("tenant-a", lost.id).await.expect("timer").status,
+            TimerStatus::Fired
+        );
+        let event = system.recv().await.expect("system event");
+        assert_eq!(event.kind.name(), "recovered_stuck_timer");
+
+        // A dispatch loop that died takes its queue claim with it until the detector restarts it.
+        let dispatch = kernel.state.dispatch.lock().unwrap().as_ref().expect("loop").abort_handle();
+        dispatch.abort();
+        while !dispatch.is_finished() {
+            tokio::task::yield_now().await;
+        }
+        let stranded = kernel.schedule(spec(60_000)).await.expect("schedule");
+        lose_fire(stranded.id).await;
+        let upcoming = kernel.schedule(spec(20)).await.expect("schedule");
+        let recovered = kernel.recover_stuck_timers(grace).await;
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].timer_id, stranded.id);
+        assert_eq!(recovered[0].action, RecoveryAction::Rearmed);
+        for id in [stranded.id, upcoming.id] {
+            let outcome = tokio::time::timeout(Duration::from_secs(1), kernel.wait("tenant-a", id))
+                .await
+                .expect("fired by the restarted loop");
+            assert!(matches!(outcome, TimerOutcome::Fired(_)));
+        }
+
+        let stats = kernel.stuck_timer_stats();
+        assert_eq!(stats.scans, 2);
+        assert_eq!((stats.fired, stats.rearmed), (1, 1));
+        assert_eq!(stats.dispatch_restarts, 1);
+    }
+
+    #[tokio::test]
+    async fn evicted_terminal_timers_are_read_back_from_the_store() {
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            terminal_retention: Some(TerminalRetention {
+                max_age: None,
+                max_count: Some(1),
+            }),
+            ..Default::default()
+        });
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+        let keyed = kernel
+            .schedule(TimerSpec {
+                idempotency_key: Some("retry-1".into()),
+                ..spec.clone()
+            })
+            .await
+            .unwrap();
+        let second = kernel.schedule(spec.clone()).await.unwrap();
+        let pending = kernel.schedule(spec.clone()).await.unwrap();
+        for id in [keyed.id, second.id] {
+            kernel.cancel("tenant-a", id, None, None).await.expect("cancelled");
+        }
+
+        assert_eq!(kernel.evict_terminal_timers().await, 1);
+        {
+            let timers = kernel.state.timers.read().await;
+            assert!(!timers.contains_key(&keyed.id));
+            assert!(timers.contains_key(&second.id));
+            assert!(timers.contains_key(&pending.id));
+        }
+        let evicted = kernel.get("tenant-a", keyed.id).await.expect("read from store");
+        assert_eq!(evicted.status, TimerStatus::Cancelled);
+        assert!(kernel.get("tenant-b", keyed.id).await.is_none());
+
+        // The key went with the timer, so a retry schedules afresh.
+        let retried = kernel
+            .schedule(TimerSpec {
+                idempotency_key: Some("retry-1".into()),
+                ..spec
+            })
+            .await
+            .unwrap();
+        assert_ne!(retried.id, keyed.id);
+        assert_eq!(kernel.evict_terminal_timers().await, 0);
+    }
+
+    #[tokio::test]
+    async fn conditional_mutations_fail_once_the_timer_moved_on() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60_000,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(timer.state_version, 1);
+
+        let moved = kernel
+            .reschedule_at_version(
+                "tenant-a",
+                timer.id,
+                Reschedule::Delta(chrono::Duration::seconds(30)),
+                Some(1),
+            )
+            .await
+            .unwrap()
+            .expect("timer");
+        assert_eq!(moved.state_version, 2);
+
+        // A second agent still holding version 1 loses the race, whichever mutation it tries.
+        let stale = kernel
+            .cancel_at_version("tenant-a", timer.id, Some(1), None, None)
+            .await;
+        assert!(matches!(
+            stale,
+            Err(KernelError::StateVersionConflict {
+                expected: 1,
+                actual: 2
+            })
+        ));
+        let stale = kernel
+            .reschedule_at_version(
+                "tenant-a",
+                timer.id,
+                Reschedule::Delta(chrono::Duration::seconds(30)),
+                Some(1),
+            )
+            .await;
+        assert!(matches!(stale, Err(KernelError::StateVersionConflict { .. })));
+
+        let cancelled = kernel
+            .cancel_at_version("tenant-a", timer.id, Some(2), None, None)
+            .await
+            .unwrap()
+            .expect("timer");
+        assert_eq!(cancelled.status, TimerStatus::Cancelled);
+        assert_eq!(cancelled.state_version, 3);
+        // Unconditional cancels of a finished timer still return it unchanged.
+        let again = kernel.cancel("tenant-a", timer.id, None, None).await.expect("timer");
+        assert_eq!(again.state_version, 3);
+    }
+
+    #[tokio::test]
+    async fn a_fire_racing_a_failover_is_recorded_once() {
+        let store = Arc::new(InMemoryTimerStore::default());
+        let old = HorologyKernel::with_store(SchedulerConfig::default(), store.clone());
+        old.set_fencing_token(1);
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+        let first = old.schedule(spec.clone()).await.unwrap();
+        let new = HorologyKernel::with_store(
+            SchedulerConfig {
+                standby: true,
+                ..Default::default()
+            },
+            store.clone(),
+        );
+        new.restore_from_store().await.expect("restore");
+        new.set_fencing_token(2);
+        let mut old_events = old.subscribe();
+        let mut new_events = new.subscribe();
+        let mut old_system = old.subscribe_system();
+
+        // The old leader records the fire first, so the new leader's attempt finds it done.
+        old.state.fire(first.id, first.fire_at).await;
+        new.state.fire(first.id, first.fire_at).await;
+        assert!(matches!(old_events.try_recv(), Ok(TimerEvent::Fired(_))));
+        assert!(new_events.try_recv().is_err());
+        let adopted = new.get("tenant-a", first.id).await.expect("timer");
+        assert_eq!(adopted.status, TimerStatus::Fired);
+
+        // Once the new leader has written under its term, the old one is fenced off.
+        let second = old.schedule(spec).await.unwrap();
+        new.apply(TimerEvent::Scheduled(second.clone())).await;
+        let _ = old_events.try_recv();
+        new.state.fire(second.id, second.fire_at).await;
+        old.state.fire(second.id, second.fire_at).await;
+        assert!(matches!(new_events.try_recv(), Ok(TimerEvent::Fired(_))));
+        assert!(old_events.try_recv().is_err());
+        assert!(!old.is_active());
+        assert_eq!(old.get("tenant-a", second.id).await.unwrap().status, TimerStatus::Scheduled);
+        let fenced = std::iter::from_fn(|| old_system.try_recv().ok())
+            .find(|event| event.kind.name() == "fenced")
+            .expect("fenced event");
+        assert_eq!(
+            fenced.kind,
+            SystemEventKind::Fenced {
+                fencing_token: 1,
+                current_token: 2
+            }
+        );
+        let stored = store.get(second.id).await.unwrap().expect("stored");
+        assert_eq!(stored.status, TimerStatus::Fired);
+    }
+
+    #[tokio::test]
+    async fn a_mock_clock_fires_timers_without_waiting_for_them() {
+        let clock = Arc::new(MockClock::default());
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            clock: clock.clone(),
+            ..Default::default()
+        });
+        let mut events = kernel.subscribe();
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60 * 60 * 1_000,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(timer.fire_at - timer.created_at, chrono::Duration::hours(1));
+        assert!(matches!(events.recv().await, Ok(TimerEvent::Scheduled(_))));
+
+        clock.advance(Duration::from_secs(59 * 60));
+        tokio::task::yield_now().await;
+        assert!(events.try_recv().is_err());
+
+        clock.advance(Duration::from_secs(60));
+        let fired = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("fired without real sleeping");
+        let Ok(TimerEvent::Fired(fired)) = fired else {
+            panic!("expected a fire, got {fired:?}");
+        };
+        assert_eq!(fired.fired_at, Some(timer.fire_at));
+        assert_eq!(fired.fire_latency_ms, Some(0));
+    }
+
+    #[tokio::test]
+    async fn a_wall_clock_step_forward_fires_timers_on_time_instead_of_late() {
+        let clock = Arc::new(MockClock::default());
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            clock: clock.clone(),
+            ..Default::default()
+        });
+        let mut system = kernel.subscribe_system();
+        let mut events = kernel.subscribe();
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60 * 60 * 1_000,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(matches!(events.recv().await, Ok(TimerEvent::Scheduled(_))));
+        assert_eq!(kernel.check_clock_drift().await, None);
+
+        // The wall clock reaches the fire time while the monotonic deadline is an hour out.
+        clock.step_wall_clock(chrono::Duration::hours(1));
+        assert_eq!(
+            kernel.check_clock_drift().await,
+            Some(ClockJump {
+                jump_ms: 60 * 60 * 1_000,
+                rearmed: 1
+            })
+        );
+        let fired = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("fired after the step");
+        let Ok(TimerEvent::Fired(fired)) = fired else {
+            panic!("expected a fire, got {fired:?}");
+        };
+        assert_eq!(fired.id, timer.id);
+        assert_eq!(fired.fire_latency_ms, Some(0));
+        let jumped = system.recv().await.unwrap();
+        assert_eq!(jumped.kind.name(), "clock_jumped");
+    }
+
+    #[tokio::test]
+    async fn a_wall_clock_step_back_defers_fires_that_would_be_early() {
+        let clock = Arc::new(MockClock::default());
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            clock: clock.clone(),
+            ..Default::default()
+        });
+        let mut events = kernel.subscribe();
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 10 * 60 * 1_000,
+                ..Default::default()
             })
             .await
-            .expect("schedule timer");
+            .unwrap();
+        assert!(matches!(events.recv().await, Ok(TimerEvent::Scheduled(_))));
 
-        let scheduled = events.recv().await.expect("scheduled event");
-        assert!(matches!(scheduled, TimerEvent::Scheduled(_)));
+        clock.step_wall_clock(chrono::Duration::minutes(-5));
+        clock.advance(Duration::from_secs(10 * 60));
+        tokio::task::yield_now().await;
+        assert!(events.try_recv().is_err(), "fired five minutes early");
+        assert_eq!(
+            kernel.get("tenant-a", timer.id).await.unwrap().status,
+            TimerStatus::Scheduled
+        );
 
-        let fired = events.recv().await.expect("fired event");
-        match fired {
-            TimerEvent::Fired(fired_timer) => {
-                assert_eq!(fired_timer.id, timer.id);
-                assert_eq!(fired_timer.status, TimerStatus::Fired);
+        clock.advance(Duration::from_secs(5 * 60));
+        let fired = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("fired once the wall clock caught up");
+        let Ok(TimerEvent::Fired(fired)) = fired else {
+            panic!("expected a fire, got {fired:?}");
+        };
+        assert_eq!(fired.fired_at, Some(timer.fire_at));
+    }
+
+    #[tokio::test]
+    async fn a_deferred_fire_does_not_repeat_its_pre_fire_notice() {
+        let clock = Arc::new(MockClock::default());
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            clock: clock.clone(),
+            ..Default::default()
+        });
+        let mut events = kernel.subscribe();
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 10 * 60 * 1_000,
+                pre_fire_notice_ms: Some(60 * 1_000),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(matches!(events.recv().await, Ok(TimerEvent::Scheduled(_))));
+
+        clock.advance(Duration::from_secs(9 * 60));
+        let notice = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("pre-fire notice");
+        assert!(matches!(notice, Ok(TimerEvent::PreFire { .. })), "got {notice:?}");
+
+        // The step back makes the fire come up early, so it is re-armed with the notice window
+        // ahead of it again.
+        clock.step_wall_clock(chrono::Duration::minutes(-5));
+        clock.advance(Duration::from_secs(60));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        clock.advance(Duration::from_secs(4 * 60 + 30));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(events.try_recv().is_err(), "pre-fire notice repeated");
+        clock.advance(Duration::from_secs(30));
+        let fired = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("fired once the wall clock caught up");
+        let Ok(TimerEvent::Fired(fired)) = fired else {
+            panic!("expected the fire without a second notice, got {fired:?}");
+        };
+        assert_eq!(fired.id, timer.id);
+    }
+
+    #[tokio::test]
+    async fn a_pre_fire_notice_is_not_repeated_after_a_restart() {
+        let clock = Arc::new(MockClock::default());
+        let store = Arc::new(InMemoryTimerStore::default());
+        let config = || SchedulerConfig {
+            clock: clock.clone(),
+            ..Default::default()
+        };
+        let kernel = HorologyKernel::with_store(config(), store.clone());
+        let mut events = kernel.subscribe();
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 10 * 60 * 1_000,
+                pre_fire_notice_ms: Some(60 * 1_000),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(matches!(events.recv().await, Ok(TimerEvent::Scheduled(_))));
+
+        clock.advance(Duration::from_secs(9 * 60 + 10));
+        let notice = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("pre-fire notice");
+        let Ok(TimerEvent::PreFire { timer: noticed, .. }) = notice else {
+            panic!("expected a pre-fire notice, got {notice:?}");
+        };
+        assert_eq!(noticed.state_version, timer.state_version + 1);
+        let stored = store.get(timer.id).await.unwrap().expect("stored");
+        assert_eq!(stored.pre_fire_sent_for, Some(timer.fire_at));
+        assert_eq!(stored.state_version, noticed.state_version);
+        kernel.shutdown().await.unwrap();
+
+        let restarted = HorologyKernel::with_store(config(), store.clone());
+        let mut events = restarted.subscribe();
+        restarted.restore_from_store().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        clock.advance(Duration::from_secs(20));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(events.try_recv().is_err(), "pre-fire notice repeated");
+        clock.advance(Duration::from_secs(30));
+        let fired = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("fired after the restart");
+        let Ok(TimerEvent::Fired(fired)) = fired else {
+            panic!("expected the fire without a second notice, got {fired:?}");
+        };
+        assert_eq!(fired.id, timer.id);
+    }
+
+    #[tokio::test]
+    async fn a_promoted_standby_does_not_repeat_a_replicated_pre_fire_notice() {
+        let clock = Arc::new(MockClock::default());
+        let primary = HorologyKernel::new(SchedulerConfig {
+            clock: clock.clone(),
+            ..Default::default()
+        });
+        let standby = HorologyKernel::new(SchedulerConfig {
+            clock: clock.clone(),
+            standby: true,
+            ..Default::default()
+        });
+        let mut replicated = primary.subscribe();
+        let timer = primary
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 10 * 60 * 1_000,
+                pre_fire_notice_ms: Some(60 * 1_000),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        standby.apply(replicated.recv().await.unwrap()).await;
+
+        clock.advance(Duration::from_secs(9 * 60 + 10));
+        let notice = tokio::time::timeout(Duration::from_secs(1), replicated.recv())
+            .await
+            .expect("pre-fire notice")
+            .unwrap();
+        assert!(matches!(notice, TimerEvent::PreFire { .. }), "got {notice:?}");
+        // As a standby replicating over gRPC receives it.
+        #[cfg(feature = "grpc")]
+        let notice = grpc::event_from_proto(grpc::event_to_proto(notice).unwrap())
+            .unwrap()
+            .expect("event");
+        standby.apply(notice).await;
+        primary.shutdown().await.unwrap();
+
+        let mut events = standby.subscribe();
+        standby.promote();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(events.try_recv().is_err(), "pre-fire notice repeated");
+        clock.advance(Duration::from_secs(60));
+        let fired = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("fired after promotion");
+        let Ok(TimerEvent::Fired(fired)) = fired else {
+            panic!("expected the fire without a second notice, got {fired:?}");
+        };
+        assert_eq!(fired.id, timer.id);
+    }
+
+    #[tokio::test]
+    async fn shutdown_drains_fires_and_flushes_the_store() {
+        let durable = Arc::new(InMemoryTimerStore::default());
+        let batching = Arc::new(BatchingTimerStore::new(
+            durable.clone(),
+            WriteBatchConfig {
+                flush_interval: Duration::from_secs(3600),
+                max_batch: 256,
+            },
+        ));
+        let kernel = HorologyKernel::with_store(SchedulerConfig::default(), batching.clone());
+        let mut events = kernel.subscribe();
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 200,
+            ..Default::default()
+        };
+        let timer = kernel.schedule(spec.clone()).await.unwrap();
+        assert!(matches!(events.recv().await, Ok(TimerEvent::Scheduled(_))));
+        assert!(durable.get(timer.id).await.unwrap().is_none());
+
+        let report = kernel.shutdown().await.unwrap();
+        assert_eq!(
+            report,
+            DrainReport {
+                snapshotted: 1,
+                pending: 1
             }
-            other => panic!("unexpected event: {:?}", other),
+        );
+        assert!(!kernel.is_active());
+        assert!(matches!(
+            kernel.schedule(spec).await,
+            Err(KernelError::Standby)
+        ));
+        assert_eq!(batching.pending(), 0);
+        let stored = durable.get(timer.id).await.unwrap().expect("flushed");
+        assert_eq!(stored.status, TimerStatus::Scheduled);
+        assert!(durable.last_heartbeat().await.unwrap().is_some());
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(events.try_recv().is_err(), "fired after shutdown");
+    }
+
+    #[tokio::test]
+    async fn a_slow_subscriber_only_loses_its_own_events() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let mut fast = kernel.subscribe();
+        let mut slow = kernel.subscribe_with(SubscriberBuffer {
+            capacity: 1,
+            drop_policy: DropPolicy::DropOldest,
+        });
+        for _ in 0..3 {
+            kernel
+                .schedule(TimerSpec {
+                    tenant_id: "tenant-a".into(),
+                    requested_by: "agent-1".into(),
+                    duration_ms: 60_000,
+                    ..Default::default()
+                })
+                .await
+                .expect("schedule");
         }
+
+        for _ in 0..3 {
+            assert!(matches!(fast.try_recv(), Ok(TimerEvent::Scheduled(_))));
+        }
+        assert_eq!(fast.dropped(), 0);
+        assert!(matches!(slow.try_recv(), Err(fanout::TryRecvError::Lagged(2))));
+        assert!(matches!(slow.try_recv(), Ok(TimerEvent::Scheduled(_))));
+        let stats = kernel.fanout_stats();
+        assert_eq!(stats.subscribers, 2);
+        assert_eq!(stats.events_dropped, 2);
+        assert_eq!(stats.buffered, 0);
     }
 
     #[tokio::test]
-    async fn cancelling_prevents_fire_event() {
+    async fn timers_due_together_fire_in_priority_order() {
         let kernel = HorologyKernel::new(SchedulerConfig::default());
         let mut events = kernel.subscribe();
+        let fire_at = Utc::now() + chrono::Duration::milliseconds(50);
+        for (name, priority) in [("low", 0), ("high", 9), ("medium", 5)] {
+            kernel
+                .schedule(TimerSpec {
+                    tenant_id: "tenant-a".into(),
+                    requested_by: "agent-1".into(),
+                    name: Some(name.into()),
+                    fire_at: Some(fire_at),
+                    priority,
+                    ..Default::default()
+                })
+                .await
+                .expect("schedule");
+        }
+
+        let mut fired = Vec::new();
+        while fired.len() < 3 {
+            if let TimerEvent::Fired(timer) = events.recv().await.expect("event") {
+                fired.push(timer.name);
+            }
+        }
+        assert_eq!(fired, ["high", "medium", "low"]);
+    }
+
+    #[tokio::test]
+    async fn reloaded_tenant_policies_set_each_tenants_horizon() {
+        const HOUR_MS: u64 = 60 * 60 * 1000;
+        let kernel = HorologyKernel::new(SchedulerConfig {
+            max_duration_ms: Some(4 * HOUR_MS),
+            ..SchedulerConfig::default()
+        });
+        let policies = InMemoryTenantPolicyStore::default();
+        policies.set(
+            "enterprise",
+            TenantPolicy {
+                max_duration_ms: Some(365 * 24 * HOUR_MS),
+                ..Default::default()
+            },
+        );
+        assert_eq!(kernel.reload_tenant_policies(&policies).await.unwrap(), 1);
+        let spec = |tenant_id: &str| TimerSpec {
+            tenant_id: tenant_id.into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 30 * 24 * HOUR_MS,
+            ..Default::default()
+        };
+        kernel.schedule(spec("enterprise")).await.expect("within the tenant's horizon");
+        assert!(matches!(
+            kernel.schedule(spec("trial")).await,
+            Err(KernelError::InvalidDuration)
+        ));
+
+        policies.remove("enterprise");
+        kernel.reload_tenant_policies(&policies).await.unwrap();
+        assert!(matches!(
+            kernel.schedule(spec("enterprise")).await,
+            Err(KernelError::InvalidDuration)
+        ));
+    }
+
+    #[tokio::test]
+    async fn legacy_imports_keep_ids_and_can_be_re_run() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let now = Utc::now();
+        let record = |status: &str, fire_in_ms: i64| LegacyTimerRecord {
+            id: Uuid::new_v4(),
+            tenant_id: "tenant-a".into(),
+            requested_by: "control-plane".into(),
+            name: format!("legacy-{status}"),
+            created_at: now - chrono::Duration::hours(1),
+            fire_at: now + chrono::Duration::milliseconds(fire_in_ms),
+            status: status.into(),
+            metadata: None,
+            labels: HashMap::new(),
+            action_bundle: None,
+            agent_binding: None,
+            accuracy_budget_ms: None,
+            pre_fire_notice_ms: None,
+            idempotency_key: None,
+            session_id: None,
+            watchdog_interval_ms: None,
+            priority: None,
+            deadline_grace_ms: None,
+        };
+        let overdue = record("scheduled", -1_000);
+        let pending = record("armed", 60_000);
+        let fired = record("fired", -5_000);
+        let records = vec![overdue.clone(), pending.clone(), fired.clone()];
+
+        let report = kernel.import_legacy(records.clone()).await.expect("import");
+        let imported: Vec<_> = report.imported.iter().map(|timer| timer.id).collect();
+        assert_eq!(imported, [overdue.id, pending.id]);
+        assert_eq!(report.skipped_terminal, [fired.id]);
+        assert_eq!(report.imported[1].fire_at, pending.fire_at);
+        assert_eq!(report.imported[1].created_at, pending.created_at);
+        assert!(matches!(
+            kernel.wait("tenant-a", overdue.id).await,
+            TimerOutcome::Fired(_)
+        ));
+
+        let rerun = kernel.import_legacy(records).await.expect("re-run");
+        assert!(rerun.imported.is_empty());
+        assert_eq!(rerun.already_present, [overdue.id, pending.id]);
+        assert_eq!(kernel.list("tenant-a").await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn idempotency_keys_deduplicate_schedules_across_restarts() {
+        let store = InMemoryTimerStore::default();
+        let kernel =
+            HorologyKernel::with_store(SchedulerConfig::default(), Arc::new(store.clone()));
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            idempotency_key: Some("retry-1".into()),
+            ..Default::default()
+        };
+        let first = kernel.schedule(spec.clone()).await.expect("schedule");
+        let again = kernel.schedule(spec.clone()).await.expect("retry");
+        assert_eq!(again.id, first.id);
+        let other_tenant = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-b".into(),
+                ..spec.clone()
+            })
+            .await
+            .expect("schedule");
+        assert_ne!(other_tenant.id, first.id);
+        assert_eq!(kernel.list("tenant-a").await.len(), 1);
+
+        let restarted = HorologyKernel::with_store(SchedulerConfig::default(), Arc::new(store));
+        restarted.restore_from_store().await.expect("restore");
+        let replayed = restarted.schedule(spec).await.expect("retry after restart");
+        assert_eq!(replayed.id, first.id);
+        assert_eq!(restarted.list("tenant-a").await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rescheduling_moves_the_fire_time_in_either_direction() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 30,
+            ..Default::default()
+        };
+        let pushed = kernel.schedule(spec.clone()).await.expect("schedule");
+        let pulled = kernel
+            .schedule(TimerSpec {
+                duration_ms: 60_000,
+                ..spec
+            })
+            .await
+            .expect("schedule");
+
+        let later = Utc::now() + chrono::Duration::milliseconds(150);
+        let moved = kernel
+            .reschedule("tenant-a", pushed.id, Reschedule::FireAt(later))
+            .await
+            .expect("reschedule")
+            .expect("timer");
+        assert_eq!(moved.fire_at, later);
+        kernel
+            .reschedule(
+                "tenant-a",
+                pulled.id,
+                Reschedule::Delta(chrono::Duration::milliseconds(-59_950)),
+            )
+            .await
+            .expect("reschedule")
+            .expect("timer");
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        let waiting = kernel.get("tenant-a", pushed.id).await.unwrap();
+        assert_eq!(waiting.status, TimerStatus::Scheduled, "stale fire entry skipped");
+
+        let fired = match kernel.wait("tenant-a", pushed.id).await {
+            TimerOutcome::Fired(timer) => timer,
+            other => panic!("unexpected outcome: {other:?}"),
+        };
+        assert!(fired.fired_at.unwrap() >= later);
+        let fired = kernel.get("tenant-a", pulled.id).await.unwrap();
+        assert_eq!(fired.status, TimerStatus::Fired);
+
+        let error = kernel
+            .reschedule("tenant-a", pulled.id, Reschedule::Delta(chrono::Duration::seconds(1)))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, KernelError::NotPending));
+        assert!(kernel
+            .reschedule("tenant-b", pulled.id, Reschedule::FireAt(later))
+            .await
+            .unwrap()
+            .is_none());
+    }
 
+    #[tokio::test]
+    async fn reschedule_deltas_past_the_calendar_are_invalid() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
         let timer = kernel
             .schedule(TimerSpec {
                 tenant_id: "tenant-a".into(),
                 requested_by: "agent-1".into(),
-                name: None,
-                duration_ms: 200,
-                fire_at: None,
-                metadata: None,
-                labels: HashMap::new(),
-                action_bundle: None,
-                agent_binding: None,
+                duration_ms: 60_000,
+                ..Default::default()
             })
             .await
             .unwrap();
+        for delta in [chrono::TimeDelta::MAX, chrono::TimeDelta::MIN] {
+            let error = kernel
+                .reschedule("tenant-a", timer.id, Reschedule::Delta(delta))
+                .await
+                .unwrap_err();
+            assert!(matches!(error, KernelError::InvalidFireTime), "{error:?}");
+        }
+        assert_eq!(
+            kernel.get("tenant-a", timer.id).await.unwrap().fire_at,
+            timer.fire_at
+        );
+    }
 
-        let _ = events.recv().await.expect("scheduled event");
+    /// Fails every write while `down` is set.
+    #[derive(Default)]
+    struct OutageStore {
+        inner: InMemoryTimerStore,
+        down: std::sync::atomic::AtomicBool,
+    }
+
+    impl OutageStore {
+        fn check(&self) -> Result<(), StoreError> {
+            if self.down.load(Ordering::SeqCst) {
+                return Err(std::io::Error::other("store offline").into());
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl TimerStore for OutageStore {
+        async fn upsert(&self, timer: &TimerInstance) -> Result<(), StoreError> {
+            self.check()?;
+            self.inner.upsert(timer).await
+        }
+
+        async fn load_all(&self) -> Result<Vec<TimerInstance>, StoreError> {
+            self.inner.load_all().await
+        }
+
+        async fn upsert_fenced(
+            &self,
+            timer: &TimerInstance,
+            fencing_token: u64,
+        ) -> Result<FencedWrite, StoreError> {
+            self.check()?;
+            self.inner.upsert_fenced(timer, fencing_token).await
+        }
+
+        async fn record_heartbeat(&self, at: DateTime<Utc>) -> Result<(), StoreError> {
+            self.inner.record_heartbeat(at).await
+        }
+
+        async fn last_heartbeat(&self) -> Result<Option<DateTime<Utc>>, StoreError> {
+            self.inner.last_heartbeat().await
+        }
+    }
+
+    #[tokio::test]
+    async fn a_cancel_the_store_refuses_is_not_acknowledged() {
+        let store = Arc::new(OutageStore::default());
+        let kernel = HorologyKernel::with_store(SchedulerConfig::default(), store.clone());
+        let timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60_000,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        store.down.store(true, Ordering::SeqCst);
+        let error = kernel
+            .cancel_at_version("tenant-a", timer.id, None, None, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, KernelError::Store(_)), "{error:?}");
+        let current = kernel.get("tenant-a", timer.id).await.unwrap();
+        assert_eq!(current.status, TimerStatus::Scheduled);
+        assert_eq!(current.state_version, timer.state_version);
 
+        store.down.store(false, Ordering::SeqCst);
         let cancelled = kernel
-            .cancel(
+            .cancel_at_version("tenant-a", timer.id, Some(timer.state_version), None, None)
+            .await
+            .unwrap()
+            .expect("timer");
+        assert_eq!(cancelled.status, TimerStatus::Cancelled);
+        let stored = store.inner.get(timer.id).await.unwrap().expect("stored");
+        assert_eq!(stored.status, TimerStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn applying_a_manifest_converges_named_timers() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let manifest = |timers: &str| {
+            TimerManifest::from_json(&format!(r#"{{"name": "billing", "timers": {timers}}}"#))
+                .expect("manifest")
+        };
+        let first = manifest(
+            r#"[{"name": "close", "duration_ms": 60000}, {"name": "report", "duration_ms": 90000}]"#,
+        );
+        let report = kernel
+            .apply_manifest("tenant-a", "gitops", &first)
+            .await
+            .expect("apply");
+        assert_eq!(report.created.len(), 2);
+        assert_eq!(report.created[0].labels[MANIFEST_LABEL], "billing");
+
+        let again = kernel
+            .apply_manifest("tenant-a", "gitops", &first)
+            .await
+            .expect("reapply");
+        assert!(again.created.is_empty() && again.updated.is_empty() && again.cancelled.is_empty());
+        assert_eq!(again.unchanged.len(), 2);
+
+        let second = manifest(r#"[{"name": "close", "duration_ms": 120000}]"#);
+        let report = kernel
+            .apply_manifest("tenant-a", "gitops", &second)
+            .await
+            .expect("apply change");
+        assert_eq!(report.updated.len(), 1);
+        assert_eq!(report.updated[0].duration_ms, 120_000);
+        let mut cancelled: Vec<_> = report.cancelled.iter().map(|t| t.name.as_str()).collect();
+        cancelled.sort();
+        assert_eq!(cancelled, ["close", "report"]);
+
+        let invalid = manifest(r#"[{"name": "close", "duration_ms": 0}]"#);
+        assert!(kernel
+            .apply_manifest("tenant-a", "gitops", &invalid)
+            .await
+            .is_err());
+        let pending = kernel
+            .get("tenant-a", report.updated[0].id)
+            .await
+            .expect("timer");
+        assert_eq!(pending.status, TimerStatus::Scheduled);
+    }
+
+    #[tokio::test]
+    async fn a_manifest_apply_refused_partway_is_rolled_back() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        kernel.set_tenant_policy(
+            "tenant-a",
+            TenantPolicy {
+                quota: TenantQuota {
+                    max_active_timers: Some(3),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let manifest = |timers: &str| {
+            TimerManifest::from_json(&format!(r#"{{"name": "billing", "timers": {timers}}}"#))
+                .expect("manifest")
+        };
+        let first = kernel
+            .apply_manifest(
                 "tenant-a",
-                timer.id,
-                Some("manual".into()),
-                Some("agent-1".into()),
+                "gitops",
+                &manifest(
+                    r#"[{"name": "a", "duration_ms": 60000}, {"name": "b", "duration_ms": 60000}]"#,
+                ),
             )
             .await
-            .expect("cancel timer");
+            .expect("apply");
 
-        assert_eq!(cancelled.status, TimerStatus::Cancelled);
-
-        let cancel_event = events.recv().await.expect("cancel event");
-        match cancel_event {
-            TimerEvent::Cancelled {
-                timer: cancelled_timer,
-                ..
-            } => {
-                assert_eq!(cancelled_timer.id, timer.id);
-            }
-            other => panic!("unexpected event: {:?}", other),
+        // Replacing both timers and adding a third fits, since the replaced ones go away.
+        let second = kernel
+            .apply_manifest(
+                "tenant-a",
+                "gitops",
+                &manifest(
+                    r#"[{"name": "a", "duration_ms": 90000}, {"name": "b", "duration_ms": 90000},
+                        {"name": "c", "duration_ms": 90000}]"#,
+                ),
+            )
+            .await
+            .expect("apply within quota");
+        assert_eq!((second.updated.len(), second.created.len()), (2, 1));
+        assert_eq!(second.cancelled.len(), 2);
+        for timer in &first.created {
+            let timer = kernel.get("tenant-a", timer.id).await.expect("timer");
+            assert_eq!(timer.status, TimerStatus::Cancelled);
         }
 
-        // Ensure no fired event occurs by waiting longer than the duration
-        tokio::time::sleep(Duration::from_millis(250)).await;
-        while let Ok(event) = events.try_recv() {
-            assert!(
-                !matches!(event, TimerEvent::Fired(_)),
-                "timer should not emit fired event after cancellation"
-            );
-        }
+        // The fourth timer runs out of quota after three schedules went through.
+        let error = kernel
+            .apply_manifest(
+                "tenant-a",
+                "gitops",
+                &manifest(
+                    r#"[{"name": "a", "duration_ms": 120000}, {"name": "b", "duration_ms": 120000},
+                        {"name": "c", "duration_ms": 120000}, {"name": "d", "duration_ms": 120000}]"#,
+                ),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                error,
+                KernelError::QuotaExceeded {
+                    quota: QuotaKind::ActiveTimers,
+                    ..
+                }
+            ),
+            "{error:?}"
+        );
+        let timers = kernel.list("tenant-a").await;
+        let mut pending: Vec<_> = timers
+            .iter()
+            .filter(|t| !t.is_terminal())
+            .map(|t| (t.name.as_str(), t.duration_ms))
+            .collect();
+        pending.sort();
+        assert_eq!(pending, [("a", 90_000), ("b", 90_000), ("c", 90_000)]);
+        let rolled_back = timers
+            .iter()
+            .filter(|t| t.duration_ms == 120_000)
+            .inspect(|t| assert_eq!(t.status, TimerStatus::Cancelled))
+            .count();
+        assert_eq!(rolled_back, 3);
     }
 }
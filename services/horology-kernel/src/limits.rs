@@ -0,0 +1,138 @@
+use serde_json::Value;
+
+use crate::{KernelError, TimerSpec};
+
+/// Caps on caller-supplied labels and metadata, protecting label indexes and event payload sizes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpecLimits {
+    pub max_labels: usize,
+    pub max_label_key_len: usize,
+    pub max_label_value_len: usize,
+    /// Nesting depth of objects/arrays in `metadata`; a flat object has depth 1.
+    pub max_metadata_depth: usize,
+    /// Size of `metadata` serialized as JSON.
+    pub max_metadata_bytes: usize,
+}
+
+impl Default for SpecLimits {
+    fn default() -> Self {
+        Self {
+            max_labels: 64,
+            max_label_key_len: 128,
+            max_label_value_len: 512,
+            max_metadata_depth: 8,
+            max_metadata_bytes: 64 * 1024,
+        }
+    }
+}
+
+impl SpecLimits {
+    pub fn check(&self, spec: &TimerSpec) -> Result<(), KernelError> {
+        if spec.labels.len() > self.max_labels {
+            return Err(violation(
+                "labels",
+                format!(
+                    "has {} entries, limit is {}",
+                    spec.labels.len(),
+                    self.max_labels
+                ),
+            ));
+        }
+        for (key, value) in &spec.labels {
+            if key.len() > self.max_label_key_len {
+                return Err(violation(
+                    format!("labels.{key}"),
+                    format!(
+                        "key is {} bytes, limit is {}",
+                        key.len(),
+                        self.max_label_key_len
+                    ),
+                ));
+            }
+            if value.len() > self.max_label_value_len {
+                return Err(violation(
+                    format!("labels.{key}"),
+                    format!(
+                        "value is {} bytes, limit is {}",
+                        value.len(),
+                        self.max_label_value_len
+                    ),
+                ));
+            }
+        }
+        if let Some(metadata) = &spec.metadata {
+            check_depth(metadata, "metadata", 0, self.max_metadata_depth)?;
+            let bytes = serde_json::to_vec(metadata)
+                .map(|json| json.len())
+                .unwrap_or(0);
+            if bytes > self.max_metadata_bytes {
+                return Err(violation(
+                    "metadata",
+                    format!(
+                        "is {bytes} bytes serialized, limit is {}",
+                        self.max_metadata_bytes
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_depth(value: &Value, path: &str, depth: usize, max: usize) -> Result<(), KernelError> {
+    let children: Box<dyn Iterator<Item = (String, &Value)>> = match value {
+        Value::Object(map) => Box::new(map.iter().map(|(key, value)| (key.clone(), value))),
+        Value::Array(items) => Box::new(
+            items
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (index.to_string(), value)),
+        ),
+        _ => return Ok(()),
+    };
+    if depth + 1 > max {
+        return Err(violation(path, format!("nests deeper than {max} levels")));
+    }
+    for (key, child) in children {
+        check_depth(child, &format!("{path}.{key}"), depth + 1, max)?;
+    }
+    Ok(())
+}
+
+fn violation(key: impl Into<String>, reason: String) -> KernelError {
+    KernelError::LimitExceeded {
+        key: key.into(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_the_offending_key() {
+        let limits = SpecLimits {
+            max_labels: 2,
+            max_label_value_len: 4,
+            max_metadata_depth: 2,
+            ..SpecLimits::default()
+        };
+        let mut spec = TimerSpec::default();
+        spec.labels.insert("env".into(), "production".into());
+        match limits.check(&spec) {
+            Err(KernelError::LimitExceeded { key, .. }) => assert_eq!(key, "labels.env"),
+            other => panic!("expected label violation, got {other:?}"),
+        }
+
+        spec.labels.insert("env".into(), "prod".into());
+        spec.metadata = Some(serde_json::json!({ "a": { "b": [1] } }));
+        match limits.check(&spec) {
+            Err(KernelError::LimitExceeded { key, .. }) => assert_eq!(key, "metadata.a.b"),
+            other => panic!("expected depth violation, got {other:?}"),
+        }
+
+        spec.metadata = Some(serde_json::json!({ "a": { "b": 1 } }));
+        assert!(limits.check(&spec).is_ok());
+    }
+}
@@ -0,0 +1,212 @@
+//! Declarative timer manifests. A manifest names a set of desired timers; applying it diffs them
+//! against the timers a previous apply created (tracked with the [`MANIFEST_LABEL`] label) and
+//! schedules, replaces, or cancels timers until the tenant matches the manifest.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{TimerInstance, TimerKind, TimerSpec};
+
+/// Label carrying the name of the manifest that owns a timer. Only timers with this label are
+/// ever replaced or cancelled by an apply.
+pub const MANIFEST_LABEL: &str = "minoots.io/manifest";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ManifestError {
+    #[error("invalid manifest: {0}")]
+    Parse(String),
+    #[error("manifest name is required")]
+    MissingName,
+    #[error("timer #{0} in the manifest has no name")]
+    MissingTimerName(usize),
+    #[error("timer `{0}` appears more than once in the manifest")]
+    DuplicateTimer(String),
+}
+
+/// JSON document describing the desired timers, e.g.
+/// `{"name": "billing", "timers": [{"name": "nightly-close", "fire_at": "2025-01-11T00:00:00Z"}]}`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TimerManifest {
+    pub name: String,
+    #[serde(default)]
+    pub timers: Vec<ManifestTimer>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestTimer {
+    pub name: String,
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub duration_us: Option<u64>,
+    #[serde(default)]
+    pub fire_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
+    #[serde(default)]
+    pub action_bundle: Option<serde_json::Value>,
+    #[serde(default)]
+    pub agent_binding: Option<serde_json::Value>,
+    #[serde(default)]
+    pub accuracy_budget_ms: Option<u64>,
+    #[serde(default)]
+    pub pre_fire_notice_ms: Option<u64>,
+    #[serde(default)]
+    pub priority: u32,
+}
+
+impl TimerManifest {
+    pub fn from_json(document: &str) -> Result<Self, ManifestError> {
+        let manifest: Self = serde_json::from_str(document)
+            .map_err(|error| ManifestError::Parse(error.to_string()))?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    pub fn validate(&self) -> Result<(), ManifestError> {
+        if self.name.trim().is_empty() {
+            return Err(ManifestError::MissingName);
+        }
+        let mut seen = HashSet::new();
+        for (index, timer) in self.timers.iter().enumerate() {
+            if timer.name.trim().is_empty() {
+                return Err(ManifestError::MissingTimerName(index));
+            }
+            if !seen.insert(timer.name.as_str()) {
+                return Err(ManifestError::DuplicateTimer(timer.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// The spec each manifest timer schedules, labelled as owned by this manifest.
+    pub fn specs(&self, tenant_id: &str, requested_by: &str) -> Vec<TimerSpec> {
+        self.timers
+            .iter()
+            .map(|timer| {
+                let mut labels = timer.labels.clone();
+                labels.insert(MANIFEST_LABEL.to_string(), self.name.clone());
+                TimerSpec {
+                    tenant_id: tenant_id.to_string(),
+                    requested_by: requested_by.to_string(),
+                    name: Some(timer.name.clone()),
+                    duration_ms: timer.duration_ms,
+                    duration_us: timer.duration_us,
+                    fire_at: timer.fire_at,
+                    metadata: timer.metadata.clone(),
+                    labels,
+                    action_bundle: timer.action_bundle.clone(),
+                    agent_binding: timer.agent_binding.clone(),
+                    accuracy_budget_ms: timer.accuracy_budget_ms,
+                    pre_fire_notice_ms: timer.pre_fire_notice_ms,
+                    idempotency_key: None,
+                    missed_fire_policy: None,
+                    session_id: None,
+                    kind: TimerKind::OneShot,
+                    priority: timer.priority,
+                }
+            })
+            .collect()
+    }
+}
+
+/// One step of converging a tenant onto a manifest.
+#[derive(Clone, Debug)]
+pub enum ManifestStep {
+    Create(TimerSpec),
+    /// The desired timer changed: schedule `spec`, then cancel `previous` if it is still pending.
+    Replace {
+        previous: Box<TimerInstance>,
+        spec: TimerSpec,
+    },
+    Unchanged(TimerInstance),
+    /// Owned by the manifest but no longer listed in it.
+    Remove(TimerInstance),
+}
+
+/// Diffs `specs` against `owned`, the non-cancelled timers labelled with the manifest. A fired or
+/// missed timer still counts as converged, so re-applying a manifest does not re-run one-shot
+/// timers.
+pub fn plan(specs: Vec<TimerSpec>, mut owned: Vec<TimerInstance>) -> Vec<ManifestStep> {
+    let mut current: HashMap<String, TimerInstance> = HashMap::new();
+    let mut steps = Vec::new();
+    owned.sort_by_key(|timer| timer.created_at);
+    for timer in owned {
+        // Only the newest timer per name is live; older pending copies are stale.
+        if let Some(older) = current.insert(timer.name.clone(), timer) {
+            if !older.is_terminal() {
+                steps.push(ManifestStep::Remove(older));
+            }
+        }
+    }
+    for spec in specs {
+        let name = spec.name.clone().unwrap_or_default();
+        steps.push(match current.remove(&name) {
+            None => ManifestStep::Create(spec),
+            Some(timer) if converged(&timer, &spec) => ManifestStep::Unchanged(timer),
+            Some(previous) => ManifestStep::Replace {
+                previous: Box::new(previous),
+                spec,
+            },
+        });
+    }
+    steps.extend(
+        current
+            .into_values()
+            .filter(|timer| !timer.is_terminal())
+            .map(ManifestStep::Remove),
+    );
+    steps
+}
+
+fn converged(timer: &TimerInstance, spec: &TimerSpec) -> bool {
+    let schedule = match (spec.fire_at, spec.duration_us) {
+        (Some(fire_at), _) => timer.fire_at == fire_at,
+        (None, Some(duration_us)) => timer.duration_us == Some(duration_us),
+        (None, None) => timer.duration_us.is_none() && timer.duration_ms == spec.duration_ms,
+    };
+    schedule
+        && timer.labels == spec.labels
+        && timer.metadata == spec.metadata
+        && timer.action_bundle == spec.action_bundle
+        && timer.agent_binding == spec.agent_binding
+        && timer.accuracy_budget_ms == spec.accuracy_budget_ms
+        && timer.pre_fire_notice_ms == spec.pre_fire_notice_ms
+        && timer.priority == spec.priority
+}
+
+/// What an apply changed, by outcome.
+#[derive(Clone, Debug, Default)]
+pub struct ApplyReport {
+    pub created: Vec<TimerInstance>,
+    /// Replacement timers scheduled for manifest entries that changed.
+    pub updated: Vec<TimerInstance>,
+    pub cancelled: Vec<TimerInstance>,
+    pub unchanged: Vec<TimerInstance>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_duplicate_and_unknown_fields() {
+        let duplicate = r#"{"name": "ops", "timers": [{"name": "a", "duration_ms": 5}, {"name": "a", "duration_ms": 6}]}"#;
+        assert_eq!(
+            TimerManifest::from_json(duplicate),
+            Err(ManifestError::DuplicateTimer("a".into()))
+        );
+        let typo = r#"{"name": "ops", "timers": [{"name": "a", "duration": 5}]}"#;
+        assert!(matches!(
+            TimerManifest::from_json(typo),
+            Err(ManifestError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    clock::{Clock, SystemClock},
+    persistence::{StoreError, UsageStore},
+};
+
+/// Closed records [`UsageMeter::default`] keeps in memory per tenant.
+pub const DEFAULT_USAGE_RETENTION: usize = 1024;
+
+/// Billable usage accumulated for a tenant.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageCounters {
+    pub timers_scheduled: u64,
+    pub fires_delivered: u64,
+    pub action_executions: u64,
+    pub event_bytes_published: u64,
+}
+
+impl UsageCounters {
+    fn is_empty(&self) -> bool {
+        *self == UsageCounters::default()
+    }
+}
+
+/// Usage for one tenant over a closed metering period.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub tenant_id: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub counters: UsageCounters,
+}
+
+struct MeterState {
+    period_start: DateTime<Utc>,
+    current: HashMap<String, UsageCounters>,
+    /// Closed records per tenant, oldest first.
+    records: HashMap<String, VecDeque<UsageRecord>>,
+    store: Option<Arc<dyn UsageStore>>,
+}
+
+/// Per-tenant usage meter. Counters accumulate for the open period until [`UsageMeter::roll_up`]
+/// closes it into [`UsageRecord`]s; the most recent `retention` records of each tenant are kept
+/// for queries, and [`UsageMeter::close_period`] also appends them to the attached store.
+#[derive(Clone)]
+pub struct UsageMeter {
+    state: Arc<Mutex<MeterState>>,
+    retention: usize,
+    clock: Arc<dyn Clock>,
+}
+
+impl UsageMeter {
+    pub fn new(retention: usize, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MeterState {
+                period_start: clock.now(),
+                current: HashMap::new(),
+                records: HashMap::new(),
+                store: None,
+            })),
+            retention,
+            clock,
+        }
+    }
+
+    /// Loads the closed records `store` holds and appends later ones to it on
+    /// [`Self::close_period`]. Returns how many records were loaded.
+    pub async fn attach_store(&self, store: Arc<dyn UsageStore>) -> Result<usize, StoreError> {
+        let stored = store.load_records().await?;
+        let mut state = self.lock();
+        for record in &stored {
+            state.retain(record.clone(), self.retention);
+        }
+        state.store = Some(store);
+        Ok(stored.len())
+    }
+
+    pub fn record_scheduled(&self, tenant_id: &str) {
+        self.update(tenant_id, |counters| counters.timers_scheduled += 1);
+    }
+
+    pub fn record_fired(&self, tenant_id: &str) {
+        self.update(tenant_id, |counters| counters.fires_delivered += 1);
+    }
+
+    pub fn record_action_executions(&self, tenant_id: &str, count: u64) {
+        self.update(tenant_id, |counters| counters.action_executions += count);
+    }
+
+    pub fn record_event_bytes(&self, tenant_id: &str, bytes: u64) {
+        self.update(tenant_id, |counters| {
+            counters.event_bytes_published += bytes
+        });
+    }
+
+    /// Counters for the open period along with its start time.
+    pub fn current(&self, tenant_id: &str) -> (DateTime<Utc>, UsageCounters) {
+        let state = self.lock();
+        (
+            state.period_start,
+            state.current.get(tenant_id).cloned().unwrap_or_default(),
+        )
+    }
+
+    /// Closed usage records for a tenant, oldest first.
+    pub fn records(&self, tenant_id: &str) -> Vec<UsageRecord> {
+        self.lock()
+            .records
+            .get(tenant_id)
+            .map(|records| records.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// [`Self::roll_up`] at the clock's current time, appending the closed records to the
+    /// attached store. They are kept in memory even when the write fails.
+    pub async fn close_period(&self) -> Result<Vec<UsageRecord>, StoreError> {
+        let closed = self.roll_up(self.clock.now());
+        let store = self.lock().store.clone();
+        if let Some(store) = store {
+            if !closed.is_empty() {
+                store.append_records(&closed).await?;
+            }
+        }
+        Ok(closed)
+    }
+
+    /// Closes the open period at `now`, returning one record per tenant with non-zero usage.
+    pub fn roll_up(&self, now: DateTime<Utc>) -> Vec<UsageRecord> {
+        let mut state = self.lock();
+        let period_start = std::mem::replace(&mut state.period_start, now);
+        let mut closed: Vec<UsageRecord> = std::mem::take(&mut state.current)
+            .into_iter()
+            .filter(|(_, counters)| !counters.is_empty())
+            .map(|(tenant_id, counters)| UsageRecord {
+                tenant_id,
+                period_start,
+                period_end: now,
+                counters,
+            })
+            .collect();
+        closed.sort_by(|a, b| a.tenant_id.cmp(&b.tenant_id));
+
+        for record in &closed {
+            state.retain(record.clone(), self.retention);
+        }
+        closed
+    }
+
+    fn update(&self, tenant_id: &str, apply: impl FnOnce(&mut UsageCounters)) {
+        let mut state = self.lock();
+        apply(state.current.entry(tenant_id.to_string()).or_default());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MeterState> {
+        self.state.lock().expect("usage meter poisoned")
+    }
+}
+
+impl MeterState {
+    fn retain(&mut self, record: UsageRecord, retention: usize) {
+        let records = self.records.entry(record.tenant_id.clone()).or_default();
+        records.push_back(record);
+        while records.len() > retention {
+            records.pop_front();
+        }
+    }
+}
+
+impl Default for UsageMeter {
+    fn default() -> Self {
+        Self::new(DEFAULT_USAGE_RETENTION, Arc::new(SystemClock))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::MockClock, persistence::FileUsageStore};
+
+    #[test]
+    fn roll_up_closes_period_per_tenant() {
+        let meter = UsageMeter::new(1, Arc::new(SystemClock));
+        meter.record_scheduled("tenant-a");
+        meter.record_fired("tenant-a");
+        meter.record_event_bytes("tenant-a", 128);
+        meter.record_scheduled("tenant-b");
+
+        let records = meter.roll_up(Utc::now());
+        assert_eq!(records.len(), 2);
+        assert_eq!(meter.current("tenant-a").1, UsageCounters::default());
+
+        let history = meter.records("tenant-a");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].counters.timers_scheduled, 1);
+        assert_eq!(history[0].counters.fires_delivered, 1);
+        assert_eq!(history[0].counters.event_bytes_published, 128);
+
+        // Idle periods do not produce records, and retention bounds each tenant's history
+        // without another tenant's usage pushing it out.
+        assert!(meter.roll_up(Utc::now()).is_empty());
+        meter.record_scheduled("tenant-c");
+        meter.roll_up(Utc::now());
+        assert_eq!(meter.records("tenant-a").len(), 1);
+        meter.record_fired("tenant-a");
+        meter.roll_up(Utc::now());
+        let history = meter.records("tenant-a");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].counters.fires_delivered, 1);
+        assert_eq!(history[0].counters.timers_scheduled, 0);
+    }
+
+    #[tokio::test]
+    async fn closed_periods_persist_with_clock_bounds() {
+        let path =
+            std::env::temp_dir().join(format!("minoots-usage-{}.jsonl", uuid::Uuid::new_v4()));
+        let clock = Arc::new(MockClock::default());
+        let opened = clock.now();
+        let meter = UsageMeter::new(DEFAULT_USAGE_RETENTION, clock.clone());
+        assert_eq!(
+            meter
+                .attach_store(Arc::new(FileUsageStore::new(&path)))
+                .await
+                .unwrap(),
+            0
+        );
+        meter.record_scheduled("tenant-a");
+        clock.advance(std::time::Duration::from_secs(3600));
+        let closed = meter.close_period().await.unwrap();
+        assert_eq!(closed[0].period_start, opened);
+        assert_eq!(closed[0].period_end, clock.now());
+
+        let restarted = UsageMeter::default();
+        assert_eq!(
+            restarted
+                .attach_store(Arc::new(FileUsageStore::new(&path)))
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(restarted.records("tenant-a"), closed);
+        let _ = std::fs::remove_file(&path);
+    }
+}
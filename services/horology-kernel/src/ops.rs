@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Subject operational events are published under, kept apart from per-tenant timer events.
+pub const SYSTEM_SUBJECT: &str = "MINOOTS_SYSTEM";
+
+/// Health and lifecycle changes of the kernel itself, for platform alerting.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SystemEventKind {
+    KernelStarted,
+    ShutdownStarted,
+    StandbyPromoted,
+    LeadershipGained {
+        node_id: String,
+    },
+    LeadershipLost {
+        node_id: String,
+    },
+    StoreDegraded {
+        reason: String,
+    },
+    RestoreCompleted {
+        timers: u64,
+        overdue: u64,
+    },
+    QuotaExceeded {
+        tenant_id: String,
+        quota: String,
+    },
+    SchedulingSpike {
+        tenant_id: String,
+        operation: String,
+        per_minute: u64,
+        baseline_per_minute: u64,
+        throttled: bool,
+    },
+    /// A pending timer was found long past due and recovered; `action` is `rearmed` or `fired`.
+    RecoveredStuckTimer {
+        timer_id: Uuid,
+        tenant_id: String,
+        overdue_ms: u64,
+        action: String,
+    },
+    /// A fire met a newer leader's fencing token in the store, so this kernel stepped down to
+    /// standby.
+    Fenced {
+        fencing_token: u64,
+        current_token: u64,
+    },
+    /// The wall clock stepped by `jump_ms` relative to monotonic time and pending timers were
+    /// re-armed against it.
+    ClockJumped {
+        jump_ms: i64,
+        rearmed: u64,
+    },
+}
+
+impl SystemEventKind {
+    /// Whether the event changes which kernel leads: promotion, lease changes, or being fenced.
+    pub fn is_leadership_change(&self) -> bool {
+        matches!(
+            self,
+            SystemEventKind::StandbyPromoted
+                | SystemEventKind::LeadershipGained { .. }
+                | SystemEventKind::LeadershipLost { .. }
+                | SystemEventKind::Fenced { .. }
+        )
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SystemEventKind::KernelStarted => "kernel_started",
+            SystemEventKind::ShutdownStarted => "shutdown_started",
+            SystemEventKind::StandbyPromoted => "standby_promoted",
+            SystemEventKind::LeadershipGained { .. } => "leadership_gained",
+            SystemEventKind::LeadershipLost { .. } => "leadership_lost",
+            SystemEventKind::StoreDegraded { .. } => "store_degraded",
+            SystemEventKind::RestoreCompleted { .. } => "restore_completed",
+            SystemEventKind::QuotaExceeded { .. } => "quota_exceeded",
+            SystemEventKind::SchedulingSpike { .. } => "scheduling_spike",
+            SystemEventKind::RecoveredStuckTimer { .. } => "recovered_stuck_timer",
+            SystemEventKind::Fenced { .. } => "fenced",
+            SystemEventKind::ClockJumped { .. } => "clock_jumped",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemEvent {
+    pub subject: String,
+    pub occurred_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: SystemEventKind,
+}
+
+impl SystemEvent {
+    pub fn new(kind: SystemEventKind, occurred_at: DateTime<Utc>) -> Self {
+        Self {
+            subject: SYSTEM_SUBJECT.to_string(),
+            occurred_at,
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_kind_alongside_subject() {
+        let event = SystemEvent::new(
+            SystemEventKind::QuotaExceeded {
+                tenant_id: "tenant-a".into(),
+                quota: "active_timers".into(),
+            },
+            Utc::now(),
+        );
+        let json = serde_json::to_value(&event).expect("serialize");
+        assert_eq!(json["subject"], SYSTEM_SUBJECT);
+        assert_eq!(json["kind"], "quota_exceeded");
+        assert_eq!(json["tenant_id"], "tenant-a");
+        assert_eq!(event.kind.name(), "quota_exceeded");
+    }
+}
@@ -0,0 +1,744 @@
+//! In-process action execution for single-binary deployments. Consumes fired timers straight from
+//! the kernel's event fanout, so no NATS or separate orchestrator process is needed.
+//!
+//! Mirrors the TypeScript orchestrator's contract for `webhook` actions (method, headers, body,
+//! `timeoutMs`, `x-minoots-*` headers). Only plain `http` targets are supported. Actions of any
+//! other kind, agent prompts included, fail with "no executor", so the timer is not settled as if
+//! they had run.
+//!
+//! `grpc` actions call a unary method on an internal service (`target`, `method` as
+//! `/package.Service/Method`, `timeoutMs`, `metadata`). The request is, in order of preference,
+//! the pre-encoded protobuf bytes of `requestBase64`, the JSON template `request` sent as a
+//! `google.protobuf.Struct`, or the fired timer as a `minoots.timer.v1.TimerEvent`. In the
+//! template, a string that is exactly `{{timer.<field>}}` (the timer's JSON fields, e.g.
+//! `{{timer.tenant_id}}` or `{{timer.metadata.plan}}`) or `{{fired_at}}` is replaced with that
+//! value. The response bytes are recorded, base64 encoded, as the action's output.
+//!
+//! Webhook and gRPC targets are held to the destination policy of event webhooks: unless the
+//! [`WebhookRegistry`] passed to [`EmbeddedOrchestrator::new`] allows private destinations, actions
+//! aimed at loopback, private, or link-local addresses (the cloud metadata endpoint included) fail
+//! without a connection being made.
+//!
+//! Webhooks of tenants whose [`TenantPolicy`](crate::TenantPolicy) sets a
+//! `webhook_signing_secret` carry `x-minoots-timestamp` and `x-minoots-signature` headers, signed
+//! like event webhooks and checked with [`verify_signature`](crate::sinks::webhook::verify_signature).
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hyper::{client::HttpConnector, Body, Client, Method, Request};
+use prost::{
+    bytes::{Buf, BufMut},
+    Message,
+};
+use prost_types::value::Kind;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tonic::{
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    codegen::http::uri::PathAndQuery,
+    metadata::{MetadataKey, MetadataValue},
+    transport::Endpoint,
+    Status,
+};
+use tracing::{info, warn};
+
+use crate::sinks::webhook::{sign, GuardedResolver, WebhookRegistry};
+use crate::{
+    fanout::RecvError, grpc::event_to_proto, ActionReport, HorologyKernel, TimerEvent,
+    TimerInstance,
+};
+
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+#[derive(Debug, Deserialize)]
+struct ActionBundle {
+    #[serde(default)]
+    actions: Vec<Action>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Action {
+    id: String,
+    kind: String,
+    #[serde(default)]
+    parameters: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookParameters {
+    url: String,
+    #[serde(default = "default_method")]
+    method: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    body: Option<Value>,
+    timeout_ms: Option<u64>,
+}
+
+fn default_method() -> String {
+    "POST".into()
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GrpcParameters {
+    target: String,
+    method: String,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+    request_base64: Option<String>,
+    request: Option<Value>,
+    timeout_ms: Option<u64>,
+}
+
+/// Passes already-encoded protobuf messages through, so any unary method can be called without
+/// its generated types.
+#[derive(Clone, Copy, Debug, Default)]
+struct RawCodec;
+
+impl Codec for RawCodec {
+    type Encode = Vec<u8>;
+    type Decode = Vec<u8>;
+    type Encoder = RawCodec;
+    type Decoder = RawCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        RawCodec
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        RawCodec
+    }
+}
+
+impl Encoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Vec<u8>, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Vec<u8>>, Status> {
+        Ok(Some(src.copy_to_bytes(src.remaining()).to_vec()))
+    }
+}
+
+/// Outcome of one action, shaped like the orchestrator's `ExecutionResult`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionResult {
+    pub action_id: String,
+    pub success: bool,
+    pub output: String,
+    pub duration_ms: u64,
+}
+
+impl From<ActionResult> for ActionReport {
+    fn from(result: ActionResult) -> Self {
+        ActionReport {
+            action_id: result.action_id,
+            success: result.success,
+            duration_ms: result.duration_ms,
+            error: (!result.success).then_some(result.output),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EmbeddedOrchestrator {
+    client: Client<HttpConnector<GuardedResolver>>,
+    /// Decides which addresses actions may reach.
+    destinations: WebhookRegistry,
+}
+
+impl Default for EmbeddedOrchestrator {
+    /// Orchestrator whose actions may not reach private destinations.
+    fn default() -> Self {
+        Self::new(WebhookRegistry::default())
+    }
+}
+
+impl EmbeddedOrchestrator {
+    /// Orchestrator whose actions may reach the destinations `destinations` allows event webhooks
+    /// to, usually the kernel's own [`HorologyKernel::webhooks`].
+    pub fn new(destinations: WebhookRegistry) -> Self {
+        Self {
+            client: Client::builder().build(destinations.guarded_connector()),
+            destinations,
+        }
+    }
+
+    /// Executes the actions of every timer the kernel fires, settling or failing the timer with the
+    /// results, until the event channel closes.
+    pub async fn run(&self, kernel: HorologyKernel) {
+        let mut events = kernel.subscribe();
+        loop {
+            match events.recv().await {
+                Ok(TimerEvent::Fired(timer)) => {
+                    let secret = kernel
+                        .tenant_policy(&timer.tenant_id)
+                        .webhook_signing_secret;
+                    let results = self.execute(&timer, secret.as_deref()).await;
+                    if !results.is_empty() {
+                        kernel
+                            .usage()
+                            .record_action_executions(&timer.tenant_id, results.len() as u64);
+                        info!(timer_id = %timer.id, ?results, "timer actions executed");
+                        let reports = results.into_iter().map(ActionReport::from).collect();
+                        if let Err(error) = kernel
+                            .report_execution(&timer.tenant_id, timer.id, reports)
+                            .await
+                        {
+                            warn!(timer_id = %timer.id, %error, "could not report timer execution");
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(
+                        skipped,
+                        "embedded orchestrator fell behind; fired timers dropped"
+                    )
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Runs a fired timer's action bundle in order and reports each action's outcome. Webhook
+    /// bodies are signed with `signing_secret` when one is given.
+    pub async fn execute(
+        &self,
+        timer: &TimerInstance,
+        signing_secret: Option<&str>,
+    ) -> Vec<ActionResult> {
+        let Some(bundle) = timer.action_bundle.clone() else {
+            return Vec::new();
+        };
+        let bundle: ActionBundle = match serde_json::from_value(bundle) {
+            Ok(bundle) => bundle,
+            Err(error) => {
+                warn!(timer_id = %timer.id, %error, "unreadable action bundle");
+                return Vec::new();
+            }
+        };
+        let mut results = Vec::new();
+        for action in &bundle.actions {
+            let started = Instant::now();
+            let outcome = match action.kind.as_str() {
+                "webhook" => self.webhook(timer, &bundle, action, signing_secret).await,
+                "grpc" => self.grpc(timer, action).await,
+                kind => Err(format!(
+                    "embedded orchestrator has no executor for {kind} actions"
+                )),
+            };
+            if let Err(error) = &outcome {
+                warn!(timer_id = %timer.id, action_id = %action.id, %error, "action failed");
+            }
+            results.push(ActionResult {
+                action_id: action.id.clone(),
+                success: outcome.is_ok(),
+                output: outcome.unwrap_or_else(|error| error),
+                duration_ms: started.elapsed().as_millis() as u64,
+            });
+        }
+        results
+    }
+
+    async fn webhook(
+        &self,
+        timer: &TimerInstance,
+        bundle: &ActionBundle,
+        action: &Action,
+        signing_secret: Option<&str>,
+    ) -> Result<String, String> {
+        let parameters: WebhookParameters = serde_json::from_value(action.parameters.clone())
+            .map_err(|error| format!("invalid webhook parameters: {error}"))?;
+        if !parameters.url.starts_with("http://") {
+            return Err("embedded orchestrator only supports plain http webhook targets".into());
+        }
+        self.destinations
+            .check_destination(&parameters.url)
+            .await
+            .map_err(|error| error.to_string())?;
+        let method = Method::from_bytes(parameters.method.to_ascii_uppercase().as_bytes())
+            .map_err(|_| format!("unsupported method {}", parameters.method))?;
+        let body = parameters
+            .body
+            .unwrap_or_else(|| json!({ "timer": timer, "event": action.kind }));
+        let mut request = Request::builder().method(method).uri(&parameters.url);
+        for (name, value) in bundle.headers.iter().chain(&parameters.headers) {
+            if !name.to_ascii_lowercase().starts_with("x-minoots-") {
+                request = request.header(name, value);
+            }
+        }
+        let body = body.to_string();
+        if let Some(secret) = signing_secret {
+            let timestamp = chrono::Utc::now().timestamp();
+            request = request
+                .header("x-minoots-timestamp", timestamp.to_string())
+                .header(
+                    "x-minoots-signature",
+                    sign(secret, timestamp, body.as_bytes()),
+                );
+        }
+        let request = request
+            .header("content-type", "application/json")
+            .header("x-minoots-timer-id", timer.id.to_string())
+            .header("x-minoots-tenant-id", &timer.tenant_id)
+            .body(Body::from(body))
+            .map_err(|error| error.to_string())?;
+        let timeout = Duration::from_millis(parameters.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+        let response = tokio::time::timeout(timeout, self.client.request(request))
+            .await
+            .map_err(|_| format!("timed out after {}ms", timeout.as_millis()))?
+            .map_err(|error| error.to_string())?;
+        if response.status().is_success() {
+            Ok(format!("HTTP {}", response.status().as_u16()))
+        } else {
+            Err(format!("HTTP {}", response.status().as_u16()))
+        }
+    }
+
+    async fn grpc(&self, timer: &TimerInstance, action: &Action) -> Result<String, String> {
+        let parameters: GrpcParameters = serde_json::from_value(action.parameters.clone())
+            .map_err(|error| format!("invalid grpc parameters: {error}"))?;
+        let path = PathAndQuery::try_from(parameters.method.as_str())
+            .ok()
+            .filter(|path| path.path().matches('/').count() == 2 && path.query().is_none())
+            .ok_or_else(|| {
+                format!(
+                    "method {} is not /package.Service/Method",
+                    parameters.method
+                )
+            })?;
+        let body = match (&parameters.request_base64, &parameters.request) {
+            (Some(_), Some(_)) => {
+                return Err("requestBase64 and request are mutually exclusive".into());
+            }
+            (Some(encoded), None) => STANDARD
+                .decode(encoded)
+                .map_err(|error| format!("invalid requestBase64: {error}"))?,
+            (None, Some(template)) => {
+                let context = json!({
+                    "timer": timer,
+                    "fired_at": timer.fired_at.unwrap_or_else(chrono::Utc::now),
+                });
+                match to_proto_value(render(template, &context)).kind {
+                    Some(Kind::StructValue(request)) => request.encode_to_vec(),
+                    _ => return Err("request must be a JSON object".into()),
+                }
+            }
+            (None, None) => event_to_proto(TimerEvent::Fired(timer.clone()))
+                .map_err(|status| status.message().to_string())?
+                .encode_to_vec(),
+        };
+        if !parameters.target.starts_with("http://") {
+            return Err("embedded orchestrator only supports plain http gRPC targets".into());
+        }
+        self.destinations
+            .check_destination(&parameters.target)
+            .await
+            .map_err(|error| error.to_string())?;
+        let timeout = Duration::from_millis(parameters.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+        let endpoint = Endpoint::from_shared(parameters.target.clone())
+            .map_err(|error| format!("invalid target {}: {error}", parameters.target))?
+            .connect_timeout(timeout);
+        let mut request = tonic::Request::new(body);
+        // Sent as `grpc-timeout`, so the server sees the same deadline.
+        request.set_timeout(timeout);
+        let metadata = request.metadata_mut();
+        for (name, value) in &parameters.metadata {
+            if name.to_ascii_lowercase().starts_with("x-minoots-") {
+                continue;
+            }
+            let key = MetadataKey::from_bytes(name.as_bytes())
+                .map_err(|_| format!("invalid metadata key {name}"))?;
+            let value = MetadataValue::try_from(value.as_str())
+                .map_err(|_| format!("invalid metadata value for {name}"))?;
+            metadata.insert(key, value);
+        }
+        metadata.insert(
+            "x-minoots-timer-id",
+            MetadataValue::try_from(timer.id.to_string()).map_err(|error| error.to_string())?,
+        );
+        metadata.insert(
+            "x-minoots-tenant-id",
+            MetadataValue::try_from(timer.tenant_id.as_str()).map_err(|error| error.to_string())?,
+        );
+        let call = async {
+            let channel = endpoint
+                .connect_with_connector(self.destinations.guarded_connector())
+                .await
+                .map_err(|error| format!("could not connect to {}: {error}", parameters.target))?;
+            let mut client = tonic::client::Grpc::new(channel);
+            client
+                .ready()
+                .await
+                .map_err(|error| format!("could not connect to {}: {error}", parameters.target))?;
+            client
+                .unary(request, path, RawCodec)
+                .await
+                .map_err(|status| format!("gRPC {:?}: {}", status.code(), status.message()))
+        };
+        let response = tokio::time::timeout(timeout, call)
+            .await
+            .map_err(|_| format!("timed out after {}ms", timeout.as_millis()))??;
+        Ok(STANDARD.encode(response.into_inner()))
+    }
+}
+
+/// Replaces every string of `template` that is exactly one `{{path}}` expression with the value at
+/// `path` in `context`, or null when there is none.
+fn render(template: &Value, context: &Value) -> Value {
+    match template {
+        Value::String(text) => match text
+            .trim()
+            .strip_prefix("{{")
+            .and_then(|rest| rest.strip_suffix("}}"))
+        {
+            Some(path) => path
+                .trim()
+                .split('.')
+                .try_fold(context, |value, segment| value.get(segment))
+                .cloned()
+                .unwrap_or(Value::Null),
+            None => template.clone(),
+        },
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|item| render(item, context)).collect())
+        }
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), render(value, context)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+/// `value` as a `google.protobuf.Value`, following the protobuf JSON mapping.
+fn to_proto_value(value: Value) -> prost_types::Value {
+    let kind = match value {
+        Value::Null => Kind::NullValue(0),
+        Value::Bool(value) => Kind::BoolValue(value),
+        Value::Number(number) => Kind::NumberValue(number.as_f64().unwrap_or_default()),
+        Value::String(text) => Kind::StringValue(text),
+        Value::Array(items) => Kind::ListValue(prost_types::ListValue {
+            values: items.into_iter().map(to_proto_value).collect(),
+        }),
+        Value::Object(fields) => Kind::StructValue(prost_types::Struct {
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (name, to_proto_value(value)))
+                .collect(),
+        }),
+    };
+    prost_types::Value { kind: Some(kind) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Orchestrator allowed to reach the loopback servers these tests run.
+    fn loopback_orchestrator() -> EmbeddedOrchestrator {
+        let destinations = WebhookRegistry::default();
+        destinations.allow_private_destinations(true);
+        EmbeddedOrchestrator::new(destinations)
+    }
+
+    #[tokio::test]
+    async fn posts_webhook_actions_of_fired_timers() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 8192];
+            let read = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request[..read]).to_string()
+        });
+
+        let timer: TimerInstance = serde_json::from_value(json!({
+            "id": uuid::Uuid::new_v4(),
+            "tenant_id": "tenant-a",
+            "requested_by": "agent-1",
+            "name": "hook",
+            "duration_ms": 1,
+            "created_at": "2025-01-10T12:00:00Z",
+            "fire_at": "2025-01-10T12:00:00Z",
+            "status": "fired",
+            "action_bundle": {
+                "headers": { "x-team": "ops" },
+                "actions": [
+                    { "id": "notify", "kind": "webhook", "parameters": { "url": url } },
+                    { "id": "secure", "kind": "webhook", "parameters": { "url": "https://example.com" } },
+                    { "id": "later", "kind": "workflow_event" }
+                ]
+            }
+        }))
+        .unwrap();
+        let results = loopback_orchestrator()
+            .execute(&timer, Some("whsec_test"))
+            .await;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].output, "HTTP 204");
+        assert!(results[0].success);
+        assert!(!results[1].success);
+        assert!(!results[2].success);
+        assert_eq!(
+            results[2].output,
+            "embedded orchestrator has no executor for workflow_event actions"
+        );
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /hook"));
+        assert!(request.contains("x-team: ops"));
+        assert!(request.contains(&format!("x-minoots-timer-id: {}", timer.id)));
+        let header = |name: &str| {
+            request
+                .lines()
+                .find_map(|line| line.strip_prefix(&format!("{name}: ")))
+                .unwrap()
+                .to_string()
+        };
+        let (_, body) = request.split_once("\r\n\r\n").unwrap();
+        assert_eq!(
+            crate::sinks::webhook::verify_signature(
+                "whsec_test",
+                &header("x-minoots-timestamp"),
+                &header("x-minoots-signature"),
+                body.as_bytes(),
+                chrono::Utc::now(),
+                crate::sinks::webhook::SIGNATURE_TOLERANCE,
+            ),
+            Ok(())
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_webhooks_to_internal_addresses_by_default() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let timer: TimerInstance = serde_json::from_value(json!({
+            "id": uuid::Uuid::new_v4(),
+            "tenant_id": "tenant-a",
+            "requested_by": "agent-1",
+            "name": "hook",
+            "duration_ms": 1,
+            "created_at": "2025-01-10T12:00:00Z",
+            "fire_at": "2025-01-10T12:00:00Z",
+            "status": "fired",
+            "action_bundle": {
+                "actions": [
+                    { "id": "loopback", "kind": "webhook", "parameters": { "url": format!("http://{address}/hook") } },
+                    { "id": "metadata", "kind": "webhook", "parameters": { "url": "http://169.254.169.254/latest/meta-data/" } },
+                    { "id": "named", "kind": "webhook", "parameters": { "url": format!("http://localhost:{}/hook", address.port()) } }
+                ]
+            }
+        }))
+        .unwrap();
+
+        let results = EmbeddedOrchestrator::default().execute(&timer, None).await;
+        assert_eq!(results.len(), 3);
+        for result in &results {
+            assert!(!result.success, "{result:?}");
+        }
+        assert!(
+            results[0].output.contains("loopback, private"),
+            "{}",
+            results[0].output
+        );
+        assert!(
+            results[1].output.contains("169.254.169.254"),
+            "{}",
+            results[1].output
+        );
+        // Nothing connected to the listener.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), listener.accept())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_grpc_calls_to_internal_addresses_by_default() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let call = |id: &str, target: String| {
+            json!({
+                "id": id,
+                "kind": "grpc",
+                "parameters": { "target": target, "method": "/billing.v1.Billing/Expire" }
+            })
+        };
+        let timer: TimerInstance = serde_json::from_value(json!({
+            "id": uuid::Uuid::new_v4(),
+            "tenant_id": "tenant-a",
+            "requested_by": "agent-1",
+            "name": "call",
+            "duration_ms": 1,
+            "created_at": "2025-01-10T12:00:00Z",
+            "fire_at": "2025-01-10T12:00:00Z",
+            "status": "fired",
+            "action_bundle": {
+                "actions": [
+                    call("loopback", format!("http://{address}")),
+                    call("named", format!("http://localhost:{}", address.port())),
+                    call("metadata", "http://169.254.169.254".into())
+                ]
+            }
+        }))
+        .unwrap();
+
+        let results = EmbeddedOrchestrator::default().execute(&timer, None).await;
+        assert_eq!(results.len(), 3);
+        for result in &results {
+            assert!(!result.success, "{result:?}");
+        }
+        assert!(
+            results[0].output.contains("loopback, private"),
+            "{}",
+            results[0].output
+        );
+        assert!(
+            results[2].output.contains("169.254.169.254"),
+            "{}",
+            results[2].output
+        );
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), listener.accept())
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn renders_request_templates_as_protobuf_structs() {
+        let context = json!({
+            "timer": { "id": "timer-1", "metadata": { "plan": "pro", "seats": 3 } },
+            "fired_at": "2025-01-10T12:00:00Z",
+        });
+        let template = json!({
+            "timer_id": "{{timer.id}}",
+            "seats": "{{ timer.metadata.seats }}",
+            "at": ["{{fired_at}}", "literal {{timer.id}}"],
+            "missing": "{{timer.nothing}}",
+        });
+        let Some(Kind::StructValue(request)) = to_proto_value(render(&template, &context)).kind
+        else {
+            panic!("template did not render to a struct");
+        };
+        let decoded = prost_types::Struct::decode(request.encode_to_vec().as_slice()).unwrap();
+        let field = |name: &str| decoded.fields[name].kind.clone().unwrap();
+        assert_eq!(field("timer_id"), Kind::StringValue("timer-1".into()));
+        assert_eq!(field("seats"), Kind::NumberValue(3.0));
+        assert_eq!(field("missing"), Kind::NullValue(0));
+        let Kind::ListValue(at) = field("at") else {
+            panic!("at is not a list");
+        };
+        assert_eq!(
+            at.values[0].kind,
+            Some(Kind::StringValue("2025-01-10T12:00:00Z".into()))
+        );
+        // Only whole-string expressions are substituted.
+        assert_eq!(
+            at.values[1].kind,
+            Some(Kind::StringValue("literal {{timer.id}}".into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn calls_unary_grpc_methods_with_raw_requests() {
+        use crate::{
+            grpc::HorologyKernelService,
+            pb::{horology_kernel_server::HorologyKernelServer, TimerGetRequest},
+            SchedulerConfig, TimerSpec,
+        };
+
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let scheduled = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60_000,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(HorologyKernelServer::new(HorologyKernelService::new(
+                    kernel.clone(),
+                )))
+                .serve_with_shutdown("127.0.0.1:50074".parse().unwrap(), async {
+                    shutdown_rx.await.ok();
+                }),
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let request = TimerGetRequest {
+            tenant_id: "tenant-a".into(),
+            timer_id: scheduled.id.to_string(),
+            ..Default::default()
+        };
+        let get = |id: &str, timer_id: String| {
+            json!({
+                "id": id,
+                "kind": "grpc",
+                "parameters": {
+                    "target": "http://127.0.0.1:50074",
+                    "method": "/minoots.timer.v1.HorologyKernel/GetTimer",
+                    "requestBase64": STANDARD.encode(TimerGetRequest { timer_id, ..request.clone() }.encode_to_vec()),
+                    "timeoutMs": 2_000
+                }
+            })
+        };
+        let mut fired = scheduled.clone();
+        fired.action_bundle = Some(json!({
+            "actions": [
+                get("found", scheduled.id.to_string()),
+                get("missing", uuid::Uuid::new_v4().to_string()),
+                { "id": "bad-method", "kind": "grpc", "parameters": { "target": "http://127.0.0.1:50074", "method": "GetTimer" } }
+            ]
+        }));
+        let results = loopback_orchestrator().execute(&fired, None).await;
+        shutdown_tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+
+        assert!(results[0].success, "{results:?}");
+        let timer =
+            crate::pb::Timer::decode(STANDARD.decode(&results[0].output).unwrap().as_slice())
+                .unwrap();
+        assert_eq!(timer.id, scheduled.id.to_string());
+        assert!(!results[1].success);
+        assert!(
+            results[1].output.starts_with("gRPC NotFound"),
+            "{}",
+            results[1].output
+        );
+        assert!(!results[2].success);
+        assert!(results[2].output.contains("/package.Service/Method"));
+    }
+}
@@ -0,0 +1,340 @@
+//! Snapshot cursors for paginated timer listings. The first page pins the complete filtered and
+//! sorted result; later pages are served from that snapshot, so a full walk reflects a single point
+//! in time no matter how timers are scheduled, fired, or cancelled between requests.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::TimerInstance;
+
+/// How long an unused snapshot is kept before its cursors expire.
+pub const SNAPSHOT_TTL: Duration = Duration::from_secs(300);
+/// Snapshots held at once; the least recently used one is dropped beyond this.
+const MAX_SNAPSHOTS: usize = 256;
+/// Snapshots one tenant (or organization, or the all-tenant scope) holds at once; its least
+/// recently used one is dropped beyond this, so one caller cannot evict everyone else's.
+pub const MAX_SNAPSHOTS_PER_TENANT: usize = 16;
+/// Timers a snapshot may pin. Larger paginated listings are refused rather than copied, except the
+/// all-tenant listing a standby hydrates from, which would otherwise never fit a large primary.
+pub const MAX_SNAPSHOT_TIMERS: usize = 50_000;
+/// Owner of snapshots of the all-tenant listing, which only the operator may take.
+pub(crate) const ALL_TENANTS_OWNER: &str = "*";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PageTokenError {
+    #[error("page_token is malformed")]
+    Malformed,
+    #[error("page_token has expired; restart the listing without a token")]
+    Expired,
+    #[error("page_token belongs to a different listing")]
+    Mismatch,
+    #[error(
+        "listing matches {matched} timers, more than the {max} a paginated listing can hold; \
+         narrow the filter"
+    )]
+    TooLarge { matched: usize, max: usize },
+}
+
+/// Page of a listing request.
+#[derive(Clone, Debug, Default)]
+pub struct PageRequest {
+    /// Identifies the listing (tenant scope, filter, and order as the caller sent them); a token is
+    /// only honoured for the listing that issued it.
+    pub listing: String,
+    /// `0` returns every remaining timer.
+    pub page_size: usize,
+    pub page_token: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TimerPage {
+    pub timers: Vec<TimerInstance>,
+    /// `None` on the last page.
+    pub next_page_token: Option<String>,
+    /// When the listing's snapshot was taken.
+    pub snapshot_at: DateTime<Utc>,
+    /// Timers in the whole snapshot.
+    pub total: usize,
+}
+
+struct Snapshot {
+    /// Tenant scope the snapshot counts against.
+    owner: String,
+    listing: String,
+    taken_at: DateTime<Utc>,
+    timers: Arc<Vec<TimerInstance>>,
+    last_used: Instant,
+}
+
+#[derive(Clone)]
+pub struct ListSnapshots {
+    inner: Arc<Mutex<HashMap<Uuid, Snapshot>>>,
+    ttl: Duration,
+}
+
+impl Default for ListSnapshots {
+    fn default() -> Self {
+        Self::new(SNAPSHOT_TTL)
+    }
+}
+
+impl ListSnapshots {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            inner: Arc::default(),
+            ttl,
+        }
+    }
+
+    /// Returns the first page of `timers`, pinning the rest as a snapshot owned by `owner` when
+    /// they do not fit.
+    pub fn first_page(
+        &self,
+        owner: &str,
+        listing: &str,
+        timers: Vec<TimerInstance>,
+        taken_at: DateTime<Utc>,
+        page_size: usize,
+    ) -> Result<TimerPage, PageTokenError> {
+        let total = timers.len();
+        if page_size == 0 || total <= page_size {
+            return Ok(TimerPage {
+                timers,
+                next_page_token: None,
+                snapshot_at: taken_at,
+                total,
+            });
+        }
+        if total > MAX_SNAPSHOT_TIMERS && owner != ALL_TENANTS_OWNER {
+            return Err(PageTokenError::TooLarge {
+                matched: total,
+                max: MAX_SNAPSHOT_TIMERS,
+            });
+        }
+        let timers = Arc::new(timers);
+        let id = Uuid::new_v4();
+        let mut snapshots = self.lock();
+        let now = Instant::now();
+        snapshots.retain(|_, snapshot| now.duration_since(snapshot.last_used) < self.ttl);
+        let owned = snapshots
+            .values()
+            .filter(|snapshot| snapshot.owner == owner)
+            .count();
+        if owned >= MAX_SNAPSHOTS_PER_TENANT {
+            evict_oldest(&mut snapshots, |snapshot| snapshot.owner == owner);
+        } else if snapshots.len() >= MAX_SNAPSHOTS {
+            evict_oldest(&mut snapshots, |_| true);
+        }
+        snapshots.insert(
+            id,
+            Snapshot {
+                owner: owner.to_string(),
+                listing: listing.to_string(),
+                taken_at,
+                timers: timers.clone(),
+                last_used: now,
+            },
+        );
+        Ok(page(id, &timers, taken_at, 0, page_size))
+    }
+
+    /// Serves the page `token` points at from its snapshot.
+    pub fn next_page(
+        &self,
+        listing: &str,
+        token: &str,
+        page_size: usize,
+    ) -> Result<TimerPage, PageTokenError> {
+        let (id, offset) = token.split_once(':').ok_or(PageTokenError::Malformed)?;
+        let id = Uuid::parse_str(id).map_err(|_| PageTokenError::Malformed)?;
+        let offset: usize = offset.parse().map_err(|_| PageTokenError::Malformed)?;
+        let mut snapshots = self.lock();
+        let snapshot = snapshots.get_mut(&id).ok_or(PageTokenError::Expired)?;
+        if snapshot.last_used.elapsed() >= self.ttl {
+            snapshots.remove(&id);
+            return Err(PageTokenError::Expired);
+        }
+        if snapshot.listing != listing {
+            return Err(PageTokenError::Mismatch);
+        }
+        if offset > snapshot.timers.len() {
+            return Err(PageTokenError::Malformed);
+        }
+        snapshot.last_used = Instant::now();
+        let result = page(id, &snapshot.timers, snapshot.taken_at, offset, page_size);
+        if result.next_page_token.is_none() {
+            snapshots.remove(&id);
+        }
+        Ok(result)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, Snapshot>> {
+        self.inner.lock().expect("list snapshots poisoned")
+    }
+}
+
+/// Drops the least recently used snapshot among those `candidate` accepts.
+fn evict_oldest(snapshots: &mut HashMap<Uuid, Snapshot>, candidate: impl Fn(&Snapshot) -> bool) {
+    if let Some(oldest) = snapshots
+        .iter()
+        .filter(|(_, snapshot)| candidate(snapshot))
+        .min_by_key(|(_, snapshot)| snapshot.last_used)
+        .map(|(id, _)| *id)
+    {
+        snapshots.remove(&oldest);
+    }
+}
+
+fn page(
+    id: Uuid,
+    timers: &[TimerInstance],
+    taken_at: DateTime<Utc>,
+    offset: usize,
+    page_size: usize,
+) -> TimerPage {
+    let end = if page_size == 0 {
+        timers.len()
+    } else {
+        offset.saturating_add(page_size).min(timers.len())
+    };
+    TimerPage {
+        timers: timers[offset..end].to_vec(),
+        next_page_token: (end < timers.len()).then(|| format!("{id}:{end}")),
+        snapshot_at: taken_at,
+        total: timers.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timers(count: usize) -> Vec<TimerInstance> {
+        (0..count)
+            .map(|i| {
+                serde_json::from_value(serde_json::json!({
+                    "id": Uuid::new_v4(),
+                    "tenant_id": "tenant-a",
+                    "requested_by": "tester",
+                    "name": format!("timer-{i}"),
+                    "duration_ms": 1000,
+                    "created_at": Utc::now(),
+                    "fire_at": Utc::now(),
+                    "status": "scheduled",
+                }))
+                .expect("timer")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn walks_a_snapshot_page_by_page() {
+        let snapshots = ListSnapshots::default();
+        let first = snapshots
+            .first_page("tenant-a", "a", timers(5), Utc::now(), 2)
+            .unwrap();
+        assert_eq!(first.total, 5);
+        let token = first.next_page_token.expect("more pages");
+        assert_eq!(
+            snapshots.next_page("b", &token, 2).unwrap_err(),
+            PageTokenError::Mismatch
+        );
+        let second = snapshots.next_page("a", &token, 2).unwrap();
+        assert_eq!(second.timers[0].name, "timer-2");
+        let last = snapshots
+            .next_page("a", &second.next_page_token.unwrap(), 2)
+            .unwrap();
+        assert_eq!(last.timers.len(), 1);
+        assert!(last.next_page_token.is_none());
+        // The finished walk releases its snapshot.
+        assert_eq!(
+            snapshots.next_page("a", &token, 2).unwrap_err(),
+            PageTokenError::Expired
+        );
+        assert_eq!(
+            snapshots.next_page("a", "not-a-token", 2).unwrap_err(),
+            PageTokenError::Malformed
+        );
+
+        let unpaged = snapshots
+            .first_page("tenant-a", "a", timers(3), Utc::now(), 0)
+            .unwrap();
+        assert_eq!(unpaged.timers.len(), 3);
+        assert!(unpaged.next_page_token.is_none());
+
+        let expiring = ListSnapshots::new(Duration::ZERO);
+        let first = expiring
+            .first_page("tenant-a", "a", timers(3), Utc::now(), 1)
+            .unwrap();
+        assert_eq!(
+            expiring
+                .next_page("a", &first.next_page_token.unwrap(), 1)
+                .unwrap_err(),
+            PageTokenError::Expired
+        );
+    }
+
+    #[test]
+    fn snapshots_are_capped_per_tenant_and_in_size() {
+        let snapshots = ListSnapshots::default();
+        let other = snapshots
+            .first_page("tenant-b", "b", timers(2), Utc::now(), 1)
+            .unwrap()
+            .next_page_token
+            .unwrap();
+        let tokens: Vec<String> = (0..=MAX_SNAPSHOTS_PER_TENANT)
+            .map(|_| {
+                snapshots
+                    .first_page("tenant-a", "a", timers(2), Utc::now(), 1)
+                    .unwrap()
+                    .next_page_token
+                    .unwrap()
+            })
+            .collect();
+        // The tenant's oldest snapshot made room for its newest; the other tenant's survived.
+        assert_eq!(
+            snapshots.next_page("a", &tokens[0], 1).unwrap_err(),
+            PageTokenError::Expired
+        );
+        assert!(snapshots.next_page("a", &tokens[1], 1).is_ok());
+        assert!(snapshots.next_page("b", &other, 1).is_ok());
+
+        assert_eq!(
+            snapshots
+                .first_page(
+                    "tenant-a",
+                    "a",
+                    timers(MAX_SNAPSHOT_TIMERS + 1),
+                    Utc::now(),
+                    10
+                )
+                .unwrap_err(),
+            PageTokenError::TooLarge {
+                matched: MAX_SNAPSHOT_TIMERS + 1,
+                max: MAX_SNAPSHOT_TIMERS
+            }
+        );
+    }
+
+    #[test]
+    fn the_all_tenant_listing_pages_past_the_size_cap() {
+        let snapshots = ListSnapshots::default();
+        let total = MAX_SNAPSHOT_TIMERS + 1;
+        let mut page = snapshots
+            .first_page(ALL_TENANTS_OWNER, "all", timers(total), Utc::now(), 20_000)
+            .unwrap();
+        let mut walked = page.timers.len();
+        while let Some(token) = page.next_page_token {
+            page = snapshots.next_page("all", &token, 20_000).unwrap();
+            walked += page.timers.len();
+        }
+        assert_eq!(walked, total);
+    }
+}
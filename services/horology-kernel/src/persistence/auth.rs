@@ -0,0 +1,134 @@
+use std::{
+    collections::HashMap,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::StoreError;
+use crate::auth::StoredApiToken;
+
+/// Durable home of issued API tokens. [`crate::TokenStore::attach_store`] loads every token at
+/// boot and writes each issue, rotation, and revocation through, so keys survive restarts.
+#[async_trait]
+pub trait AuthStore: Send + Sync {
+    async fn save_token(&self, token: &StoredApiToken) -> Result<(), StoreError>;
+
+    async fn load_tokens(&self) -> Result<Vec<StoredApiToken>, StoreError>;
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryAuthStore {
+    tokens: Arc<Mutex<HashMap<Uuid, StoredApiToken>>>,
+}
+
+impl InMemoryAuthStore {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, StoredApiToken>> {
+        self.tokens.lock().expect("in-memory auth store poisoned")
+    }
+}
+
+#[async_trait]
+impl AuthStore for InMemoryAuthStore {
+    async fn save_token(&self, token: &StoredApiToken) -> Result<(), StoreError> {
+        self.lock().insert(token.token.id, token.clone());
+        Ok(())
+    }
+
+    async fn load_tokens(&self) -> Result<Vec<StoredApiToken>, StoreError> {
+        Ok(self.lock().values().cloned().collect())
+    }
+}
+
+/// JSON-lines log of token records, one line per change; the last line for a token wins on load.
+/// Token changes are rare, so the log is never compacted. A missing file means no tokens.
+pub struct FileAuthStore {
+    path: PathBuf,
+    writer: Mutex<()>,
+}
+
+impl FileAuthStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            writer: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthStore for FileAuthStore {
+    async fn save_token(&self, token: &StoredApiToken) -> Result<(), StoreError> {
+        let mut line = serde_json::to_vec(token)?;
+        line.push(b'\n');
+        let _guard = self.writer.lock().expect("auth store writer poisoned");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&line)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    async fn load_tokens(&self) -> Result<Vec<StoredApiToken>, StoreError> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+        let mut tokens = HashMap::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let token: StoredApiToken = serde_json::from_str(line)?;
+            tokens.insert(token.token.id, token);
+        }
+        Ok(tokens.into_values().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Scope, TokenStore};
+
+    #[tokio::test]
+    async fn file_store_restores_live_and_revoked_tokens() {
+        let path =
+            std::env::temp_dir().join(format!("minoots-tokens-{}.jsonl", uuid::Uuid::new_v4()));
+        let tokens = TokenStore::default();
+        assert_eq!(
+            tokens
+                .attach_store(Arc::new(FileAuthStore::new(&path)))
+                .await
+                .unwrap(),
+            0
+        );
+        let (kept, secret) = tokens.issue("tenant-a", vec![Scope::Read], None);
+        tokens.persist(kept.id).await.unwrap();
+        let (_, rotated) = tokens.rotate("tenant-a", kept.id).unwrap();
+        tokens.persist(kept.id).await.unwrap();
+        let (revoked, revoked_secret) = tokens.issue("tenant-a", vec![Scope::Admin], None);
+        tokens.persist(revoked.id).await.unwrap();
+        tokens.revoke("tenant-a", revoked.id).unwrap();
+        tokens.persist(revoked.id).await.unwrap();
+
+        let restarted = TokenStore::default();
+        assert_eq!(
+            restarted
+                .attach_store(Arc::new(FileAuthStore::new(&path)))
+                .await
+                .unwrap(),
+            2
+        );
+        assert!(restarted.authenticate(&secret).is_none());
+        assert!(restarted.authenticate(&revoked_secret).is_none());
+        let principal = restarted.authenticate(&rotated).expect("rotated secret");
+        assert!(principal.allows(Scope::Read, "tenant-a"));
+        assert!(restarted.list("tenant-a")[1].revoked_at.is_some());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::{sync::Notify, task::JoinHandle};
+use uuid::Uuid;
+
+use super::{FencedWrite, StoreError, TimerStore};
+use crate::TimerInstance;
+
+/// When [`BatchingTimerStore`] forwards queued writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WriteBatchConfig {
+    /// Longest a write waits in the queue.
+    pub flush_interval: Duration,
+    /// Queued timers that trigger a flush without waiting for the interval, and the most sent in one
+    /// `upsert_many`.
+    pub max_batch: usize,
+}
+
+impl Default for WriteBatchConfig {
+    fn default() -> Self {
+        Self {
+            flush_interval: Duration::from_millis(10),
+            max_batch: 256,
+        }
+    }
+}
+
+/// Latest unflushed copy of each timer, in the order timers were first queued.
+#[derive(Default)]
+struct Queue {
+    order: VecDeque<Uuid>,
+    latest: HashMap<Uuid, TimerInstance>,
+}
+
+impl Queue {
+    fn push(&mut self, timer: &TimerInstance) {
+        if self.latest.insert(timer.id, timer.clone()).is_none() {
+            self.order.push_back(timer.id);
+        }
+    }
+
+    fn take(&mut self, max: usize) -> Vec<TimerInstance> {
+        let count = self.order.len().min(max);
+        self.order
+            .drain(..count)
+            .filter_map(|id| self.latest.remove(&id))
+            .collect()
+    }
+
+    /// Puts a failed batch back ahead of everything queued since, unless a newer copy was queued
+    /// meanwhile.
+    fn requeue(&mut self, batch: Vec<TimerInstance>) {
+        for timer in batch.into_iter().rev() {
+            if !self.latest.contains_key(&timer.id) {
+                self.order.push_front(timer.id);
+                self.latest.insert(timer.id, timer);
+            }
+        }
+    }
+}
+
+struct Shared {
+    inner: Arc<dyn TimerStore>,
+    config: WriteBatchConfig,
+    queue: Mutex<Queue>,
+    /// Held for the duration of a flush so two batches never race and a timer's copies reach the
+    /// inner store in the order they were written.
+    flushing: tokio::sync::Mutex<()>,
+    wake: Notify,
+}
+
+impl Shared {
+    fn queue(&self) -> std::sync::MutexGuard<'_, Queue> {
+        self.queue.lock().expect("write batch queue poisoned")
+    }
+
+    async fn flush(&self) -> Result<(), StoreError> {
+        let _flushing = self.flushing.lock().await;
+        loop {
+            let batch = self.queue().take(self.config.max_batch);
+            if batch.is_empty() {
+                return Ok(());
+            }
+            if let Err(error) = self.inner.upsert_many(&batch).await {
+                self.queue().requeue(batch);
+                return Err(error);
+            }
+        }
+    }
+}
+
+/// Store-and-forward front for a [`TimerStore`] whose writes cost a round-trip. Upserts are queued
+/// and acknowledged immediately; a background task coalesces them, keeping only the latest copy of
+/// each timer, and forwards them with `upsert_many` every [`WriteBatchConfig::flush_interval`] or
+/// once [`WriteBatchConfig::max_batch`] timers are waiting. A failed flush is retried on the next
+/// one.
+///
+/// Writes still in the queue are lost if the process dies, so heartbeats flush first: everything
+/// written before the latest heartbeat is durable, and restore treats the time after it as
+/// downtime. Call [`BatchingTimerStore::flush`] before shutting down.
+pub struct BatchingTimerStore {
+    shared: Arc<Shared>,
+    flusher: JoinHandle<()>,
+}
+
+impl BatchingTimerStore {
+    /// Must be called inside a Tokio runtime, which runs the flush task.
+    pub fn new(inner: Arc<dyn TimerStore>, config: WriteBatchConfig) -> Self {
+        let shared = Arc::new(Shared {
+            inner,
+            config,
+            queue: Mutex::new(Queue::default()),
+            flushing: tokio::sync::Mutex::new(()),
+            wake: Notify::new(),
+        });
+        let flusher = tokio::spawn({
+            let shared = shared.clone();
+            async move {
+                loop {
+                    tokio::select! {
+                        _ = tokio::time::sleep(shared.config.flush_interval) => {}
+                        _ = shared.wake.notified() => {}
+                    }
+                    if let Err(error) = shared.flush().await {
+                        tracing::error!(%error, "failed to flush batched timer writes");
+                    }
+                }
+            }
+        });
+        Self { shared, flusher }
+    }
+
+    /// Forwards every queued write now.
+    pub async fn flush(&self) -> Result<(), StoreError> {
+        self.shared.flush().await
+    }
+
+    /// Timers with writes that have not reached the inner store.
+    pub fn pending(&self) -> usize {
+        self.shared.queue().order.len()
+    }
+
+    fn enqueue<'a>(&self, timers: impl IntoIterator<Item = &'a TimerInstance>) {
+        let mut queue = self.shared.queue();
+        for timer in timers {
+            queue.push(timer);
+        }
+        if queue.order.len() >= self.shared.config.max_batch {
+            self.shared.wake.notify_one();
+        }
+    }
+}
+
+impl Drop for BatchingTimerStore {
+    fn drop(&mut self) {
+        self.flusher.abort();
+    }
+}
+
+#[async_trait]
+impl TimerStore for BatchingTimerStore {
+    async fn upsert(&self, timer: &TimerInstance) -> Result<(), StoreError> {
+        self.enqueue([timer]);
+        Ok(())
+    }
+
+    async fn upsert_many(&self, timers: &[TimerInstance]) -> Result<(), StoreError> {
+        self.enqueue(timers);
+        Ok(())
+    }
+
+    async fn load_all(&self) -> Result<Vec<TimerInstance>, StoreError> {
+        self.flush().await?;
+        self.shared.inner.load_all().await
+    }
+
+    async fn flush(&self) -> Result<(), StoreError> {
+        self.shared.flush().await
+    }
+
+    async fn compact(&self) -> Result<Option<usize>, StoreError> {
+        self.flush().await?;
+        self.shared.inner.compact().await
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<TimerInstance>, StoreError> {
+        let queued = self.shared.queue().latest.get(&id).cloned();
+        match queued {
+            Some(timer) => Ok(Some(timer)),
+            None => self.shared.inner.get(id).await,
+        }
+    }
+
+    /// Forwarded right away, after the queued writes it must not be overtaken by.
+    async fn upsert_fenced(
+        &self,
+        timer: &TimerInstance,
+        fencing_token: u64,
+    ) -> Result<FencedWrite, StoreError> {
+        self.flush().await?;
+        self.shared.inner.upsert_fenced(timer, fencing_token).await
+    }
+
+    async fn record_heartbeat(&self, at: DateTime<Utc>) -> Result<(), StoreError> {
+        self.flush().await?;
+        self.shared.inner.record_heartbeat(at).await
+    }
+
+    async fn last_heartbeat(&self) -> Result<Option<DateTime<Utc>>, StoreError> {
+        self.shared.inner.last_heartbeat().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::InMemoryTimerStore;
+    use crate::{TimerStatus, TIMER_SCHEMA_VERSION};
+
+    fn timer(id: Uuid, status: TimerStatus) -> TimerInstance {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": TIMER_SCHEMA_VERSION,
+            "id": id,
+            "tenant_id": "tenant-a",
+            "requested_by": "agent-1",
+            "name": "batched",
+            "duration_ms": 1_000,
+            "created_at": Utc::now(),
+            "fire_at": Utc::now(),
+            "status": status,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn coalesces_writes_and_keeps_the_latest_copy() {
+        let inner = Arc::new(InMemoryTimerStore::default());
+        let store = BatchingTimerStore::new(
+            inner.clone(),
+            WriteBatchConfig {
+                flush_interval: Duration::from_secs(3_600),
+                max_batch: 3,
+            },
+        );
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        store
+            .upsert(&timer(first, TimerStatus::Scheduled))
+            .await
+            .unwrap();
+        store
+            .upsert(&timer(first, TimerStatus::Fired))
+            .await
+            .unwrap();
+        store
+            .upsert(&timer(second, TimerStatus::Scheduled))
+            .await
+            .unwrap();
+        assert_eq!(store.pending(), 2);
+        assert!(inner.load_all().await.unwrap().is_empty());
+
+        store.record_heartbeat(Utc::now()).await.unwrap();
+        assert_eq!(store.pending(), 0);
+        let stored = inner.load_all().await.unwrap();
+        assert_eq!(stored.len(), 2);
+        let first = stored.iter().find(|timer| timer.id == first).unwrap();
+        assert_eq!(first.status, TimerStatus::Fired);
+
+        let third = Uuid::new_v4();
+        store
+            .upsert_many(&[
+                timer(third, TimerStatus::Scheduled),
+                timer(Uuid::new_v4(), TimerStatus::Scheduled),
+                timer(Uuid::new_v4(), TimerStatus::Scheduled),
+            ])
+            .await
+            .unwrap();
+        // A full batch is forwarded without waiting for the interval.
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while store.pending() > 0 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("full batch flushed");
+        store.flush().await.unwrap();
+        assert_eq!(inner.load_all().await.unwrap().len(), 5);
+    }
+}
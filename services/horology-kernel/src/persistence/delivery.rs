@@ -0,0 +1,141 @@
+use std::{
+    collections::HashMap,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::StoreError;
+use crate::DeliveryReceipt;
+
+/// Durable home of delivery receipts. [`crate::DeliveryLedger::attach_store`] loads them at boot
+/// and writes each timer's receipts through whenever they change; an empty set deletes them.
+#[async_trait]
+pub trait DeliveryStore: Send + Sync {
+    async fn save_receipts(
+        &self,
+        timer_id: Uuid,
+        receipts: &[DeliveryReceipt],
+    ) -> Result<(), StoreError>;
+
+    /// Every timer with receipts. Called once, before any save.
+    async fn load_receipts(&self) -> Result<HashMap<Uuid, Vec<DeliveryReceipt>>, StoreError>;
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryDeliveryStore {
+    receipts: Arc<Mutex<HashMap<Uuid, Vec<DeliveryReceipt>>>>,
+}
+
+impl InMemoryDeliveryStore {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, Vec<DeliveryReceipt>>> {
+        self.receipts
+            .lock()
+            .expect("in-memory delivery store poisoned")
+    }
+}
+
+#[async_trait]
+impl DeliveryStore for InMemoryDeliveryStore {
+    async fn save_receipts(
+        &self,
+        timer_id: Uuid,
+        receipts: &[DeliveryReceipt],
+    ) -> Result<(), StoreError> {
+        let mut stored = self.lock();
+        if receipts.is_empty() {
+            stored.remove(&timer_id);
+        } else {
+            stored.insert(timer_id, receipts.to_vec());
+        }
+        Ok(())
+    }
+
+    async fn load_receipts(&self) -> Result<HashMap<Uuid, Vec<DeliveryReceipt>>, StoreError> {
+        Ok(self.lock().clone())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    timer_id: Uuid,
+    receipts: Vec<DeliveryReceipt>,
+}
+
+/// JSON-lines log of each timer's receipts, one line per change; the last line for a timer wins.
+/// Receipts change on every fired delivery, so loading rewrites the log down to the live set.
+/// A missing file means no receipts.
+pub struct FileDeliveryStore {
+    path: PathBuf,
+    writer: Mutex<()>,
+}
+
+impl FileDeliveryStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            writer: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl DeliveryStore for FileDeliveryStore {
+    async fn save_receipts(
+        &self,
+        timer_id: Uuid,
+        receipts: &[DeliveryReceipt],
+    ) -> Result<(), StoreError> {
+        let mut line = serde_json::to_vec(&Record {
+            timer_id,
+            receipts: receipts.to_vec(),
+        })?;
+        line.push(b'\n');
+        let _guard = self.writer.lock().expect("delivery store writer poisoned");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&line)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    async fn load_receipts(&self) -> Result<HashMap<Uuid, Vec<DeliveryReceipt>>, StoreError> {
+        let _guard = self.writer.lock().expect("delivery store writer poisoned");
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(error) => return Err(error.into()),
+        };
+        let mut receipts = HashMap::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let record: Record = serde_json::from_str(line)?;
+            if record.receipts.is_empty() {
+                receipts.remove(&record.timer_id);
+            } else {
+                receipts.insert(record.timer_id, record.receipts);
+            }
+        }
+        let mut compacted = Vec::new();
+        for (timer_id, receipts) in &receipts {
+            serde_json::to_writer(
+                &mut compacted,
+                &Record {
+                    timer_id: *timer_id,
+                    receipts: receipts.clone(),
+                },
+            )?;
+            compacted.push(b'\n');
+        }
+        let temporary = self.path.with_extension("compacting");
+        std::fs::write(&temporary, compacted)?;
+        std::fs::rename(&temporary, &self.path)?;
+        Ok(receipts)
+    }
+}
@@ -0,0 +1,397 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{FencedWrite, StoreError, TimerStore};
+use crate::TimerInstance;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Record {
+    Timer(Box<TimerInstance>),
+    Heartbeat(DateTime<Utc>),
+    /// Highest fencing token presented to [`TimerStore::upsert_fenced`].
+    Fence(u64),
+}
+
+#[derive(Default)]
+struct Contents {
+    timers: HashMap<Uuid, TimerInstance>,
+    heartbeat: Option<DateTime<Utc>>,
+    fencing_token: u64,
+}
+
+impl Contents {
+    fn apply(&mut self, record: Record) {
+        match record {
+            Record::Timer(timer) => {
+                self.timers.insert(timer.id, *timer);
+            }
+            Record::Heartbeat(at) => self.heartbeat = Some(at),
+            Record::Fence(token) => self.fencing_token = self.fencing_token.max(token),
+        }
+    }
+}
+
+/// How [`FileTimerStore`] keeps its file small. The defaults compact only when the file is opened
+/// and keep finished timers forever.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FileStoreOptions {
+    /// Drop fired, cancelled, missed, acknowledged, and failed timers this long after they
+    /// finished. Their idempotency keys are forgotten with them.
+    pub terminal_retention: Option<Duration>,
+    /// Also compact while running, once this many records were appended since the last compaction.
+    pub compact_every: Option<usize>,
+}
+
+struct Log {
+    file: File,
+    /// Records appended since the last compaction.
+    appended: usize,
+    /// What the file holds, so reads and conditional writes never parse it again.
+    contents: Contents,
+}
+
+/// Append-only JSON-lines store for single-node and edge deployments without an external database.
+/// Every write appends the latest copy of a timer; compaction rewrites the file to one line per
+/// retained timer. The latest copies are also kept in memory, so lookups and fenced writes are
+/// answered without reading the file.
+pub struct FileTimerStore {
+    path: PathBuf,
+    options: FileStoreOptions,
+    log: Mutex<Log>,
+}
+
+impl FileTimerStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Self::open_with(path, FileStoreOptions::default())
+    }
+
+    pub fn open_with(
+        path: impl AsRef<Path>,
+        options: FileStoreOptions,
+    ) -> Result<Self, StoreError> {
+        let path = path.as_ref().to_path_buf();
+        let mut contents = read(&path)?;
+        let file = rewrite(&path, options, Utc::now(), &mut contents)?.1;
+        Ok(Self {
+            path,
+            options,
+            log: Mutex::new(Log {
+                file,
+                appended: 0,
+                contents,
+            }),
+        })
+    }
+
+    /// Rewrites the file to the latest copy of each retained timer, returning how many finished
+    /// timers were dropped.
+    pub fn compact(&self) -> Result<usize, StoreError> {
+        let mut log = self.lock();
+        self.compact_locked(&mut log)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Log> {
+        self.log.lock().expect("file store poisoned")
+    }
+
+    fn compact_locked(&self, log: &mut Log) -> Result<usize, StoreError> {
+        let (dropped, file) = rewrite(&self.path, self.options, Utc::now(), &mut log.contents)?;
+        log.file = file;
+        log.appended = 0;
+        Ok(dropped)
+    }
+
+    fn append(&self, record: Record) -> Result<(), StoreError> {
+        let mut log = self.lock();
+        self.append_locked(&mut log, record)
+    }
+
+    fn append_locked(&self, log: &mut Log, record: Record) -> Result<(), StoreError> {
+        write_record(&mut log.file, &record)?;
+        log.contents.apply(record);
+        self.appended(log, 1)
+    }
+
+    fn appended(&self, log: &mut Log, records: usize) -> Result<(), StoreError> {
+        log.appended += records;
+        if self
+            .options
+            .compact_every
+            .is_some_and(|every| log.appended >= every)
+        {
+            let dropped = self.compact_locked(log)?;
+            tracing::debug!(path = %self.path.display(), dropped, "compacted file store");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TimerStore for FileTimerStore {
+    async fn upsert(&self, timer: &TimerInstance) -> Result<(), StoreError> {
+        self.append(Record::Timer(Box::new(timer.clone())))
+    }
+
+    /// Appends every timer with a single write.
+    async fn upsert_many(&self, timers: &[TimerInstance]) -> Result<(), StoreError> {
+        let mut lines = Vec::new();
+        for timer in timers {
+            serde_json::to_writer(&mut lines, &Record::Timer(Box::new(timer.clone())))?;
+            lines.push(b'\n');
+        }
+        let mut log = self.lock();
+        log.file.write_all(&lines)?;
+        for timer in timers {
+            log.contents.timers.insert(timer.id, timer.clone());
+        }
+        self.appended(&mut log, timers.len())
+    }
+
+    async fn load_all(&self) -> Result<Vec<TimerInstance>, StoreError> {
+        Ok(self.lock().contents.timers.values().cloned().collect())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<TimerInstance>, StoreError> {
+        Ok(self.lock().contents.timers.get(&id).cloned())
+    }
+
+    /// Checks and appends under one lock, so no other write lands in between.
+    async fn upsert_fenced(
+        &self,
+        timer: &TimerInstance,
+        fencing_token: u64,
+    ) -> Result<FencedWrite, StoreError> {
+        let mut log = self.lock();
+        let current = log.contents.fencing_token;
+        if fencing_token < current {
+            return Ok(FencedWrite::Fenced { current });
+        }
+        if let Some(stored) = log
+            .contents
+            .timers
+            .get(&timer.id)
+            .filter(|stored| stored.state_version >= timer.state_version)
+        {
+            return Ok(FencedWrite::Superseded(Box::new(stored.clone())));
+        }
+        if fencing_token > current {
+            self.append_locked(&mut log, Record::Fence(fencing_token))?;
+        }
+        self.append_locked(&mut log, Record::Timer(Box::new(timer.clone())))?;
+        Ok(FencedWrite::Written)
+    }
+
+    async fn compact(&self) -> Result<Option<usize>, StoreError> {
+        FileTimerStore::compact(self).map(Some)
+    }
+
+    async fn record_heartbeat(&self, at: DateTime<Utc>) -> Result<(), StoreError> {
+        self.append(Record::Heartbeat(at))
+    }
+
+    async fn last_heartbeat(&self) -> Result<Option<DateTime<Utc>>, StoreError> {
+        Ok(self.lock().contents.heartbeat)
+    }
+}
+
+fn read(path: &Path) -> Result<Contents, StoreError> {
+    let mut contents = Contents::default();
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(contents),
+        Err(error) => return Err(error.into()),
+    };
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        contents.apply(serde_json::from_str(&line)?);
+    }
+    Ok(contents)
+}
+
+/// Rewrites the file at `path` to `contents`, dropping finished timers past their retention from
+/// both, and opens it for appending; returns how many timers were dropped.
+fn rewrite(
+    path: &Path,
+    options: FileStoreOptions,
+    now: DateTime<Utc>,
+    contents: &mut Contents,
+) -> Result<(usize, File), StoreError> {
+    let cutoff = options
+        .terminal_retention
+        .map(|retention| now - chrono::Duration::from_std(retention).unwrap_or_default());
+    let before = contents.timers.len();
+    contents.timers.retain(|_, timer| {
+        !cutoff.is_some_and(|cutoff| timer.is_terminal() && timer.finished_at() < cutoff)
+    });
+    let dropped = before - contents.timers.len();
+    let compacted = path.with_extension("compact");
+    {
+        let mut file = File::create(&compacted)?;
+        for timer in contents.timers.values() {
+            write_record(&mut file, &Record::Timer(Box::new(timer.clone())))?;
+        }
+        if let Some(at) = contents.heartbeat {
+            write_record(&mut file, &Record::Heartbeat(at))?;
+        }
+        if contents.fencing_token > 0 {
+            write_record(&mut file, &Record::Fence(contents.fencing_token))?;
+        }
+        file.sync_all()?;
+    }
+    std::fs::rename(&compacted, path)?;
+    Ok((dropped, OpenOptions::new().append(true).open(path)?))
+}
+
+fn write_record(file: &mut File, record: &Record) -> Result<(), StoreError> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    file.write_all(&line)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{HorologyKernel, SchedulerConfig, TimerSpec};
+
+    #[tokio::test]
+    async fn reopening_keeps_latest_copy_of_each_timer() {
+        let path = std::env::temp_dir().join(format!("minoots-store-{}.jsonl", Uuid::new_v4()));
+        let store = FileTimerStore::open(&path).expect("open");
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let mut timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60_000,
+                ..Default::default()
+            })
+            .await
+            .expect("schedule");
+        store.upsert(&timer).await.unwrap();
+        timer.status = crate::TimerStatus::Cancelled;
+        store.upsert(&timer).await.unwrap();
+        let at = Utc::now();
+        store.record_heartbeat(at).await.unwrap();
+        drop(store);
+
+        let reopened = FileTimerStore::open(&path).expect("reopen");
+        let restored = reopened.load_all().await.unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, timer.id);
+        assert_eq!(restored[0].status, crate::TimerStatus::Cancelled);
+        assert_eq!(reopened.last_heartbeat().await.unwrap(), Some(at));
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn compaction_drops_timers_finished_before_the_retention() {
+        let path = std::env::temp_dir().join(format!("minoots-store-{}.jsonl", Uuid::new_v4()));
+        let store = FileTimerStore::open_with(
+            &path,
+            FileStoreOptions {
+                terminal_retention: Some(Duration::from_secs(60)),
+                compact_every: Some(4),
+            },
+        )
+        .expect("open");
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+        let pending = kernel.schedule(spec.clone()).await.expect("schedule");
+        let mut old = kernel.schedule(spec.clone()).await.expect("schedule");
+        let mut recent = kernel.schedule(spec).await.expect("schedule");
+        old.status = crate::TimerStatus::Cancelled;
+        old.cancelled_at = Some(Utc::now() - chrono::Duration::minutes(5));
+        recent.status = crate::TimerStatus::Fired;
+        recent.fired_at = Some(Utc::now());
+        store.upsert(&pending).await.unwrap();
+        store.upsert(&old).await.unwrap();
+        store.upsert(&recent).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 3);
+
+        // The fourth append triggers compaction.
+        store.record_heartbeat(Utc::now()).await.unwrap();
+        let mut ids: Vec<_> = store
+            .load_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|timer| timer.id)
+            .collect();
+        ids.sort();
+        let mut expected = vec![pending.id, recent.id];
+        expected.sort();
+        assert_eq!(ids, expected);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 3);
+        assert_eq!(store.compact().unwrap(), 0);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn fenced_writes_are_atomic_and_the_token_survives_a_reopen() {
+        let path = std::env::temp_dir().join(format!("minoots-store-{}.jsonl", Uuid::new_v4()));
+        let store = Arc::new(FileTimerStore::open(&path).expect("open"));
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let mut timer = kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60_000,
+                ..Default::default()
+            })
+            .await
+            .expect("schedule");
+        store.upsert(&timer).await.unwrap();
+
+        // Racing writers of the same next state: exactly one wins.
+        timer.state_version += 1;
+        let writers: Vec<_> = (0..16)
+            .map(|_| {
+                let (store, timer) = (store.clone(), timer.clone());
+                tokio::spawn(async move { store.upsert_fenced(&timer, 3).await.unwrap() })
+            })
+            .collect();
+        let mut written = 0;
+        for writer in writers {
+            if matches!(writer.await.unwrap(), FencedWrite::Written) {
+                written += 1;
+            }
+        }
+        assert_eq!(written, 1);
+        store.compact().unwrap();
+        drop(store);
+
+        let reopened = FileTimerStore::open(&path).expect("reopen");
+        let stored = reopened.get(timer.id).await.unwrap().expect("stored");
+        assert_eq!(stored.state_version, timer.state_version);
+        timer.state_version += 1;
+        assert!(matches!(
+            reopened.upsert_fenced(&timer, 2).await.unwrap(),
+            FencedWrite::Fenced { current: 3 }
+        ));
+        let _ = std::fs::remove_file(&path);
+    }
+}
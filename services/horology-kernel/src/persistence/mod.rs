@@ -0,0 +1,256 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::TimerInstance;
+
+pub mod auth;
+pub mod batching;
+pub mod delivery;
+pub mod file;
+pub mod policy;
+pub mod usage;
+
+pub use auth::{AuthStore, FileAuthStore, InMemoryAuthStore};
+pub use batching::{BatchingTimerStore, WriteBatchConfig};
+pub use delivery::{DeliveryStore, FileDeliveryStore, InMemoryDeliveryStore};
+pub use file::{FileStoreOptions, FileTimerStore};
+pub use policy::{FileTenantPolicyStore, InMemoryTenantPolicyStore, TenantPolicyStore};
+pub use usage::{FileUsageStore, InMemoryUsageStore, UsageStore};
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("store i/o failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("store record is malformed: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Outcome of [`TimerStore::upsert_fenced`].
+#[derive(Clone, Debug)]
+pub enum FencedWrite {
+    Written,
+    /// The store already holds this or a later state of the timer, e.g. a fire another kernel
+    /// recorded first; carries the stored copy.
+    Superseded(Box<TimerInstance>),
+    /// A writer presenting a newer fencing token has written to the store since.
+    Fenced {
+        current: u64,
+    },
+}
+
+/// Durable home of timer state. The kernel writes through on every state transition and rebuilds
+/// its in-memory map from [`TimerStore::load_all`] on restore.
+#[async_trait]
+pub trait TimerStore: Send + Sync {
+    async fn upsert(&self, timer: &TimerInstance) -> Result<(), StoreError>;
+
+    /// Writes several timers at once; stores that can batch the write should override this.
+    async fn upsert_many(&self, timers: &[TimerInstance]) -> Result<(), StoreError> {
+        for timer in timers {
+            self.upsert(timer).await?;
+        }
+        Ok(())
+    }
+
+    async fn load_all(&self) -> Result<Vec<TimerInstance>, StoreError>;
+
+    /// The latest copy of one timer; stores with keyed lookups should override the full scan.
+    async fn get(&self, id: Uuid) -> Result<Option<TimerInstance>, StoreError> {
+        Ok(self
+            .load_all()
+            .await?
+            .into_iter()
+            .find(|timer| timer.id == id))
+    }
+
+    /// Conditional write behind exactly-once fires: stores `timer` only while the stored copy is at
+    /// an older `state_version` and no writer has presented a fencing token above `fencing_token`,
+    /// which becomes the store's token. The check and the write must be atomic, under the same
+    /// lock or transaction as every other write, or a stale holder could write in between; there is
+    /// no default for that reason.
+    async fn upsert_fenced(
+        &self,
+        timer: &TimerInstance,
+        fencing_token: u64,
+    ) -> Result<FencedWrite, StoreError>;
+
+    /// Makes every write acknowledged so far durable; stores that acknowledge writes before they
+    /// persist them must override it.
+    async fn flush(&self) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    /// Reclaims space held by superseded or expired records, returning how many timers were
+    /// dropped; `None` for stores that have nothing to compact.
+    async fn compact(&self) -> Result<Option<usize>, StoreError> {
+        Ok(None)
+    }
+
+    /// Records that the kernel was alive at `at`; the latest heartbeat marks where a downtime
+    /// window starts.
+    async fn record_heartbeat(&self, at: DateTime<Utc>) -> Result<(), StoreError>;
+
+    async fn last_heartbeat(&self) -> Result<Option<DateTime<Utc>>, StoreError>;
+}
+
+/// Process-local store. State does not survive the process, but a second kernel restoring from
+/// the same instance behaves like a restart, which is what tests use it for.
+#[derive(Clone, Default)]
+pub struct InMemoryTimerStore {
+    timers: Arc<Mutex<HashMap<Uuid, TimerInstance>>>,
+    heartbeat: Arc<Mutex<Option<DateTime<Utc>>>>,
+    fencing_token: Arc<Mutex<u64>>,
+}
+
+#[async_trait]
+impl TimerStore for InMemoryTimerStore {
+    async fn upsert(&self, timer: &TimerInstance) -> Result<(), StoreError> {
+        self.timers
+            .lock()
+            .expect("in-memory store poisoned")
+            .insert(timer.id, timer.clone());
+        Ok(())
+    }
+
+    async fn upsert_many(&self, timers: &[TimerInstance]) -> Result<(), StoreError> {
+        let mut stored = self.timers.lock().expect("in-memory store poisoned");
+        for timer in timers {
+            stored.insert(timer.id, timer.clone());
+        }
+        Ok(())
+    }
+
+    async fn load_all(&self) -> Result<Vec<TimerInstance>, StoreError> {
+        Ok(self
+            .timers
+            .lock()
+            .expect("in-memory store poisoned")
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<TimerInstance>, StoreError> {
+        Ok(self
+            .timers
+            .lock()
+            .expect("in-memory store poisoned")
+            .get(&id)
+            .cloned())
+    }
+
+    async fn upsert_fenced(
+        &self,
+        timer: &TimerInstance,
+        fencing_token: u64,
+    ) -> Result<FencedWrite, StoreError> {
+        let mut current = self.fencing_token.lock().expect("in-memory store poisoned");
+        if fencing_token < *current {
+            return Ok(FencedWrite::Fenced { current: *current });
+        }
+        *current = fencing_token;
+        let mut timers = self.timers.lock().expect("in-memory store poisoned");
+        if let Some(stored) = timers
+            .get(&timer.id)
+            .filter(|stored| stored.state_version >= timer.state_version)
+        {
+            return Ok(FencedWrite::Superseded(Box::new(stored.clone())));
+        }
+        timers.insert(timer.id, timer.clone());
+        Ok(FencedWrite::Written)
+    }
+
+    async fn record_heartbeat(&self, at: DateTime<Utc>) -> Result<(), StoreError> {
+        *self.heartbeat.lock().expect("in-memory store poisoned") = Some(at);
+        Ok(())
+    }
+
+    async fn last_heartbeat(&self) -> Result<Option<DateTime<Utc>>, StoreError> {
+        Ok(*self.heartbeat.lock().expect("in-memory store poisoned"))
+    }
+}
+
+/// What happened to a timer whose fire time fell inside the downtime window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GapOutcome {
+    /// Re-armed with zero delay on restore, `late_by_ms` after its fire time.
+    FiredLate,
+    /// Not fired; marked [`crate::TimerStatus::Missed`] under its [`MissedFirePolicy`].
+    Missed,
+}
+
+/// What `restore_from_store` does with a pending timer whose fire time passed during downtime.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum MissedFirePolicy {
+    #[default]
+    FireImmediately,
+    SkipAndMarkMissed,
+    /// Fire when at most `grace_ms` late, otherwise mark the timer missed.
+    FireWithGrace {
+        grace_ms: u64,
+    },
+}
+
+impl MissedFirePolicy {
+    pub fn outcome(self, late_by_ms: u64) -> GapOutcome {
+        match self {
+            MissedFirePolicy::FireImmediately => GapOutcome::FiredLate,
+            MissedFirePolicy::FireWithGrace { grace_ms } if late_by_ms <= grace_ms => {
+                GapOutcome::FiredLate
+            }
+            MissedFirePolicy::SkipAndMarkMissed | MissedFirePolicy::FireWithGrace { .. } => {
+                GapOutcome::Missed
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for MissedFirePolicy {
+    type Err = String;
+
+    /// Parses `fire`, `skip`, or `grace:<ms>`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.to_ascii_lowercase();
+        match value.split_once(':') {
+            Some(("grace", grace_ms)) => grace_ms
+                .parse()
+                .map(|grace_ms| MissedFirePolicy::FireWithGrace { grace_ms })
+                .map_err(|_| format!("invalid grace period `{grace_ms}`")),
+            _ => match value.as_str() {
+                "fire" | "fire_immediately" => Ok(MissedFirePolicy::FireImmediately),
+                "skip" | "skip_and_mark_missed" => Ok(MissedFirePolicy::SkipAndMarkMissed),
+                other => Err(format!("unknown missed fire policy `{other}`")),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FireGapEntry {
+    pub timer_id: Uuid,
+    pub tenant_id: String,
+    pub fire_at: DateTime<Utc>,
+    pub late_by_ms: u64,
+    pub outcome: GapOutcome,
+}
+
+/// Timers that came due while the kernel was down, computed by `restore_from_store`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FireGapReport {
+    /// Last heartbeat before the restart; `None` when the store never saw one.
+    pub downtime_started_at: Option<DateTime<Utc>>,
+    pub restored_at: DateTime<Utc>,
+    /// Timers loaded from the store, terminal ones included.
+    pub restored: u64,
+    pub entries: Vec<FireGapEntry>,
+}
@@ -0,0 +1,96 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+
+use super::StoreError;
+use crate::TenantPolicy;
+
+/// Source of per-tenant policy overrides. [`crate::HorologyKernel::reload_tenant_policies`] reads
+/// the whole set and swaps it in, so edits take effect without restarting the kernel.
+#[async_trait]
+pub trait TenantPolicyStore: Send + Sync {
+    /// Every tenant or organization with a policy, keyed by tenant id.
+    async fn load_policies(&self) -> Result<HashMap<String, TenantPolicy>, StoreError>;
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryTenantPolicyStore {
+    policies: Arc<Mutex<HashMap<String, TenantPolicy>>>,
+}
+
+impl InMemoryTenantPolicyStore {
+    pub fn set(&self, tenant_id: impl Into<String>, policy: TenantPolicy) {
+        self.lock().insert(tenant_id.into(), policy);
+    }
+
+    pub fn remove(&self, tenant_id: &str) {
+        self.lock().remove(tenant_id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, TenantPolicy>> {
+        self.policies
+            .lock()
+            .expect("in-memory policy store poisoned")
+    }
+}
+
+#[async_trait]
+impl TenantPolicyStore for InMemoryTenantPolicyStore {
+    async fn load_policies(&self) -> Result<HashMap<String, TenantPolicy>, StoreError> {
+        Ok(self.lock().clone())
+    }
+}
+
+/// JSON document mapping tenant ids to policies, re-read on every load, e.g.
+/// `{"acme": {"max_duration_ms": 31536000000}, "trial": {"max_duration_ms": 14400000}}`.
+/// A missing file means no overrides.
+pub struct FileTenantPolicyStore {
+    path: PathBuf,
+}
+
+impl FileTenantPolicyStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+#[async_trait]
+impl TenantPolicyStore for FileTenantPolicyStore {
+    async fn load_policies(&self) -> Result<HashMap<String, TenantPolicy>, StoreError> {
+        match std::fs::read(&self.path) {
+            Ok(document) => Ok(serde_json::from_slice(&document)?),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_store_reads_the_current_document() {
+        let path =
+            std::env::temp_dir().join(format!("minoots-policies-{}.json", uuid::Uuid::new_v4()));
+        let store = FileTenantPolicyStore::new(&path);
+        assert!(store.load_policies().await.unwrap().is_empty());
+
+        std::fs::write(&path, r#"{"acme": {"max_duration_ms": 31536000000}}"#).unwrap();
+        let policies = store.load_policies().await.unwrap();
+        assert_eq!(policies["acme"].max_duration_ms, Some(31_536_000_000));
+
+        std::fs::write(&path, r#"{"acme": {"max_duration_ms": "soon"}}"#).unwrap();
+        assert!(matches!(
+            store.load_policies().await,
+            Err(StoreError::Serialization(_))
+        ));
+        let _ = std::fs::remove_file(&path);
+    }
+}
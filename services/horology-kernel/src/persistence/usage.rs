@@ -0,0 +1,93 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+
+use super::StoreError;
+use crate::UsageRecord;
+
+/// Durable home of closed usage periods. [`crate::UsageMeter::attach_store`] loads them at boot
+/// and [`crate::UsageMeter::close_period`] appends each period it closes, so billing history
+/// survives restarts.
+#[async_trait]
+pub trait UsageStore: Send + Sync {
+    async fn append_records(&self, records: &[UsageRecord]) -> Result<(), StoreError>;
+
+    /// Every stored record, oldest first.
+    async fn load_records(&self) -> Result<Vec<UsageRecord>, StoreError>;
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryUsageStore {
+    records: Arc<Mutex<Vec<UsageRecord>>>,
+}
+
+impl InMemoryUsageStore {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<UsageRecord>> {
+        self.records.lock().expect("in-memory usage store poisoned")
+    }
+}
+
+#[async_trait]
+impl UsageStore for InMemoryUsageStore {
+    async fn append_records(&self, records: &[UsageRecord]) -> Result<(), StoreError> {
+        self.lock().extend_from_slice(records);
+        Ok(())
+    }
+
+    async fn load_records(&self) -> Result<Vec<UsageRecord>, StoreError> {
+        Ok(self.lock().clone())
+    }
+}
+
+/// JSON-lines log of closed usage records, one line per tenant and period. Records never change
+/// once closed, so the log is only appended to. A missing file means no history.
+pub struct FileUsageStore {
+    path: PathBuf,
+    writer: Mutex<()>,
+}
+
+impl FileUsageStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            writer: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl UsageStore for FileUsageStore {
+    async fn append_records(&self, records: &[UsageRecord]) -> Result<(), StoreError> {
+        let mut lines = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut lines, record)?;
+            lines.push(b'\n');
+        }
+        let _guard = self.writer.lock().expect("usage store writer poisoned");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&lines)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    async fn load_records(&self) -> Result<Vec<UsageRecord>, StoreError> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+}
@@ -0,0 +1,562 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+
+use crate::{TimerEvent, TimerInstance, TimerStatus, EVENT_KINDS};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("invalid timer query at offset {offset}: {message}")]
+pub struct QueryError {
+    pub offset: usize,
+    pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    fn holds(self, value: DateTime<Utc>, bound: DateTime<Utc>) -> bool {
+        match self {
+            Comparison::Lt => value < bound,
+            Comparison::Le => value <= bound,
+            Comparison::Gt => value > bound,
+            Comparison::Ge => value >= bound,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LabelPredicate {
+    pub key: String,
+    pub value: String,
+    pub negated: bool,
+}
+
+impl FromStr for LabelPredicate {
+    type Err = QueryError;
+
+    /// Parses a label selector, `key=value` or `key!=value`.
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        let (key, value, negated) = match selector.split_once("!=") {
+            Some((key, value)) => (key, value, true),
+            None => match selector.split_once('=') {
+                Some((key, value)) => (key, value, false),
+                None => {
+                    return Err(error(
+                        0,
+                        format!("label selector `{selector}` must be key=value or key!=value"),
+                    ))
+                }
+            },
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(error(0, format!("label selector `{selector}` has no key")));
+        }
+        Ok(LabelPredicate {
+            key: key.to_string(),
+            value: value.trim().to_string(),
+            negated,
+        })
+    }
+}
+
+impl LabelPredicate {
+    pub fn matches(&self, timer: &TimerInstance) -> bool {
+        (timer.labels.get(&self.key) == Some(&self.value)) != self.negated
+    }
+}
+
+/// Order of `ListTimers` results. Ties are broken by timer id so pages are stable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimerOrder {
+    #[default]
+    FireAtAsc,
+    FireAtDesc,
+    CreatedAtAsc,
+    CreatedAtDesc,
+    Name,
+}
+
+impl TimerOrder {
+    pub fn sort(self, timers: &mut [TimerInstance]) {
+        match self {
+            TimerOrder::FireAtAsc => timers.sort_by_key(|t| (t.fire_at, t.id)),
+            TimerOrder::FireAtDesc => timers.sort_by_key(|t| std::cmp::Reverse((t.fire_at, t.id))),
+            TimerOrder::CreatedAtAsc => timers.sort_by_key(|t| (t.created_at, t.id)),
+            TimerOrder::CreatedAtDesc => {
+                timers.sort_by_key(|t| std::cmp::Reverse((t.created_at, t.id)))
+            }
+            TimerOrder::Name => timers.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id))),
+        }
+    }
+}
+
+/// Filters applied to `ListTimers`. Every populated field must match (logical AND).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimerFilter {
+    /// `None` matches any status; `Some` restricts to the listed ones.
+    pub statuses: Option<Vec<TimerStatus>>,
+    pub labels: Vec<LabelPredicate>,
+    pub fire_at: Vec<(Comparison, DateTime<Utc>)>,
+    pub name_prefix: Option<String>,
+}
+
+impl TimerFilter {
+    /// Parses the compact query syntax, e.g.
+    /// `status in (scheduled,armed) AND label.env=prod AND fire_at < now+1h`.
+    ///
+    /// Clauses are joined with `AND` (case-insensitive). Supported clauses are `status = s`,
+    /// `status in (s, ...)`, `label.<key> = v`, `label.<key> != v`, and `fire_at` compared with
+    /// `<`, `<=`, `>`, `>=` against an RFC 3339 time or `now[+-]<n><ms|s|m|h|d>`.
+    pub fn parse(query: &str, now: DateTime<Utc>) -> Result<Self, QueryError> {
+        let tokens = tokenize(query)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            end: query.len(),
+            now,
+        };
+        let mut filter = TimerFilter::default();
+        if parser.peek().is_none() {
+            return Ok(filter);
+        }
+        loop {
+            parser.clause(&mut filter)?;
+            match parser.next() {
+                None => break,
+                Some((_, Token::Word(word))) if word.eq_ignore_ascii_case("and") => continue,
+                Some((offset, token)) => {
+                    return Err(error(offset, format!("expected AND, found {token}")))
+                }
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Narrows the allowed statuses; repeated restrictions intersect.
+    pub fn restrict_statuses(&mut self, statuses: Vec<TimerStatus>) {
+        self.statuses = Some(match self.statuses.take() {
+            Some(existing) => existing
+                .into_iter()
+                .filter(|status| statuses.contains(status))
+                .collect(),
+            None => statuses,
+        });
+    }
+
+    pub fn matches(&self, timer: &TimerInstance) -> bool {
+        self.statuses
+            .as_ref()
+            .is_none_or(|statuses| statuses.contains(&timer.status))
+            && self.labels.iter().all(|predicate| predicate.matches(timer))
+            && self
+                .fire_at
+                .iter()
+                .all(|(comparison, bound)| comparison.holds(timer.fire_at, *bound))
+            && self
+                .name_prefix
+                .as_deref()
+                .is_none_or(|prefix| timer.name.starts_with(prefix))
+    }
+}
+
+/// Filters applied to a timer event stream. Every populated field must match (logical AND).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// Event types as named by [`TimerEvent::kind`]; empty matches every type.
+    pub kinds: Vec<&'static str>,
+    pub labels: Vec<LabelPredicate>,
+    pub name_prefix: Option<String>,
+}
+
+impl EventFilter {
+    /// Adds an event type to match, named with or without the `timer.` subject prefix, e.g.
+    /// `fired` or `timer.fired`.
+    pub fn add_topic(&mut self, topic: &str) -> Result<(), QueryError> {
+        let kind = topic.strip_prefix("timer.").unwrap_or(topic);
+        let kind = EVENT_KINDS
+            .iter()
+            .find(|known| **known == kind)
+            .ok_or_else(|| error(0, format!("unknown event topic `{topic}`")))?;
+        self.kinds.push(kind);
+        Ok(())
+    }
+
+    pub fn matches(&self, event: &TimerEvent) -> bool {
+        let timer = event.timer();
+        (self.kinds.is_empty() || self.kinds.contains(&event.kind()))
+            && self.labels.iter().all(|predicate| predicate.matches(timer))
+            && self
+                .name_prefix
+                .as_deref()
+                .is_none_or(|prefix| timer.name.starts_with(prefix))
+    }
+}
+
+pub fn parse_status(value: &str) -> Option<TimerStatus> {
+    match value.to_ascii_lowercase().as_str() {
+        "scheduled" => Some(TimerStatus::Scheduled),
+        "armed" => Some(TimerStatus::Armed),
+        "fired" => Some(TimerStatus::Fired),
+        "cancelled" | "canceled" => Some(TimerStatus::Cancelled),
+        "missed" => Some(TimerStatus::Missed),
+        "acknowledged" => Some(TimerStatus::Acknowledged),
+        "failed" => Some(TimerStatus::Failed),
+        "settled" => Some(TimerStatus::Settled),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Op(&'static str),
+    Open,
+    Close,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "`{word}`"),
+            Token::Op(op) => write!(f, "`{op}`"),
+            Token::Open => f.write_str("`(`"),
+            Token::Close => f.write_str("`)`"),
+            Token::Comma => f.write_str("`,`"),
+        }
+    }
+}
+
+fn error(offset: usize, message: impl Into<String>) -> QueryError {
+    QueryError {
+        offset,
+        message: message.into(),
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = query.char_indices().peekable();
+    while let Some(&(offset, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                tokens.push((
+                    offset,
+                    match c {
+                        '(' => Token::Open,
+                        ')' => Token::Close,
+                        _ => Token::Comma,
+                    },
+                ));
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let with_eq = chars.next_if(|&(_, next)| next == '=').is_some();
+                let op = match (c, with_eq) {
+                    ('=', _) => "=",
+                    ('!', true) => "!=",
+                    ('<', false) => "<",
+                    ('<', true) => "<=",
+                    ('>', false) => ">",
+                    ('>', true) => ">=",
+                    _ => return Err(error(offset, "expected `!=`")),
+                };
+                tokens.push((offset, Token::Op(op)));
+            }
+            '"' => {
+                chars.next();
+                let mut word = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, c)) => word.push(c),
+                        None => return Err(error(offset, "unterminated string")),
+                    }
+                }
+                tokens.push((offset, Token::Word(word)));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some((_, c)) =
+                    chars.next_if(|&(_, c)| !c.is_whitespace() && !"(),=!<>\"".contains(c))
+                {
+                    word.push(c);
+                }
+                tokens.push((offset, Token::Word(word)));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    end: usize,
+    now: DateTime<Utc>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&(usize, Token)> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<(usize, Token)> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect_next(&mut self, what: &str) -> Result<(usize, Token), QueryError> {
+        self.next()
+            .ok_or_else(|| error(self.end, format!("expected {what}, found end of query")))
+    }
+
+    fn word(&mut self, what: &str) -> Result<(usize, String), QueryError> {
+        match self.expect_next(what)? {
+            (offset, Token::Word(word)) => Ok((offset, word)),
+            (offset, token) => Err(error(offset, format!("expected {what}, found {token}"))),
+        }
+    }
+
+    fn clause(&mut self, filter: &mut TimerFilter) -> Result<(), QueryError> {
+        let (offset, field) = self.word("a field")?;
+        if field.eq_ignore_ascii_case("status") {
+            let statuses = self.status_values()?;
+            filter.restrict_statuses(statuses);
+        } else if let Some(key) = field.strip_prefix("label.") {
+            let negated = match self.expect_next("`=` or `!=`")? {
+                (_, Token::Op("=")) => false,
+                (_, Token::Op("!=")) => true,
+                (offset, token) => {
+                    return Err(error(
+                        offset,
+                        format!("expected `=` or `!=`, found {token}"),
+                    ))
+                }
+            };
+            let (_, value) = self.word("a label value")?;
+            filter.labels.push(LabelPredicate {
+                key: key.to_string(),
+                value,
+                negated,
+            });
+        } else if field.eq_ignore_ascii_case("fire_at") {
+            let comparison = match self.expect_next("a comparison")? {
+                (_, Token::Op("<")) => Comparison::Lt,
+                (_, Token::Op("<=")) => Comparison::Le,
+                (_, Token::Op(">")) => Comparison::Gt,
+                (_, Token::Op(">=")) => Comparison::Ge,
+                (offset, token) => {
+                    return Err(error(
+                        offset,
+                        format!("expected `<`, `<=`, `>` or `>=`, found {token}"),
+                    ))
+                }
+            };
+            let (offset, value) = self.word("a time")?;
+            let bound = parse_time(&value, self.now).ok_or_else(|| {
+                error(
+                    offset,
+                    format!("`{value}` is not an RFC 3339 time or now[+-]<duration>"),
+                )
+            })?;
+            filter.fire_at.push((comparison, bound));
+        } else {
+            return Err(error(offset, format!("unknown field `{field}`")));
+        }
+        Ok(())
+    }
+
+    fn status_values(&mut self) -> Result<Vec<TimerStatus>, QueryError> {
+        let status = |parser: &mut Parser| {
+            let (offset, value) = parser.word("a status")?;
+            parse_status(&value).ok_or_else(|| error(offset, format!("unknown status `{value}`")))
+        };
+        match self.expect_next("`=` or `in`")? {
+            (_, Token::Op("=")) => Ok(vec![status(self)?]),
+            (_, Token::Word(word)) if word.eq_ignore_ascii_case("in") => {
+                match self.expect_next("`(`")? {
+                    (_, Token::Open) => {}
+                    (offset, token) => {
+                        return Err(error(offset, format!("expected `(`, found {token}")))
+                    }
+                }
+                let mut statuses = vec![status(self)?];
+                loop {
+                    match self.expect_next("`,` or `)`")? {
+                        (_, Token::Comma) => statuses.push(status(self)?),
+                        (_, Token::Close) => return Ok(statuses),
+                        (offset, token) => {
+                            return Err(error(
+                                offset,
+                                format!("expected `,` or `)`, found {token}"),
+                            ))
+                        }
+                    }
+                }
+            }
+            (offset, token) => Err(error(
+                offset,
+                format!("expected `=` or `in`, found {token}"),
+            )),
+        }
+    }
+}
+
+fn parse_time(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let Some(offset) = value.strip_prefix("now") else {
+        return DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|time| time.with_timezone(&Utc));
+    };
+    if offset.is_empty() {
+        return Some(now);
+    }
+    let (sign, amount) = match offset.split_at(1) {
+        ("+", amount) => (1, amount),
+        ("-", amount) => (-1, amount),
+        _ => return None,
+    };
+    let split = amount.find(|c: char| !c.is_ascii_digit())?;
+    let (digits, unit) = amount.split_at(split);
+    let count: i64 = digits.parse().ok()?;
+    let span = match unit {
+        "ms" => Duration::milliseconds(count),
+        "s" => Duration::seconds(count),
+        "m" => Duration::minutes(count),
+        "h" => Duration::hours(count),
+        "d" => Duration::days(count),
+        _ => return None,
+    };
+    Some(now + span * sign)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_compound_queries_and_reports_offsets() {
+        let now = Utc::now();
+        let filter = TimerFilter::parse(
+            "status in (scheduled,armed) AND label.env=prod and fire_at < now+1h",
+            now,
+        )
+        .expect("valid query");
+        assert_eq!(
+            filter.statuses,
+            Some(vec![TimerStatus::Scheduled, TimerStatus::Armed])
+        );
+        assert_eq!(
+            filter.labels,
+            vec![LabelPredicate {
+                key: "env".into(),
+                value: "prod".into(),
+                negated: false,
+            }]
+        );
+        assert_eq!(
+            filter.fire_at,
+            vec![(Comparison::Lt, now + Duration::hours(1))]
+        );
+
+        let mut narrowed = TimerFilter::parse("status = fired", now).unwrap();
+        narrowed.restrict_statuses(vec![TimerStatus::Scheduled]);
+        assert_eq!(narrowed.statuses, Some(vec![]));
+
+        let err = TimerFilter::parse("status = scheduled AND owner = me", now).unwrap_err();
+        assert_eq!(err.offset, 23);
+        assert!(TimerFilter::parse("fire_at < tomorrow", now).is_err());
+        assert_eq!(TimerFilter::parse("  ", now), Ok(TimerFilter::default()));
+    }
+
+    #[test]
+    fn parses_label_selectors_and_sorts_timers() {
+        assert_eq!(
+            "tier != batch".parse::<LabelPredicate>(),
+            Ok(LabelPredicate {
+                key: "tier".into(),
+                value: "batch".into(),
+                negated: true,
+            })
+        );
+        assert!("env".parse::<LabelPredicate>().is_err());
+        assert!("=prod".parse::<LabelPredicate>().is_err());
+
+        let now = Utc::now();
+        let timer = |name: &str, fire_in_s: i64| -> TimerInstance {
+            serde_json::from_value(serde_json::json!({
+                "id": uuid::Uuid::new_v4(),
+                "tenant_id": "tenant-a",
+                "requested_by": "tester",
+                "name": name,
+                "duration_ms": fire_in_s * 1000,
+                "created_at": now - Duration::seconds(fire_in_s),
+                "fire_at": now + Duration::seconds(fire_in_s),
+                "status": "scheduled",
+            }))
+            .expect("timer")
+        };
+        let mut timers = vec![timer("b", 2), timer("c", 1), timer("a", 3)];
+        let names =
+            |timers: &[TimerInstance]| timers.iter().map(|t| t.name.clone()).collect::<String>();
+        TimerOrder::FireAtAsc.sort(&mut timers);
+        assert_eq!(names(&timers), "cba");
+        TimerOrder::FireAtDesc.sort(&mut timers);
+        assert_eq!(names(&timers), "abc");
+        TimerOrder::CreatedAtAsc.sort(&mut timers);
+        assert_eq!(names(&timers), "abc");
+        TimerOrder::Name.sort(&mut timers);
+        assert_eq!(names(&timers), "abc");
+
+        let filter = TimerFilter {
+            name_prefix: Some("b".into()),
+            ..TimerFilter::default()
+        };
+        assert!(filter.matches(&timer("billing", 1)));
+        assert!(!filter.matches(&timer("audit", 1)));
+    }
+
+    #[test]
+    fn event_filters_match_topic_labels_and_name_prefix() {
+        let now = Utc::now();
+        let timer: TimerInstance = serde_json::from_value(serde_json::json!({
+            "id": uuid::Uuid::new_v4(),
+            "tenant_id": "tenant-a",
+            "requested_by": "tester",
+            "name": "billing-renewal",
+            "duration_ms": 1000,
+            "created_at": now,
+            "fire_at": now,
+            "status": "fired",
+            "labels": {"env": "prod"},
+        }))
+        .expect("timer");
+        let fired = TimerEvent::Fired(timer.clone());
+        let scheduled = TimerEvent::Scheduled(timer);
+
+        let mut filter = EventFilter::default();
+        assert!(filter.matches(&scheduled));
+        filter.add_topic("timer.fired").unwrap();
+        assert!(filter.add_topic("timer.exploded").is_err());
+        filter.labels.push("env=prod".parse().unwrap());
+        filter.name_prefix = Some("billing-".into());
+        assert!(filter.matches(&fired));
+        assert!(!filter.matches(&scheduled));
+        filter.labels.push("env!=prod".parse().unwrap());
+        assert!(!filter.matches(&fired));
+    }
+}
@@ -0,0 +1,258 @@
+//! Per-tenant quotas: how many timers a tenant may hold pending, how fast it may schedule, how
+//! much pending duration it may accumulate, and how fast each of its callers may send requests. Scheduler-wide defaults live on
+//! [`crate::SchedulerConfig::quotas`]; a tenant's [`crate::TenantPolicy`] overrides them.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Quota limits; an unset limit is unlimited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantQuota {
+    /// Pending (scheduled or armed) timers the tenant may hold at once.
+    #[serde(default)]
+    pub max_active_timers: Option<u64>,
+    /// Schedules accepted per calendar minute.
+    #[serde(default)]
+    pub max_schedules_per_minute: Option<u64>,
+    /// Summed `duration_ms` of the tenant's pending timers.
+    #[serde(default)]
+    pub max_total_duration_ms: Option<u64>,
+    /// Sustained requests per second for each caller (token or JWT subject) of the tenant.
+    #[serde(default)]
+    pub max_requests_per_second: Option<u64>,
+    /// Requests a caller may send at once after idling; defaults to one second's worth.
+    #[serde(default)]
+    pub request_burst: Option<u64>,
+}
+
+impl TenantQuota {
+    /// Fills every unset limit from `fallback`.
+    pub fn or(self, fallback: &TenantQuota) -> Self {
+        Self {
+            max_active_timers: self.max_active_timers.or(fallback.max_active_timers),
+            max_schedules_per_minute: self
+                .max_schedules_per_minute
+                .or(fallback.max_schedules_per_minute),
+            max_total_duration_ms: self
+                .max_total_duration_ms
+                .or(fallback.max_total_duration_ms),
+            max_requests_per_second: self
+                .max_requests_per_second
+                .or(fallback.max_requests_per_second),
+            request_burst: self.request_burst.or(fallback.request_burst),
+        }
+    }
+
+    /// Whether an active-timer or total-duration limit is set, i.e. whether
+    /// [`TenantQuota::check_pending`] needs the tenant's pending timers.
+    pub fn limits_pending(&self) -> bool {
+        self.max_active_timers.is_some() || self.max_total_duration_ms.is_some()
+    }
+
+    /// Checks that adding a timer of `duration_ms` to the tenant's pending timers, given by their
+    /// durations, stays within the active-timer and total-duration limits. `pending` is only
+    /// walked when one of those limits is set.
+    pub fn check_pending(
+        &self,
+        pending: impl Iterator<Item = u64>,
+        duration_ms: u64,
+    ) -> Result<(), QuotaViolation> {
+        if !self.limits_pending() {
+            return Ok(());
+        }
+        let (active, total_ms) = pending.fold((1u64, duration_ms), |(count, total), duration| {
+            (count + 1, total.saturating_add(duration))
+        });
+        if let Some(limit) = self.max_active_timers.filter(|limit| active > *limit) {
+            return Err(QuotaViolation {
+                quota: QuotaKind::ActiveTimers,
+                limit,
+            });
+        }
+        if let Some(limit) = self.max_total_duration_ms.filter(|limit| total_ms > *limit) {
+            return Err(QuotaViolation {
+                quota: QuotaKind::TotalDuration,
+                limit,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaKind {
+    ActiveTimers,
+    ScheduleRate,
+    TotalDuration,
+}
+
+impl QuotaKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            QuotaKind::ActiveTimers => "active_timers",
+            QuotaKind::ScheduleRate => "schedule_rate",
+            QuotaKind::TotalDuration => "total_duration",
+        }
+    }
+}
+
+impl fmt::Display for QuotaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A limit a request would have crossed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuotaViolation {
+    pub quota: QuotaKind,
+    pub limit: u64,
+}
+
+/// Counts each tenant's accepted schedules in the current minute.
+#[derive(Clone, Default)]
+pub struct ScheduleRateLimiter {
+    windows: Arc<Mutex<HashMap<String, (i64, u64)>>>,
+}
+
+impl ScheduleRateLimiter {
+    /// Takes one schedule from the tenant's allowance for the minute containing `at`, or returns
+    /// `false` when `limit` schedules were already accepted in it.
+    pub fn try_acquire(&self, tenant_id: &str, limit: u64, at: DateTime<Utc>) -> bool {
+        let minute = at.timestamp().div_euclid(60);
+        let mut windows = self.windows.lock().expect("schedule rate limiter poisoned");
+        let window = windows.entry(tenant_id.to_string()).or_insert((minute, 0));
+        if window.0 != minute {
+            *window = (minute, 0);
+        }
+        if window.1 >= limit {
+            return false;
+        }
+        window.1 += 1;
+        true
+    }
+}
+
+/// Buckets untouched this long are dropped once the limiter tracks many callers.
+const IDLE_BUCKET: Duration = Duration::from_secs(600);
+const MAX_TRACKED_CALLERS: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token buckets of requests, one per `(tenant, principal)`: a bucket holds up to `burst`
+/// requests and refills at the per-second rate.
+#[derive(Clone, Default)]
+pub struct RequestRateLimiter {
+    buckets: Arc<Mutex<HashMap<(String, String), Bucket>>>,
+}
+
+impl RequestRateLimiter {
+    /// Takes one request from the caller's bucket, or returns how long until one is available.
+    pub fn try_acquire(
+        &self,
+        tenant_id: &str,
+        principal: &str,
+        per_second: u64,
+        burst: u64,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let rate = per_second.max(1) as f64;
+        let capacity = burst.max(1) as f64;
+        let mut buckets = self.buckets.lock().expect("request rate limiter poisoned");
+        if buckets.len() >= MAX_TRACKED_CALLERS {
+            buckets.retain(|_, bucket| {
+                now.saturating_duration_since(bucket.refilled_at) < IDLE_BUCKET
+            });
+        }
+        let bucket = buckets
+            .entry((tenant_id.to_string(), principal.to_string()))
+            .or_insert(Bucket {
+                tokens: capacity,
+                refilled_at: now,
+            });
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(capacity);
+        bucket.refilled_at = bucket.refilled_at.max(now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_limits_and_rate_windows() {
+        let quota = TenantQuota {
+            max_active_timers: Some(2),
+            max_total_duration_ms: Some(1_000),
+            ..Default::default()
+        };
+        assert_eq!(quota.check_pending([400].into_iter(), 500), Ok(()));
+        assert_eq!(
+            quota.check_pending([400].into_iter(), 700),
+            Err(QuotaViolation {
+                quota: QuotaKind::TotalDuration,
+                limit: 1_000
+            })
+        );
+        assert_eq!(
+            quota
+                .check_pending([100, 100].into_iter(), 100)
+                .map_err(|violation| violation.quota),
+            Err(QuotaKind::ActiveTimers)
+        );
+
+        let limiter = ScheduleRateLimiter::default();
+        let at = DateTime::from_timestamp(120, 0).unwrap();
+        assert!(limiter.try_acquire("tenant-a", 1, at));
+        assert!(!limiter.try_acquire("tenant-a", 1, at));
+        assert!(limiter.try_acquire("tenant-b", 1, at));
+        assert!(limiter.try_acquire("tenant-a", 1, at + chrono::Duration::seconds(60)));
+    }
+
+    #[test]
+    fn request_buckets_refill_per_caller() {
+        let limiter = RequestRateLimiter::default();
+        let start = Instant::now();
+        assert!(limiter
+            .try_acquire("tenant-a", "token-1", 2, 2, start)
+            .is_ok());
+        assert!(limiter
+            .try_acquire("tenant-a", "token-1", 2, 2, start)
+            .is_ok());
+        assert_eq!(
+            limiter.try_acquire("tenant-a", "token-1", 2, 2, start),
+            Err(Duration::from_millis(500))
+        );
+        assert!(limiter
+            .try_acquire("tenant-a", "token-2", 2, 2, start)
+            .is_ok());
+        assert!(limiter
+            .try_acquire("tenant-b", "token-1", 2, 2, start)
+            .is_ok());
+        let later = start + Duration::from_millis(500);
+        assert!(limiter
+            .try_acquire("tenant-a", "token-1", 2, 2, later)
+            .is_ok());
+        assert!(limiter
+            .try_acquire("tenant-a", "token-1", 2, 2, later)
+            .is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! Per-caller request rate limiting for the gRPC API. [`RateLimitLayer`] charges every request to a
+//! token bucket keyed by `(tenant, principal)` and fails it with `RESOURCE_EXHAUSTED` once the
+//! bucket is empty, carrying `retry-after` (seconds) and `grpc-retry-pushback-ms` metadata. Limits
+//! are the tenant's [`crate::TenantQuota::max_requests_per_second`] and
+//! [`crate::TenantQuota::request_burst`], so the policy store sets them per tenant; tenants without
+//! a limit are not tracked.
+//!
+//! The layer reads the [`Principal`] that [`crate::grpc::authenticate`] attaches, so it wraps the
+//! service inside the interceptor (see [`RateLimitLayer`]). Callers without a tenant of their own,
+//! i.e. the operator token or anyone while authentication is off, are charged to the tenant named
+//! by the `x-minoots-tenant-id` header.
+//!
+//! The REST gateway charges the same buckets through [`RateLimitLayer::check_caller`], so share
+//! one layer between the two (see [`crate::rest::router`]).
+
+use std::convert::Infallible;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures_core::future::BoxFuture;
+use tonic::body::BoxBody;
+use tonic::codegen::http;
+use tonic::metadata::MetadataValue;
+use tonic::server::NamedService;
+use tonic::Status;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{HorologyKernel, Principal, RequestRateLimiter};
+
+pub const TENANT_HEADER: &str = "x-minoots-tenant-id";
+
+/// Builds [`RateLimit`] services. Apply it to the bare server and put the interceptor outside:
+/// `InterceptedService::new(layer.layer(service.into_server()), grpc::authenticate(tokens))`.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    kernel: HorologyKernel,
+    limiter: RequestRateLimiter,
+}
+
+impl RateLimitLayer {
+    pub fn new(kernel: HorologyKernel) -> Self {
+        Self {
+            kernel,
+            limiter: RequestRateLimiter::default(),
+        }
+    }
+
+    /// Charges one request to the caller's bucket, if its tenant has a request rate limit.
+    #[allow(clippy::result_large_err)]
+    fn check<B>(&self, request: &http::Request<B>) -> Result<(), Status> {
+        self.check_caller(request.extensions().get::<Principal>(), request.headers())
+    }
+
+    /// Charges one request by `principal` (`None` when authentication is off) to its bucket,
+    /// taking the tenant from `headers` for callers without one of their own.
+    #[allow(clippy::result_large_err)]
+    pub fn check_caller(
+        &self,
+        principal: Option<&Principal>,
+        headers: &http::HeaderMap,
+    ) -> Result<(), Status> {
+        let tenant_id = match principal.and_then(|principal| principal.tenant_id.as_deref()) {
+            Some(tenant_id) => tenant_id,
+            None => headers
+                .get(TENANT_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default(),
+        };
+        let quota = self.kernel.tenant_quota(tenant_id);
+        let Some(per_second) = quota.max_requests_per_second else {
+            return Ok(());
+        };
+        let caller = principal.map_or_else(|| "anonymous".to_string(), Principal::label);
+        let burst = quota.request_burst.unwrap_or(per_second);
+        self.limiter
+            .try_acquire(tenant_id, &caller, per_second, burst, Instant::now())
+            .map_err(|retry_after| rate_limited(tenant_id, retry_after))
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    layer: RateLimitLayer,
+}
+
+impl<S, B> Service<http::Request<B>> for RateLimit<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>, Error = Infallible>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        match self.layer.check(&request) {
+            Ok(()) => Box::pin(self.inner.call(request)),
+            Err(status) => Box::pin(async move { Ok(status.to_http()) }),
+        }
+    }
+}
+
+impl<S: NamedService> NamedService for RateLimit<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+fn rate_limited(tenant_id: &str, retry_after: Duration) -> Status {
+    let millis = retry_after.as_millis().max(1) as u64;
+    let mut status = Status::resource_exhausted(format!(
+        "request rate limit for tenant `{tenant_id}` exceeded; retry in {millis}ms"
+    ));
+    let metadata = status.metadata_mut();
+    metadata.insert("retry-after", MetadataValue::from(millis.div_ceil(1000)));
+    metadata.insert("grpc-retry-pushback-ms", MetadataValue::from(millis));
+    status
+}
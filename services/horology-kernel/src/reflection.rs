@@ -0,0 +1,224 @@
+//! `grpc.reflection.v1.ServerReflection`, so tools such as grpcurl can list the kernel's services
+//! and fetch their descriptors without a copy of the protos. Descriptors come from the sets the
+//! build emits, [`pb::FILE_DESCRIPTOR_SET`], [`pb::ADMIN_FILE_DESCRIPTOR_SET`], and
+//! [`pb::STANDARD_FILE_DESCRIPTOR_SET`].
+
+use std::collections::{HashSet, VecDeque};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures_core::Stream;
+use prost::Message;
+use prost_types::{DescriptorProto, FileDescriptorProto, FileDescriptorSet};
+use tokio_stream::StreamExt;
+use tonic::{Code, Request, Response, Status, Streaming};
+
+use crate::pb;
+use crate::pb::reflection::server_reflection_request::MessageRequest;
+use crate::pb::reflection::server_reflection_response::MessageResponse;
+use crate::pb::reflection::server_reflection_server::{ServerReflection, ServerReflectionServer};
+use crate::pb::reflection::{
+    ErrorResponse, ExtensionNumberResponse, FileDescriptorResponse, ListServiceResponse,
+    ServerReflectionRequest, ServerReflectionResponse, ServiceResponse,
+};
+
+pub const REFLECTION_SERVICE: &str = "grpc.reflection.v1.ServerReflection";
+
+pub type ReflectionStream =
+    Pin<Box<dyn Stream<Item = Result<ServerReflectionResponse, Status>> + Send + 'static>>;
+
+#[derive(Clone)]
+pub struct ReflectionService {
+    files: Arc<Vec<FileDescriptorProto>>,
+}
+
+impl Default for ReflectionService {
+    fn default() -> Self {
+        let mut names = HashSet::new();
+        // The admin set repeats the kernel proto it imports.
+        let files = [
+            pb::FILE_DESCRIPTOR_SET,
+            pb::ADMIN_FILE_DESCRIPTOR_SET,
+            pb::STANDARD_FILE_DESCRIPTOR_SET,
+        ]
+        .into_iter()
+        .flat_map(|set| {
+            FileDescriptorSet::decode(set)
+                .expect("descriptor sets emitted by the build decode")
+                .file
+        })
+        .filter(|file| names.insert(file.name().to_string()))
+        .collect();
+        Self {
+            files: Arc::new(files),
+        }
+    }
+}
+
+impl ReflectionService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_server(self) -> ServerReflectionServer<Self> {
+        ServerReflectionServer::new(self)
+    }
+
+    /// Full names of every service described, i.e. the kernel, admin, health, and reflection
+    /// services.
+    pub fn services(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .flat_map(|file| {
+                file.service
+                    .iter()
+                    .map(|service| qualified(file.package(), service.name()))
+            })
+            .collect()
+    }
+
+    fn respond(&self, request: ServerReflectionRequest) -> ServerReflectionResponse {
+        let response = match &request.message_request {
+            Some(MessageRequest::ListServices(_)) => {
+                MessageResponse::ListServicesResponse(ListServiceResponse {
+                    service: self
+                        .services()
+                        .into_iter()
+                        .map(|name| ServiceResponse { name })
+                        .collect(),
+                })
+            }
+            Some(MessageRequest::FileByFilename(name)) => self
+                .file_response(self.files.iter().find(|file| file.name() == name), || {
+                    format!("file `{name}` not found")
+                }),
+            Some(MessageRequest::FileContainingSymbol(symbol)) => self.file_response(
+                self.files.iter().find(|file| declares_symbol(file, symbol)),
+                || format!("symbol `{symbol}` not found"),
+            ),
+            // The kernel's protos declare no extensions.
+            Some(MessageRequest::FileContainingExtension(extension)) => error(
+                Code::NotFound,
+                format!(
+                    "extension {} of `{}` not found",
+                    extension.extension_number, extension.containing_type
+                ),
+            ),
+            Some(MessageRequest::AllExtensionNumbersOfType(type_name)) => {
+                if self
+                    .files
+                    .iter()
+                    .any(|file| declares_symbol(file, type_name))
+                {
+                    MessageResponse::AllExtensionNumbersResponse(ExtensionNumberResponse {
+                        base_type_name: type_name.clone(),
+                        extension_number: Vec::new(),
+                    })
+                } else {
+                    error(Code::NotFound, format!("type `{type_name}` not found"))
+                }
+            }
+            None => error(
+                Code::InvalidArgument,
+                "empty reflection request".to_string(),
+            ),
+        };
+        ServerReflectionResponse {
+            valid_host: request.host.clone(),
+            original_request: Some(request),
+            message_response: Some(response),
+        }
+    }
+
+    /// `file` followed by its transitive dependencies, each once.
+    fn file_response(
+        &self,
+        file: Option<&FileDescriptorProto>,
+        not_found: impl FnOnce() -> String,
+    ) -> MessageResponse {
+        let Some(file) = file else {
+            return error(Code::NotFound, not_found());
+        };
+        let mut seen = HashSet::from([file.name().to_string()]);
+        let mut queue = VecDeque::from([file]);
+        let mut encoded = Vec::new();
+        while let Some(file) = queue.pop_front() {
+            encoded.push(file.encode_to_vec());
+            for dependency in &file.dependency {
+                if seen.insert(dependency.clone()) {
+                    queue.extend(self.files.iter().find(|file| file.name() == dependency));
+                }
+            }
+        }
+        MessageResponse::FileDescriptorResponse(FileDescriptorResponse {
+            file_descriptor_proto: encoded,
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl ServerReflection for ReflectionService {
+    type ServerReflectionInfoStream = ReflectionStream;
+
+    #[allow(clippy::result_large_err)]
+    async fn server_reflection_info(
+        &self,
+        request: Request<Streaming<ServerReflectionRequest>>,
+    ) -> Result<Response<Self::ServerReflectionInfoStream>, Status> {
+        let reflection = self.clone();
+        let responses = request
+            .into_inner()
+            .map(move |request| request.map(|request| reflection.respond(request)));
+        Ok(Response::new(Box::pin(responses)))
+    }
+}
+
+fn error(code: Code, message: String) -> MessageResponse {
+    MessageResponse::ErrorResponse(ErrorResponse {
+        error_code: code as i32,
+        error_message: message,
+    })
+}
+
+fn qualified(package: &str, name: &str) -> String {
+    if package.is_empty() {
+        name.to_string()
+    } else {
+        format!("{package}.{name}")
+    }
+}
+
+/// Whether `file` declares `symbol`: a service or one of its methods, or a message or enum at
+/// any nesting depth.
+fn declares_symbol(file: &FileDescriptorProto, symbol: &str) -> bool {
+    let symbol = symbol.strip_prefix('.').unwrap_or(symbol);
+    let package = file.package();
+    file.service.iter().any(|service| {
+        let name = qualified(package, service.name());
+        name == symbol
+            || service
+                .method
+                .iter()
+                .any(|method| qualified(&name, method.name()) == symbol)
+    }) || file
+        .enum_type
+        .iter()
+        .any(|enumeration| qualified(package, enumeration.name()) == symbol)
+        || file
+            .message_type
+            .iter()
+            .any(|message| declares_message(package, message, symbol))
+}
+
+fn declares_message(scope: &str, message: &DescriptorProto, symbol: &str) -> bool {
+    let name = qualified(scope, message.name());
+    name == symbol
+        || message
+            .enum_type
+            .iter()
+            .any(|enumeration| qualified(&name, enumeration.name()) == symbol)
+        || message
+            .nested_type
+            .iter()
+            .any(|nested| declares_message(&name, nested, symbol))
+}
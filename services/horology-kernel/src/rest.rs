@@ -0,0 +1,468 @@
+//! REST/JSON gateway for clients without a gRPC stack, served by the kernel binary on
+//! `KERNEL_HTTP_ADDR`:
+//!
+//! - `POST /v1/timers` schedules a timer from a JSON body shaped like `TimerScheduleRequest`
+//!   (`action_bundle`, `metadata`, and `agent_binding` as JSON values, `fire_at` instead of
+//!   `fire_time_iso`, `missed_fire_policy` as `fire`, `skip`, or `grace:<ms>`).
+//! - `GET /v1/timers?tenant_id=...` lists timers (`status`, `label`, `name_prefix`, `query`,
+//!   `page_size`, `page_token`, `include_projects`; `status` and `label` may repeat).
+//! - `DELETE /v1/timers/{id}?tenant_id=...` cancels a timer (`requested_by`, `reason`).
+//! - `GET /v1/events?tenant_id=...` streams timer events as server-sent events (`topic`, `label`,
+//!   `name_prefix`, `subscriber_id`), with the event sequence as the SSE id. A reconnecting
+//!   `EventSource` resumes after its `Last-Event-ID` (`resume_from` on a first connection), which
+//!   needs event history on the kernel like `resume_from_sequence`.
+//! - `GET /v1/events/signed?tenant_id=...` streams the same events as signed envelopes,
+//!   `{"timestamp", "signature", "body"}`, where `body` is `{"sequence", "event"}` as a string and
+//!   `signature` is `sha256=<hex>` of `<timestamp>.<body>` keyed with the tenant's
+//!   `webhook_signing_secret`, the scheme event webhooks use, so consumers can check envelopes
+//!   with [`verify_signature`](crate::sinks::webhook::verify_signature).
+//!
+//! Every route is translated into the matching gRPC request and served by
+//! [`HorologyKernelService`], behind the same bearer-token [`authenticate`] check and the same
+//! per-caller [`RateLimitLayer`] buckets, so validation, scopes, limits, and error semantics are
+//! those of the gRPC API; a limited request gets `429` with `Retry-After`. Timers and events are rendered in the
+//! kernel's serde form; errors as `{"error": {"code": "...", "message": "..."}}`.
+
+use std::{collections::HashMap, convert::Infallible};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, RawQuery, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{delete, get},
+    Router,
+};
+use chrono::Utc;
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio_stream::{Stream, StreamExt};
+use tonic::{metadata::MetadataValue, Code, Request, Status};
+
+use crate::grpc::{
+    authenticate, event_from_proto, missed_fire_policy_to_proto, timer_from_proto,
+    HorologyKernelService,
+};
+use crate::pb::{
+    self, horology_kernel_server::HorologyKernel as _, timer_schedule_request::ScheduleTime,
+};
+use crate::rate_limit::RateLimitLayer;
+use crate::sinks::webhook::sign;
+use crate::{MissedFirePolicy, Principal, TokenStore};
+
+#[derive(Clone)]
+struct Gateway {
+    service: HorologyKernelService,
+    tokens: TokenStore,
+    limits: RateLimitLayer,
+}
+
+/// The gateway's routes; serve with `axum::Server` alongside the gRPC server. Pass the
+/// [`RateLimitLayer`] that wraps the gRPC service so both APIs draw on the same buckets.
+pub fn router(
+    service: HorologyKernelService,
+    tokens: TokenStore,
+    limits: RateLimitLayer,
+) -> Router {
+    Router::new()
+        .route("/v1/timers", get(list_timers).post(schedule_timer))
+        .route("/v1/timers/:id", delete(cancel_timer))
+        .route("/v1/events", get(stream_events))
+        .route("/v1/events/signed", get(stream_signed_events))
+        .with_state(Gateway {
+            service,
+            tokens,
+            limits,
+        })
+}
+
+/// A gRPC status rendered as an HTTP error.
+struct ApiError(Status);
+
+impl From<Status> for ApiError {
+    fn from(status: Status) -> Self {
+        Self(status)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, code) = match self.0.code() {
+            Code::InvalidArgument => (StatusCode::BAD_REQUEST, "invalid_argument"),
+            Code::OutOfRange => (StatusCode::BAD_REQUEST, "out_of_range"),
+            Code::FailedPrecondition => (StatusCode::BAD_REQUEST, "failed_precondition"),
+            Code::Unauthenticated => (StatusCode::UNAUTHORIZED, "unauthenticated"),
+            Code::PermissionDenied => (StatusCode::FORBIDDEN, "permission_denied"),
+            Code::NotFound => (StatusCode::NOT_FOUND, "not_found"),
+            Code::AlreadyExists => (StatusCode::CONFLICT, "already_exists"),
+            Code::Aborted => (StatusCode::CONFLICT, "aborted"),
+            Code::ResourceExhausted => (StatusCode::TOO_MANY_REQUESTS, "resource_exhausted"),
+            Code::Unimplemented => (StatusCode::NOT_IMPLEMENTED, "unimplemented"),
+            Code::Unavailable => (StatusCode::SERVICE_UNAVAILABLE, "unavailable"),
+            Code::DeadlineExceeded => (StatusCode::GATEWAY_TIMEOUT, "deadline_exceeded"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+        };
+        let body = json!({ "error": { "code": code, "message": self.0.message() } });
+        let mut response = (status, json_body(&body)).into_response();
+        if let Some(retry_after) = self.0.metadata().get("retry-after") {
+            if let Ok(value) = header::HeaderValue::from_bytes(retry_after.as_bytes()) {
+                response.headers_mut().insert(header::RETRY_AFTER, value);
+            }
+        }
+        response
+    }
+}
+
+fn json_body(value: &Value) -> ([(header::HeaderName, &'static str); 1], String) {
+    (
+        [(header::CONTENT_TYPE, "application/json")],
+        value.to_string(),
+    )
+}
+
+/// Runs the gRPC authentication interceptor on the HTTP `authorization` header, charges the
+/// caller's rate limit, and wraps `message` in a request carrying the resulting principal.
+#[allow(clippy::result_large_err)]
+fn grpc_request<T>(
+    gateway: &Gateway,
+    headers: &HeaderMap,
+    message: T,
+) -> Result<Request<T>, ApiError> {
+    let mut request = Request::new(());
+    if let Some(value) = headers.get(header::AUTHORIZATION) {
+        let value = value
+            .to_str()
+            .ok()
+            .and_then(|value| MetadataValue::try_from(value).ok())
+            .ok_or_else(|| Status::unauthenticated("authorization must be a bearer token"))?;
+        request.metadata_mut().insert("authorization", value);
+    }
+    let (metadata, extensions, ()) = authenticate(gateway.tokens.clone())(request)?.into_parts();
+    gateway
+        .limits
+        .check_caller(extensions.get::<Principal>(), headers)?;
+    Ok(Request::from_parts(metadata, extensions, message))
+}
+
+/// Query parameters in order; repeated keys appear once per value.
+fn query_pairs(query: Option<String>) -> Vec<(String, String)> {
+    let decode = |value: &str| {
+        percent_decode_str(&value.replace('+', " "))
+            .decode_utf8_lossy()
+            .into_owned()
+    };
+    query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), decode(value))
+        })
+        .collect()
+}
+
+struct Query(Vec<(String, String)>);
+
+impl Query {
+    fn one(&self, key: &str) -> String {
+        self.0
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default()
+    }
+
+    fn all(&self, key: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(name, _)| name == key)
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn number<N: std::str::FromStr + Default>(&self, key: &str) -> Result<N, Status> {
+        let value = self.one(key);
+        if value.is_empty() {
+            return Ok(N::default());
+        }
+        value
+            .parse()
+            .map_err(|_| Status::invalid_argument(format!("{key} must be a number")))
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        matches!(self.one(key).as_str(), "true" | "1")
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleBody {
+    tenant_id: String,
+    requested_by: String,
+    #[serde(default)]
+    name: String,
+    duration_ms: Option<u64>,
+    fire_at: Option<String>,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+    metadata: Option<Value>,
+    action_bundle: Option<Value>,
+    agent_binding: Option<Value>,
+    #[serde(default)]
+    accuracy_budget_ms: u64,
+    #[serde(default)]
+    pre_fire_notice_ms: u64,
+    #[serde(default)]
+    idempotency_key: String,
+    missed_fire_policy: Option<String>,
+    #[serde(default)]
+    session_id: String,
+    #[serde(default)]
+    watchdog_interval_ms: u64,
+    #[serde(default)]
+    priority: u32,
+    #[serde(default)]
+    deadline: bool,
+    #[serde(default)]
+    deadline_grace_ms: u64,
+}
+
+impl TryFrom<ScheduleBody> for pb::TimerScheduleRequest {
+    type Error = Status;
+
+    fn try_from(body: ScheduleBody) -> Result<Self, Status> {
+        let schedule_time = match (body.duration_ms, body.fire_at) {
+            (Some(duration_ms), None) => Some(ScheduleTime::DurationMs(duration_ms)),
+            (None, Some(fire_at)) => Some(ScheduleTime::FireTimeIso(fire_at)),
+            (None, None) => None,
+            (Some(_), Some(_)) => {
+                return Err(Status::invalid_argument(
+                    "set either duration_ms or fire_at, not both",
+                ))
+            }
+        };
+        let missed_fire_policy = body
+            .missed_fire_policy
+            .map(|policy| policy.parse::<MissedFirePolicy>())
+            .transpose()
+            .map_err(|error| Status::invalid_argument(format!("missed_fire_policy: {error}")))?;
+        let (missed_fire_policy, missed_fire_grace_ms) =
+            missed_fire_policy_to_proto(missed_fire_policy);
+        let json = |value: Option<Value>| value.map(|value| value.to_string()).unwrap_or_default();
+        Ok(pb::TimerScheduleRequest {
+            tenant_id: body.tenant_id,
+            requested_by: body.requested_by,
+            name: body.name,
+            schedule_time,
+            action_bundle_json: json(body.action_bundle),
+            labels: body.labels,
+            metadata_json: json(body.metadata),
+            agent_binding_json: json(body.agent_binding),
+            accuracy_budget_ms: body.accuracy_budget_ms,
+            pre_fire_notice_ms: body.pre_fire_notice_ms,
+            idempotency_key: body.idempotency_key,
+            missed_fire_policy: missed_fire_policy as i32,
+            missed_fire_grace_ms,
+            session_id: body.session_id,
+            watchdog_interval_ms: body.watchdog_interval_ms,
+            priority: body.priority,
+            deadline: body.deadline,
+            deadline_grace_ms: body.deadline_grace_ms,
+        })
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn timer_json(timer: Option<pb::Timer>) -> Result<Value, Status> {
+    let timer = timer_from_proto(timer.ok_or_else(|| Status::internal("timer missing"))?)?;
+    serde_json::to_value(timer).map_err(|error| Status::internal(error.to_string()))
+}
+
+async fn schedule_timer(
+    State(gateway): State<Gateway>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, ApiError> {
+    let body: ScheduleBody = serde_json::from_slice(&body)
+        .map_err(|error| Status::invalid_argument(format!("invalid request body: {error}")))?;
+    let request = grpc_request(
+        &gateway,
+        &headers,
+        pb::TimerScheduleRequest::try_from(body)?,
+    )?;
+    let response = gateway.service.schedule_timer(request).await?.into_inner();
+    Ok((StatusCode::CREATED, json_body(&timer_json(response.timer)?)))
+}
+
+#[allow(clippy::result_large_err)]
+async fn list_timers(
+    State(gateway): State<Gateway>,
+    headers: HeaderMap,
+    RawQuery(query): RawQuery,
+) -> Result<impl IntoResponse, ApiError> {
+    let query = Query(query_pairs(query));
+    let request = pb::TimerListRequest {
+        tenant_id: query.one("tenant_id"),
+        page_size: query.number("page_size")?,
+        page_token: query.one("page_token"),
+        statuses: query.all("status"),
+        include_projects: query.flag("include_projects"),
+        query: query.one("query"),
+        label_selectors: query.all("label"),
+        name_prefix: query.one("name_prefix"),
+        ..Default::default()
+    };
+    let request = grpc_request(&gateway, &headers, request)?;
+    let response = gateway.service.list_timers(request).await?.into_inner();
+    let timers = response
+        .timers
+        .into_iter()
+        .map(|timer| timer_json(Some(timer)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(json_body(&json!({
+        "timers": timers,
+        "next_page_token": response.next_page_token,
+        "snapshot_at": response.snapshot_at_iso,
+        "total_size": response.total_size,
+    })))
+}
+
+async fn cancel_timer(
+    State(gateway): State<Gateway>,
+    headers: HeaderMap,
+    Path(timer_id): Path<String>,
+    RawQuery(query): RawQuery,
+) -> Result<impl IntoResponse, ApiError> {
+    let query = Query(query_pairs(query));
+    let request = pb::TimerCancelRequest {
+        tenant_id: query.one("tenant_id"),
+        timer_id,
+        requested_by: query.one("requested_by"),
+        reason: query.one("reason"),
+        expected_state_version: query.number("expected_state_version")?,
+    };
+    let request = grpc_request(&gateway, &headers, request)?;
+    let timer = gateway.service.cancel_timer(request).await?.into_inner();
+    Ok(json_body(&timer_json(Some(timer))?))
+}
+
+async fn stream_events(
+    State(gateway): State<Gateway>,
+    headers: HeaderMap,
+    RawQuery(query): RawQuery,
+) -> Result<impl IntoResponse, ApiError> {
+    let query = Query(query_pairs(query));
+    let events = open_event_stream(&gateway, &headers, &query).await?;
+    Ok(sse(events, None))
+}
+
+async fn stream_signed_events(
+    State(gateway): State<Gateway>,
+    headers: HeaderMap,
+    RawQuery(query): RawQuery,
+) -> Result<impl IntoResponse, ApiError> {
+    let query = Query(query_pairs(query));
+    // Opened first so the caller is authorized for the tenant before its secret is looked up.
+    let events = open_event_stream(&gateway, &headers, &query).await?;
+    let secret = gateway
+        .service
+        .kernel()
+        .tenant_policy(&query.one("tenant_id"))
+        .webhook_signing_secret
+        .ok_or_else(|| {
+            Status::failed_precondition("tenant has no webhook_signing_secret to sign events with")
+        })?;
+    Ok(sse(events, Some(secret)))
+}
+
+type EventStream =
+    <HorologyKernelService as pb::horology_kernel_server::HorologyKernel>::StreamTimerEventsStream;
+
+/// Opens the tenant's gRPC event stream, resuming after `Last-Event-ID` (sent by `EventSource`
+/// when it reconnects) or, on a first connection, after the `resume_from` query parameter.
+async fn open_event_stream(
+    gateway: &Gateway,
+    headers: &HeaderMap,
+    query: &Query,
+) -> Result<EventStream, ApiError> {
+    let resume_from_sequence = match headers.get("last-event-id") {
+        Some(value) => value
+            .to_str()
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .ok_or_else(|| Status::invalid_argument("Last-Event-ID must be an event sequence"))?,
+        None => query.number("resume_from")?,
+    };
+    let request = pb::TimerEventStreamRequest {
+        tenant_id: query.one("tenant_id"),
+        topics: query.all("topic"),
+        include_projects: query.flag("include_projects"),
+        subscriber_id: query.one("subscriber_id"),
+        resume_from_sequence,
+        label_selectors: query.all("label"),
+        name_prefix: query.one("name_prefix"),
+    };
+    let request = grpc_request(gateway, headers, request)?;
+    Ok(gateway
+        .service
+        .stream_timer_events(request)
+        .await?
+        .into_inner())
+}
+
+fn sse(
+    events: EventStream,
+    secret: Option<String>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // A stream error (e.g. the subscriber fell too far behind) is sent as an `error` event, after
+    // which the stream ends.
+    let mut failed = false;
+    let events = events
+        .map_while(move |event| {
+            if failed {
+                return None;
+            }
+            let event = event_to_sse(event, secret.as_deref());
+            failed = event.is_err();
+            Some(event.unwrap_or_else(Some))
+        })
+        .filter_map(|event| event.map(Ok::<_, Infallible>));
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// `Ok(None)` for events the kernel does not expose; `Err` carries the final `error` event.
+/// With a `secret`, the data is a signed envelope instead of the bare event.
+fn event_to_sse(
+    event: Result<pb::TimerEvent, Status>,
+    secret: Option<&str>,
+) -> Result<Option<Event>, Event> {
+    let error_event = |status: Status| {
+        let error = json!({ "code": format!("{:?}", status.code()), "message": status.message() });
+        Event::default().event("error").data(error.to_string())
+    };
+    let event = event.map_err(error_event)?;
+    let sequence = event.sequence;
+    let Some(event) = event_from_proto(event).map_err(error_event)? else {
+        return Ok(None);
+    };
+    let mut data = serde_json::to_string(&event)
+        .map_err(|error| error_event(Status::internal(error.to_string())))?;
+    if let Some(secret) = secret {
+        let body = format!(r#"{{"sequence":{sequence},"event":{data}}}"#);
+        let timestamp = Utc::now().timestamp();
+        let signature = sign(secret, timestamp, body.as_bytes());
+        data = json!({ "timestamp": timestamp, "signature": signature, "body": body }).to_string();
+    }
+    let mut sse = Event::default().event(event.kind()).data(data);
+    if sequence > 0 {
+        sse = sse.id(sequence.to_string());
+    }
+    Ok(Some(sse))
+}
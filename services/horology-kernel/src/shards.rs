@@ -0,0 +1,139 @@
+//! The kernel's timer table, split into shards by timer id so that schedules, fires, and cancels
+//! of different timers do not queue behind one lock. Single-timer operations lock the timer's
+//! shard; scans and operations spanning timers lock every shard, always in index order. No path
+//! holds one shard while locking another, so the two never deadlock.
+
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use uuid::Uuid;
+
+use crate::TimerInstance;
+
+pub(crate) type TimerMap = HashMap<Uuid, TimerInstance>;
+
+#[derive(Clone)]
+pub(crate) struct TimerShards {
+    shards: Arc<[RwLock<TimerMap>]>,
+}
+
+impl TimerShards {
+    pub(crate) fn new(count: usize) -> Self {
+        Self {
+            shards: (0..count.max(1))
+                .map(|_| RwLock::new(HashMap::new()))
+                .collect(),
+        }
+    }
+
+    /// The shard holding `id`.
+    pub(crate) fn shard(&self, id: &Uuid) -> &RwLock<TimerMap> {
+        &self.shards[shard_index(id, self.shards.len())]
+    }
+
+    /// Read access to every shard.
+    pub(crate) async fn read(&self) -> AllShards<RwLockReadGuard<'_, TimerMap>> {
+        let mut guards = Vec::with_capacity(self.shards.len());
+        for shard in self.shards.iter() {
+            guards.push(shard.read().await);
+        }
+        AllShards { guards }
+    }
+
+    /// Exclusive access to every shard.
+    pub(crate) async fn write(&self) -> AllShards<RwLockWriteGuard<'_, TimerMap>> {
+        let mut guards = Vec::with_capacity(self.shards.len());
+        for shard in self.shards.iter() {
+            guards.push(shard.write().await);
+        }
+        AllShards { guards }
+    }
+}
+
+/// The low half of a UUID is random for both v4 and v7 ids; v7 ids lead with their timestamp.
+fn shard_index(id: &Uuid, count: usize) -> usize {
+    (id.as_u64_pair().1 % count as u64) as usize
+}
+
+/// Every shard locked at once, read like a single map.
+pub(crate) struct AllShards<G> {
+    guards: Vec<G>,
+}
+
+impl<G: Deref<Target = TimerMap>> AllShards<G> {
+    fn shard(&self, id: &Uuid) -> &TimerMap {
+        &self.guards[shard_index(id, self.guards.len())]
+    }
+
+    pub(crate) fn get(&self, id: &Uuid) -> Option<&TimerInstance> {
+        self.shard(id).get(id)
+    }
+
+    pub(crate) fn contains_key(&self, id: &Uuid) -> bool {
+        self.shard(id).contains_key(id)
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &TimerInstance> {
+        self.guards.iter().flat_map(|shard| shard.values())
+    }
+}
+
+impl<G: DerefMut<Target = TimerMap>> AllShards<G> {
+    fn shard_mut(&mut self, id: &Uuid) -> &mut TimerMap {
+        let index = shard_index(id, self.guards.len());
+        &mut self.guards[index]
+    }
+
+    #[cfg(test)]
+    pub(crate) fn get_mut(&mut self, id: &Uuid) -> Option<&mut TimerInstance> {
+        self.shard_mut(id).get_mut(id)
+    }
+
+    pub(crate) fn insert(&mut self, id: Uuid, timer: TimerInstance) -> Option<TimerInstance> {
+        self.shard_mut(&id).insert(id, timer)
+    }
+
+    pub(crate) fn remove(&mut self, id: &Uuid) -> Option<TimerInstance> {
+        self.shard_mut(id).remove(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn all_shards_see_what_single_shards_hold() {
+        let shards = TimerShards::new(4);
+        let now = chrono::Utc::now();
+        let timers: Vec<TimerInstance> = (0..32)
+            .map(|_| {
+                let spec = crate::TimerSpec::default();
+                TimerInstance::from_spec(&spec, Uuid::new_v4(), now, now, Default::default())
+            })
+            .collect();
+        for timer in &timers {
+            shards
+                .shard(&timer.id)
+                .write()
+                .await
+                .insert(timer.id, timer.clone());
+        }
+
+        let all = shards.read().await;
+        assert_eq!(all.values().count(), timers.len());
+        assert!(timers.iter().all(|timer| all.contains_key(&timer.id)));
+        drop(all);
+        let removed = shards.write().await.remove(&timers[0].id);
+        assert_eq!(removed.map(|timer| timer.id), Some(timers[0].id));
+        assert!(!shards
+            .shard(&timers[0].id)
+            .read()
+            .await
+            .contains_key(&timers[0].id));
+    }
+}
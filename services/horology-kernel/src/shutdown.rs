@@ -0,0 +1,108 @@
+use std::{future::Future, pin::Pin, time::Duration};
+
+use tracing::{info, warn};
+
+type StopFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+struct Component {
+    name: &'static str,
+    timeout: Duration,
+    stop: StopFuture,
+}
+
+/// Result of stopping one component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopOutcome {
+    Stopped,
+    TimedOut,
+}
+
+/// Stops registered components one at a time, in registration order, giving each its own timeout.
+/// Register ingress first (so no new work arrives), then producers, then sinks and final flushes.
+#[derive(Default)]
+pub struct ShutdownCoordinator {
+    components: Vec<Component>,
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<F>(&mut self, name: &'static str, timeout: Duration, stop: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.components.push(Component {
+            name,
+            timeout,
+            stop: Box::pin(stop),
+        });
+    }
+
+    /// Runs every stop future and reports how each one finished. A component that exceeds its
+    /// timeout is abandoned so later components still get to stop.
+    pub async fn shutdown(self) -> Vec<(&'static str, StopOutcome)> {
+        let mut report = Vec::with_capacity(self.components.len());
+        for component in self.components {
+            let outcome = match tokio::time::timeout(component.timeout, component.stop).await {
+                Ok(()) => {
+                    info!(component = component.name, "component stopped");
+                    StopOutcome::Stopped
+                }
+                Err(_) => {
+                    warn!(
+                        component = component.name,
+                        timeout_ms = component.timeout.as_millis() as u64,
+                        "component did not stop in time"
+                    );
+                    StopOutcome::TimedOut
+                }
+            };
+            report.push((component.name, outcome));
+        }
+        report
+    }
+}
+
+/// What [`crate::HorologyKernel::shutdown`] left in the store for the next kernel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DrainReport {
+    /// Timers written in the final snapshot, terminal ones included.
+    pub snapshotted: usize,
+    /// Timers still waiting to fire, which a restore or a promoted standby picks up.
+    pub pending: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn stops_in_order_and_abandons_slow_components() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut coordinator = ShutdownCoordinator::new();
+        for name in ["ingress", "stuck", "flush"] {
+            let order = order.clone();
+            coordinator.register(name, Duration::from_millis(20), async move {
+                if name == "stuck" {
+                    std::future::pending::<()>().await;
+                }
+                order.lock().unwrap().push(name);
+            });
+        }
+
+        let report = coordinator.shutdown().await;
+        assert_eq!(
+            report,
+            vec![
+                ("ingress", StopOutcome::Stopped),
+                ("stuck", StopOutcome::TimedOut),
+                ("flush", StopOutcome::Stopped),
+            ]
+        );
+        assert_eq!(*order.lock().unwrap(), vec!["ingress", "flush"]);
+    }
+}
@@ -0,0 +1,195 @@
+//! Events an [`EventSink`](super::EventSink) could not deliver within its
+//! [`RetryPolicy`](super::RetryPolicy). They are parked here instead of dropped, listed for
+//! operators, and handed back to their sink's forwarder by [`DeadLetterQueue::replay`] once the
+//! broker is healthy again. The queue is bounded; past [`CAPACITY`] the oldest letters go first.
+
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::watch;
+use tracing::warn;
+
+use crate::RecordedEvent;
+
+/// Most events parked at once, across sinks.
+pub const CAPACITY: usize = 10_000;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DeadLetter {
+    pub sink: String,
+    pub event: RecordedEvent,
+    /// Sends attempted before the event was parked.
+    pub attempts: u32,
+    /// Error of the last attempt.
+    pub error: String,
+    pub parked_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DeadLetterStats {
+    /// Events parked so far, counting each time a replayed event is parked again.
+    pub parked: u64,
+    pub replayed: u64,
+    /// Events dropped because the queue was full.
+    pub discarded: u64,
+    /// Events parked right now.
+    pub pending: usize,
+}
+
+#[derive(Default)]
+struct Letters {
+    parked: VecDeque<DeadLetter>,
+    /// Replayed events per sink, waiting for that sink's forwarder.
+    replaying: HashMap<String, VecDeque<RecordedEvent>>,
+    /// Sinks with a running forwarder, i.e. those replayed events can be handed to.
+    forwarders: BTreeSet<String>,
+    stats: DeadLetterStats,
+}
+
+/// Shared by the kernel's transports and every sink forwarder; clones share the queue.
+#[derive(Clone)]
+pub struct DeadLetterQueue {
+    letters: Arc<Mutex<Letters>>,
+    /// Bumped on every replay so idle forwarders wake up.
+    replays: Arc<watch::Sender<u64>>,
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        Self {
+            letters: Arc::default(),
+            replays: Arc::new(watch::channel(0).0),
+        }
+    }
+}
+
+impl DeadLetterQueue {
+    pub fn park(&self, sink: &str, events: &[RecordedEvent], attempts: u32, error: &str) {
+        let parked_at = Utc::now();
+        let mut letters = self.letters.lock().unwrap();
+        let mut discarded = 0;
+        for event in events {
+            if letters.parked.len() == CAPACITY {
+                letters.parked.pop_front();
+                discarded += 1;
+            }
+            letters.parked.push_back(DeadLetter {
+                sink: sink.to_string(),
+                event: event.clone(),
+                attempts,
+                error: error.to_string(),
+                parked_at,
+            });
+            letters.stats.parked += 1;
+        }
+        letters.stats.discarded += discarded;
+        if discarded > 0 {
+            warn!(discarded, "dead-letter queue full; oldest events discarded");
+        }
+    }
+
+    /// Parked events, oldest first.
+    pub fn list(&self) -> Vec<DeadLetter> {
+        self.letters
+            .lock()
+            .unwrap()
+            .parked
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    pub fn stats(&self) -> DeadLetterStats {
+        let letters = self.letters.lock().unwrap();
+        DeadLetterStats {
+            pending: letters.parked.len(),
+            ..letters.stats
+        }
+    }
+
+    /// Hands up to `limit` parked events of `sink`, or of every sink when `None`, back to their
+    /// forwarders, oldest first. Returns how many were replayed. Events that fail again are
+    /// parked again.
+    pub fn replay(&self, sink: Option<&str>, limit: usize) -> usize {
+        let mut letters = self.letters.lock().unwrap();
+        let Letters {
+            parked, replaying, ..
+        } = &mut *letters;
+        let mut replayed = 0;
+        parked.retain(|letter| {
+            if replayed == limit || sink.is_some_and(|sink| sink != letter.sink) {
+                return true;
+            }
+            replaying
+                .entry(letter.sink.clone())
+                .or_default()
+                .push_back(letter.event.clone());
+            replayed += 1;
+            false
+        });
+        letters.stats.replayed += replayed as u64;
+        drop(letters);
+        if replayed > 0 {
+            self.replays.send_modify(|version| *version += 1);
+        }
+        replayed
+    }
+
+    /// Hands `events`, e.g. retained history a broker lost, to the forwarder of `sink` ahead of
+    /// new events. Returns `false`, queuing nothing, when no forwarder for `sink` is running.
+    pub fn redeliver(&self, sink: &str, events: Vec<RecordedEvent>) -> bool {
+        let mut letters = self.letters.lock().unwrap();
+        if !letters.forwarders.contains(sink) {
+            return false;
+        }
+        let count = events.len();
+        letters
+            .replaying
+            .entry(sink.to_string())
+            .or_default()
+            .extend(events);
+        drop(letters);
+        if count > 0 {
+            self.replays.send_modify(|version| *version += 1);
+        }
+        true
+    }
+
+    /// Names of the sinks with a running forwarder.
+    pub fn forwarders(&self) -> Vec<String> {
+        self.letters
+            .lock()
+            .unwrap()
+            .forwarders
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn register_forwarder(&self, sink: &str) {
+        self.letters
+            .lock()
+            .unwrap()
+            .forwarders
+            .insert(sink.to_string());
+    }
+
+    /// Changes whenever events are replayed.
+    pub(crate) fn watch_replays(&self) -> watch::Receiver<u64> {
+        self.replays.subscribe()
+    }
+
+    /// Up to `max` replayed events waiting for `sink`.
+    pub(crate) fn take_replayed(&self, sink: &str, max: usize) -> Vec<RecordedEvent> {
+        let mut letters = self.letters.lock().unwrap();
+        let Some(queue) = letters.replaying.get_mut(sink) else {
+            return Vec::new();
+        };
+        let count = queue.len().min(max);
+        queue.drain(..count).collect()
+    }
+}
@@ -0,0 +1,208 @@
+//! Message bodies for the event sinks: the event JSON, or a protobuf [`pb::EventEnvelope`]
+//! wrapping the same `pb::TimerEvent` the gRPC stream sends, optionally signed with HMAC-SHA256.
+//!
+//! A signed envelope's `signature` covers `<sequence>.<recorded_at_iso>.` followed by the
+//! encoded event, so a consumer can check an envelope with [`open`] before trusting it.
+
+use std::str::FromStr;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, SecondsFormat, Utc};
+use prost::Message;
+
+use super::{hmac_sha256, SinkError};
+use crate::grpc::{event_from_proto, event_to_proto};
+use crate::{pb, RecordedEvent};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SinkEncoding {
+    #[default]
+    Json,
+    Protobuf,
+}
+
+impl FromStr for SinkEncoding {
+    type Err = SinkError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "json" => Ok(SinkEncoding::Json),
+            "protobuf" | "proto" => Ok(SinkEncoding::Protobuf),
+            other => Err(SinkError::Envelope(format!(
+                "unknown sink encoding `{other}`; expected `json` or `protobuf`"
+            ))),
+        }
+    }
+}
+
+/// A shared secret for signing envelopes; `key_id` tells consumers which one was used.
+#[derive(Clone)]
+pub struct SigningKey {
+    pub key_id: String,
+    secret: Vec<u8>,
+}
+
+impl SigningKey {
+    pub fn new(key_id: impl Into<String>, secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            key_id: key_id.into(),
+            secret: secret.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// How a sink turns an event into a message body. Signing applies to protobuf envelopes only.
+#[derive(Debug, Clone, Default)]
+pub struct Encoder {
+    encoding: SinkEncoding,
+    signing_key: Option<SigningKey>,
+}
+
+impl Encoder {
+    pub fn new(encoding: SinkEncoding, signing_key: Option<SigningKey>) -> Self {
+        Self {
+            encoding,
+            signing_key,
+        }
+    }
+
+    pub fn encoding(&self) -> SinkEncoding {
+        self.encoding
+    }
+
+    /// Sent as the `content_type` message attribute.
+    pub fn content_type(&self) -> &'static str {
+        match self.encoding {
+            SinkEncoding::Json => "application/json",
+            SinkEncoding::Protobuf => "application/x-protobuf",
+        }
+    }
+
+    pub fn encode(&self, event: &RecordedEvent) -> Result<Vec<u8>, SinkError> {
+        match self.encoding {
+            SinkEncoding::Json => Ok(serde_json::to_vec(event)?),
+            SinkEncoding::Protobuf => Ok(self.envelope(event)?.encode_to_vec()),
+        }
+    }
+
+    /// The body for brokers whose messages are text: JSON as is, an envelope as base64.
+    pub fn encode_text(&self, event: &RecordedEvent) -> Result<String, SinkError> {
+        match self.encoding {
+            SinkEncoding::Json => Ok(serde_json::to_string(event)?),
+            SinkEncoding::Protobuf => Ok(STANDARD.encode(self.encode(event)?)),
+        }
+    }
+
+    fn envelope(&self, event: &RecordedEvent) -> Result<pb::EventEnvelope, SinkError> {
+        let mut proto = event_to_proto(event.event.clone())
+            .map_err(|status| SinkError::Envelope(status.message().to_string()))?;
+        proto.sequence = event.sequence;
+        let mut envelope = pb::EventEnvelope {
+            sequence: event.sequence,
+            recorded_at_iso: event
+                .recorded_at
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            event: proto.encode_to_vec(),
+            ..Default::default()
+        };
+        if let Some(key) = &self.signing_key {
+            envelope.key_id = key.key_id.clone();
+            envelope.signature = signature(&key.secret, &envelope).to_vec();
+        }
+        Ok(envelope)
+    }
+}
+
+/// Decodes an envelope, checking its signature against `secret` when one is given.
+pub fn open(bytes: &[u8], secret: Option<&[u8]>) -> Result<RecordedEvent, SinkError> {
+    let invalid = |message: String| SinkError::Envelope(message);
+    let envelope = pb::EventEnvelope::decode(bytes).map_err(|error| invalid(error.to_string()))?;
+    if let Some(secret) = secret {
+        let expected = signature(secret, &envelope);
+        let matches = envelope.signature.len() == expected.len()
+            && envelope
+                .signature
+                .iter()
+                .zip(expected)
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0;
+        if !matches {
+            return Err(invalid(format!(
+                "signature mismatch for event {}",
+                envelope.sequence
+            )));
+        }
+    }
+    let proto = pb::TimerEvent::decode(envelope.event.as_slice())
+        .map_err(|error| invalid(error.to_string()))?;
+    let event = event_from_proto(proto)
+        .map_err(|status| invalid(status.message().to_string()))?
+        .ok_or_else(|| {
+            invalid(format!(
+                "event {} carries no timer event",
+                envelope.sequence
+            ))
+        })?;
+    Ok(RecordedEvent {
+        sequence: envelope.sequence,
+        recorded_at: DateTime::parse_from_rfc3339(&envelope.recorded_at_iso)
+            .map_err(|error| invalid(error.to_string()))?
+            .with_timezone(&Utc),
+        event,
+    })
+}
+
+fn signature(secret: &[u8], envelope: &pb::EventEnvelope) -> [u8; 32] {
+    let mut signed = format!("{}.{}.", envelope.sequence, envelope.recorded_at_iso).into_bytes();
+    signed.extend_from_slice(&envelope.event);
+    hmac_sha256(secret, &signed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TimerEvent, TimerInstance, TimerSpec};
+
+    #[test]
+    fn signed_envelopes_round_trip_and_reject_tampering() {
+        let now = chrono::Utc::now();
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            ..Default::default()
+        };
+        let timer =
+            TimerInstance::from_spec(&spec, uuid::Uuid::new_v4(), now, now, Default::default());
+        let event = RecordedEvent {
+            sequence: 42,
+            recorded_at: now,
+            event: TimerEvent::Fired(timer.clone()),
+        };
+        let encoder = Encoder::new(
+            SinkEncoding::Protobuf,
+            Some(SigningKey::new("k1", b"secret".to_vec())),
+        );
+
+        let bytes = encoder.encode(&event).unwrap();
+        let opened = open(&bytes, Some(b"secret")).unwrap();
+        assert_eq!(opened.sequence, 42);
+        assert_eq!(opened.event.kind(), "fired");
+        assert_eq!(opened.event.timer().id, timer.id);
+        assert_eq!(
+            pb::EventEnvelope::decode(bytes.as_slice()).unwrap().key_id,
+            "k1"
+        );
+        assert!(open(&bytes, Some(b"other secret")).is_err());
+
+        let mut tampered = pb::EventEnvelope::decode(bytes.as_slice()).unwrap();
+        tampered.sequence = 43;
+        assert!(open(&tampered.encode_to_vec(), Some(b"secret")).is_err());
+    }
+}
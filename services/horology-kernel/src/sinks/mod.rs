@@ -0,0 +1,386 @@
+//! Forwarding of timer events to external brokers. Every [`EventSink`] is driven by its own
+//! [`forward`] loop on its own subscription, so several sinks run side by side and a slow or
+//! failing one only lags itself. Delivery is at least once: a batch that fails is retried whole,
+//! up to the [`RetryPolicy`], and then parked in the [`DeadLetterQueue`] for a later replay.
+
+#[cfg(feature = "cloud-sinks")]
+pub mod aws;
+pub mod dead_letters;
+#[cfg(feature = "cloud-sinks")]
+pub mod envelope;
+#[cfg(feature = "cloud-sinks")]
+pub mod pubsub;
+pub mod webhook;
+
+use std::{future::Future, time::Duration};
+
+use async_trait::async_trait;
+use thiserror::Error;
+use tracing::{info, warn};
+
+pub use dead_letters::{DeadLetter, DeadLetterQueue, DeadLetterStats};
+pub use webhook::{EventWebhook, WebhookError, WebhookRegistry};
+
+use crate::fanout::{RecvError, TryRecvError};
+use crate::{EventSubscription, RecordedEvent};
+
+const RETRY_INITIAL: Duration = Duration::from_millis(250);
+const RETRY_MAX: Duration = Duration::from_secs(30);
+
+/// How often [`forward`] sends a failing batch before parking it as dead letters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Sends per batch, counting the first; at least 1.
+    pub max_attempts: u32,
+    /// Wait before the second attempt, doubling up to `max_backoff`.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Eight attempts, spread over about half a minute.
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            initial_backoff: RETRY_INITIAL,
+            max_backoff: RETRY_MAX,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SinkError {
+    #[error("sink request failed: {0}")]
+    Transport(String),
+    #[error("sink responded with {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error("sink rejected {failed} of {total} events")]
+    Rejected { failed: usize, total: usize },
+    #[error("invalid sink payload: {0}")]
+    Payload(#[from] serde_json::Error),
+    #[error("invalid event envelope: {0}")]
+    Envelope(String),
+}
+
+#[cfg(feature = "grpc")]
+impl From<hyper::Error> for SinkError {
+    fn from(error: hyper::Error) -> Self {
+        SinkError::Transport(error.to_string())
+    }
+}
+
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// Short name for task names and logs, e.g. `pubsub`.
+    fn name(&self) -> &str;
+
+    /// Most events one [`EventSink::send`] accepts.
+    fn max_batch(&self) -> usize {
+        100
+    }
+
+    /// Publishes `events`, oldest first. An error means the batch is sent again.
+    async fn send(&self, events: &[RecordedEvent]) -> Result<(), SinkError>;
+}
+
+/// Sends `events` to `sink` in batches of whatever is buffered, retrying failed batches with
+/// exponential backoff per `retry` and parking those that run out of attempts in `dead_letters`.
+/// Replayed dead letters go out ahead of new events. Once `stop` resolves, sends what is still
+/// buffered once more and returns.
+pub async fn forward(
+    sink: &dyn EventSink,
+    mut events: EventSubscription,
+    retry: RetryPolicy,
+    dead_letters: &DeadLetterQueue,
+    stop: impl Future<Output = ()>,
+) {
+    tokio::pin!(stop);
+    let max_batch = sink.max_batch().max(1);
+    let mut replays = dead_letters.watch_replays();
+    dead_letters.register_forwarder(sink.name());
+    loop {
+        replays.borrow_and_update();
+        let mut batch = dead_letters.take_replayed(sink.name(), max_batch);
+        if batch.is_empty() {
+            let first = tokio::select! {
+                event = events.recv_recorded() => event,
+                _ = replays.changed() => continue,
+                _ = &mut stop => break,
+            };
+            match first {
+                Ok(event) => batch.push(event),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(
+                        sink = sink.name(),
+                        skipped, "event sink fell behind; events dropped"
+                    );
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            }
+            fill(sink, &mut events, &mut batch, max_batch);
+        }
+
+        let mut backoff = retry.initial_backoff;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let error = match sink.send(&batch).await {
+                Ok(()) => break,
+                Err(error) => error,
+            };
+            if attempts >= retry.max_attempts {
+                warn!(
+                    sink = sink.name(),
+                    %error,
+                    attempts,
+                    events = batch.len(),
+                    "event sink send failed; events dead-lettered"
+                );
+                dead_letters.park(sink.name(), &batch, attempts, &error.to_string());
+                break;
+            }
+            warn!(
+                sink = sink.name(),
+                %error,
+                events = batch.len(),
+                "event sink send failed; retrying"
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(backoff) => backoff = (backoff * 2).min(retry.max_backoff),
+                _ = &mut stop => {
+                    warn!(
+                        sink = sink.name(),
+                        events = batch.len(),
+                        "stopped while retrying; events dead-lettered"
+                    );
+                    dead_letters.park(sink.name(), &batch, attempts, &error.to_string());
+                    return;
+                }
+            }
+        }
+    }
+
+    let mut forwarded = 0;
+    loop {
+        let mut batch = Vec::new();
+        fill(sink, &mut events, &mut batch, max_batch);
+        if batch.is_empty() {
+            break;
+        }
+        if let Err(error) = sink.send(&batch).await {
+            warn!(
+                sink = sink.name(),
+                %error,
+                events = batch.len(),
+                "final event sink send failed; events dead-lettered"
+            );
+            dead_letters.park(sink.name(), &batch, 1, &error.to_string());
+            break;
+        }
+        forwarded += batch.len();
+    }
+    info!(sink = sink.name(), forwarded, "event sink drained");
+}
+
+/// Tops `batch` up to `max_batch` with events already buffered.
+fn fill(
+    sink: &dyn EventSink,
+    events: &mut EventSubscription,
+    batch: &mut Vec<RecordedEvent>,
+    max_batch: usize,
+) {
+    while batch.len() < max_batch {
+        match events.try_recv_recorded() {
+            Ok(event) => batch.push(event),
+            Err(TryRecvError::Lagged(skipped)) => {
+                warn!(
+                    sink = sink.name(),
+                    skipped, "event sink fell behind; events dropped"
+                )
+            }
+            Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+        }
+    }
+}
+
+/// HMAC-SHA256 (RFC 2104), for signing requests and message bodies.
+#[cfg(feature = "grpc")]
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    let mut block = [0u8; 64];
+    if key.len() > block.len() {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let inner = Sha256::new()
+        .chain_update(block.map(|byte| byte ^ 0x36))
+        .chain_update(data)
+        .finalize();
+    Sha256::new()
+        .chain_update(block.map(|byte| byte ^ 0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+#[cfg(feature = "grpc")]
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Attributes brokers can route and filter on without parsing the body.
+#[cfg(feature = "cloud-sinks")]
+fn attributes(event: &RecordedEvent, content_type: &str) -> [(&'static str, String); 5] {
+    let timer = event.event.timer();
+    [
+        ("content_type", content_type.to_string()),
+        ("event_type", event.event.kind().to_string()),
+        ("tenant_id", timer.tenant_id.clone()),
+        ("timer_id", timer.id.to_string()),
+        ("sequence", event.sequence.to_string()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{HorologyKernel, SchedulerConfig, TimerSpec};
+
+    #[derive(Default)]
+    struct FlakySink {
+        attempts: Mutex<usize>,
+        /// Fail every send instead of only the first.
+        down: Mutex<bool>,
+        sent: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl EventSink for FlakySink {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn max_batch(&self) -> usize {
+            2
+        }
+
+        async fn send(&self, events: &[RecordedEvent]) -> Result<(), SinkError> {
+            let mut attempts = self.attempts.lock().unwrap();
+            *attempts += 1;
+            if *attempts == 1 || *self.down.lock().unwrap() {
+                return Err(SinkError::Transport("connection refused".into()));
+            }
+            let mut sent = self.sent.lock().unwrap();
+            sent.extend(events.iter().map(|event| event.sequence));
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "grpc")]
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_batches_are_retried_and_the_rest_drained_on_stop() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let sink = Arc::new(FlakySink::default());
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let forwarder = tokio::spawn({
+            let sink = sink.clone();
+            let events = kernel.subscribe();
+            async move {
+                let dead_letters = DeadLetterQueue::default();
+                forward(
+                    sink.as_ref(),
+                    events,
+                    RetryPolicy::default(),
+                    &dead_letters,
+                    async {
+                        let _ = stop_rx.await;
+                    },
+                )
+                .await
+            }
+        });
+        for _ in 0..3 {
+            kernel
+                .schedule(TimerSpec {
+                    tenant_id: "tenant-a".into(),
+                    requested_by: "agent-1".into(),
+                    duration_ms: 60_000,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+
+        tokio::time::sleep(RETRY_INITIAL * 2).await;
+        let _ = stop_tx.send(());
+        forwarder.await.unwrap();
+        assert_eq!(*sink.sent.lock().unwrap(), [1, 2, 3]);
+        assert!(*sink.attempts.lock().unwrap() >= 2);
+    }
+
+    #[tokio::test]
+    async fn exhausted_batches_are_dead_lettered_until_replayed() {
+        let kernel = HorologyKernel::new(SchedulerConfig::default());
+        let sink = Arc::new(FlakySink::default());
+        *sink.down.lock().unwrap() = true;
+        let dead_letters = DeadLetterQueue::default();
+        let retry = RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        };
+        let forwarder = tokio::spawn({
+            let sink = sink.clone();
+            let events = kernel.subscribe();
+            let dead_letters = dead_letters.clone();
+            async move {
+                forward(
+                    sink.as_ref(),
+                    events,
+                    retry,
+                    &dead_letters,
+                    std::future::pending(),
+                )
+                .await
+            }
+        });
+        kernel
+            .schedule(TimerSpec {
+                tenant_id: "tenant-a".into(),
+                requested_by: "agent-1".into(),
+                duration_ms: 60_000,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let parked = dead_letters.list();
+        assert_eq!(parked.len(), 1);
+        assert_eq!(parked[0].sink, "flaky");
+        assert_eq!(parked[0].attempts, 2);
+        assert!(sink.sent.lock().unwrap().is_empty());
+
+        *sink.down.lock().unwrap() = false;
+        assert_eq!(dead_letters.replay(Some("other"), usize::MAX), 0);
+        assert_eq!(dead_letters.replay(Some("flaky"), usize::MAX), 1);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(*sink.sent.lock().unwrap(), [1]);
+        let stats = dead_letters.stats();
+        assert_eq!((stats.parked, stats.replayed, stats.pending), (1, 1, 0));
+        forwarder.abort();
+    }
+}
@@ -0,0 +1,148 @@
+//! Google Cloud Pub/Sub sink, publishing through the REST API's `topics.publish`.
+//!
+//! Requests go out over plain HTTP: to the Pub/Sub emulator, or to a sidecar that originates TLS
+//! to `pubsub.googleapis.com`. Credentials are a bearer token read from a file before every
+//! publish, so whatever refreshes the token (e.g. a metadata-server sidecar) can rewrite it.
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hyper::{client::HttpConnector, Body, Client, Request};
+use serde_json::{json, Map, Value};
+
+use super::envelope::Encoder;
+use super::{attributes, EventSink, SinkError};
+use crate::RecordedEvent;
+
+/// Where `gcloud beta emulators pubsub start` listens by default.
+pub const EMULATOR_URL: &str = "http://127.0.0.1:8085";
+
+pub struct PubSubSink {
+    publish_url: String,
+    token_file: Option<PathBuf>,
+    encoder: Encoder,
+    client: Client<HttpConnector>,
+}
+
+impl PubSubSink {
+    /// Publishes to `topic`, a full `projects/<project>/topics/<topic>` name, through `base_url`.
+    pub fn new(base_url: &str, topic: &str, token_file: Option<PathBuf>) -> Self {
+        Self {
+            publish_url: format!("{}/v1/{topic}:publish", base_url.trim_end_matches('/')),
+            token_file,
+            encoder: Encoder::default(),
+            client: Client::new(),
+        }
+    }
+
+    /// Encodes messages with `encoder` instead of as plain JSON.
+    pub fn with_encoder(mut self, encoder: Encoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    fn body(&self, events: &[RecordedEvent]) -> Result<Value, SinkError> {
+        let messages = events
+            .iter()
+            .map(|event| {
+                let attributes: Map<String, Value> = attributes(event, self.encoder.content_type())
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), Value::String(value)))
+                    .collect();
+                Ok(json!({
+                    "data": STANDARD.encode(self.encoder.encode(event)?),
+                    "attributes": attributes,
+                }))
+            })
+            .collect::<Result<Vec<_>, SinkError>>()?;
+        Ok(json!({ "messages": messages }))
+    }
+}
+
+#[async_trait]
+impl EventSink for PubSubSink {
+    fn name(&self) -> &str {
+        "pubsub"
+    }
+
+    /// Pub/Sub accepts up to 1,000 messages per publish.
+    fn max_batch(&self) -> usize {
+        1_000
+    }
+
+    async fn send(&self, events: &[RecordedEvent]) -> Result<(), SinkError> {
+        let mut request =
+            Request::post(&self.publish_url).header("content-type", "application/json");
+        if let Some(path) = &self.token_file {
+            let token = std::fs::read_to_string(path).map_err(|error| {
+                SinkError::Transport(format!("reading {}: {error}", path.display()))
+            })?;
+            request = request.header("authorization", format!("Bearer {}", token.trim()));
+        }
+        let request = request
+            .body(Body::from(self.body(events)?.to_string()))
+            .map_err(|error| SinkError::Transport(error.to_string()))?;
+        let response = self.client.request(request).await?;
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await?;
+        if !status.is_success() {
+            return Err(SinkError::Status {
+                status: status.as_u16(),
+                body: String::from_utf8_lossy(&bytes).into_owned(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sinks::envelope::{open, SigningKey, SinkEncoding};
+    use crate::{TimerEvent, TimerInstance, TimerSpec};
+
+    #[test]
+    fn messages_carry_the_event_as_data_and_routing_attributes() {
+        let now = chrono::Utc::now();
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            ..Default::default()
+        };
+        let timer =
+            TimerInstance::from_spec(&spec, uuid::Uuid::new_v4(), now, now, Default::default());
+        let event = RecordedEvent {
+            sequence: 7,
+            recorded_at: now,
+            event: TimerEvent::Fired(timer.clone()),
+        };
+
+        let sink = PubSubSink::new(EMULATOR_URL, "projects/p/topics/t", None);
+        let body = sink.body(std::slice::from_ref(&event)).unwrap();
+        let message = &body["messages"][0];
+        assert_eq!(message["attributes"]["content_type"], "application/json");
+        assert_eq!(message["attributes"]["event_type"], "fired");
+        assert_eq!(message["attributes"]["tenant_id"], "tenant-a");
+        assert_eq!(message["attributes"]["timer_id"], timer.id.to_string());
+        assert_eq!(message["attributes"]["sequence"], "7");
+        let data = STANDARD.decode(message["data"].as_str().unwrap()).unwrap();
+        let decoded: RecordedEvent = serde_json::from_slice(&data).unwrap();
+        assert_eq!(decoded.sequence, 7);
+        assert_eq!(decoded.event.timer().id, timer.id);
+
+        let sink = sink.with_encoder(Encoder::new(
+            SinkEncoding::Protobuf,
+            Some(SigningKey::new("k1", b"secret".to_vec())),
+        ));
+        let body = sink.body(&[event]).unwrap();
+        let message = &body["messages"][0];
+        assert_eq!(
+            message["attributes"]["content_type"],
+            "application/x-protobuf"
+        );
+        let data = STANDARD.decode(message["data"].as_str().unwrap()).unwrap();
+        let opened = open(&data, Some(b"secret")).unwrap();
+        assert_eq!(opened.sequence, 7);
+        assert_eq!(opened.event.timer().id, timer.id);
+    }
+}
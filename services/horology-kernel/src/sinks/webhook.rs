@@ -0,0 +1,905 @@
+//! Per-tenant webhooks: each timer event is POSTed as JSON to every endpoint its tenant
+//! registered for it, without a broker in between. Webhooks an organization registers also
+//! receive the events of its projects.
+//!
+//! Every request carries `x-minoots-webhook-id`, `x-minoots-timestamp` (Unix seconds) and
+//! `x-minoots-signature: sha256=<hex>`, the HMAC-SHA256 of `<timestamp>.<body>` keyed with the
+//! secret returned at registration. Receivers should check both with [`verify_signature`], which
+//! also accepts the webhook actions the embedded orchestrator signs the same way. Delivery is at
+//! least once; `sequence` in the body identifies repeats.
+//!
+//! Webhooks may not reach loopback, private, link-local, or unspecified addresses unless
+//! [`WebhookRegistry::allow_private_destinations`] is set: the host is resolved and checked at
+//! registration and again on every connection, so a tenant cannot aim the kernel at the cloud
+//! metadata endpoint or at services inside its network.
+//!
+//! The kernel has no TLS client, so `https://` endpoints are refused; HTTPS receivers are reached
+//! through a TLS-originating egress proxy. Registrations live in memory unless the registry is
+//! attached to a file with [`WebhookRegistry::attach_file`], which holds the signing secrets and
+//! should be protected like the API token store.
+
+use std::{
+    collections::HashMap,
+    fs::OpenOptions,
+    io::Write,
+    net::{IpAddr, SocketAddr, ToSocketAddrs},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::persistence::StoreError;
+use crate::query::LabelPredicate;
+use crate::{EventFilter, TenantDirectory};
+
+const SECRET_PREFIX: &str = "whsec_";
+
+/// Most webhooks one tenant may register.
+pub const MAX_PER_TENANT: usize = 10;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WebhookError {
+    #[error("webhook url must be an absolute http:// url, got `{0}`")]
+    InvalidUrl(String),
+    #[error(
+        "https:// webhooks are not supported: the kernel has no TLS client; register the http:// \
+         address of a TLS-originating egress proxy instead, got `{0}`"
+    )]
+    HttpsUnsupported(String),
+    #[error("webhook host `{0}` does not resolve")]
+    Unresolvable(String),
+    #[error(
+        "webhook host `{host}` resolves to {address}, a loopback, private, link-local, or \
+         unspecified address"
+    )]
+    ForbiddenDestination { host: String, address: IpAddr },
+    #[error("tenant {tenant_id} already has the maximum of {max} webhooks")]
+    TooMany { tenant_id: String, max: usize },
+    #[error("failed to persist webhook: {0}")]
+    Persist(String),
+}
+
+/// A registered endpoint. Its secret is only returned once, by [`WebhookRegistry::register`].
+#[derive(Clone, Debug)]
+pub struct EventWebhook {
+    pub id: Uuid,
+    pub tenant_id: String,
+    pub url: String,
+    /// Events of the tenant, and of its projects for an organization, that are sent; the default
+    /// filter sends all of them.
+    pub filter: EventFilter,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct Registered {
+    webhooks: Vec<EventWebhook>,
+    secrets: HashMap<Uuid, String>,
+    /// JSON-lines file each registration is appended to.
+    file: Option<PathBuf>,
+}
+
+/// Line of a webhook file.
+#[derive(Serialize, Deserialize)]
+struct StoredWebhook {
+    id: Uuid,
+    tenant_id: String,
+    url: String,
+    topics: Vec<String>,
+    label_selectors: Vec<String>,
+    name_prefix: Option<String>,
+    description: Option<String>,
+    created_at: DateTime<Utc>,
+    secret: String,
+}
+
+impl StoredWebhook {
+    fn new(webhook: &EventWebhook, secret: &str) -> Self {
+        Self {
+            id: webhook.id,
+            tenant_id: webhook.tenant_id.clone(),
+            url: webhook.url.clone(),
+            topics: webhook
+                .filter
+                .kinds
+                .iter()
+                .map(|kind| kind.to_string())
+                .collect(),
+            label_selectors: webhook
+                .filter
+                .labels
+                .iter()
+                .map(|label| {
+                    let op = if label.negated { "!=" } else { "=" };
+                    format!("{}{op}{}", label.key, label.value)
+                })
+                .collect(),
+            name_prefix: webhook.filter.name_prefix.clone(),
+            description: webhook.description.clone(),
+            created_at: webhook.created_at,
+            secret: secret.to_string(),
+        }
+    }
+
+    fn into_webhook(self) -> Result<(EventWebhook, String), String> {
+        let mut filter = EventFilter {
+            name_prefix: self.name_prefix,
+            ..Default::default()
+        };
+        for topic in &self.topics {
+            filter.add_topic(topic).map_err(|error| error.to_string())?;
+        }
+        for selector in &self.label_selectors {
+            let label = LabelPredicate::from_str(selector).map_err(|error| error.to_string())?;
+            filter.labels.push(label);
+        }
+        let webhook = EventWebhook {
+            id: self.id,
+            tenant_id: self.tenant_id,
+            url: self.url,
+            filter,
+            description: self.description,
+            created_at: self.created_at,
+        };
+        Ok((webhook, self.secret))
+    }
+}
+
+/// Webhooks registered through the API, shared by the kernel and the webhook sink.
+#[derive(Clone, Default)]
+pub struct WebhookRegistry {
+    registered: Arc<Mutex<Registered>>,
+    allow_private: Arc<AtomicBool>,
+    /// Resolves the organization whose webhooks also receive a project's events.
+    tenants: Arc<Mutex<TenantDirectory>>,
+}
+
+impl WebhookRegistry {
+    /// Registry that sends a project's events to its organization's webhooks as well, looking
+    /// the organization up in `tenants`.
+    pub fn with_tenants(tenants: Arc<Mutex<TenantDirectory>>) -> Self {
+        Self {
+            tenants,
+            ..Default::default()
+        }
+    }
+
+    /// Loads the webhooks in the JSON-lines file at `path` and appends every later registration
+    /// to it, returning how many were loaded. A missing file means no webhooks.
+    pub fn attach_file(&self, path: impl AsRef<Path>) -> Result<usize, StoreError> {
+        let path = path.as_ref().to_path_buf();
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error.into()),
+        };
+        let mut registered = self.lock();
+        let mut loaded = 0;
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let stored: StoredWebhook = serde_json::from_str(line)?;
+            let (webhook, secret) = stored.into_webhook().map_err(|error| {
+                StoreError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+            })?;
+            if registered.secrets.contains_key(&webhook.id) {
+                continue;
+            }
+            registered.secrets.insert(webhook.id, secret);
+            registered.webhooks.push(webhook);
+            loaded += 1;
+        }
+        registered.file = Some(path);
+        Ok(loaded)
+    }
+
+    /// Organization whose webhooks also receive the tenant's events.
+    pub fn organization_of(&self, tenant_id: &str) -> Option<String> {
+        self.tenants
+            .lock()
+            .expect("tenant directory poisoned")
+            .organization_of(tenant_id)
+            .map(str::to_string)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Registered> {
+        self.registered.lock().expect("webhook registry poisoned")
+    }
+
+    /// Lets webhooks reach loopback, private, and link-local addresses, for deployments whose
+    /// receivers run inside the kernel's own network.
+    pub fn allow_private_destinations(&self, allow: bool) {
+        self.allow_private.store(allow, Ordering::Relaxed);
+    }
+
+    pub fn allows_private_destinations(&self) -> bool {
+        self.allow_private.load(Ordering::Relaxed)
+    }
+
+    /// Registers `url` for `tenant_id`'s events, returning the webhook and its signing secret.
+    pub async fn register(
+        &self,
+        tenant_id: &str,
+        url: &str,
+        filter: EventFilter,
+        description: Option<String>,
+    ) -> Result<(EventWebhook, String), WebhookError> {
+        if url.starts_with("https://") {
+            return Err(WebhookError::HttpsUnsupported(url.to_string()));
+        }
+        let (host, port) =
+            destination(url).ok_or_else(|| WebhookError::InvalidUrl(url.to_string()))?;
+        self.resolve(&host, port).await?;
+        let mut registered = self.lock();
+        let existing = registered
+            .webhooks
+            .iter()
+            .filter(|webhook| webhook.tenant_id == tenant_id)
+            .count();
+        if existing >= MAX_PER_TENANT {
+            return Err(WebhookError::TooMany {
+                tenant_id: tenant_id.to_string(),
+                max: MAX_PER_TENANT,
+            });
+        }
+        let webhook = EventWebhook {
+            id: Uuid::new_v4(),
+            tenant_id: tenant_id.to_string(),
+            url: url.to_string(),
+            filter,
+            description,
+            created_at: Utc::now(),
+        };
+        let secret = format!(
+            "{SECRET_PREFIX}{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        );
+        if let Some(path) = &registered.file {
+            append(path, &StoredWebhook::new(&webhook, &secret))
+                .map_err(|error| WebhookError::Persist(error.to_string()))?;
+        }
+        registered.secrets.insert(webhook.id, secret.clone());
+        registered.webhooks.push(webhook.clone());
+        Ok((webhook, secret))
+    }
+
+    /// Webhooks of `tenant_id`, oldest first.
+    pub fn list(&self, tenant_id: &str) -> Vec<EventWebhook> {
+        self.lock()
+            .webhooks
+            .iter()
+            .filter(|webhook| webhook.tenant_id == tenant_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Addresses `host` resolves to, failing if any of them is off limits.
+    pub async fn resolve(&self, host: &str, port: u16) -> Result<Vec<SocketAddr>, WebhookError> {
+        let unresolvable = || WebhookError::Unresolvable(host.to_string());
+        let addresses: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+            Ok(ip) => vec![SocketAddr::new(ip, port)],
+            Err(_) => {
+                let name = host.to_string();
+                tokio::task::spawn_blocking(move || (name.as_str(), port).to_socket_addrs())
+                    .await
+                    .map_err(|_| unresolvable())?
+                    .map_err(|_| unresolvable())?
+                    .collect()
+            }
+        };
+        if addresses.is_empty() {
+            return Err(unresolvable());
+        }
+        if !self.allows_private_destinations() {
+            if let Some(address) = addresses.iter().find(|address| is_internal(address.ip())) {
+                return Err(WebhookError::ForbiddenDestination {
+                    host: host.to_string(),
+                    address: address.ip(),
+                });
+            }
+        }
+        Ok(addresses)
+    }
+
+    /// Webhooks `event` goes to, with their secrets.
+    #[cfg(any(feature = "grpc", test))]
+    fn targets(&self, event: &crate::TimerEvent) -> Vec<(EventWebhook, String)> {
+        let tenant_id = &event.timer().tenant_id;
+        let organization_id = self.organization_of(tenant_id);
+        let registered = self.lock();
+        registered
+            .webhooks
+            .iter()
+            .filter(|webhook| {
+                (&webhook.tenant_id == tenant_id
+                    || organization_id.as_ref() == Some(&webhook.tenant_id))
+                    && webhook.filter.matches(event)
+            })
+            .map(|webhook| (webhook.clone(), registered.secrets[&webhook.id].clone()))
+            .collect()
+    }
+}
+
+fn append(path: &Path, stored: &StoredWebhook) -> Result<(), StoreError> {
+    let mut line = serde_json::to_vec(stored)?;
+    line.push(b'\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&line)?;
+    file.sync_data()?;
+    Ok(())
+}
+
+/// Host and port of an absolute `http://` URL without credentials.
+fn destination(url: &str) -> Option<(String, u16)> {
+    let authority = url.strip_prefix("http://")?.split(['/', '?', '#']).next()?;
+    if authority.contains('@') {
+        return None;
+    }
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(bracketed) => {
+            let (host, rest) = bracketed.split_once(']')?;
+            match rest {
+                "" => (host, None),
+                rest => (host, Some(rest.strip_prefix(':')?)),
+            }
+        }
+        None => match authority.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+    if host.is_empty() {
+        return None;
+    }
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => 80,
+    };
+    Some((host.to_string(), port))
+}
+
+/// Whether `ip` is loopback, private, link-local, unspecified, or otherwise not a public
+/// destination.
+fn is_internal(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // Carrier-grade NAT, 100.64.0.0/10.
+                || (a == 100 && b & 0xc0 == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_internal(IpAddr::V4(mapped)),
+            None => {
+                let first = ip.segments()[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    // Unique local, fc00::/7.
+                    || first & 0xfe00 == 0xfc00
+                    // Link-local, fe80::/10.
+                    || first & 0xffc0 == 0xfe80
+            }
+        },
+    }
+}
+
+/// How far a signed request's timestamp may be from the receiver's clock before
+/// [`verify_signature`] rejects it as a replay.
+pub const SIGNATURE_TOLERANCE: std::time::Duration = std::time::Duration::from_secs(300);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("signature headers are missing or malformed")]
+    Malformed,
+    #[error("request was signed {age_secs}s away from now, outside the tolerance")]
+    Stale { age_secs: i64 },
+    #[error("signature does not match the body")]
+    Mismatch,
+}
+
+/// `x-minoots-signature` value for `body` sent at `timestamp` (Unix seconds).
+#[cfg(feature = "grpc")]
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    use crate::sinks::{hex, hmac_sha256};
+
+    let mut signed = format!("{timestamp}.").into_bytes();
+    signed.extend_from_slice(body);
+    format!("sha256={}", hex(&hmac_sha256(secret.as_bytes(), &signed)))
+}
+
+/// Checks the `x-minoots-timestamp` and `x-minoots-signature` headers of a request against its
+/// raw `body`, rejecting timestamps more than `tolerance` away from `now`.
+#[cfg(feature = "grpc")]
+pub fn verify_signature(
+    secret: &str,
+    timestamp: &str,
+    signature: &str,
+    body: &[u8],
+    now: DateTime<Utc>,
+    tolerance: std::time::Duration,
+) -> Result<(), SignatureError> {
+    let timestamp: i64 = timestamp.parse().map_err(|_| SignatureError::Malformed)?;
+    if !signature.starts_with("sha256=") {
+        return Err(SignatureError::Malformed);
+    }
+    let age_secs = now.timestamp() - timestamp;
+    if age_secs.unsigned_abs() > tolerance.as_secs() {
+        return Err(SignatureError::Stale { age_secs });
+    }
+    let expected = sign(secret, timestamp, body);
+    // Compared in full so the time taken does not reveal how much of a forgery matched.
+    let differences = expected
+        .bytes()
+        .zip(signature.bytes())
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+    if differences != 0 || expected.len() != signature.len() {
+        return Err(SignatureError::Mismatch);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "embedded-orchestrator")]
+pub(crate) use sink::GuardedResolver;
+#[cfg(feature = "grpc")]
+pub use sink::WebhookSink;
+
+#[cfg(feature = "grpc")]
+mod sink {
+    use std::{
+        collections::HashSet,
+        future::Future,
+        io,
+        net::SocketAddr,
+        pin::Pin,
+        sync::Mutex,
+        task::{Context, Poll},
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use hyper::{
+        client::{connect::dns::Name, HttpConnector},
+        Body, Client, Request,
+    };
+    use uuid::Uuid;
+
+    use super::{destination, sign, WebhookError, WebhookRegistry};
+    use crate::sinks::{EventSink, SinkError};
+    use crate::{DeliveryLedger, RecordedEvent, TimerEvent};
+
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Resolves webhook hosts through [`WebhookRegistry::resolve`], so a name that pointed at a
+    /// public address at registration cannot be switched to an internal one later.
+    #[derive(Clone)]
+    pub(crate) struct GuardedResolver(WebhookRegistry);
+
+    impl WebhookRegistry {
+        /// Connector that refuses host names resolving to addresses the registry forbids. Pair it
+        /// with [`WebhookRegistry::check_destination`], as it never sees addresses in the URL.
+        pub(crate) fn guarded_connector(&self) -> HttpConnector<GuardedResolver> {
+            HttpConnector::new_with_resolver(GuardedResolver(self.clone()))
+        }
+
+        /// Checks a destination URL whose host is an address rather than a name; the
+        /// [`guarded_connector`](Self::guarded_connector) checks names when it connects.
+        pub(crate) async fn check_destination(&self, url: &str) -> Result<(), WebhookError> {
+            let (host, port) =
+                destination(url).ok_or_else(|| WebhookError::InvalidUrl(url.to_string()))?;
+            if host.parse::<std::net::IpAddr>().is_ok() {
+                self.resolve(&host, port).await?;
+            }
+            Ok(())
+        }
+    }
+
+    impl tower_service::Service<Name> for GuardedResolver {
+        type Response = std::vec::IntoIter<SocketAddr>;
+        type Error = io::Error;
+        type Future = Pin<Box<dyn Future<Output = io::Result<Self::Response>> + Send>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, name: Name) -> Self::Future {
+            let registry = self.0.clone();
+            Box::pin(async move {
+                // The connector sets the port of the URL on the returned addresses.
+                registry
+                    .resolve(name.as_str(), 0)
+                    .await
+                    .map(Vec::into_iter)
+                    .map_err(|error| io::Error::new(io::ErrorKind::PermissionDenied, error))
+            })
+        }
+    }
+
+    /// Sends events to the webhooks in a [`WebhookRegistry`]. A batch is retried until every
+    /// endpoint accepted its events, but endpoints that already did are not sent them again.
+    pub struct WebhookSink {
+        registry: WebhookRegistry,
+        client: Client<HttpConnector<GuardedResolver>>,
+        /// `(webhook, sequence)` pairs of the batch being retried that were already delivered.
+        delivered: Mutex<HashSet<(Uuid, u64)>>,
+        /// Receives a `webhook:<id>` receipt for each fired event a webhook accepted.
+        ledger: Option<DeliveryLedger>,
+    }
+
+    impl WebhookSink {
+        pub fn new(registry: WebhookRegistry) -> Self {
+            Self {
+                client: Client::builder().build(registry.guarded_connector()),
+                registry,
+                delivered: Mutex::default(),
+                ledger: None,
+            }
+        }
+
+        /// Records accepted fired events in `ledger`, so webhook receivers can acknowledge them.
+        pub fn with_ledger(mut self, ledger: DeliveryLedger) -> Self {
+            self.ledger = Some(ledger);
+            self
+        }
+
+        async fn post(&self, url: &str, request: Request<Body>) -> Result<(), SinkError> {
+            // The connector only resolves names; addresses in the URL are checked here.
+            self.registry
+                .check_destination(url)
+                .await
+                .map_err(|error| match error {
+                    WebhookError::InvalidUrl(_) => {
+                        SinkError::Transport(format!("{url} is not an http:// url"))
+                    }
+                    error => SinkError::Transport(error.to_string()),
+                })?;
+            let response = tokio::time::timeout(REQUEST_TIMEOUT, self.client.request(request))
+                .await
+                .map_err(|_| SinkError::Transport(format!("{url} timed out")))??;
+            let status = response.status();
+            if !status.is_success() {
+                let bytes = hyper::body::to_bytes(response.into_body()).await?;
+                return Err(SinkError::Status {
+                    status: status.as_u16(),
+                    body: String::from_utf8_lossy(&bytes).into_owned(),
+                });
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl EventSink for WebhookSink {
+        fn name(&self) -> &str {
+            "webhook"
+        }
+
+        async fn send(&self, events: &[RecordedEvent]) -> Result<(), SinkError> {
+            let mut first_error = None;
+            for event in events {
+                for (webhook, secret) in self.registry.targets(&event.event) {
+                    let key = (webhook.id, event.sequence);
+                    if self
+                        .delivered
+                        .lock()
+                        .expect("webhook deliveries poisoned")
+                        .contains(&key)
+                    {
+                        continue;
+                    }
+                    let body = serde_json::to_vec(event)?;
+                    let timestamp = chrono::Utc::now().timestamp();
+                    let request = Request::post(&webhook.url)
+                        .header("content-type", "application/json")
+                        .header("x-minoots-webhook-id", webhook.id.to_string())
+                        .header("x-minoots-timestamp", timestamp.to_string())
+                        .header("x-minoots-signature", sign(&secret, timestamp, &body))
+                        .body(Body::from(body))
+                        .map_err(|error| SinkError::Transport(error.to_string()))?;
+                    match self.post(&webhook.url, request).await {
+                        Ok(()) => {
+                            self.delivered
+                                .lock()
+                                .expect("webhook deliveries poisoned")
+                                .insert(key);
+                            if let (Some(ledger), TimerEvent::Fired(timer)) =
+                                (&self.ledger, &event.event)
+                            {
+                                let subscriber_id = format!("webhook:{}", webhook.id);
+                                ledger.record(timer.id, &subscriber_id, event.sequence);
+                            }
+                        }
+                        Err(error) => {
+                            first_error.get_or_insert(error);
+                        }
+                    }
+                }
+            }
+            match first_error {
+                Some(error) => Err(error),
+                None => {
+                    self.delivered
+                        .lock()
+                        .expect("webhook deliveries poisoned")
+                        .clear();
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TimerEvent, TimerInstance, TimerSpec};
+
+    #[tokio::test]
+    async fn events_go_to_matching_webhooks_of_their_tenant() {
+        let registry = WebhookRegistry::default();
+        assert_eq!(
+            registry
+                .register(
+                    "tenant-a",
+                    "https://example.com",
+                    EventFilter::default(),
+                    None
+                )
+                .await
+                .unwrap_err(),
+            WebhookError::HttpsUnsupported("https://example.com".into())
+        );
+        let (all, secret) = registry
+            .register(
+                "tenant-a",
+                "http://192.0.2.10/hook",
+                EventFilter::default(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(secret.starts_with(SECRET_PREFIX));
+        let mut fired_only = EventFilter::default();
+        fired_only.add_topic("timer.fired").unwrap();
+        registry
+            .register("tenant-a", "http://192.0.2.10/fired", fired_only, None)
+            .await
+            .unwrap();
+        registry
+            .register(
+                "tenant-b",
+                "http://198.51.100.7/hook",
+                EventFilter::default(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let now = Utc::now();
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            ..Default::default()
+        };
+        let timer = TimerInstance::from_spec(&spec, Uuid::new_v4(), now, now, Default::default());
+        let targets = registry.targets(&TimerEvent::Scheduled(timer.clone()));
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].0.id, all.id);
+        assert_eq!(targets[0].1, secret);
+        assert_eq!(registry.targets(&TimerEvent::Fired(timer)).len(), 2);
+        assert_eq!(registry.list("tenant-a").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn organization_webhooks_receive_project_events() {
+        let tenants = Arc::new(Mutex::new(TenantDirectory::default()));
+        let registry = WebhookRegistry::with_tenants(tenants.clone());
+        let (org, _) = registry
+            .register(
+                "acme",
+                "http://192.0.2.10/org",
+                EventFilter::default(),
+                None,
+            )
+            .await
+            .unwrap();
+        let now = Utc::now();
+        let spec = TimerSpec {
+            tenant_id: "acme-web".into(),
+            ..Default::default()
+        };
+        let timer = TimerInstance::from_spec(&spec, Uuid::new_v4(), now, now, Default::default());
+        assert!(registry
+            .targets(&TimerEvent::Fired(timer.clone()))
+            .is_empty());
+
+        tenants.lock().unwrap().register_project("acme", "acme-web");
+        let targets = registry.targets(&TimerEvent::Fired(timer));
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].0.id, org.id);
+    }
+
+    #[tokio::test]
+    async fn registrations_survive_a_restart_through_the_file() {
+        let path = std::env::temp_dir().join(format!("minoots-webhooks-{}.jsonl", Uuid::new_v4()));
+        let registry = WebhookRegistry::default();
+        assert_eq!(registry.attach_file(&path).unwrap(), 0);
+        let mut filter = EventFilter::default();
+        filter.add_topic("fired").unwrap();
+        filter.labels.push("env!=dev".parse().unwrap());
+        filter.name_prefix = Some("billing-".into());
+        let (webhook, secret) = registry
+            .register(
+                "tenant-a",
+                "http://192.0.2.10/hook",
+                filter,
+                Some("billing".into()),
+            )
+            .await
+            .unwrap();
+
+        let restarted = WebhookRegistry::default();
+        assert_eq!(restarted.attach_file(&path).unwrap(), 1);
+        let listed = restarted.list("tenant-a");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, webhook.id);
+        assert_eq!(listed[0].filter, webhook.filter);
+        assert_eq!(listed[0].description.as_deref(), Some("billing"));
+        assert_eq!(restarted.lock().secrets[&webhook.id], secret);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn internal_destinations_are_refused_unless_allowed() {
+        let registry = WebhookRegistry::default();
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://localhost/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://10.1.2.3/hook",
+            "http://[::1]/hook",
+            "http://[::ffff:192.168.0.1]/hook",
+            "http://[fd00::1]/hook",
+            "http://0.0.0.0/hook",
+        ] {
+            assert!(
+                matches!(
+                    registry
+                        .register("tenant-a", url, EventFilter::default(), None)
+                        .await,
+                    Err(WebhookError::ForbiddenDestination { .. })
+                ),
+                "{url}"
+            );
+        }
+        for url in [
+            "http://user:pw@192.0.2.1/hook",
+            "http://[::1/hook",
+            "http://:80/",
+        ] {
+            assert_eq!(
+                registry
+                    .register("tenant-a", url, EventFilter::default(), None)
+                    .await
+                    .unwrap_err(),
+                WebhookError::InvalidUrl(url.into())
+            );
+        }
+        assert!(registry.list("tenant-a").is_empty());
+
+        registry.allow_private_destinations(true);
+        registry
+            .register(
+                "tenant-a",
+                "http://10.1.2.3:8080/hook",
+                EventFilter::default(),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[cfg(feature = "grpc")]
+    #[tokio::test]
+    async fn posts_events_signed_with_the_webhook_secret() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        use crate::sinks::{hex, hmac_sha256, EventSink, SinkError};
+        use crate::RecordedEvent;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 8192];
+            let read = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request[..read]).to_string()
+        });
+        let registry = WebhookRegistry::default();
+        registry.allow_private_destinations(true);
+        let (webhook, secret) = registry
+            .register("tenant-a", &url, EventFilter::default(), None)
+            .await
+            .unwrap();
+
+        let now = Utc::now();
+        let spec = TimerSpec {
+            tenant_id: "tenant-a".into(),
+            ..Default::default()
+        };
+        let timer = TimerInstance::from_spec(&spec, Uuid::new_v4(), now, now, Default::default());
+        let event = RecordedEvent {
+            sequence: 3,
+            recorded_at: now,
+            event: TimerEvent::Fired(timer),
+        };
+        WebhookSink::new(registry.clone())
+            .send(&[event])
+            .await
+            .unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /hook"));
+        assert!(request.contains(&format!("x-minoots-webhook-id: {}", webhook.id)));
+        let header = |name: &str| {
+            request
+                .lines()
+                .find_map(|line| line.strip_prefix(&format!("{name}: ")))
+                .unwrap()
+                .to_string()
+        };
+        let (_, body) = request.split_once("\r\n\r\n").unwrap();
+        let signed = format!("{}.{body}", header("x-minoots-timestamp"));
+        assert_eq!(
+            header("x-minoots-signature"),
+            format!(
+                "sha256={}",
+                hex(&hmac_sha256(secret.as_bytes(), signed.as_bytes()))
+            )
+        );
+        let verify = |body: &str, now| {
+            verify_signature(
+                &secret,
+                &header("x-minoots-timestamp"),
+                &header("x-minoots-signature"),
+                body.as_bytes(),
+                now,
+                SIGNATURE_TOLERANCE,
+            )
+        };
+        assert_eq!(verify(body, Utc::now()), Ok(()));
+        assert_eq!(verify("{}", Utc::now()), Err(SignatureError::Mismatch));
+        assert!(matches!(
+            verify(body, Utc::now() + chrono::Duration::minutes(10)),
+            Err(SignatureError::Stale { .. })
+        ));
+        let delivered: RecordedEvent = serde_json::from_str(body).unwrap();
+        assert_eq!(delivered.sequence, 3);
+
+        // Checked again before each request, not just at registration.
+        registry.allow_private_destinations(false);
+        let event = RecordedEvent {
+            sequence: 4,
+            ..delivered
+        };
+        assert!(matches!(
+            WebhookSink::new(registry).send(&[event]).await,
+            Err(SinkError::Transport(error)) if error.contains("127.0.0.1")
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// How a fired timer's latency compared with its `accuracy_budget_ms`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetOutcome {
+    WithinBudget,
+    OverBudget,
+}
+
+impl BudgetOutcome {
+    pub fn evaluate(latency_ms: u64, budget_ms: u64) -> Self {
+        if latency_ms <= budget_ms {
+            BudgetOutcome::WithinBudget
+        } else {
+            BudgetOutcome::OverBudget
+        }
+    }
+}
+
+/// Accuracy-budget results aggregated for one tenant.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetStats {
+    /// Fires that carried a budget.
+    pub evaluated: u64,
+    pub violations: u64,
+    pub max_overrun_ms: u64,
+}
+
+impl BudgetStats {
+    pub fn violation_rate(&self) -> f64 {
+        if self.evaluated == 0 {
+            0.0
+        } else {
+            self.violations as f64 / self.evaluated as f64
+        }
+    }
+}
+
+/// Per-tenant accuracy-budget aggregation for SLO reporting.
+#[derive(Clone, Default)]
+pub struct BudgetTracker {
+    stats: Arc<Mutex<HashMap<String, BudgetStats>>>,
+}
+
+impl BudgetTracker {
+    pub fn record(&self, tenant_id: &str, latency_ms: u64, budget_ms: u64) -> BudgetOutcome {
+        let outcome = BudgetOutcome::evaluate(latency_ms, budget_ms);
+        let mut stats = self.stats.lock().expect("budget tracker poisoned");
+        let entry = stats.entry(tenant_id.to_string()).or_default();
+        entry.evaluated += 1;
+        if outcome == BudgetOutcome::OverBudget {
+            entry.violations += 1;
+            entry.max_overrun_ms = entry.max_overrun_ms.max(latency_ms - budget_ms);
+        }
+        outcome
+    }
+
+    pub fn stats(&self, tenant_id: &str) -> BudgetStats {
+        self.stats
+            .lock()
+            .expect("budget tracker poisoned")
+            .get(tenant_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregates_violation_rate_per_tenant() {
+        let tracker = BudgetTracker::default();
+        assert_eq!(
+            tracker.record("tenant-a", 3, 5),
+            BudgetOutcome::WithinBudget
+        );
+        assert_eq!(tracker.record("tenant-a", 12, 5), BudgetOutcome::OverBudget);
+
+        let stats = tracker.stats("tenant-a");
+        assert_eq!(stats.evaluated, 2);
+        assert_eq!(stats.violations, 1);
+        assert_eq!(stats.max_overrun_ms, 7);
+        assert!((stats.violation_rate() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(tracker.stats("tenant-b").violation_rate(), 0.0);
+    }
+}
@@ -0,0 +1,191 @@
+use std::time::Duration;
+
+use thiserror::Error;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Extensions, Request, Response, Status};
+use tracing::{info, warn};
+
+use crate::grpc::{event_from_proto, timer_from_proto, ALL_TENANTS};
+use crate::pb::horology_kernel_client::HorologyKernelClient;
+use crate::pb::{
+    self, TimerCancelRequest, TimerEventStreamRequest, TimerGetRequest, TimerListRequest,
+    TimerScheduleRequest,
+};
+use crate::{HorologyKernel, TimerEvent};
+
+#[derive(Debug, Error)]
+pub enum FollowError {
+    #[error("failed to connect to primary: {0}")]
+    Connect(#[from] tonic::transport::Error),
+    #[error("primary rejected replication: {0}")]
+    Rpc(#[from] Status),
+}
+
+/// Keeps a warm standby hydrated from an active kernel's all-tenant event stream until the standby
+/// is promoted. Needs the primary's operator token when the primary enforces authentication.
+pub struct StandbyFollower {
+    primary: String,
+    token: Option<String>,
+    retry_interval: Duration,
+    snapshot_page_size: u32,
+}
+
+impl StandbyFollower {
+    pub fn new(primary: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            primary: primary.into(),
+            token,
+            retry_interval: Duration::from_secs(1),
+            snapshot_page_size: 500,
+        }
+    }
+
+    /// Timers per `ListTimers` page while hydrating (default 500). Pages keep every response well
+    /// under gRPC's message size limit and let the standby apply timers as they arrive.
+    pub fn snapshot_page_size(mut self, page_size: u32) -> Self {
+        self.snapshot_page_size = page_size.max(1);
+        self
+    }
+
+    /// Replicates until promotion, reconnecting after stream failures.
+    pub async fn run(&self, kernel: HorologyKernel) {
+        while !kernel.is_active() {
+            tokio::select! {
+                result = self.follow(&kernel) => match result {
+                    Ok(()) => warn!(primary = %self.primary, "primary event stream ended"),
+                    Err(error) => warn!(primary = %self.primary, %error, "standby replication failed"),
+                },
+                _ = kernel.until_active() => break,
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(self.retry_interval) => {}
+                _ = kernel.until_active() => break,
+            }
+        }
+        info!(primary = %self.primary, "standby promoted; replication stopped");
+    }
+
+    async fn follow(&self, kernel: &HorologyKernel) -> Result<(), FollowError> {
+        let channel = primary_endpoint(self.primary.clone())?.connect().await?;
+        let mut client = HorologyKernelClient::new(channel);
+        // Subscribe before taking the snapshot so nothing scheduled in between is missed; applying
+        // is idempotent, so overlap between the two is harmless.
+        let mut events = client
+            .stream_timer_events(self.request(TimerEventStreamRequest {
+                tenant_id: ALL_TENANTS.into(),
+                ..Default::default()
+            }))
+            .await?
+            .into_inner();
+        // The primary pins the listing on the first page, so the walk is one consistent snapshot
+        // however long it takes.
+        let mut hydrated = 0;
+        let mut page_token = String::new();
+        loop {
+            let page = client
+                .list_timers(self.request(TimerListRequest {
+                    tenant_id: ALL_TENANTS.into(),
+                    page_size: self.snapshot_page_size,
+                    page_token,
+                    ..Default::default()
+                }))
+                .await?
+                .into_inner();
+            hydrated += page.timers.len();
+            for timer in page.timers {
+                kernel
+                    .apply(TimerEvent::Scheduled(timer_from_proto(timer)?))
+                    .await;
+            }
+            if page.next_page_token.is_empty() {
+                break;
+            }
+            page_token = page.next_page_token;
+        }
+        info!(primary = %self.primary, hydrated, "standby hydrated from primary");
+
+        while let Some(event) = events.message().await? {
+            if let Some(event) = event_from_proto(event)? {
+                kernel.apply(event).await;
+            }
+        }
+        Ok(())
+    }
+
+    fn request<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        if let Some(value) = self
+            .token
+            .as_ref()
+            .and_then(|token| format!("Bearer {token}").parse().ok())
+        {
+            request.metadata_mut().insert("authorization", value);
+        }
+        request
+    }
+}
+
+/// Sends the writes a standby cannot take, and the reads that must not be stale, to the primary it
+/// follows and returns the primary's answer, so clients and load balancers need not know which
+/// kernel is active. The caller's
+/// metadata, credentials and deadline included, goes along unchanged, so the primary authorizes
+/// the write exactly as it would a direct call. The connection is opened on first use and reused.
+#[derive(Clone)]
+pub struct PrimaryForwarder {
+    client: HorologyKernelClient<Channel>,
+}
+
+impl PrimaryForwarder {
+    pub fn new(primary: impl Into<String>) -> Result<Self, tonic::transport::Error> {
+        let channel = primary_endpoint(primary.into())?.connect_lazy();
+        Ok(Self {
+            client: HorologyKernelClient::new(channel),
+        })
+    }
+
+    pub async fn schedule_timer(
+        &self,
+        request: Request<TimerScheduleRequest>,
+    ) -> Result<Response<pb::TimerScheduleResponse>, Status> {
+        self.client.clone().schedule_timer(forwarded(request)).await
+    }
+
+    pub async fn cancel_timer(
+        &self,
+        request: Request<TimerCancelRequest>,
+    ) -> Result<Response<pb::Timer>, Status> {
+        self.client.clone().cancel_timer(forwarded(request)).await
+    }
+
+    pub async fn get_timer(
+        &self,
+        request: Request<TimerGetRequest>,
+    ) -> Result<Response<pb::Timer>, Status> {
+        self.client.clone().get_timer(forwarded(request)).await
+    }
+
+    pub async fn list_timers(
+        &self,
+        request: Request<TimerListRequest>,
+    ) -> Result<Response<pb::TimerListResponse>, Status> {
+        self.client.clone().list_timers(forwarded(request)).await
+    }
+}
+
+/// Connection settings for links to the primary. HTTP/2 keepalive pings detect a primary that
+/// vanished without closing the connection (a partition, a killed node), which would otherwise
+/// leave the follower waiting on a silent event stream and forwarded calls hanging.
+fn primary_endpoint(primary: String) -> Result<Endpoint, tonic::transport::Error> {
+    Ok(Endpoint::from_shared(primary)?
+        .connect_timeout(Duration::from_secs(5))
+        .http2_keep_alive_interval(Duration::from_secs(10))
+        .keep_alive_timeout(Duration::from_secs(5))
+        .keep_alive_while_idle(true)
+        .tcp_nodelay(true))
+}
+
+/// The caller's request without the extensions this kernel attached, such as its [`crate::Principal`].
+fn forwarded<T>(request: Request<T>) -> Request<T> {
+    let (metadata, _, message) = request.into_parts();
+    Request::from_parts(metadata, Extensions::default(), message)
+}
@@ -0,0 +1,340 @@
+//! Per-subscriber limits on timer event streams. Each stream gets a [`StreamGovernor`] that caps
+//! its event rate, bandwidth, and event size; events over a cap are dropped and the subscriber is
+//! told so through in-stream throttle notices rather than by the stream silently falling behind.
+//! [`StreamMetrics`] keeps throughput per principal and subscriber for operators.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Stream caps; an unset cap is unlimited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamLimits {
+    #[serde(default)]
+    pub max_events_per_second: Option<u64>,
+    /// Encoded event bytes per second.
+    #[serde(default)]
+    pub max_bytes_per_second: Option<u64>,
+    /// Largest single encoded event.
+    #[serde(default)]
+    pub max_event_bytes: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleReason {
+    EventRate,
+    Bandwidth,
+    EventSize,
+    /// The subscriber's buffer overflowed before the stream could send the events.
+    Lagged,
+}
+
+impl ThrottleReason {
+    pub fn name(self) -> &'static str {
+        match self {
+            ThrottleReason::EventRate => "event_rate",
+            ThrottleReason::Bandwidth => "bandwidth",
+            ThrottleReason::EventSize => "event_size",
+            ThrottleReason::Lagged => "lagged",
+        }
+    }
+}
+
+/// Tells a subscriber that events were dropped; `reason` is the cap that dropped the latest one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThrottleNotice {
+    pub reason: ThrottleReason,
+    pub dropped_events: u64,
+    pub dropped_bytes: u64,
+}
+
+/// What to do with one event; either way `notice`, when set, is sent to the subscriber first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Admission {
+    Deliver { notice: Option<ThrottleNotice> },
+    Drop { notice: Option<ThrottleNotice> },
+}
+
+/// Applies [`StreamLimits`] to one stream over one-second windows. The first dropped event of a
+/// throttling episode is replaced by a notice; drops after it are summarised in a notice sent
+/// ahead of the next delivered event.
+pub struct StreamGovernor {
+    limits: StreamLimits,
+    window_start: Instant,
+    window_events: u64,
+    window_bytes: u64,
+    throttled: bool,
+    unreported: Option<ThrottleNotice>,
+}
+
+impl StreamGovernor {
+    pub fn new(limits: StreamLimits) -> Self {
+        Self {
+            limits,
+            window_start: Instant::now(),
+            window_events: 0,
+            window_bytes: 0,
+            throttled: false,
+            unreported: None,
+        }
+    }
+
+    pub fn admit(&mut self, bytes: u64) -> Admission {
+        self.admit_at(bytes, Instant::now())
+    }
+
+    fn admit_at(&mut self, bytes: u64, now: Instant) -> Admission {
+        if now.duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.window_events = 0;
+            self.window_bytes = 0;
+        }
+        let reason = if self.limits.max_event_bytes.is_some_and(|max| bytes > max) {
+            Some(ThrottleReason::EventSize)
+        } else if self
+            .limits
+            .max_events_per_second
+            .is_some_and(|max| self.window_events >= max)
+        {
+            Some(ThrottleReason::EventRate)
+        } else if self
+            .limits
+            .max_bytes_per_second
+            .is_some_and(|max| self.window_bytes.saturating_add(bytes) > max)
+        {
+            Some(ThrottleReason::Bandwidth)
+        } else {
+            None
+        };
+
+        let Some(reason) = reason else {
+            self.window_events += 1;
+            self.window_bytes = self.window_bytes.saturating_add(bytes);
+            self.throttled = false;
+            return Admission::Deliver {
+                notice: self.unreported.take(),
+            };
+        };
+        if !self.throttled {
+            self.throttled = true;
+            return Admission::Drop {
+                notice: Some(ThrottleNotice {
+                    reason,
+                    dropped_events: 1,
+                    dropped_bytes: bytes,
+                }),
+            };
+        }
+        let pending = self.unreported.get_or_insert(ThrottleNotice {
+            reason,
+            dropped_events: 0,
+            dropped_bytes: 0,
+        });
+        pending.reason = reason;
+        pending.dropped_events += 1;
+        pending.dropped_bytes = pending.dropped_bytes.saturating_add(bytes);
+        Admission::Drop { notice: None }
+    }
+}
+
+/// Throughput of one subscriber's streams, summed over every stream it opened.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SubscriberStats {
+    /// API token id, `root` for the operator token, or `anonymous` when auth is off.
+    pub principal: String,
+    /// Empty for streams opened without a subscriber id.
+    pub subscriber_id: String,
+    pub first_connected_at: DateTime<Utc>,
+    pub active_streams: u64,
+    pub events_delivered: u64,
+    pub bytes_delivered: u64,
+    pub events_dropped: u64,
+    pub bytes_dropped: u64,
+    pub throttle_notices: u64,
+    /// Events lost to a full subscriber buffer; see [`crate::SubscriberBuffer`].
+    pub events_lagged: u64,
+    pub last_event_at: Option<DateTime<Utc>>,
+}
+
+impl SubscriberStats {
+    /// Average delivered bytes per second since the subscriber first connected.
+    pub fn bytes_per_second(&self, now: DateTime<Utc>) -> f64 {
+        let seconds = (now - self.first_connected_at).num_milliseconds().max(1) as f64 / 1000.0;
+        self.bytes_delivered as f64 / seconds
+    }
+}
+
+type SubscriberKey = (String, String);
+
+#[derive(Clone, Default)]
+pub struct StreamMetrics {
+    inner: Arc<Mutex<BTreeMap<SubscriberKey, SubscriberStats>>>,
+}
+
+impl StreamMetrics {
+    /// Registers an open stream; the returned meter counts its traffic until dropped.
+    pub fn open(&self, principal: &str, subscriber_id: &str) -> StreamMeter {
+        let key = (principal.to_string(), subscriber_id.to_string());
+        self.lock()
+            .entry(key.clone())
+            .or_insert_with(|| SubscriberStats {
+                principal: key.0.clone(),
+                subscriber_id: key.1.clone(),
+                first_connected_at: Utc::now(),
+                active_streams: 0,
+                events_delivered: 0,
+                bytes_delivered: 0,
+                events_dropped: 0,
+                bytes_dropped: 0,
+                throttle_notices: 0,
+                events_lagged: 0,
+                last_event_at: None,
+            })
+            .active_streams += 1;
+        StreamMeter {
+            metrics: self.clone(),
+            key,
+        }
+    }
+
+    /// Every subscriber seen since startup, ordered by principal then subscriber id.
+    pub fn snapshot(&self) -> Vec<SubscriberStats> {
+        self.lock().values().cloned().collect()
+    }
+
+    fn update(&self, key: &SubscriberKey, apply: impl FnOnce(&mut SubscriberStats)) {
+        if let Some(stats) = self.lock().get_mut(key) {
+            apply(stats);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<SubscriberKey, SubscriberStats>> {
+        self.inner.lock().expect("stream metrics poisoned")
+    }
+}
+
+/// Counts one stream's traffic into [`StreamMetrics`].
+pub struct StreamMeter {
+    metrics: StreamMetrics,
+    key: SubscriberKey,
+}
+
+impl StreamMeter {
+    pub fn delivered(&self, bytes: u64) {
+        self.metrics.update(&self.key, |stats| {
+            stats.events_delivered += 1;
+            stats.bytes_delivered = stats.bytes_delivered.saturating_add(bytes);
+            stats.last_event_at = Some(Utc::now());
+        });
+    }
+
+    pub fn dropped(&self, bytes: u64) {
+        self.metrics.update(&self.key, |stats| {
+            stats.events_dropped += 1;
+            stats.bytes_dropped = stats.bytes_dropped.saturating_add(bytes);
+        });
+    }
+
+    pub fn lagged(&self, events: u64) {
+        self.metrics
+            .update(&self.key, |stats| stats.events_lagged += events);
+    }
+
+    pub fn notified(&self) {
+        self.metrics
+            .update(&self.key, |stats| stats.throttle_notices += 1);
+    }
+}
+
+impl Drop for StreamMeter {
+    fn drop(&mut self) {
+        self.metrics.update(&self.key, |stats| {
+            stats.active_streams = stats.active_streams.saturating_sub(1)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_over_the_caps_and_reports_them_in_notices() {
+        let mut governor = StreamGovernor::new(StreamLimits {
+            max_events_per_second: Some(2),
+            max_bytes_per_second: None,
+            max_event_bytes: Some(100),
+        });
+        let start = Instant::now();
+        let deliver = |notice| Admission::Deliver { notice };
+        assert_eq!(governor.admit_at(10, start), deliver(None));
+        assert_eq!(
+            governor.admit_at(500, start),
+            Admission::Drop {
+                notice: Some(ThrottleNotice {
+                    reason: ThrottleReason::EventSize,
+                    dropped_events: 1,
+                    dropped_bytes: 500,
+                })
+            }
+        );
+        assert_eq!(governor.admit_at(10, start), deliver(None));
+        assert_eq!(
+            governor.admit_at(10, start),
+            Admission::Drop {
+                notice: Some(ThrottleNotice {
+                    reason: ThrottleReason::EventRate,
+                    dropped_events: 1,
+                    dropped_bytes: 10,
+                })
+            }
+        );
+        assert_eq!(
+            governor.admit_at(20, start),
+            Admission::Drop { notice: None }
+        );
+        assert_eq!(
+            governor.admit_at(30, start),
+            Admission::Drop { notice: None }
+        );
+        let next_window = start + Duration::from_secs(1);
+        assert_eq!(
+            governor.admit_at(10, next_window),
+            deliver(Some(ThrottleNotice {
+                reason: ThrottleReason::EventRate,
+                dropped_events: 2,
+                dropped_bytes: 50,
+            }))
+        );
+        assert_eq!(governor.admit_at(10, next_window), deliver(None));
+    }
+
+    #[test]
+    fn meters_traffic_per_principal_and_subscriber() {
+        let metrics = StreamMetrics::default();
+        let first = metrics.open("token-a", "orchestrator");
+        let second = metrics.open("token-a", "orchestrator");
+        first.delivered(40);
+        second.dropped(10);
+        second.notified();
+        drop(first);
+        let other = metrics.open("root", "");
+        other.delivered(5);
+
+        let stats = metrics.snapshot();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].principal, "root");
+        let orchestrator = &stats[1];
+        assert_eq!(orchestrator.active_streams, 1);
+        assert_eq!(orchestrator.events_delivered, 1);
+        assert_eq!(orchestrator.bytes_delivered, 40);
+        assert_eq!(orchestrator.events_dropped, 1);
+        assert_eq!(orchestrator.throttle_notices, 1);
+    }
+}
@@ -0,0 +1,272 @@
+//! Human-readable rendering of timer lifecycle events for `kernel --tail-events` and
+//! `minoots-tail`.
+
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "grpc")]
+use crate::pb;
+use crate::{TimerEvent, TimerInstance};
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    Scheduled,
+    PreFire,
+    Rescheduled,
+    Fired,
+    Cancelled,
+    Missed,
+    Acknowledged,
+    Failed,
+    Settled,
+    /// The stream dropped events for exceeding the subscriber's caps.
+    Throttled,
+}
+
+impl EventKind {
+    fn label(self) -> &'static str {
+        match self {
+            EventKind::Scheduled => "SCHEDULED",
+            EventKind::PreFire => "PRE-FIRE",
+            EventKind::Rescheduled => "RESCHEDULED",
+            EventKind::Fired => "FIRED",
+            EventKind::Cancelled => "CANCELLED",
+            EventKind::Missed => "MISSED",
+            EventKind::Acknowledged => "ACKED",
+            EventKind::Failed => "FAILED",
+            EventKind::Settled => "SETTLED",
+            EventKind::Throttled => "THROTTLED",
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            EventKind::Scheduled => "\x1b[36m",
+            EventKind::PreFire => "\x1b[35m",
+            EventKind::Rescheduled => "\x1b[34m",
+            EventKind::Fired => "\x1b[32m",
+            EventKind::Cancelled => "\x1b[33m",
+            EventKind::Missed => "\x1b[31m",
+            EventKind::Acknowledged => "\x1b[32m",
+            EventKind::Failed => "\x1b[31m",
+            EventKind::Settled => "\x1b[32m",
+            EventKind::Throttled => "\x1b[31m",
+        }
+    }
+}
+
+/// Transport-independent view of an event holding just what a tail line shows.
+#[derive(Clone, Debug)]
+pub struct EventLine {
+    pub kind: EventKind,
+    pub tenant_id: String,
+    pub timer_id: String,
+    pub name: String,
+    pub fire_at: Option<DateTime<Utc>>,
+    pub fired_at: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+}
+
+impl EventLine {
+    fn from_timer(kind: EventKind, timer: &TimerInstance, reason: Option<String>) -> Self {
+        Self {
+            kind,
+            tenant_id: timer.tenant_id.clone(),
+            timer_id: timer.id.to_string(),
+            name: timer.name.clone(),
+            fire_at: Some(timer.fire_at),
+            fired_at: timer.fired_at,
+            reason,
+        }
+    }
+
+    /// Builds a line from a streamed gRPC event; returns `None` for empty events.
+    #[cfg(feature = "grpc")]
+    pub fn from_proto(event: &pb::TimerEvent) -> Option<Self> {
+        let (kind, timer, reason) = match event.event.as_ref()? {
+            pb::timer_event::Event::Scheduled(inner) => {
+                (EventKind::Scheduled, inner.timer.as_ref()?, None)
+            }
+            pb::timer_event::Event::PreFire(inner) => {
+                (EventKind::PreFire, inner.timer.as_ref()?, None)
+            }
+            pb::timer_event::Event::Rescheduled(inner) => {
+                (EventKind::Rescheduled, inner.timer.as_ref()?, None)
+            }
+            pb::timer_event::Event::Fired(inner) => (EventKind::Fired, inner.timer.as_ref()?, None),
+            pb::timer_event::Event::Cancelled(inner) => (
+                EventKind::Cancelled,
+                inner.timer.as_ref()?,
+                Some(inner.reason.clone()).filter(|reason| !reason.is_empty()),
+            ),
+            pb::timer_event::Event::Missed(inner) => {
+                (EventKind::Missed, inner.timer.as_ref()?, None)
+            }
+            pb::timer_event::Event::Acknowledged(inner) => {
+                (EventKind::Acknowledged, inner.timer.as_ref()?, None)
+            }
+            pb::timer_event::Event::Failed(inner) => {
+                let timer = inner.timer.as_ref()?;
+                let reason = timer
+                    .failure_reason
+                    .as_ref()
+                    .map(|reason| reason.message.clone());
+                (EventKind::Failed, timer, reason)
+            }
+            pb::timer_event::Event::Settled(inner) => {
+                (EventKind::Settled, inner.timer.as_ref()?, None)
+            }
+            pb::timer_event::Event::Throttled(inner) => {
+                return Some(Self {
+                    kind: EventKind::Throttled,
+                    tenant_id: String::new(),
+                    timer_id: String::new(),
+                    name: String::new(),
+                    fire_at: None,
+                    fired_at: None,
+                    reason: Some(format!(
+                        "{} dropped_events={} dropped_bytes={}",
+                        inner.reason, inner.dropped_events, inner.dropped_bytes
+                    )),
+                })
+            }
+        };
+        Some(Self {
+            kind,
+            tenant_id: timer.tenant_id.clone(),
+            timer_id: timer.id.clone(),
+            name: timer.name.clone(),
+            fire_at: parse_iso(&timer.fire_at_iso),
+            fired_at: parse_iso(&timer.fired_at_iso),
+            reason,
+        })
+    }
+
+    /// Renders the line relative to `now`, optionally with ANSI colors.
+    pub fn render(&self, now: DateTime<Utc>, color: bool) -> String {
+        let relative = match (self.kind, self.fire_at) {
+            (EventKind::Fired, Some(fire_at)) => {
+                let fired_at = self.fired_at.unwrap_or(now);
+                format!("{} late", format_span(fired_at - fire_at))
+            }
+            (_, Some(fire_at)) if fire_at > now => {
+                format!("fires in {}", format_span(fire_at - now))
+            }
+            (_, Some(fire_at)) => format!("due {} ago", format_span(now - fire_at)),
+            (_, None) => String::new(),
+        };
+        let reason = self
+            .reason
+            .as_ref()
+            .map(|reason| format!(" reason={reason}"))
+            .unwrap_or_default();
+        let (paint, reset, dim) = if color {
+            (self.kind.color(), RESET, DIM)
+        } else {
+            ("", "", "")
+        };
+        if self.timer_id.is_empty() {
+            return format!(
+                "{dim}{}{reset} {paint}{:<9}{reset}{reason}",
+                now.format("%H:%M:%S%.3f"),
+                self.kind.label(),
+            );
+        }
+        format!(
+            "{dim}{}{reset} {paint}{:<9}{reset} {}/{} {dim}({}){reset} {relative}{reason}",
+            now.format("%H:%M:%S%.3f"),
+            self.kind.label(),
+            self.tenant_id,
+            self.name,
+            self.timer_id,
+        )
+    }
+}
+
+impl From<&TimerEvent> for EventLine {
+    fn from(event: &TimerEvent) -> Self {
+        match event {
+            TimerEvent::Scheduled(timer) => {
+                EventLine::from_timer(EventKind::Scheduled, timer, None)
+            }
+            TimerEvent::PreFire { timer, .. } => {
+                EventLine::from_timer(EventKind::PreFire, timer, None)
+            }
+            TimerEvent::Rescheduled { timer, .. } => {
+                EventLine::from_timer(EventKind::Rescheduled, timer, None)
+            }
+            TimerEvent::Fired(timer) => EventLine::from_timer(EventKind::Fired, timer, None),
+            TimerEvent::Cancelled { timer, reason } => {
+                EventLine::from_timer(EventKind::Cancelled, timer, reason.clone())
+            }
+            TimerEvent::Missed { timer, .. } => {
+                EventLine::from_timer(EventKind::Missed, timer, None)
+            }
+            TimerEvent::Acknowledged(timer) => {
+                EventLine::from_timer(EventKind::Acknowledged, timer, None)
+            }
+            TimerEvent::Failed(timer) => EventLine::from_timer(
+                EventKind::Failed,
+                timer,
+                timer.failure_reason.as_ref().map(ToString::to_string),
+            ),
+            TimerEvent::Settled(timer) => EventLine::from_timer(EventKind::Settled, timer, None),
+        }
+    }
+}
+
+/// Whether tail output should be colored, honoring the `NO_COLOR` convention.
+pub fn color_enabled() -> bool {
+    std::env::var_os("NO_COLOR").is_none()
+}
+
+#[cfg(feature = "grpc")]
+fn parse_iso(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn format_span(span: chrono::Duration) -> String {
+    let millis = span.num_milliseconds().max(0);
+    if millis < 1_000 {
+        format!("{millis}ms")
+    } else if millis < 60_000 {
+        format!("{:.1}s", millis as f64 / 1_000.0)
+    } else {
+        format!("{}m{:02}s", millis / 60_000, (millis % 60_000) / 1_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_relative_times_without_color() {
+        let now = Utc::now();
+        let line = EventLine {
+            kind: EventKind::Scheduled,
+            tenant_id: "tenant-a".into(),
+            timer_id: "abc".into(),
+            name: "reminder".into(),
+            fire_at: Some(now + chrono::Duration::milliseconds(2_500)),
+            fired_at: None,
+            reason: None,
+        };
+        let rendered = line.render(now, false);
+        assert!(rendered.contains("SCHEDULED tenant-a/reminder (abc) fires in 2.5s"));
+        assert!(!rendered.contains('\x1b'));
+
+        let fired = EventLine {
+            kind: EventKind::Fired,
+            fired_at: line
+                .fire_at
+                .map(|at| at + chrono::Duration::milliseconds(4)),
+            ..line
+        };
+        assert!(fired.render(now, false).ends_with("4ms late"));
+    }
+}
@@ -0,0 +1,292 @@
+//! Registry of the kernel's long-lived background tasks (dispatch loop, event forwarders, standby
+//! follower, heartbeats). Tasks spawned through [`TaskRegistry::spawn`] are listed by name with
+//! their poll activity, so operators can spot a stuck or leaked task, and how each one ended is
+//! kept for a while after it stops.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+/// Ended tasks kept in [`TaskReport::recently_ended`].
+const ENDED_HISTORY: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Running,
+    Completed,
+    Panicked,
+    /// Dropped before completing, e.g. aborted during shutdown.
+    Aborted,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TaskInfo {
+    pub id: u64,
+    pub name: String,
+    pub state: TaskState,
+    pub started_at: DateTime<Utc>,
+    pub polls: u64,
+    pub last_polled_at: Option<DateTime<Utc>>,
+    /// Set while a poll is in progress; a poll that has been running for long is blocking its
+    /// worker thread.
+    pub busy_since: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TaskCounts {
+    pub spawned: u64,
+    pub completed: u64,
+    pub panicked: u64,
+    pub aborted: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TaskReport {
+    pub counts: TaskCounts,
+    /// Running tasks in spawn order.
+    pub running: Vec<TaskInfo>,
+    /// Most recently ended tasks, newest first.
+    pub recently_ended: Vec<TaskInfo>,
+}
+
+struct Slot {
+    id: u64,
+    name: String,
+    started_at: DateTime<Utc>,
+    polls: AtomicU64,
+    /// Milliseconds since the epoch; 0 when unset.
+    last_polled_ms: AtomicI64,
+    busy_since_ms: AtomicI64,
+    panicked: AtomicBool,
+}
+
+impl Slot {
+    fn info(&self, state: TaskState, ended_at: Option<DateTime<Utc>>) -> TaskInfo {
+        TaskInfo {
+            id: self.id,
+            name: self.name.clone(),
+            state,
+            started_at: self.started_at,
+            polls: self.polls.load(Ordering::Relaxed),
+            last_polled_at: from_millis(self.last_polled_ms.load(Ordering::Relaxed)),
+            busy_since: from_millis(self.busy_since_ms.load(Ordering::Relaxed)),
+            ended_at,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: u64,
+    running: BTreeMap<u64, Arc<Slot>>,
+    ended: VecDeque<TaskInfo>,
+    counts: TaskCounts,
+}
+
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    inner: Arc<Mutex<Registry>>,
+}
+
+impl TaskRegistry {
+    /// Spawns `future` on the runtime as a named, tracked task.
+    pub fn spawn<F>(&self, name: impl Into<String>, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let slot = self.register(name.into());
+        tokio::spawn(Tracked {
+            future: Box::pin(future),
+            guard: SlotGuard {
+                registry: self.clone(),
+                slot,
+                completed: false,
+            },
+        })
+    }
+
+    pub fn report(&self) -> TaskReport {
+        let registry = self.registry();
+        TaskReport {
+            counts: registry.counts,
+            running: registry
+                .running
+                .values()
+                .map(|slot| slot.info(TaskState::Running, None))
+                .collect(),
+            recently_ended: registry.ended.iter().cloned().collect(),
+        }
+    }
+
+    fn register(&self, name: String) -> Arc<Slot> {
+        let mut registry = self.registry();
+        registry.next_id += 1;
+        registry.counts.spawned += 1;
+        let slot = Arc::new(Slot {
+            id: registry.next_id,
+            name,
+            started_at: Utc::now(),
+            polls: AtomicU64::new(0),
+            last_polled_ms: AtomicI64::new(0),
+            busy_since_ms: AtomicI64::new(0),
+            panicked: AtomicBool::new(false),
+        });
+        registry.running.insert(slot.id, slot.clone());
+        slot
+    }
+
+    fn end(&self, slot: &Slot, state: TaskState) {
+        if state == TaskState::Panicked {
+            tracing::error!(task = %slot.name, id = slot.id, "background task panicked");
+        }
+        let mut registry = self.registry();
+        registry.running.remove(&slot.id);
+        match state {
+            TaskState::Completed => registry.counts.completed += 1,
+            TaskState::Panicked => registry.counts.panicked += 1,
+            TaskState::Aborted => registry.counts.aborted += 1,
+            TaskState::Running => {}
+        }
+        registry
+            .ended
+            .push_front(slot.info(state, Some(Utc::now())));
+        registry.ended.truncate(ENDED_HISTORY);
+    }
+
+    fn registry(&self) -> std::sync::MutexGuard<'_, Registry> {
+        self.inner.lock().expect("task registry poisoned")
+    }
+}
+
+/// Records how a task ended when its future is dropped.
+struct SlotGuard {
+    registry: TaskRegistry,
+    slot: Arc<Slot>,
+    completed: bool,
+}
+
+impl Drop for SlotGuard {
+    fn drop(&mut self) {
+        let state = if self.slot.panicked.load(Ordering::Relaxed) {
+            TaskState::Panicked
+        } else if self.completed {
+            TaskState::Completed
+        } else {
+            TaskState::Aborted
+        };
+        self.registry.end(&self.slot, state);
+    }
+}
+
+/// Clears the busy marker after a poll and flags the slot if the poll unwound.
+struct PollGuard<'a>(&'a Slot);
+
+impl Drop for PollGuard<'_> {
+    fn drop(&mut self) {
+        self.0.busy_since_ms.store(0, Ordering::Relaxed);
+        if std::thread::panicking() {
+            self.0.panicked.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+struct Tracked<F> {
+    future: Pin<Box<F>>,
+    guard: SlotGuard,
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let slot = &this.guard.slot;
+        let now = Utc::now().timestamp_millis();
+        slot.polls.fetch_add(1, Ordering::Relaxed);
+        slot.last_polled_ms.store(now, Ordering::Relaxed);
+        slot.busy_since_ms.store(now, Ordering::Relaxed);
+        let _poll = PollGuard(slot);
+        let output = this.future.as_mut().poll(cx);
+        if output.is_ready() {
+            this.guard.completed = true;
+        }
+        output
+    }
+}
+
+fn from_millis(millis: i64) -> Option<DateTime<Utc>> {
+    (millis != 0)
+        .then(|| DateTime::from_timestamp_millis(millis))
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tracks_running_and_ended_tasks() {
+        let registry = TaskRegistry::default();
+        let completed = registry.spawn("one-shot", async { 7 });
+        assert_eq!(completed.await.unwrap(), 7);
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+        let waiting = registry.spawn("waiter", async move {
+            let _ = release_rx.await;
+        });
+        let aborted = registry.spawn("forever", std::future::pending::<()>());
+        let panicked = registry.spawn("faulty", async { panic!("boom") });
+        assert!(panicked.await.unwrap_err().is_panic());
+        aborted.abort();
+        let _ = aborted.await;
+
+        let report = registry.report();
+        assert_eq!(
+            report
+                .running
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["waiter"]
+        );
+        let ended: Vec<_> = report
+            .recently_ended
+            .iter()
+            .map(|t| (t.name.as_str(), t.state))
+            .collect();
+        assert_eq!(
+            ended,
+            vec![
+                ("forever", TaskState::Aborted),
+                ("faulty", TaskState::Panicked),
+                ("one-shot", TaskState::Completed),
+            ]
+        );
+        assert_eq!(
+            report.counts,
+            TaskCounts {
+                spawned: 4,
+                completed: 1,
+                panicked: 1,
+                aborted: 1,
+            }
+        );
+
+        release_tx.send(()).unwrap();
+        waiting.await.unwrap();
+        assert!(registry.report().running.is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::quota::TenantQuota;
+
+/// Policy knobs that can be set on an organization and inherited by its projects. Every field is
+/// optional: an unset field on a project falls back to the organization, then to the scheduler
+/// defaults.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantPolicy {
+    pub max_duration_ms: Option<u64>,
+    #[serde(default)]
+    pub quota: TenantQuota,
+    /// Key the embedded orchestrator signs the tenant's webhook actions with; see
+    /// [`crate::sinks::webhook::verify_signature`].
+    #[serde(default)]
+    pub webhook_signing_secret: Option<String>,
+    /// Project tenants of this organization. Listing them here registers them, so a policy file
+    /// can declare the hierarchy; projects do not inherit the list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<String>,
+}
+
+impl TenantPolicy {
+    /// Fills every unset field from `parent`.
+    fn inherit(mut self, parent: &TenantPolicy) -> Self {
+        self.max_duration_ms = self.max_duration_ms.or(parent.max_duration_ms);
+        self.quota = self.quota.or(&parent.quota);
+        self.webhook_signing_secret = self
+            .webhook_signing_secret
+            .or_else(|| parent.webhook_signing_secret.clone());
+        self
+    }
+}
+
+/// Selects the tenants a read (list/stream) applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TenantScope {
+    /// A single tenant, whether it is a project or a standalone tenant.
+    Tenant(String),
+    /// An organization together with every project registered under it.
+    Organization(String),
+    /// Every tenant; reserved for operators and standby replication.
+    All,
+}
+
+/// Two-level tenancy model: organizations own project tenants. Tenants that are never registered
+/// as a project behave as standalone top-level tenants.
+#[derive(Clone, Debug, Default)]
+pub struct TenantDirectory {
+    /// Projects registered through [`TenantDirectory::register_project`].
+    organizations: HashMap<String, String>,
+    /// Projects the policies list, rebuilt whenever they change.
+    declared: HashMap<String, String>,
+    policies: HashMap<String, TenantPolicy>,
+}
+
+impl TenantDirectory {
+    pub fn register_project(
+        &mut self,
+        organization_id: impl Into<String>,
+        project_id: impl Into<String>,
+    ) {
+        self.organizations
+            .insert(project_id.into(), organization_id.into());
+    }
+
+    pub fn organization_of(&self, tenant_id: &str) -> Option<&str> {
+        self.organizations
+            .get(tenant_id)
+            .or_else(|| self.declared.get(tenant_id))
+            .map(String::as_str)
+    }
+
+    pub fn set_policy(&mut self, tenant_id: impl Into<String>, policy: TenantPolicy) {
+        self.policies.insert(tenant_id.into(), policy);
+        self.declare_projects();
+    }
+
+    pub fn replace_policies(&mut self, policies: HashMap<String, TenantPolicy>) {
+        self.policies = policies;
+        self.declare_projects();
+    }
+
+    fn declare_projects(&mut self) {
+        self.declared = self
+            .policies
+            .iter()
+            .flat_map(|(organization_id, policy)| {
+                policy
+                    .projects
+                    .iter()
+                    .map(move |project_id| (project_id.clone(), organization_id.clone()))
+            })
+            .collect();
+    }
+
+    /// Resolves the policy for a tenant, layering the project's own policy over its organization's.
+    pub fn effective_policy(&self, tenant_id: &str) -> TenantPolicy {
+        let own = self.policies.get(tenant_id).cloned().unwrap_or_default();
+        match self
+            .organization_of(tenant_id)
+            .and_then(|org| self.policies.get(org))
+        {
+            Some(parent) => own.inherit(parent),
+            None => own,
+        }
+    }
+
+    pub fn in_scope(&self, scope: &TenantScope, tenant_id: &str) -> bool {
+        match scope {
+            TenantScope::Tenant(tenant) => tenant == tenant_id,
+            TenantScope::Organization(org) => {
+                org == tenant_id || self.organization_of(tenant_id) == Some(org.as_str())
+            }
+            TenantScope::All => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projects_inherit_unset_policy_fields() {
+        let mut directory = TenantDirectory::default();
+        directory.register_project("acme", "acme-web");
+        directory.set_policy(
+            "acme",
+            TenantPolicy {
+                max_duration_ms: Some(60_000),
+                quota: TenantQuota {
+                    max_active_timers: Some(10),
+                    ..Default::default()
+                },
+                webhook_signing_secret: Some("org-secret".into()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            directory.effective_policy("acme-web").max_duration_ms,
+            Some(60_000)
+        );
+        directory.set_policy(
+            "acme-web",
+            TenantPolicy {
+                max_duration_ms: Some(1_000),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            directory.effective_policy("acme-web").max_duration_ms,
+            Some(1_000)
+        );
+        assert_eq!(
+            directory
+                .effective_policy("acme-web")
+                .quota
+                .max_active_timers,
+            Some(10)
+        );
+        assert_eq!(
+            directory
+                .effective_policy("acme-web")
+                .webhook_signing_secret
+                .as_deref(),
+            Some("org-secret")
+        );
+        assert_eq!(directory.effective_policy("other").max_duration_ms, None);
+
+        let scope = TenantScope::Organization("acme".into());
+        assert!(directory.in_scope(&scope, "acme"));
+        assert!(directory.in_scope(&scope, "acme-web"));
+        assert!(!directory.in_scope(&scope, "other"));
+    }
+
+    #[test]
+    fn policies_declare_projects() {
+        let mut directory = TenantDirectory::default();
+        let policies: HashMap<String, TenantPolicy> = serde_json::from_str(
+            r#"{"acme": {"max_duration_ms": 60000, "projects": ["acme-web"]}}"#,
+        )
+        .unwrap();
+        directory.replace_policies(policies);
+        assert_eq!(directory.organization_of("acme-web"), Some("acme"));
+        assert_eq!(
+            directory.effective_policy("acme-web").max_duration_ms,
+            Some(60_000)
+        );
+        assert!(directory.effective_policy("acme-web").projects.is_empty());
+        assert!(directory.in_scope(&TenantScope::Organization("acme".into()), "acme-web"));
+
+        directory.replace_policies(HashMap::new());
+        assert_eq!(directory.organization_of("acme-web"), None);
+    }
+}
@@ -1,14 +1,21 @@
+#![cfg(feature = "grpc")]
+
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::time::Duration;
 
-use horology_kernel::grpc::HorologyKernelService;
+use horology_kernel::grpc::{self, HorologyKernelService};
 use horology_kernel::pb::horology_kernel_client::HorologyKernelClient;
 use horology_kernel::pb::horology_kernel_server::HorologyKernelServer;
 use horology_kernel::pb::{
-    timer_schedule_request, TimerCancelRequest, TimerListRequest, TimerScheduleRequest,
+    timer_event, timer_schedule_request, AcknowledgeDeliveryRequest, ActionResult, ApiDescriptorRequest, DeliveryStatusRequest,
+    IssueApiTokenRequest, ListEventWebhooksRequest, ListStreamSubscribersRequest, QueryEventsRequest, RegisterEventWebhookRequest, ReplayDeadLettersRequest, ReportTimerExecutionRequest, schedule_timers_batch_result, ScheduleTimersBatchRequest, TimerCancelRequest, TimerEventStreamRequest, TimerListRequest, TimerScheduleRequest,
+};
+use horology_kernel::standby::StandbyFollower;
+use horology_kernel::{
+    EventHistory, EventRetention, HorologyKernel, RecordedEvent, SchedulerConfig, StreamLimits, TimerEvent, TimerSpec,
+    TimerStatus,
 };
-use horology_kernel::{HorologyKernel, SchedulerConfig};
 use tokio::sync::oneshot;
 use tonic::transport::Server;
 
@@ -46,6 +53,7 @@ async fn grpc_schedule_and_cancel_roundtrip() {
             labels: HashMap::new(),
             action_bundle_json: String::new(),
             agent_binding_json: String::new(),
+            ..Default::default()
         }))
         .await
         .expect("schedule response")
@@ -60,18 +68,64 @@ async fn grpc_schedule_and_cancel_roundtrip() {
             page_size: 0,
             page_token: String::new(),
             statuses: vec![],
+            ..Default::default()
         }))
         .await
         .expect("list response")
         .into_inner();
     assert_eq!(list_response.timers.len(), 1);
 
+    let filtered = client
+        .list_timers(tonic::Request::new(TimerListRequest {
+            tenant_id: "tenant-test".into(),
+            name_prefix: "integ".into(),
+            fire_at_until_iso: timer.fire_at_iso.clone(),
+            ..Default::default()
+        }))
+        .await
+        .expect("filtered list response")
+        .into_inner();
+    assert!(filtered.timers.is_empty(), "fire_at_until_iso is exclusive");
+    let selected = client
+        .list_timers(tonic::Request::new(TimerListRequest {
+            tenant_id: "tenant-test".into(),
+            label_selectors: vec!["env!=prod".into()],
+            fire_at_from_iso: timer.fire_at_iso.clone(),
+            sort_order: horology_kernel::pb::TimerSortOrder::FireAtDesc as i32,
+            ..Default::default()
+        }))
+        .await
+        .expect("selected list response")
+        .into_inner();
+    assert_eq!(selected.timers.len(), 1);
+    let invalid = client
+        .list_timers(tonic::Request::new(TimerListRequest {
+            tenant_id: "tenant-test".into(),
+            label_selectors: vec!["env".into()],
+            ..Default::default()
+        }))
+        .await
+        .expect_err("selector without a value");
+    assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+
+    let stale = client
+        .cancel_timer(tonic::Request::new(TimerCancelRequest {
+            tenant_id: "tenant-test".into(),
+            timer_id: timer.id.clone(),
+            expected_state_version: timer.state_version + 1,
+            ..Default::default()
+        }))
+        .await
+        .expect_err("stale state version");
+    assert_eq!(stale.code(), tonic::Code::Aborted);
+
     let cancel_response = client
         .cancel_timer(tonic::Request::new(TimerCancelRequest {
             tenant_id: "tenant-test".into(),
             timer_id: timer.id.clone(),
             requested_by: "agent-test".into(),
             reason: "integration".into(),
+            expected_state_version: timer.state_version,
         }))
         .await
         .expect("cancel response")
@@ -79,7 +133,1601 @@ async fn grpc_schedule_and_cancel_roundtrip() {
 
     assert_eq!(cancel_response.id, timer.id);
     assert_eq!(cancel_response.status, horology_kernel::pb::TimerStatus::Cancelled as i32);
+    assert_eq!(cancel_response.state_version, timer.state_version + 1);
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_fired_events_are_recorded_in_delivery_ledger() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50062".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50062")
+        .await
+        .expect("connect to kernel");
+
+    let mut events = client
+        .stream_timer_events(tonic::Request::new(TimerEventStreamRequest {
+            tenant_id: "tenant-test".into(),
+            topics: vec![],
+            subscriber_id: "orchestrator-1".into(),
+            ..Default::default()
+        }))
+        .await
+        .expect("stream response")
+        .into_inner();
+
+    let timer = client
+        .schedule_timer(tonic::Request::new(TimerScheduleRequest {
+            tenant_id: "tenant-test".into(),
+            requested_by: "agent-test".into(),
+            name: "delivery".into(),
+            schedule_time: Some(timer_schedule_request::ScheduleTime::DurationMs(30)),
+            metadata_json: String::new(),
+            labels: HashMap::new(),
+            action_bundle_json: String::new(),
+            agent_binding_json: String::new(),
+            ..Default::default()
+        }))
+        .await
+        .expect("schedule response")
+        .into_inner()
+        .timer
+        .expect("timer payload");
+
+    loop {
+        let event = events.message().await.expect("stream event").expect("open stream");
+        if matches!(event.event, Some(timer_event::Event::Fired(_))) {
+            break;
+        }
+    }
+
+    let acknowledged = client
+        .acknowledge_delivery(tonic::Request::new(AcknowledgeDeliveryRequest {
+            tenant_id: "tenant-test".into(),
+            timer_id: timer.id.clone(),
+            subscriber_id: "orchestrator-1".into(),
+        }))
+        .await
+        .expect("ack response")
+        .into_inner();
+    assert!(acknowledged.acknowledged);
+
+    let status = client
+        .get_delivery_status(tonic::Request::new(DeliveryStatusRequest {
+            tenant_id: "tenant-test".into(),
+            timer_id: timer.id.clone(),
+        }))
+        .await
+        .expect("delivery status")
+        .into_inner();
+    assert_eq!(status.receipts.len(), 1);
+    assert_eq!(status.receipts[0].subscriber_id, "orchestrator-1");
+    assert_eq!(status.receipts[0].sequence, 2);
+    assert!(!status.receipts[0].acknowledged_at_iso.is_empty());
+
+    // Graceful shutdown waits for open streams, so close ours first.
+    drop(events);
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_api_tokens_are_scoped_to_tenant_and_operation() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    kernel.tokens().set_root_secret("operator-secret");
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50063".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let tokens = kernel.tokens().clone();
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::with_interceptor(service, grpc::authenticate(tokens)))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50063")
+        .await
+        .expect("connect to kernel");
+    let schedule = || TimerScheduleRequest {
+        tenant_id: "tenant-test".into(),
+        requested_by: "agent-test".into(),
+        schedule_time: Some(timer_schedule_request::ScheduleTime::DurationMs(60_000)),
+        ..Default::default()
+    };
+
+    let anonymous = client
+        .schedule_timer(tonic::Request::new(schedule()))
+        .await
+        .expect_err("anonymous schedule rejected");
+    assert_eq!(anonymous.code(), tonic::Code::Unauthenticated);
+
+    let issued = client
+        .issue_api_token(authorized(
+            "operator-secret",
+            IssueApiTokenRequest {
+                tenant_id: "tenant-test".into(),
+                scopes: vec![horology_kernel::pb::TokenScope::Schedule as i32],
+                description: "scheduler".into(),
+            },
+        ))
+        .await
+        .expect("issue token")
+        .into_inner();
+
+    let timer = client
+        .schedule_timer(authorized(&issued.secret, schedule()))
+        .await
+        .expect("scoped schedule")
+        .into_inner()
+        .timer
+        .expect("timer payload");
+
+    let denied = client
+        .cancel_timer(authorized(
+            &issued.secret,
+            TimerCancelRequest {
+                tenant_id: "tenant-test".into(),
+                timer_id: timer.id.clone(),
+                requested_by: "agent-test".into(),
+                reason: String::new(),
+                ..Default::default()
+            },
+        ))
+        .await
+        .expect_err("cancel without scope");
+    assert_eq!(denied.code(), tonic::Code::PermissionDenied);
+
+    let other_tenant = client
+        .schedule_timer(authorized(
+            &issued.secret,
+            TimerScheduleRequest {
+                tenant_id: "tenant-other".into(),
+                ..schedule()
+            },
+        ))
+        .await
+        .expect_err("cross-tenant schedule");
+    assert_eq!(other_tenant.code(), tonic::Code::PermissionDenied);
+
+    let list = || TimerListRequest {
+        tenant_id: "tenant-test".into(),
+        ..Default::default()
+    };
+    let unreadable = client
+        .list_timers(authorized(&issued.secret, list()))
+        .await
+        .expect_err("list without read scope");
+    assert_eq!(unreadable.code(), tonic::Code::PermissionDenied);
+
+    let reader = client
+        .issue_api_token(authorized(
+            "operator-secret",
+            IssueApiTokenRequest {
+                tenant_id: "tenant-test".into(),
+                scopes: vec![horology_kernel::pb::TokenScope::Read as i32],
+                description: "dashboard".into(),
+            },
+        ))
+        .await
+        .expect("issue read token")
+        .into_inner();
+    let listed = client
+        .list_timers(authorized(&reader.secret, list()))
+        .await
+        .expect("list with read scope")
+        .into_inner();
+    assert_eq!(listed.timers.len(), 1);
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_standby_follows_primary_until_promoted() {
+    let primary = HorologyKernel::new(SchedulerConfig::default());
+    let service = HorologyKernelService::new(primary.clone());
+    let addr: SocketAddr = "127.0.0.1:50064".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let spec = |name: &str| horology_kernel::TimerSpec {
+        tenant_id: "tenant-test".into(),
+        requested_by: "agent-test".into(),
+        name: Some(name.into()),
+        duration_ms: 60_000,
+        labels: HashMap::from([("env".to_string(), "prod".to_string())]),
+        ..Default::default()
+    };
+    let before = primary.schedule(spec("before")).await.expect("schedule");
+    let untouched = primary.schedule(spec("untouched")).await.expect("schedule");
+
+    let standby = HorologyKernel::new(SchedulerConfig {
+        standby: true,
+        ..SchedulerConfig::default()
+    });
+    let follower = tokio::spawn({
+        let standby = standby.clone();
+        // One timer per page, so hydration walks several pages of the primary's snapshot.
+        let follower = StandbyFollower::new("http://127.0.0.1:50064", None).snapshot_page_size(1);
+        async move { follower.run(standby).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let after = primary.schedule(spec("after")).await.expect("schedule");
+    primary.cancel("tenant-test", before.id, None, None).await.expect("cancel");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let replicated = standby.get("tenant-test", before.id).await.expect("snapshot replicated");
+    assert_eq!(replicated.status, TimerStatus::Cancelled);
+    assert!(standby.get("tenant-test", untouched.id).await.is_some());
+    let replicated = standby.get("tenant-test", after.id).await.expect("event replicated");
+    assert_eq!(replicated.status, TimerStatus::Scheduled);
+    assert_eq!(replicated.fire_at, after.fire_at);
+    assert_eq!(replicated.labels, after.labels);
+
+    standby.promote();
+    follower.await.expect("follower stops after promotion");
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+fn authorized<T>(secret: &str, message: T) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    request
+        .metadata_mut()
+        .insert("authorization", format!("Bearer {secret}").parse().unwrap());
+    request
+}
+
+#[tokio::test]
+async fn grpc_stream_caps_drop_events_with_throttle_notices() {
+    let kernel = HorologyKernel::new(SchedulerConfig {
+        stream_limits: StreamLimits {
+            max_events_per_second: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50065".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50065")
+        .await
+        .expect("connect to kernel");
+
+    let mut events = client
+        .stream_timer_events(tonic::Request::new(TimerEventStreamRequest {
+            tenant_id: "tenant-test".into(),
+            subscriber_id: "slow-consumer".into(),
+            ..Default::default()
+        }))
+        .await
+        .expect("stream response")
+        .into_inner();
+
+    for _ in 0..3 {
+        client
+            .schedule_timer(tonic::Request::new(TimerScheduleRequest {
+                tenant_id: "tenant-test".into(),
+                requested_by: "agent-test".into(),
+                schedule_time: Some(timer_schedule_request::ScheduleTime::DurationMs(60_000)),
+                ..Default::default()
+            }))
+            .await
+            .expect("schedule response");
+    }
+
+    let first = events.message().await.expect("stream event").expect("open stream");
+    assert!(matches!(first.event, Some(timer_event::Event::Scheduled(_))));
+    let notice = events.message().await.expect("stream event").expect("open stream");
+    match notice.event {
+        Some(timer_event::Event::Throttled(throttled)) => {
+            assert_eq!(throttled.reason, "event_rate");
+            assert_eq!(throttled.dropped_events, 1);
+        }
+        other => panic!("expected a throttle notice, got {other:?}"),
+    }
+
+    let subscribers = client
+        .list_stream_subscribers(tonic::Request::new(ListStreamSubscribersRequest {}))
+        .await
+        .expect("subscribers response")
+        .into_inner()
+        .subscribers;
+    assert_eq!(subscribers.len(), 1);
+    assert_eq!(subscribers[0].principal, "anonymous");
+    assert_eq!(subscribers[0].subscriber_id, "slow-consumer");
+    assert_eq!(subscribers[0].active_streams, 1);
+    assert_eq!(subscribers[0].events_delivered, 1);
+    assert_eq!(subscribers[0].events_dropped, 2);
+
+    drop(events);
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_batch_schedule_returns_a_result_per_item() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50066".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50066")
+        .await
+        .expect("connect to kernel");
+
+    let item = |duration_ms| TimerScheduleRequest {
+        tenant_id: "tenant-test".into(),
+        requested_by: "agent-test".into(),
+        schedule_time: Some(timer_schedule_request::ScheduleTime::DurationMs(duration_ms)),
+        ..Default::default()
+    };
+    let results = client
+        .schedule_timers_batch(tonic::Request::new(ScheduleTimersBatchRequest {
+            items: vec![item(60_000), item(0), item(30_000)],
+        }))
+        .await
+        .expect("batch response")
+        .into_inner()
+        .results;
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[0].result, Some(schedule_timers_batch_result::Result::Timer(_))));
+    match &results[1].result {
+        Some(schedule_timers_batch_result::Result::Error(error)) => {
+            assert_eq!(error.code, tonic::Code::InvalidArgument as i32);
+        }
+        other => panic!("expected an item error, got {other:?}"),
+    }
+    assert!(matches!(results[2].result, Some(schedule_timers_batch_result::Result::Timer(_))));
+    assert_eq!(kernel.list("tenant-test").await.len(), 2);
+
+    let oversized = client
+        .schedule_timers_batch(tonic::Request::new(ScheduleTimersBatchRequest {
+            items: vec![item(60_000); horology_kernel::MAX_BATCH_SIZE + 1],
+        }))
+        .await
+        .expect_err("oversized batch");
+    assert_eq!(oversized.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_query_events_reads_retained_history() {
+    let kernel = HorologyKernel::new(SchedulerConfig {
+        event_history: Some(EventHistory::new(EventRetention::days(7))),
+        ..Default::default()
+    });
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50067".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50067")
+        .await
+        .expect("connect to kernel");
+
+    let timer = client
+        .schedule_timer(tonic::Request::new(TimerScheduleRequest {
+            tenant_id: "tenant-test".into(),
+            requested_by: "agent-test".into(),
+            schedule_time: Some(timer_schedule_request::ScheduleTime::DurationMs(60_000)),
+            ..Default::default()
+        }))
+        .await
+        .expect("schedule response")
+        .into_inner()
+        .timer
+        .expect("timer");
+    client
+        .cancel_timer(tonic::Request::new(TimerCancelRequest {
+            tenant_id: "tenant-test".into(),
+            timer_id: timer.id.clone(),
+            ..Default::default()
+        }))
+        .await
+        .expect("cancel response");
+
+    let events = client
+        .query_events(tonic::Request::new(QueryEventsRequest {
+            tenant_id: "tenant-test".into(),
+            timer_id: timer.id.clone(),
+            types: vec!["cancelled".into()],
+            ..Default::default()
+        }))
+        .await
+        .expect("query response")
+        .into_inner()
+        .events;
+    assert_eq!(events.len(), 1);
+    assert!(matches!(
+        events[0].event.as_ref().and_then(|event| event.event.as_ref()),
+        Some(timer_event::Event::Cancelled(_))
+    ));
+
+    let unknown = client
+        .query_events(tonic::Request::new(QueryEventsRequest {
+            tenant_id: "tenant-test".into(),
+            types: vec!["exploded".into()],
+            ..Default::default()
+        }))
+        .await
+        .expect_err("unknown type");
+    assert_eq!(unknown.code(), tonic::Code::InvalidArgument);
 
     let _ = shutdown_tx.send(());
     server.await.expect("server join");
 }
+
+#[tokio::test]
+async fn grpc_api_descriptor_describes_the_served_contract() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50068".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50068")
+        .await
+        .expect("connect to kernel");
+
+    let descriptor = client
+        .get_api_descriptor(tonic::Request::new(ApiDescriptorRequest {}))
+        .await
+        .expect("descriptor response")
+        .into_inner();
+    assert_eq!(descriptor.kernel_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(descriptor.descriptor_sha256.len(), 64);
+
+    let files = <prost_types::FileDescriptorSet as prost::Message>::decode(
+        descriptor.file_descriptor_set.as_slice(),
+    )
+    .expect("descriptor set")
+    .file;
+    let methods: Vec<&str> = files
+        .iter()
+        .flat_map(|file| &file.service)
+        .flat_map(|service| &service.method)
+        .map(|method| method.name())
+        .collect();
+    assert!(methods.contains(&"ScheduleTimer"));
+    assert!(methods.contains(&"GetApiDescriptor"));
+
+    let openapi: serde_json::Value = serde_json::from_str(&descriptor.openapi_json).expect("openapi json");
+    let schedule = &openapi["paths"]["/v1/HorologyKernel/ScheduleTimer"]["post"];
+    assert_eq!(
+        schedule["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/TimerScheduleRequest"
+    );
+    assert!(openapi["components"]["schemas"]["TimerScheduleRequest"]["properties"]["tenantId"].is_object());
+    assert!(openapi["paths"]["/v1/HorologyKernel/StreamTimerEvents"]["post"]["x-grpc-streaming"]["server"]
+        .as_bool()
+        .unwrap_or_default());
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_streams_resume_after_the_last_processed_sequence() {
+    let kernel = HorologyKernel::new(SchedulerConfig {
+        event_history: Some(EventHistory::new(EventRetention::days(1))),
+        ..Default::default()
+    });
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50069".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50069")
+        .await
+        .expect("connect to kernel");
+    let schedule = TimerScheduleRequest {
+        tenant_id: "tenant-test".into(),
+        requested_by: "agent-test".into(),
+        schedule_time: Some(timer_schedule_request::ScheduleTime::DurationMs(60_000)),
+        ..Default::default()
+    };
+    for _ in 0..3 {
+        client
+            .schedule_timer(tonic::Request::new(schedule.clone()))
+            .await
+            .expect("schedule response");
+    }
+
+    let mut events = client
+        .stream_timer_events(tonic::Request::new(TimerEventStreamRequest {
+            tenant_id: "tenant-test".into(),
+            resume_from_sequence: 1,
+            ..Default::default()
+        }))
+        .await
+        .expect("stream response")
+        .into_inner();
+    client
+        .schedule_timer(tonic::Request::new(schedule))
+        .await
+        .expect("schedule response");
+
+    let mut sequences = Vec::new();
+    for _ in 0..3 {
+        let event = events.message().await.expect("stream event").expect("open stream");
+        assert!(matches!(event.event, Some(timer_event::Event::Scheduled(_))));
+        sequences.push(event.sequence);
+    }
+    assert_eq!(sequences, [2, 3, 4]);
+
+    let ahead = client
+        .stream_timer_events(tonic::Request::new(TimerEventStreamRequest {
+            tenant_id: "tenant-test".into(),
+            resume_from_sequence: 99,
+            ..Default::default()
+        }))
+        .await
+        .expect_err("sequence ahead of the kernel");
+    assert_eq!(ahead.code(), tonic::Code::OutOfRange);
+
+    drop(events);
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_streams_only_deliver_events_matching_their_filters() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50070".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50070")
+        .await
+        .expect("connect to kernel");
+
+    let invalid = client
+        .stream_timer_events(tonic::Request::new(TimerEventStreamRequest {
+            tenant_id: "tenant-test".into(),
+            topics: vec!["timer.exploded".into()],
+            ..Default::default()
+        }))
+        .await
+        .expect_err("unknown topic");
+    assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+
+    let mut events = client
+        .stream_timer_events(tonic::Request::new(TimerEventStreamRequest {
+            tenant_id: "tenant-test".into(),
+            topics: vec!["timer.cancelled".into()],
+            label_selectors: vec!["env=prod".into()],
+            name_prefix: "billing-".into(),
+            ..Default::default()
+        }))
+        .await
+        .expect("stream response")
+        .into_inner();
+
+    let mut matching = None;
+    for (name, env) in [("billing-renewal", "prod"), ("billing-trial", "staging"), ("audit", "prod")] {
+        let timer = client
+            .schedule_timer(tonic::Request::new(TimerScheduleRequest {
+                tenant_id: "tenant-test".into(),
+                requested_by: "agent-test".into(),
+                name: name.into(),
+                labels: HashMap::from([("env".to_string(), env.to_string())]),
+                schedule_time: Some(timer_schedule_request::ScheduleTime::DurationMs(60_000)),
+                ..Default::default()
+            }))
+            .await
+            .expect("schedule response")
+            .into_inner()
+            .timer
+            .expect("timer payload");
+        client
+            .cancel_timer(tonic::Request::new(TimerCancelRequest {
+                tenant_id: "tenant-test".into(),
+                timer_id: timer.id.clone(),
+                ..Default::default()
+            }))
+            .await
+            .expect("cancel response");
+        if name == "billing-renewal" {
+            matching = Some(timer.id);
+        }
+    }
+
+    let event = events.message().await.expect("stream event").expect("open stream");
+    match event.event {
+        Some(timer_event::Event::Cancelled(cancelled)) => {
+            assert_eq!(cancelled.timer.map(|timer| timer.id), matching);
+        }
+        other => panic!("expected the matching cancellation, got {other:?}"),
+    }
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), events.message()).await.is_err(),
+        "non-matching events were streamed"
+    );
+
+    drop(events);
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_replay_dead_letters_hands_parked_events_back_per_sink() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50071".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let timer = kernel
+        .schedule(TimerSpec {
+            tenant_id: "tenant-test".into(),
+            requested_by: "agent-test".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        })
+        .await
+        .expect("schedule");
+    let events: Vec<_> = (1..=3)
+        .map(|sequence| RecordedEvent {
+            sequence,
+            recorded_at: chrono::Utc::now(),
+            event: TimerEvent::Fired(timer.clone()),
+        })
+        .collect();
+    kernel.dead_letters().park("sqs", &events[..2], 8, "connection refused");
+    kernel.dead_letters().park("pubsub", &events[2..], 8, "connection refused");
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50071")
+        .await
+        .expect("connect to kernel");
+    let response = client
+        .replay_dead_letters(tonic::Request::new(ReplayDeadLettersRequest {
+            sink: "sqs".into(),
+            limit: 1,
+        }))
+        .await
+        .expect("replay response")
+        .into_inner();
+    assert_eq!((response.replayed, response.remaining, response.parked_total), (1, 2, 3));
+
+    let response = client
+        .replay_dead_letters(tonic::Request::new(ReplayDeadLettersRequest::default()))
+        .await
+        .expect("replay response")
+        .into_inner();
+    assert_eq!((response.replayed, response.remaining), (2, 0));
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_event_webhooks_are_registered_per_tenant() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50072".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50072")
+        .await
+        .expect("connect to kernel");
+
+    let invalid = client
+        .register_event_webhook(tonic::Request::new(RegisterEventWebhookRequest {
+            tenant_id: "tenant-test".into(),
+            url: "ftp://hooks.test".into(),
+            ..Default::default()
+        }))
+        .await
+        .expect_err("unsupported scheme");
+    assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+    let internal = client
+        .register_event_webhook(tonic::Request::new(RegisterEventWebhookRequest {
+            tenant_id: "tenant-test".into(),
+            url: "http://169.254.169.254/latest/meta-data".into(),
+            ..Default::default()
+        }))
+        .await
+        .expect_err("metadata endpoint");
+    assert_eq!(internal.code(), tonic::Code::InvalidArgument);
+
+    let registered = client
+        .register_event_webhook(tonic::Request::new(RegisterEventWebhookRequest {
+            tenant_id: "tenant-test".into(),
+            url: "http://203.0.113.20/minoots".into(),
+            topics: vec!["fired".into()],
+            label_selectors: vec!["env=prod".into()],
+            description: "billing".into(),
+            ..Default::default()
+        }))
+        .await
+        .expect("register response")
+        .into_inner();
+    assert!(!registered.secret.is_empty());
+    let webhook = registered.webhook.expect("webhook payload");
+    assert_eq!(webhook.topics, ["timer.fired"]);
+    assert_eq!(webhook.label_selectors, ["env=prod"]);
+
+    let listed = client
+        .list_event_webhooks(tonic::Request::new(ListEventWebhooksRequest {
+            tenant_id: "tenant-test".into(),
+        }))
+        .await
+        .expect("list response")
+        .into_inner();
+    assert_eq!(listed.webhooks, [webhook]);
+    let other = client
+        .list_event_webhooks(tonic::Request::new(ListEventWebhooksRequest {
+            tenant_id: "tenant-other".into(),
+        }))
+        .await
+        .expect("list response")
+        .into_inner();
+    assert!(other.webhooks.is_empty());
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_execution_reports_settle_or_fail_fired_timers() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let service = HorologyKernelService::new(kernel.clone());
+    let addr: SocketAddr = "127.0.0.1:50073".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(HorologyKernelServer::new(service))
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50073")
+        .await
+        .expect("connect to kernel");
+
+    let spec = TimerSpec {
+        tenant_id: "tenant-test".into(),
+        requested_by: "agent-test".into(),
+        duration_ms: 10,
+        ..Default::default()
+    };
+    let settled = kernel.schedule(spec.clone()).await.expect("schedule");
+    let failed = kernel.schedule(spec).await.expect("schedule");
+    kernel.wait("tenant-test", settled.id).await;
+    kernel.wait("tenant-test", failed.id).await;
+
+    let report = |timer_id: String, actions: Vec<ActionResult>| {
+        tonic::Request::new(ReportTimerExecutionRequest {
+            tenant_id: "tenant-test".into(),
+            timer_id,
+            actions,
+        })
+    };
+    let timer = client
+        .report_timer_execution(report(
+            settled.id.to_string(),
+            vec![ActionResult {
+                action_id: "notify".into(),
+                success: true,
+                duration_ms: 40,
+                ..Default::default()
+            }],
+        ))
+        .await
+        .expect("report response")
+        .into_inner();
+    assert_eq!(timer.status, horology_kernel::pb::TimerStatus::Settled as i32);
+    assert!(!timer.settled_at_iso.is_empty());
+    let execution = timer.execution.expect("execution report");
+    assert_eq!(execution.actions[0].duration_ms, 40);
+
+    let timer = client
+        .report_timer_execution(report(
+            failed.id.to_string(),
+            vec![ActionResult {
+                action_id: "notify".into(),
+                success: false,
+                duration_ms: 10_000,
+                error: "timed out".into(),
+                ..Default::default()
+            }],
+        ))
+        .await
+        .expect("report response")
+        .into_inner();
+    assert_eq!(timer.status, horology_kernel::pb::TimerStatus::Failed as i32);
+    let reason = timer.failure_reason.expect("failure reason");
+    assert_eq!(reason.code, "actions_failed");
+    assert_eq!((reason.failed_actions, reason.total_actions), (1, 1));
+    assert_eq!(reason.message, "1 of 1 actions failed: timed out");
+
+    let pending = kernel
+        .schedule(TimerSpec {
+            tenant_id: "tenant-test".into(),
+            requested_by: "agent-test".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        })
+        .await
+        .expect("schedule");
+    let error = client
+        .report_timer_execution(report(pending.id.to_string(), Vec::new()))
+        .await
+        .expect_err("timer has not fired");
+    assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_health_follows_leadership_and_reflection_lists_services() {
+    use horology_kernel::health::HealthService;
+    use horology_kernel::pb::health::health_check_response::ServingStatus;
+    use horology_kernel::pb::health::health_client::HealthClient;
+    use horology_kernel::pb::health::HealthCheckRequest;
+    use horology_kernel::pb::reflection::server_reflection_client::ServerReflectionClient;
+    use horology_kernel::pb::reflection::server_reflection_request::MessageRequest;
+    use horology_kernel::pb::reflection::server_reflection_response::MessageResponse;
+    use horology_kernel::pb::reflection::ServerReflectionRequest;
+    use horology_kernel::reflection::ReflectionService;
+    use prost::Message;
+
+    let kernel = HorologyKernel::new(SchedulerConfig { standby: true, ..Default::default() });
+    let addr: SocketAddr = "127.0.0.1:50075".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let server = tokio::spawn({
+        let kernel = kernel.clone();
+        async move {
+            Server::builder()
+                .add_service(HorologyKernelServer::new(HorologyKernelService::new(kernel.clone())))
+                .add_service(HealthService::new(kernel).into_server())
+                .add_service(ReflectionService::new().into_server())
+                .serve_with_shutdown(addr, async {
+                    shutdown_rx.await.ok();
+                })
+                .await
+                .unwrap();
+        }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut health = HealthClient::connect("http://127.0.0.1:50075").await.expect("connect to kernel");
+    let check = |service: &str| HealthCheckRequest { service: service.into() };
+    let status = health.check(check("minoots.timer.v1.HorologyKernel")).await.unwrap().into_inner().status;
+    assert_eq!(status, ServingStatus::NotServing as i32);
+    let mut watch = health.watch(check("")).await.unwrap().into_inner();
+    assert_eq!(watch.message().await.unwrap().unwrap().status, ServingStatus::NotServing as i32);
+
+    kernel.promote();
+    assert_eq!(health.check(check("")).await.unwrap().into_inner().status, ServingStatus::Serving as i32);
+    let update = tokio::time::timeout(Duration::from_secs(3), watch.message()).await.expect("status change");
+    assert_eq!(update.unwrap().unwrap().status, ServingStatus::Serving as i32);
+    let unknown = health.check(check("example.Unknown")).await.unwrap_err();
+    assert_eq!(unknown.code(), tonic::Code::NotFound);
+
+    let mut reflection = ServerReflectionClient::connect("http://127.0.0.1:50075").await.unwrap();
+    let requests = [
+        MessageRequest::ListServices(String::new()),
+        MessageRequest::FileContainingSymbol("minoots.timer.v1.HorologyKernel.ScheduleTimer".into()),
+        MessageRequest::FileContainingSymbol("minoots.timer.v1.Missing".into()),
+    ]
+    .map(|request| ServerReflectionRequest { host: String::new(), message_request: Some(request) });
+    let mut responses = reflection
+        .server_reflection_info(tokio_stream::iter(requests))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let Some(MessageResponse::ListServicesResponse(list)) = responses.message().await.unwrap().unwrap().message_response else {
+        panic!("expected a service list");
+    };
+    let mut services: Vec<_> = list.service.into_iter().map(|service| service.name).collect();
+    services.sort();
+    assert_eq!(
+        services,
+        [
+            "grpc.health.v1.Health",
+            "grpc.reflection.v1.ServerReflection",
+            "minoots.admin.v1.KernelAdmin",
+            "minoots.timer.v1.HorologyKernel"
+        ]
+    );
+    let Some(MessageResponse::FileDescriptorResponse(files)) = responses.message().await.unwrap().unwrap().message_response else {
+        panic!("expected a file descriptor");
+    };
+    let file = prost_types::FileDescriptorProto::decode(files.file_descriptor_proto[0].as_slice()).unwrap();
+    assert_eq!(file.name(), "timer.proto");
+    let Some(MessageResponse::ErrorResponse(error)) = responses.message().await.unwrap().unwrap().message_response else {
+        panic!("expected an error");
+    };
+    assert_eq!(error.error_code, tonic::Code::NotFound as i32);
+
+    // An open watch would hold up the graceful shutdown.
+    drop(watch);
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_rate_limits_each_caller_of_a_tenant() {
+    use horology_kernel::rate_limit::RateLimitLayer;
+    use horology_kernel::{Scope, TenantPolicy, TenantQuota};
+    use tonic::service::interceptor::InterceptedService;
+    use tower_layer::Layer;
+
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    kernel.tokens().set_root_secret("operator-secret");
+    kernel.set_tenant_policy(
+        "tenant-limited",
+        TenantPolicy {
+            quota: TenantQuota {
+                max_requests_per_second: Some(1),
+                request_burst: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    let (_, first) = kernel.tokens().issue("tenant-limited", vec![Scope::Read], None);
+    let (_, second) = kernel.tokens().issue("tenant-limited", vec![Scope::Read], None);
+    let limited = InterceptedService::new(
+        RateLimitLayer::new(kernel.clone()).layer(HorologyKernelService::new(kernel.clone()).into_server()),
+        grpc::authenticate(kernel.tokens().clone()),
+    );
+    let addr: SocketAddr = "127.0.0.1:50076".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(limited)
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50076").await.expect("connect to kernel");
+    let list = || TimerListRequest {
+        tenant_id: "tenant-limited".into(),
+        ..Default::default()
+    };
+    for _ in 0..2 {
+        client.list_timers(authorized(&first, list())).await.expect("within burst");
+    }
+    let limited = client
+        .list_timers(authorized(&first, list()))
+        .await
+        .expect_err("burst exhausted");
+    assert_eq!(limited.code(), tonic::Code::ResourceExhausted);
+    assert_eq!(limited.metadata().get("retry-after").unwrap(), "1");
+    let pushback: u64 = limited.metadata().get("grpc-retry-pushback-ms").unwrap().to_str().unwrap().parse().unwrap();
+    assert!(pushback > 0 && pushback <= 1_000);
+
+    // Each token has its own bucket, and untracked tenants are not limited.
+    client.list_timers(authorized(&second, list())).await.expect("other caller");
+    for _ in 0..3 {
+        client
+            .list_timers(authorized(
+                "operator-secret",
+                TimerListRequest {
+                    tenant_id: "tenant-free".into(),
+                    ..Default::default()
+                },
+            ))
+            .await
+            .expect("unlimited tenant");
+    }
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_admin_service_reports_counts_and_settles_stuck_timers() {
+    use horology_kernel::admin::AdminService;
+    use horology_kernel::pb::admin::kernel_admin_client::KernelAdminClient;
+    use horology_kernel::pb::admin::{
+        ClusterStatusRequest, CompactStoreRequest, ForceSettleTimerRequest, LeadershipRequest, ReplayEventsRequest,
+        TenantTimerCountsRequest,
+    };
+    use horology_kernel::Scope;
+    use tonic::service::interceptor::InterceptedService;
+
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    kernel.tokens().set_root_secret("operator-secret");
+    kernel.set_fencing_token(7);
+    let (_, tenant_admin) = kernel.tokens().issue("tenant-a", vec![Scope::Admin], None);
+    let admin = InterceptedService::new(
+        AdminService::new(kernel.clone()).into_server(),
+        grpc::authenticate(kernel.tokens().clone()),
+    );
+    let addr: SocketAddr = "127.0.0.1:50077".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(admin)
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = KernelAdminClient::connect("http://127.0.0.1:50077").await.expect("connect to kernel");
+    let spec = |tenant_id: &str, duration_ms: u64| TimerSpec {
+        tenant_id: tenant_id.into(),
+        requested_by: "agent-test".into(),
+        duration_ms,
+        ..Default::default()
+    };
+    let stuck = kernel.schedule(spec("tenant-a", 10)).await.expect("schedule");
+    let failed = kernel.schedule(spec("tenant-a", 10)).await.expect("schedule");
+    kernel.schedule(spec("tenant-a", 60_000)).await.expect("schedule");
+    kernel.schedule(spec("tenant-b", 60_000)).await.expect("schedule");
+    kernel.wait("tenant-a", stuck.id).await;
+    kernel.wait("tenant-a", failed.id).await;
+
+    let status = client
+        .get_cluster_status(authorized("operator-secret", ClusterStatusRequest {}))
+        .await
+        .expect("cluster status")
+        .into_inner();
+    assert!(status.active && status.store_healthy);
+    assert_eq!((status.timers, status.tenants, status.fencing_token), (4, 2, 7));
+    let denied = client
+        .get_cluster_status(authorized(&tenant_admin, ClusterStatusRequest {}))
+        .await
+        .expect_err("tenant tokens cannot see the cluster");
+    assert_eq!(denied.code(), tonic::Code::PermissionDenied);
+    let leadership = client
+        .get_leadership(authorized("operator-secret", LeadershipRequest {}))
+        .await
+        .expect("leadership")
+        .into_inner();
+    assert!(leadership.active && leadership.last_change.is_empty());
+
+    let counts = client
+        .list_tenant_timer_counts(authorized(
+            &tenant_admin,
+            TenantTimerCountsRequest {
+                tenant_id: "tenant-a".into(),
+            },
+        ))
+        .await
+        .expect("tenant counts")
+        .into_inner()
+        .tenants;
+    assert_eq!(counts.len(), 1);
+    assert_eq!((counts[0].fired, counts[0].total), (2, 3));
+    let denied = client
+        .list_tenant_timer_counts(authorized(&tenant_admin, TenantTimerCountsRequest::default()))
+        .await
+        .expect_err("listing every tenant needs the operator token");
+    assert_eq!(denied.code(), tonic::Code::PermissionDenied);
+
+    let settle = |timer_id: String, failure_reason: &str| ForceSettleTimerRequest {
+        tenant_id: "tenant-a".into(),
+        timer_id,
+        failure_reason: failure_reason.into(),
+    };
+    let timer = client
+        .force_settle_timer(authorized(&tenant_admin, settle(stuck.id.to_string(), "")))
+        .await
+        .expect("force settle")
+        .into_inner();
+    assert_eq!(timer.status, horology_kernel::pb::TimerStatus::Settled as i32);
+    let timer = client
+        .force_settle_timer(authorized(&tenant_admin, settle(failed.id.to_string(), "orchestrator lost")))
+        .await
+        .expect("force fail")
+        .into_inner();
+    assert_eq!(timer.status, horology_kernel::pb::TimerStatus::Failed as i32);
+    let reason = timer.failure_reason.expect("failure reason");
+    assert_eq!((reason.code.as_str(), reason.error.as_str()), ("failed_by_operator", "orchestrator lost"));
+    assert_eq!(kernel.get("tenant-a", failed.id).await.unwrap().status, TimerStatus::Failed);
+
+    let replay = client
+        .replay_events(authorized(
+            "operator-secret",
+            ReplayEventsRequest {
+                sink: "sqs".into(),
+                ..Default::default()
+            },
+        ))
+        .await
+        .expect_err("replay needs event history");
+    assert_eq!(replay.code(), tonic::Code::FailedPrecondition);
+    let compacted = client
+        .compact_store(authorized("operator-secret", CompactStoreRequest {}))
+        .await
+        .expect("compact store")
+        .into_inner();
+    assert!(!compacted.compacted);
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_schedule_stream_acknowledges_each_request_in_order() {
+    use horology_kernel::pb::schedule_timer_stream_ack;
+    use horology_kernel::Scope;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    kernel.tokens().set_root_secret("operator-secret");
+    let (_, secret) = kernel.tokens().issue("tenant-a", vec![Scope::Schedule], None);
+    let service = HorologyKernelServer::with_interceptor(
+        HorologyKernelService::new(kernel.clone()),
+        grpc::authenticate(kernel.tokens().clone()),
+    );
+    let addr: SocketAddr = "127.0.0.1:50078".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(service)
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50078").await.expect("connect to kernel");
+    let item = |tenant_id: &str, duration_ms| TimerScheduleRequest {
+        tenant_id: tenant_id.into(),
+        requested_by: "agent-test".into(),
+        schedule_time: Some(timer_schedule_request::ScheduleTime::DurationMs(duration_ms)),
+        ..Default::default()
+    };
+    let (requests_tx, requests_rx) = tokio::sync::mpsc::channel(8);
+    let mut acks = client
+        .schedule_timer_stream(authorized(&secret, ReceiverStream::new(requests_rx)))
+        .await
+        .expect("open schedule stream")
+        .into_inner();
+
+    requests_tx.send(item("tenant-a", 60_000)).await.unwrap();
+    let ack = acks.message().await.expect("ack").expect("first ack");
+    assert_eq!(ack.index, 0);
+    assert!(matches!(ack.result, Some(schedule_timer_stream_ack::Result::Timer(_))));
+    for request in [item("tenant-a", 0), item("tenant-b", 60_000), item("tenant-a", 30_000)] {
+        requests_tx.send(request).await.unwrap();
+    }
+    drop(requests_tx);
+    let mut rest = Vec::new();
+    while let Some(ack) = acks.message().await.expect("ack") {
+        rest.push(ack);
+    }
+    assert_eq!(rest.iter().map(|ack| ack.index).collect::<Vec<_>>(), vec![1, 2, 3]);
+    let codes: Vec<_> = rest
+        .iter()
+        .map(|ack| match &ack.result {
+            Some(schedule_timer_stream_ack::Result::Error(error)) => error.code,
+            Some(schedule_timer_stream_ack::Result::Timer(_)) => tonic::Code::Ok as i32,
+            None => panic!("ack without a result"),
+        })
+        .collect();
+    assert_eq!(
+        codes,
+        vec![
+            tonic::Code::InvalidArgument as i32,
+            tonic::Code::PermissionDenied as i32,
+            tonic::Code::Ok as i32
+        ]
+    );
+    assert_eq!(kernel.list("tenant-a").await.len(), 2);
+    assert!(kernel.list("tenant-b").await.is_empty());
+
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_watch_sends_a_snapshot_then_the_changes_after_it() {
+    use horology_kernel::pb::{watch_timers_update, WatchTimersRequest};
+    use horology_kernel::Scope;
+
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    kernel.tokens().set_root_secret("operator-secret");
+    let (_, stream_only) = kernel.tokens().issue("tenant-a", vec![Scope::Stream], None);
+    let (_, watcher) = kernel.tokens().issue("tenant-a", vec![Scope::Read, Scope::Stream], None);
+    let service = HorologyKernelServer::with_interceptor(
+        HorologyKernelService::new(kernel.clone()),
+        grpc::authenticate(kernel.tokens().clone()),
+    );
+    let addr: SocketAddr = "127.0.0.1:50079".parse().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let server = tokio::spawn(async move {
+        Server::builder()
+            .add_service(service)
+            .serve_with_shutdown(addr, async {
+                shutdown_rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50079").await.expect("connect to kernel");
+    let spec = |env: &str, duration_ms| TimerSpec {
+        tenant_id: "tenant-a".into(),
+        requested_by: "agent-test".into(),
+        duration_ms,
+        labels: HashMap::from([("env".to_string(), env.to_string())]),
+        ..Default::default()
+    };
+    let later = kernel.schedule(spec("prod", 120_000)).await.expect("schedule");
+    let sooner = kernel.schedule(spec("prod", 60_000)).await.expect("schedule");
+    kernel.schedule(spec("dev", 60_000)).await.expect("schedule");
+    let cancelled = kernel.schedule(spec("prod", 60_000)).await.expect("schedule");
+    kernel.cancel("tenant-a", cancelled.id, None, None).await.expect("cancel");
+
+    let request = || WatchTimersRequest {
+        tenant_id: "tenant-a".into(),
+        label_selectors: vec!["env=prod".into()],
+        ..Default::default()
+    };
+    let denied = client
+        .watch_timers(authorized(&stream_only, request()))
+        .await
+        .expect_err("watching needs the read scope");
+    assert_eq!(denied.code(), tonic::Code::PermissionDenied);
+    let mut updates = client
+        .watch_timers(authorized(&watcher, request()))
+        .await
+        .expect("open watch")
+        .into_inner();
+    let mut snapshot = Vec::new();
+    loop {
+        match updates.message().await.expect("update").expect("open stream").update {
+            Some(watch_timers_update::Update::Timer(timer)) => snapshot.push(timer.id),
+            Some(watch_timers_update::Update::SnapshotComplete(complete)) => {
+                assert_eq!(complete.timers, 2);
+                break;
+            }
+            other => panic!("expected the snapshot, got {other:?}"),
+        }
+    }
+    assert_eq!(snapshot, vec![sooner.id.to_string(), later.id.to_string()]);
+
+    kernel.schedule(spec("dev", 60_000)).await.expect("schedule");
+    let added = kernel.schedule(spec("prod", 90_000)).await.expect("schedule");
+    kernel.cancel("tenant-a", later.id, None, None).await.expect("cancel");
+    let mut changes = Vec::new();
+    while changes.len() < 2 {
+        match updates.message().await.expect("update").expect("open stream").update {
+            Some(watch_timers_update::Update::Event(event)) => changes.push(event.event.expect("event")),
+            other => panic!("expected an event, got {other:?}"),
+        }
+    }
+    assert!(matches!(&changes[0], timer_event::Event::Scheduled(e) if e.timer.as_ref().unwrap().id == added.id.to_string()));
+    assert!(matches!(&changes[1], timer_event::Event::Cancelled(e) if e.timer.as_ref().unwrap().id == later.id.to_string()));
+
+    drop(updates);
+    let _ = shutdown_tx.send(());
+    server.await.expect("server join");
+}
+
+#[tokio::test]
+async fn grpc_standby_forwards_writes_to_its_primary_until_promoted() {
+    use horology_kernel::standby::PrimaryForwarder;
+
+    let primary = HorologyKernel::new(SchedulerConfig::default());
+    primary.tokens().set_root_secret("operator-secret");
+    let standby = HorologyKernel::new(SchedulerConfig {
+        standby: true,
+        ..SchedulerConfig::default()
+    });
+    // Both kernels accept the same tokens, as with a shared token store; the primary enforces them,
+    // so forwarded calls only succeed because the caller's credentials go along.
+    let serve = |port: u16, service: HorologyKernelService| {
+        let service = HorologyKernelServer::with_interceptor(service, grpc::authenticate(primary.tokens().clone()));
+        let addr: SocketAddr = format!("127.0.0.1:{port}").parse().unwrap();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            Server::builder()
+                .add_service(service)
+                .serve_with_shutdown(addr, async {
+                    shutdown_rx.await.ok();
+                })
+                .await
+                .unwrap();
+        });
+        (shutdown_tx, server)
+    };
+    let primary_server = serve(50080, HorologyKernelService::new(primary.clone()));
+    let forwarder = PrimaryForwarder::new("http://127.0.0.1:50080").expect("primary url");
+    let standby_server = serve(50081, HorologyKernelService::new(standby.clone()).forward_to_primary(forwarder));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50081").await.expect("connect to standby");
+    let schedule = || TimerScheduleRequest {
+        tenant_id: "tenant-test".into(),
+        requested_by: "agent-test".into(),
+        schedule_time: Some(timer_schedule_request::ScheduleTime::DurationMs(60_000)),
+        ..Default::default()
+    };
+    let timer = client
+        .schedule_timer(authorized("operator-secret", schedule()))
+        .await
+        .expect("forwarded schedule")
+        .into_inner()
+        .timer
+        .expect("timer");
+    let timer_id = timer.id.parse().unwrap();
+    assert!(primary.get("tenant-test", timer_id).await.is_some());
+    assert!(standby.get("tenant-test", timer_id).await.is_none());
+
+    let cancelled = client
+        .cancel_timer(authorized(
+            "operator-secret",
+            TimerCancelRequest {
+                tenant_id: "tenant-test".into(),
+                timer_id: timer.id,
+                ..Default::default()
+            },
+        ))
+        .await
+        .expect("forwarded cancel")
+        .into_inner();
+    assert_eq!(cancelled.status, horology_kernel::pb::TimerStatus::Cancelled as i32);
+    assert_eq!(primary.get("tenant-test", timer_id).await.unwrap().status, TimerStatus::Cancelled);
+
+    standby.promote();
+    let local = client
+        .schedule_timer(authorized("operator-secret", schedule()))
+        .await
+        .expect("schedule on the promoted standby")
+        .into_inner()
+        .timer
+        .expect("timer");
+    assert!(standby.get("tenant-test", local.id.parse().unwrap()).await.is_some());
+
+    for (shutdown_tx, server) in [standby_server, primary_server] {
+        let _ = shutdown_tx.send(());
+        server.await.expect("server join");
+    }
+}
+
+#[tokio::test]
+async fn grpc_standby_reads_are_current_unless_stale_reads_are_allowed() {
+    use horology_kernel::pb::TimerGetRequest;
+    use horology_kernel::standby::PrimaryForwarder;
+
+    let primary = HorologyKernel::new(SchedulerConfig::default());
+    // Never hydrated, so any answer from its own copy is visibly stale.
+    let standby = HorologyKernel::new(SchedulerConfig {
+        standby: true,
+        ..SchedulerConfig::default()
+    });
+    let serve = |port: u16, service: HorologyKernelService| {
+        let addr: SocketAddr = format!("127.0.0.1:{port}").parse().unwrap();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            Server::builder()
+                .add_service(service.into_server())
+                .serve_with_shutdown(addr, async {
+                    shutdown_rx.await.ok();
+                })
+                .await
+                .unwrap();
+        });
+        (shutdown_tx, server)
+    };
+    let primary_server = serve(50082, HorologyKernelService::new(primary.clone()));
+    let forwarder = PrimaryForwarder::new("http://127.0.0.1:50082").expect("primary url");
+    let standby_server = serve(50083, HorologyKernelService::new(standby).forward_to_primary(forwarder));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let timer = primary
+        .schedule(TimerSpec {
+            tenant_id: "tenant-test".into(),
+            requested_by: "agent-test".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        })
+        .await
+        .expect("schedule");
+    let mut client = HorologyKernelClient::connect("http://127.0.0.1:50083").await.expect("connect to standby");
+    let get = |allow_stale| {
+        tonic::Request::new(TimerGetRequest {
+            tenant_id: "tenant-test".into(),
+            timer_id: timer.id.to_string(),
+            allow_stale,
+        })
+    };
+    let list = |allow_stale| {
+        tonic::Request::new(TimerListRequest {
+            tenant_id: "tenant-test".into(),
+            allow_stale,
+            ..Default::default()
+        })
+    };
+
+    let current = client.get_timer(get(false)).await.expect("read through the primary").into_inner();
+    assert_eq!(current.id, timer.id.to_string());
+    assert_eq!(client.list_timers(list(false)).await.expect("list").into_inner().timers.len(), 1);
+    let stale = client.get_timer(get(true)).await.expect_err("the standby's own copy");
+    assert_eq!(stale.code(), tonic::Code::NotFound);
+    assert!(client.list_timers(list(true)).await.expect("list").into_inner().timers.is_empty());
+
+    for (shutdown_tx, server) in [standby_server, primary_server] {
+        let _ = shutdown_tx.send(());
+        server.await.expect("server join");
+    }
+}
+
+#[tokio::test]
+async fn grpc_update_timer_refuses_out_of_range_deltas() {
+    use horology_kernel::pb::horology_kernel_server::HorologyKernel as _;
+    use horology_kernel::pb::{timer_update_request, TimerUpdateRequest};
+
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let service = HorologyKernelService::new(kernel.clone());
+    let timer = kernel
+        .schedule(TimerSpec {
+            tenant_id: "tenant-a".into(),
+            requested_by: "agent-1".into(),
+            duration_ms: 60_000,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    for delta in [i64::MIN, i64::MAX] {
+        let status = service
+            .update_timer(tonic::Request::new(TimerUpdateRequest {
+                tenant_id: "tenant-a".into(),
+                timer_id: timer.id.to_string(),
+                change: Some(timer_update_request::Change::DelayDeltaMs(delta)),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument, "{delta}: {status:?}");
+    }
+    assert_eq!(kernel.get("tenant-a", timer.id).await.unwrap().fire_at, timer.fire_at);
+}
@@ -0,0 +1,425 @@
+//! Guards the library surface other services and SDKs link against. Two layers:
+//!
+//! - Compile-time: the samples below build `TimerSpec` and `TimerInstance` without `..` and match
+//!   every enum variant without `_`, and the stores implement every required trait method. Adding a
+//!   field, variant, or required method stops this file compiling.
+//! - Snapshot: the serialized form of those samples is compared with `tests/snapshots/public_api.json`.
+//!   Additions only need the snapshot refreshed (`UPDATE_API_SNAPSHOT=1 cargo test --test public_api`);
+//!   removed or retyped fields also need an incompatible version bump in `Cargo.toml` (the minor
+//!   version while the crate is `0.x`) before the snapshot will update.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use horology_kernel::{
+    ActionReport, BudgetOutcome, ExecutionReport, FailureReason, FencedWrite, MissedFirePolicy, StoreError, TenantPolicy, TenantPolicyStore,
+    TimerEvent, TimerInstance, TimerKind, TimerSpec, TimerStatus, TimerStore, TIMER_SCHEMA_VERSION,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const SNAPSHOT: &str = "tests/snapshots/public_api.json";
+
+fn at(second: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, second).unwrap()
+}
+
+fn sample_spec() -> TimerSpec {
+    TimerSpec {
+        tenant_id: "acme".into(),
+        requested_by: "agent-1".into(),
+        name: Some("renewal".into()),
+        duration_ms: 60_000,
+        duration_us: Some(60_000_000),
+        fire_at: Some(at(1)),
+        metadata: Some(json!({"plan": "pro"})),
+        labels: HashMap::from([("env".to_string(), "prod".to_string())]),
+        action_bundle: Some(json!({"actions": []})),
+        agent_binding: Some(json!({"agent": "billing"})),
+        accuracy_budget_ms: Some(50),
+        pre_fire_notice_ms: Some(1_000),
+        idempotency_key: Some("renewal-1".into()),
+        missed_fire_policy: Some(MissedFirePolicy::FireWithGrace { grace_ms: 500 }),
+        session_id: Some("session-1".into()),
+        kind: TimerKind::Deadline { grace_ms: 250 },
+        priority: 3,
+    }
+}
+
+fn sample_instance() -> TimerInstance {
+    TimerInstance {
+        schema_version: TIMER_SCHEMA_VERSION,
+        id: Uuid::from_u128(0x6f1c2f43_7c1e_4c5a_9d59_0b3c8f1d2e4a),
+        tenant_id: "acme".into(),
+        requested_by: "agent-1".into(),
+        name: "renewal".into(),
+        duration_ms: 60_000,
+        duration_us: Some(60_000_000),
+        created_at: at(0),
+        fire_at: at(1),
+        status: TimerStatus::Failed,
+        metadata: Some(json!({"plan": "pro"})),
+        labels: HashMap::from([("env".to_string(), "prod".to_string())]),
+        action_bundle: Some(json!({"actions": []})),
+        agent_binding: Some(json!({"agent": "billing"})),
+        fired_at: Some(at(2)),
+        cancelled_at: Some(at(3)),
+        cancel_reason: Some("superseded".into()),
+        cancelled_by: Some("operator".into()),
+        accuracy_budget_ms: Some(50),
+        fire_latency_ms: Some(4),
+        fire_latency_us: Some(4_200),
+        budget_outcome: Some(BudgetOutcome::WithinBudget),
+        pre_fire_notice_ms: Some(1_000),
+        pre_fire_sent_for: Some(at(1)),
+        idempotency_key: Some("renewal-1".into()),
+        missed_fire_policy: Some(MissedFirePolicy::SkipAndMarkMissed),
+        session_id: Some("session-1".into()),
+        snooze_count: 1,
+        kind: TimerKind::Watchdog {
+            interval_ms: 30_000,
+        },
+        last_kicked_at: Some(at(4)),
+        priority: 3,
+        acknowledged_at: Some(at(5)),
+        failed_at: Some(at(6)),
+        failure_reason: Some(FailureReason::DeadlineExceeded { grace_ms: 250 }),
+        settled_at: Some(at(7)),
+        execution: Some(ExecutionReport {
+            actions: vec![ActionReport {
+                action_id: "notify".into(),
+                success: false,
+                duration_ms: 120,
+                error: Some("HTTP 503".into()),
+            }],
+            completed_at: at(7),
+        }),
+        state_version: 3,
+    }
+}
+
+fn sample_events(timer: TimerInstance) -> Vec<TimerEvent> {
+    let events = vec![
+        TimerEvent::Scheduled(timer.clone()),
+        TimerEvent::PreFire {
+            timer: timer.clone(),
+            fires_in_ms: 1_000,
+        },
+        TimerEvent::Fired(timer.clone()),
+        TimerEvent::Cancelled {
+            timer: timer.clone(),
+            reason: Some("superseded".into()),
+        },
+        TimerEvent::Missed {
+            timer: timer.clone(),
+            late_by_ms: 90_000,
+        },
+        TimerEvent::Rescheduled {
+            timer: timer.clone(),
+            previous_fire_at: at(0),
+        },
+        TimerEvent::Acknowledged(timer.clone()),
+        TimerEvent::Failed(timer.clone()),
+        TimerEvent::Settled(timer),
+    ];
+    for event in &events {
+        match event {
+            TimerEvent::Scheduled(_)
+            | TimerEvent::PreFire { .. }
+            | TimerEvent::Fired(_)
+            | TimerEvent::Cancelled { .. }
+            | TimerEvent::Missed { .. }
+            | TimerEvent::Rescheduled { .. }
+            | TimerEvent::Acknowledged(_)
+            | TimerEvent::Failed(_)
+            | TimerEvent::Settled(_) => {}
+        }
+    }
+    events
+}
+
+fn sample_statuses() -> Vec<TimerStatus> {
+    let statuses = vec![
+        TimerStatus::Scheduled,
+        TimerStatus::Armed,
+        TimerStatus::Fired,
+        TimerStatus::Cancelled,
+        TimerStatus::Missed,
+        TimerStatus::Acknowledged,
+        TimerStatus::Failed,
+        TimerStatus::Settled,
+    ];
+    for status in &statuses {
+        match status {
+            TimerStatus::Scheduled
+            | TimerStatus::Armed
+            | TimerStatus::Fired
+            | TimerStatus::Cancelled
+            | TimerStatus::Missed
+            | TimerStatus::Acknowledged
+            | TimerStatus::Failed
+            | TimerStatus::Settled => {}
+        }
+    }
+    statuses
+}
+
+fn sample_kinds() -> Vec<TimerKind> {
+    let kinds = vec![
+        TimerKind::OneShot,
+        TimerKind::Watchdog {
+            interval_ms: 30_000,
+        },
+        TimerKind::Deadline { grace_ms: 250 },
+    ];
+    for kind in &kinds {
+        match kind {
+            TimerKind::OneShot | TimerKind::Watchdog { .. } | TimerKind::Deadline { .. } => {}
+        }
+    }
+    kinds
+}
+
+fn sample_failure_reasons() -> Vec<FailureReason> {
+    let reasons = vec![
+        FailureReason::DeadlineExceeded { grace_ms: 250 },
+        FailureReason::ActionsFailed {
+            failed: 1,
+            total: 2,
+            error: Some("HTTP 503".into()),
+        },
+        FailureReason::FailedByOperator {
+            reason: "orchestrator lost".into(),
+        },
+    ];
+    for reason in &reasons {
+        match reason {
+            FailureReason::DeadlineExceeded { .. }
+            | FailureReason::ActionsFailed { .. }
+            | FailureReason::FailedByOperator { .. } => {}
+        }
+    }
+    reasons
+}
+
+fn to_values<T: Serialize>(samples: Vec<T>) -> Value {
+    samples
+        .iter()
+        .map(|sample| serde_json::to_value(sample).unwrap())
+        .collect()
+}
+
+fn surface() -> Value {
+    json!({
+        "TimerSpec": serde_json::to_value(sample_spec()).unwrap(),
+        "TimerEvent": to_values(sample_events(sample_instance())),
+        "TimerStatus": to_values(sample_statuses()),
+        "TimerKind": to_values(sample_kinds()),
+        "FailureReason": to_values(sample_failure_reasons()),
+    })
+}
+
+/// JSON type of a value, ignoring its contents; a field whose type changes is a breaking change
+/// even when its name survives.
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Paths present in `recorded` that `current` drops or retypes. Free-form JSON fields
+/// (`metadata`, `action_bundle`, `agent_binding`) and map keys (`labels`) are compared by type only.
+fn breaking_changes(path: &str, recorded: &Value, current: &Value, out: &mut Vec<String>) {
+    if type_of(recorded) != type_of(current) {
+        out.push(format!(
+            "{path}: {} became {}",
+            type_of(recorded),
+            type_of(current)
+        ));
+        return;
+    }
+    let opaque = ["metadata", "action_bundle", "agent_binding", "labels"];
+    if opaque
+        .iter()
+        .any(|field| path.ends_with(&format!(".{field}")))
+    {
+        return;
+    }
+    match (recorded, current) {
+        (Value::Object(recorded), Value::Object(current)) => {
+            for (key, value) in recorded {
+                let child = format!("{path}.{key}");
+                match current.get(key) {
+                    Some(next) => breaking_changes(&child, value, next, out),
+                    None => out.push(format!("{child}: removed")),
+                }
+            }
+        }
+        (Value::Array(recorded), Value::Array(current)) => {
+            for (index, value) in recorded.iter().enumerate() {
+                let child = format!("{path}[{index}]");
+                match current.get(index) {
+                    Some(next) => breaking_changes(&child, value, next, out),
+                    None => out.push(format!("{child}: removed")),
+                }
+            }
+        }
+        (Value::String(recorded), Value::String(current))
+            if recorded != current && is_tag(path) =>
+        {
+            out.push(format!("{path}: `{recorded}` became `{current}`"));
+        }
+        _ => {}
+    }
+}
+
+/// Whether the string at `path` names an enum variant rather than carrying sample data.
+fn is_tag(path: &str) -> bool {
+    [
+        ".type",
+        ".kind",
+        ".code",
+        ".policy",
+        ".status",
+        ".budget_outcome",
+    ]
+    .iter()
+    .any(|suffix| path.ends_with(suffix))
+        || path.starts_with(".TimerStatus[")
+}
+
+fn parse_version(version: &str) -> (u64, u64, u64) {
+    let mut parts = version.split('.').map(|part| part.parse().unwrap_or(0));
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
+/// Cargo's compatibility rule: `0.x` releases break on a minor bump, later ones on a major bump.
+fn is_incompatible_bump(recorded: &str, current: &str) -> bool {
+    let (recorded_major, recorded_minor, _) = parse_version(recorded);
+    let (major, minor, _) = parse_version(current);
+    if recorded_major == 0 {
+        major > 0 || minor > recorded_minor
+    } else {
+        major > recorded_major
+    }
+}
+
+#[test]
+fn public_api_matches_snapshot() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SNAPSHOT);
+    let current = surface();
+    let version = env!("CARGO_PKG_VERSION");
+    let update = std::env::var_os("UPDATE_API_SNAPSHOT").is_some();
+
+    let recorded: Option<Value> = std::fs::read_to_string(&path)
+        .ok()
+        .map(|contents| serde_json::from_str(&contents).expect("snapshot is valid JSON"));
+    if let Some(recorded) = &recorded {
+        let recorded_version = recorded["version"].as_str().unwrap_or("0.0.0");
+        let mut breaking = Vec::new();
+        breaking_changes("", &recorded["surface"], &current, &mut breaking);
+        assert!(
+            breaking.is_empty() || is_incompatible_bump(recorded_version, version),
+            "breaking changes to the public API need an incompatible version bump from {recorded_version} \
+             (currently {version}):\n  {}",
+            breaking.join("\n  ")
+        );
+        if recorded["surface"] == current {
+            return;
+        }
+    }
+
+    let snapshot = json!({ "version": version, "surface": current });
+    if update {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            serde_json::to_string_pretty(&snapshot).unwrap() + "\n",
+        )
+        .unwrap();
+        return;
+    }
+    panic!(
+        "public API differs from {SNAPSHOT}; review the change and rerun with UPDATE_API_SNAPSHOT=1"
+    );
+}
+
+#[test]
+fn incompatible_bumps_follow_cargo_rules() {
+    assert!(is_incompatible_bump("0.1.0", "0.2.0"));
+    assert!(is_incompatible_bump("0.1.3", "1.0.0"));
+    assert!(!is_incompatible_bump("0.1.0", "0.1.7"));
+    assert!(is_incompatible_bump("1.4.0", "2.0.0"));
+    assert!(!is_incompatible_bump("1.4.0", "1.9.0"));
+
+    let mut breaking = Vec::new();
+    let recorded =
+        json!({"TimerSpec": {"name": "renewal", "priority": 3}, "TimerStatus": ["fired"]});
+    let current = json!({"TimerSpec": {"name": "renewal", "priority": "high", "extra": 1}, "TimerStatus": ["done"]});
+    breaking_changes("", &recorded, &current, &mut breaking);
+    assert_eq!(
+        breaking,
+        vec![
+            ".TimerSpec.priority: number became string",
+            ".TimerStatus[0]: `fired` became `done`",
+        ]
+    );
+}
+
+struct NullStore;
+
+#[async_trait]
+impl TimerStore for NullStore {
+    async fn upsert(&self, _timer: &TimerInstance) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    async fn load_all(&self) -> Result<Vec<TimerInstance>, StoreError> {
+        Ok(Vec::new())
+    }
+
+    async fn upsert_fenced(
+        &self,
+        _timer: &TimerInstance,
+        _fencing_token: u64,
+    ) -> Result<FencedWrite, StoreError> {
+        Ok(FencedWrite::Written)
+    }
+
+    async fn record_heartbeat(&self, _at: DateTime<Utc>) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    async fn last_heartbeat(&self) -> Result<Option<DateTime<Utc>>, StoreError> {
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl TenantPolicyStore for NullStore {
+    async fn load_policies(&self) -> Result<HashMap<String, TenantPolicy>, StoreError> {
+        Ok(HashMap::new())
+    }
+}
+
+#[tokio::test]
+async fn store_traits_keep_their_methods() {
+    let store: &dyn TimerStore = &NullStore;
+    store.upsert_many(&[sample_instance()]).await.unwrap();
+    assert!(store.load_all().await.unwrap().is_empty());
+    assert!(store.get(Uuid::nil()).await.unwrap().is_none());
+    let policies: &dyn TenantPolicyStore = &NullStore;
+    assert!(policies.load_policies().await.unwrap().is_empty());
+}
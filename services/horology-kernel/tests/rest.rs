@@ -0,0 +1,241 @@
+#![cfg(feature = "grpc")]
+
+use std::net::SocketAddr;
+
+use horology_kernel::grpc::HorologyKernelService;
+use horology_kernel::rate_limit::{RateLimitLayer, TENANT_HEADER};
+use horology_kernel::sinks::webhook::{verify_signature, SIGNATURE_TOLERANCE};
+use horology_kernel::{
+    rest, EventHistory, EventQuery, EventRetention, HorologyKernel, SchedulerConfig, TenantPolicy,
+    TenantQuota,
+};
+use hyper::body::HttpBody;
+use hyper::{body, Body, Client, Method, Request, StatusCode};
+use serde_json::{json, Value};
+
+async fn serve(kernel: &HorologyKernel) -> SocketAddr {
+    let router = rest::router(
+        HorologyKernelService::new(kernel.clone()),
+        kernel.tokens().clone(),
+        RateLimitLayer::new(kernel.clone()),
+    );
+    let server =
+        axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(router.into_make_service());
+    let addr = server.local_addr();
+    tokio::spawn(server);
+    addr
+}
+
+async fn call(method: Method, uri: String, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(
+            body.map(|body| Body::from(body.to_string()))
+                .unwrap_or_default(),
+        )
+        .unwrap();
+    let response = Client::new()
+        .request(request)
+        .await
+        .expect("gateway response");
+    let status = response.status();
+    let bytes = body::to_bytes(response.into_body()).await.unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn rest_schedule_list_and_cancel_roundtrip() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let addr = serve(&kernel).await;
+
+    let (status, created) = call(
+        Method::POST,
+        format!("http://{addr}/v1/timers"),
+        Some(json!({
+            "tenant_id": "tenant-rest",
+            "requested_by": "curl",
+            "name": "over http",
+            "duration_ms": 60_000,
+            "labels": {"team": "ops"},
+            "metadata": {"source": "script"},
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["name"], "over http");
+    assert_eq!(created["metadata"]["source"], "script");
+    let timer_id = created["id"].as_str().expect("timer id").to_string();
+
+    let (status, listed) = call(
+        Method::GET,
+        format!("http://{addr}/v1/timers?tenant_id=tenant-rest&label=team%3Dops"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed["timers"].as_array().unwrap().len(), 1);
+    assert_eq!(listed["timers"][0]["id"], timer_id.as_str());
+
+    let (status, cancelled) = call(
+        Method::DELETE,
+        format!("http://{addr}/v1/timers/{timer_id}?tenant_id=tenant-rest&requested_by=curl&reason=done"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cancelled["status"], "cancelled");
+
+    let (status, missing) = call(
+        Method::DELETE,
+        format!(
+            "http://{addr}/v1/timers/{}?tenant_id=tenant-rest",
+            uuid::Uuid::new_v4()
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(missing["error"]["code"], "not_found");
+}
+
+#[tokio::test]
+async fn rest_rejects_invalid_schedule_bodies() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    let addr = serve(&kernel).await;
+
+    let (status, error) = call(
+        Method::POST,
+        format!("http://{addr}/v1/timers"),
+        Some(json!({"tenant_id": "tenant-rest", "requested_by": "curl", "duration_ms": 10, "fire_at": "2030-01-01T00:00:00Z"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"]["code"], "invalid_argument");
+
+    let (status, _) = call(
+        Method::POST,
+        format!("http://{addr}/v1/timers"),
+        Some(json!({"tenant_id": "tenant-rest", "requested_by": "curl", "duration_ms": 10, "unknown": true})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn rest_requests_draw_on_the_tenant_rate_limit() {
+    let kernel = HorologyKernel::new(SchedulerConfig::default());
+    kernel.set_tenant_policy(
+        "tenant-limited",
+        TenantPolicy {
+            quota: TenantQuota {
+                max_requests_per_second: Some(1),
+                request_burst: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    let addr = serve(&kernel).await;
+    let list = || {
+        let request = Request::get(format!("http://{addr}/v1/timers?tenant_id=tenant-limited"))
+            .header(TENANT_HEADER, "tenant-limited")
+            .body(Body::empty())
+            .unwrap();
+        Client::new().request(request)
+    };
+    for _ in 0..2 {
+        assert_eq!(list().await.unwrap().status(), StatusCode::OK);
+    }
+    let limited = list().await.unwrap();
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(limited.headers()["retry-after"], "1");
+    let bytes = body::to_bytes(limited.into_body()).await.unwrap();
+    let error: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(error["error"]["code"], "resource_exhausted");
+}
+
+#[tokio::test]
+async fn rest_streams_signed_envelopes_resuming_after_last_event_id() {
+    let kernel = HorologyKernel::new(SchedulerConfig {
+        event_history: Some(EventHistory::new(EventRetention::days(1))),
+        ..Default::default()
+    });
+    kernel.set_tenant_policy(
+        "tenant-sse",
+        TenantPolicy {
+            webhook_signing_secret: Some("whsec_test".into()),
+            ..Default::default()
+        },
+    );
+    let addr = serve(&kernel).await;
+    let mut ids = Vec::new();
+    for name in ["first", "second"] {
+        let (status, created) = call(
+            Method::POST,
+            format!("http://{addr}/v1/timers"),
+            Some(json!({"tenant_id": "tenant-sse", "requested_by": "dashboard", "name": name, "duration_ms": 60_000})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        ids.push(created["id"].as_str().unwrap().to_string());
+    }
+    let history = kernel
+        .query_events("tenant-sse", &EventQuery::default())
+        .unwrap();
+    let (first_sequence, second_sequence) = (history[0].sequence, history[1].sequence);
+
+    let request = Request::get(format!(
+        "http://{addr}/v1/events/signed?tenant_id=tenant-sse"
+    ))
+    .header("last-event-id", first_sequence.to_string())
+    .body(Body::empty())
+    .unwrap();
+    let response = Client::new().request(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let mut body = response.into_body();
+    let mut text = String::new();
+    while !text.contains("\n\n") {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.data())
+            .await
+            .expect("replayed event")
+            .unwrap()
+            .unwrap();
+        text.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    let field = |name: &str| {
+        text.lines()
+            .find_map(|line| line.strip_prefix(&format!("{name}:")))
+            .map(|value| value.trim_start().to_string())
+            .unwrap()
+    };
+    assert_eq!(field("event"), "scheduled");
+    assert_eq!(field("id"), second_sequence.to_string());
+    let envelope: Value = serde_json::from_str(&field("data")).unwrap();
+    let signed = envelope["body"].as_str().unwrap();
+    verify_signature(
+        "whsec_test",
+        &envelope["timestamp"].to_string(),
+        envelope["signature"].as_str().unwrap(),
+        signed.as_bytes(),
+        chrono::Utc::now(),
+        SIGNATURE_TOLERANCE,
+    )
+    .expect("envelope signed with the tenant secret");
+    let signed: Value = serde_json::from_str(signed).unwrap();
+    assert_eq!(signed["sequence"], second_sequence);
+    assert_eq!(signed["event"]["data"]["id"], ids[1].as_str());
+
+    let (status, error) = call(
+        Method::GET,
+        format!("http://{addr}/v1/events/signed?tenant_id=tenant-unsigned"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"]["code"], "failed_precondition");
+}
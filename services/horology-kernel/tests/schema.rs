@@ -0,0 +1,59 @@
+//! Every released `TimerInstance` layout must stay readable. Add a fixture here whenever
+//! `TIMER_SCHEMA_VERSION` is bumped.
+
+use horology_kernel::{TimerEvent, TimerInstance, TimerStatus, TIMER_SCHEMA_VERSION};
+
+const FIXTURES: &[(&str, &str)] = &[
+    ("v1", include_str!("fixtures/timer_instance_v1.json")),
+    (
+        "v1-minimal",
+        include_str!("fixtures/timer_instance_v1_minimal.json"),
+    ),
+];
+
+#[test]
+fn released_fixtures_load_and_round_trip() {
+    for (name, fixture) in FIXTURES {
+        let timer: TimerInstance = serde_json::from_str(fixture)
+            .unwrap_or_else(|error| panic!("fixture {name} failed to load: {error}"));
+        assert!(
+            timer.schema_version <= TIMER_SCHEMA_VERSION,
+            "fixture {name}"
+        );
+
+        let encoded = serde_json::to_value(&timer).unwrap();
+        let decoded: TimerInstance = serde_json::from_value(encoded.clone()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            encoded,
+            "fixture {name}"
+        );
+    }
+}
+
+#[test]
+fn missing_optional_fields_fall_back_to_defaults() {
+    let timer: TimerInstance =
+        serde_json::from_str(include_str!("fixtures/timer_instance_v1_minimal.json")).unwrap();
+    assert_eq!(timer.schema_version, 1);
+    assert_eq!(timer.status, TimerStatus::Scheduled);
+    assert!(timer.labels.is_empty());
+    assert!(timer.metadata.is_none());
+    assert!(timer.cancelled_at.is_none());
+}
+
+#[test]
+fn events_wrapping_legacy_instances_still_decode() {
+    let payload = format!(
+        r#"{{"type":"Cancelled","data":{{"timer":{},"reason":"superseded"}}}}"#,
+        include_str!("fixtures/timer_instance_v1.json")
+    );
+    let event: TimerEvent = serde_json::from_str(&payload).unwrap();
+    match event {
+        TimerEvent::Cancelled { timer, reason } => {
+            assert_eq!(timer.labels.get("env").map(String::as_str), Some("prod"));
+            assert_eq!(reason.as_deref(), Some("superseded"));
+        }
+        other => panic!("unexpected event: {:?}", other),
+    }
+}
@@ -0,0 +1,100 @@
+//! Behaviour every `TimerStore` must share. Each backend in the tree gets a test below that runs
+//! the whole suite unconditionally; a backend that needs external infrastructure should start it
+//! from its test rather than skip.
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use horology_kernel::{
+    BatchingTimerStore, FencedWrite, FileTimerStore, HorologyKernel, InMemoryTimerStore,
+    SchedulerConfig, TimerInstance, TimerStatus, TimerStore, WriteBatchConfig,
+};
+use uuid::Uuid;
+
+fn timer() -> TimerInstance {
+    let mut timer: TimerInstance =
+        serde_json::from_str(include_str!("fixtures/timer_instance_v1.json")).unwrap();
+    timer.id = Uuid::new_v4();
+    timer.status = TimerStatus::Scheduled;
+    timer.fired_at = None;
+    timer.cancelled_at = None;
+    timer.cancel_reason = None;
+    timer.cancelled_by = None;
+    timer
+}
+
+async fn exercise(store: Arc<dyn TimerStore>) {
+    assert!(store.load_all().await.unwrap().is_empty());
+    assert_eq!(store.last_heartbeat().await.unwrap(), None);
+
+    let mut first = timer();
+    let second = timer();
+    store.upsert(&first).await.unwrap();
+    store.upsert(&second).await.unwrap();
+    first.status = TimerStatus::Cancelled;
+    store.upsert(&first).await.unwrap();
+
+    let mut loaded = store.load_all().await.unwrap();
+    loaded.sort_by_key(|timer| timer.id != first.id);
+    assert_eq!(loaded.len(), 2, "upserts replace earlier copies");
+    assert_eq!(loaded[0].status, TimerStatus::Cancelled);
+    assert_eq!(loaded[1].id, second.id);
+    let fetched = store.get(first.id).await.unwrap().expect("stored timer");
+    assert_eq!(fetched.status, TimerStatus::Cancelled);
+    assert!(store.get(Uuid::new_v4()).await.unwrap().is_none());
+
+    // Fenced writes only move a timer forward.
+    let superseded = store.upsert_fenced(&first, 1).await.unwrap();
+    assert!(matches!(superseded, FencedWrite::Superseded(_)));
+    first.state_version += 1;
+    let written = store.upsert_fenced(&first, 1).await.unwrap();
+    assert!(matches!(written, FencedWrite::Written));
+    // A writer presenting a newer token locks out the older one.
+    first.state_version += 1;
+    let newer = store.upsert_fenced(&first, 2).await.unwrap();
+    assert!(matches!(newer, FencedWrite::Written));
+    first.state_version += 1;
+    let stale = store.upsert_fenced(&first, 1).await.unwrap();
+    assert!(matches!(stale, FencedWrite::Fenced { current: 2 }));
+
+    let earlier = Utc::now() - Duration::seconds(30);
+    store.record_heartbeat(earlier).await.unwrap();
+    let latest = Utc::now();
+    store.record_heartbeat(latest).await.unwrap();
+    assert_eq!(store.last_heartbeat().await.unwrap(), Some(latest));
+
+    // A kernel restored from the store sees the pending timer and skips the terminal one.
+    let kernel = HorologyKernel::with_store(SchedulerConfig::default(), store);
+    let report = kernel.restore_from_store().await.unwrap();
+    assert_eq!(report.restored, 2);
+    assert_eq!(report.downtime_started_at, Some(latest));
+    let restored = kernel
+        .get(&second.tenant_id, second.id)
+        .await
+        .expect("pending timer restored");
+    assert_ne!(restored.status, TimerStatus::Cancelled);
+}
+
+#[tokio::test]
+async fn in_memory_store_conforms() {
+    exercise(Arc::new(InMemoryTimerStore::default())).await;
+}
+
+#[tokio::test]
+async fn file_store_conforms() {
+    let path = std::env::temp_dir().join(format!("minoots-conformance-{}.jsonl", Uuid::new_v4()));
+    exercise(Arc::new(FileTimerStore::open(&path).unwrap())).await;
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn batching_store_conforms() {
+    let path = std::env::temp_dir().join(format!("minoots-conformance-{}.jsonl", Uuid::new_v4()));
+    let file = Arc::new(FileTimerStore::open(&path).unwrap());
+    exercise(Arc::new(BatchingTimerStore::new(
+        file,
+        WriteBatchConfig::default(),
+    )))
+    .await;
+    let _ = std::fs::remove_file(&path);
+}